@@ -72,7 +72,7 @@ pub enum Commands {
     ///   octofhir-codegen init --template typescript
     ///   octofhir-codegen init --output ./my-project
     Init {
-        /// Template to use (typescript, rust, python, java, multi)
+        /// Template to use (typescript, rust, python, java, kotlin, json-schema, openapi, markdown, multi)
         #[arg(short, long, value_name = "TEMPLATE", help = "Template to use for initialization")]
         template: Option<String>,
 
@@ -106,7 +106,7 @@ pub enum Commands {
             short,
             long,
             value_name = "LANGUAGE",
-            help = "Target language (typescript, rust, python, java)"
+            help = "Target language (typescript, rust, python, java, kotlin, json-schema, openapi, markdown)"
         )]
         language: Option<String>,
 
@@ -115,7 +115,7 @@ pub enum Commands {
         output: Option<PathBuf>,
 
         /// FHIR version (overrides config)
-        #[arg(long, value_name = "VERSION", help = "FHIR version (R4, R5, R6)")]
+        #[arg(long, value_name = "VERSION", help = "FHIR version (R4, R4B, R5, R6)")]
         fhir_version: Option<String>,
 
         /// Additional packages to include (can be specified multiple times)
@@ -149,6 +149,17 @@ pub enum Commands {
         detailed: bool,
     },
 
+    /// Manage the configuration file
+    ///
+    /// Examples:
+    ///   octofhir-codegen config migrate
+    ///   octofhir-codegen config migrate --dry-run
+    Config {
+        /// Configuration action to perform
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+
     /// List available code generators
     ///
     /// Shows all available language generators and their current status.
@@ -218,6 +229,65 @@ pub enum Commands {
         export: Option<PathBuf>,
     },
 
+    /// Generate synthetic FHIR instances
+    ///
+    /// Builds random but structurally valid instances of a resource type or
+    /// profile from the configured packages, written as NDJSON. Resources the
+    /// instances reference are generated too and follow them in the output.
+    ///
+    /// Examples:
+    ///   octofhir-codegen synth Patient --count 100
+    ///   octofhir-codegen synth http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient
+    ///   octofhir-codegen synth Observation --seed 42 --bundle --output observations.json
+    Synth {
+        /// Resource type, profile URL or profile name to generate
+        #[arg(value_name = "TYPE", help = "Resource type or profile URL")]
+        target: String,
+
+        /// Number of instances to generate
+        #[arg(short = 'n', long, default_value_t = 1, help = "Number of instances")]
+        count: usize,
+
+        /// Seed for reproducible output
+        #[arg(long, value_name = "SEED", help = "Random seed")]
+        seed: Option<u64>,
+
+        /// Wrap the instances in a collection Bundle instead of NDJSON
+        #[arg(long, help = "Write a collection Bundle")]
+        bundle: bool,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long, value_name = "FILE", help = "Output file")]
+        output: Option<PathBuf>,
+
+        /// Additional packages to include (can be specified multiple times)
+        #[arg(long, value_name = "PACKAGE", help = "FHIR package (format: name@version)")]
+        package: Vec<String>,
+    },
+
+    /// Validate a FHIR resource
+    ///
+    /// Checks a JSON resource against its type, or a profile, as defined by the
+    /// configured packages and prints the result as an OperationOutcome. Exits
+    /// with a non-zero status when errors are found.
+    ///
+    /// Examples:
+    ///   octofhir-codegen validate-resource patient.json
+    ///   octofhir-codegen validate-resource patient.json --profile http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient
+    ValidateResource {
+        /// JSON file holding the resource
+        #[arg(value_name = "FILE", help = "Resource JSON file")]
+        file: PathBuf,
+
+        /// Profile URL or name to validate against instead of the base type
+        #[arg(long, value_name = "URL", help = "Profile to validate against")]
+        profile: Option<String>,
+
+        /// Additional packages to include (can be specified multiple times)
+        #[arg(long, value_name = "PACKAGE", help = "FHIR package (format: name@version)")]
+        package: Vec<String>,
+    },
+
     /// Update canonical-manager packages
     ///
     /// Updates FHIR packages managed by the canonical-manager.
@@ -262,6 +332,34 @@ pub enum Commands {
     },
 }
 
+/// Configuration file subcommands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Rewrite a legacy configuration file in the current schema
+    ///
+    /// Reads a configuration file in the old flat layout (top-level
+    /// `fhir_version`/`output_dir` and `type`-tagged generators) and rewrites
+    /// it using the current versioned schema. The original file is kept as
+    /// `<file>.bak` unless `--no-backup` is given.
+    ///
+    /// Examples:
+    ///   octofhir-codegen config migrate
+    ///   octofhir-codegen config migrate --output ./codegen.new.toml
+    Migrate {
+        /// Write the migrated configuration here instead of in place
+        #[arg(short, long, value_name = "FILE", help = "Write migrated config to FILE")]
+        output: Option<PathBuf>,
+
+        /// Print the migrated configuration without writing it
+        #[arg(long, help = "Print the migrated configuration only")]
+        dry_run: bool,
+
+        /// Don't keep a backup of the original file
+        #[arg(long, help = "Don't write a .bak backup")]
+        no_backup: bool,
+    },
+}
+
 impl Cli {
     /// Parse CLI arguments from environment
     pub fn parse_args() -> Self {
@@ -414,6 +512,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_synth_command() {
+        let cli = Cli::try_parse_from([
+            "octofhir-codegen",
+            "synth",
+            "Patient",
+            "--count",
+            "100",
+            "--seed",
+            "42",
+        ])
+        .unwrap();
+
+        if let Commands::Synth { target, count, seed, bundle, output, .. } = cli.command {
+            assert_eq!(target, "Patient");
+            assert_eq!(count, 100);
+            assert_eq!(seed, Some(42));
+            assert!(!bundle);
+            assert!(output.is_none());
+        } else {
+            panic!("Expected Synth command");
+        }
+    }
+
+    #[test]
+    fn test_validate_resource_command() {
+        let cli = Cli::try_parse_from([
+            "octofhir-codegen",
+            "validate-resource",
+            "patient.json",
+            "--profile",
+            "http://example.org/StructureDefinition/named-patient",
+        ])
+        .unwrap();
+
+        if let Commands::ValidateResource { file, profile, package } = cli.command {
+            assert_eq!(file, PathBuf::from("patient.json"));
+            assert_eq!(
+                profile.as_deref(),
+                Some("http://example.org/StructureDefinition/named-patient")
+            );
+            assert!(package.is_empty());
+        } else {
+            panic!("Expected ValidateResource command");
+        }
+    }
+
     #[test]
     fn test_describe_command() {
         let cli = Cli::try_parse_from(["octofhir-codegen", "describe", "typescript", "--examples"])
@@ -427,6 +572,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_migrate_command() {
+        let cli =
+            Cli::try_parse_from(["octofhir-codegen", "config", "migrate", "--dry-run"]).unwrap();
+
+        if let Commands::Config { action: ConfigCommands::Migrate { output, dry_run, no_backup } } =
+            cli.command
+        {
+            assert!(output.is_none());
+            assert!(dry_run);
+            assert!(!no_backup);
+        } else {
+            panic!("Expected Config Migrate command");
+        }
+    }
+
     #[test]
     fn test_command_result_success() {
         let result = CommandResult::success();
@@ -7,17 +7,22 @@
 //! - File output management
 
 use crate::cli::{CodegenConfig, OutputFormatter};
+use crate::config::PluginsConfig;
 use crate::core::TypeGraphBuilder;
 use crate::core::ir::{FhirVersion, TypeGraph};
-use crate::languages::typescript::{PackageConfig, TypeScriptSdkGenerator};
+use crate::generator::{
+    CodeGenerator, GeneratedFile, GeneratorConfig, GeneratorRegistry, Language,
+};
 use anyhow::{Context, Result};
 use indicatif::ProgressBar;
 use octofhir_canonical_manager::{CanonicalManager, FcmConfig};
-use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Maximum number of package examples loaded per resource type for generated tests
+const EXAMPLES_PER_RESOURCE: usize = 10;
+
 /// Options for SDK generation
 #[derive(Debug, Clone)]
 pub struct GenerateOptions {
@@ -74,6 +79,9 @@ pub async fn execute_generate(
 
     // Step 2: Determine which generator to use
     let language = determine_language(&config, &options)?;
+    let config_dir = options.config_path.parent().unwrap_or_else(|| Path::new("."));
+    let registry = load_generator_registry(&config.plugins, config_dir, formatter);
+    let (generator, generator_config) = prepare_generator(&registry, &config, &language)?;
     formatter.success(&format!("Generating {} SDK", language));
 
     // Step 3: Set up output directory
@@ -86,11 +94,21 @@ pub async fn execute_generate(
 
     // Step 5: Build type graph
     formatter.info("Building type graph from FHIR packages...");
-    let mut type_graph = build_type_graph(&config, canonical_manager, formatter).await?;
+    let mut type_graph =
+        build_type_graph(&config, Arc::clone(&canonical_manager), formatter).await?;
 
     // Step 5.5: Apply resource filtering
     filter_type_graph(&mut type_graph, &config, formatter);
 
+    // Step 5.6: Load package examples for generated round-trip tests
+    if generator_config.emit_tests {
+        formatter.info("Loading example resources for generated tests...");
+        TypeGraphBuilder::new(canonical_manager, type_graph.fhir_version)
+            .add_examples(&mut type_graph, EXAMPLES_PER_RESOURCE)
+            .await
+            .context("Failed to load example resources")?;
+    }
+
     formatter.success(&format!(
         "Type graph built: {} resources, {} datatypes",
         type_graph.resources.len(),
@@ -99,14 +117,17 @@ pub async fn execute_generate(
 
     // Step 6: Generate SDK files
     formatter.info(&format!("Generating {} SDK files...", language));
-    let files = generate_sdk_files(&config, &type_graph, &language)?;
+    let code = generator.generate(&type_graph, &generator_config).await?;
+    for warning in &code.manifest.warnings {
+        formatter.warning(warning);
+    }
 
     // Step 7: Write files to disk
     formatter.info("Writing files to disk...");
-    write_files_to_disk(&output_dir, &files, formatter)?;
+    write_files_to_disk(&output_dir, &code.files, formatter)?;
 
     Ok(GenerationResult {
-        files_generated: files.len(),
+        files_generated: code.file_count(),
         output_path: output_dir,
         language,
         fhir_version: config.fhir.version.clone(),
@@ -118,18 +139,31 @@ fn apply_cli_overrides(config: &mut CodegenConfig, options: &GenerateOptions) {
     if let Some(ref lang) = options.language_override {
         // Enable the specified generator
         match lang.to_lowercase().as_str() {
-            "typescript" => {
-                config.generators.typescript =
-                    Some(crate::cli::config::TypeScriptGeneratorConfig {
-                        enabled: true,
-                        ..Default::default()
-                    });
+            "typescript" | "ts" => {
+                // Keep any configured options; only force the generator on
+                let typescript = config.generators.typescript.get_or_insert_with(Default::default);
+                typescript.enabled = true;
+            }
+            "rust" | "rs" => {
+                let rust = config.generators.rust.get_or_insert_with(Default::default);
+                rust.enabled = true;
+            }
+            "kotlin" | "kt" => {
+                let kotlin = config.generators.kotlin.get_or_insert_with(Default::default);
+                kotlin.enabled = true;
+            }
+            "json-schema" | "json_schema" | "jsonschema" => {
+                let json_schema =
+                    config.generators.json_schema.get_or_insert_with(Default::default);
+                json_schema.enabled = true;
+            }
+            "openapi" | "open-api" | "oas" => {
+                let openapi = config.generators.openapi.get_or_insert_with(Default::default);
+                openapi.enabled = true;
             }
-            "rust" => {
-                config.generators.rust = Some(crate::cli::config::RustGeneratorConfig {
-                    enabled: true,
-                    ..Default::default()
-                });
+            "markdown" | "md" | "docs" => {
+                let markdown = config.generators.markdown.get_or_insert_with(Default::default);
+                markdown.enabled = true;
             }
             _ => {}
         }
@@ -151,7 +185,11 @@ fn apply_cli_overrides(config: &mut CodegenConfig, options: &GenerateOptions) {
 /// Determine which language to generate
 fn determine_language(config: &CodegenConfig, options: &GenerateOptions) -> Result<String> {
     if let Some(ref lang) = options.language_override {
-        return Ok(lang.clone());
+        // Normalize aliases such as "ts" or "kt" to the canonical language name
+        return Ok(lang
+            .parse::<Language>()
+            .map(|l| l.to_string())
+            .unwrap_or_else(|_| lang.clone()));
     }
 
     // Check which generator is enabled in config
@@ -171,6 +209,26 @@ fn determine_language(config: &CodegenConfig, options: &GenerateOptions) -> Resu
         return Ok("Java".to_string());
     }
 
+    if config.generators.kotlin.as_ref().is_some_and(|g| g.enabled) {
+        return Ok("Kotlin".to_string());
+    }
+
+    if config.generators.json_schema.as_ref().is_some_and(|g| g.enabled) {
+        return Ok(Language::JsonSchema.to_string());
+    }
+
+    if config.generators.openapi.as_ref().is_some_and(|g| g.enabled) {
+        return Ok(Language::OpenApi.to_string());
+    }
+
+    if config.generators.markdown.as_ref().is_some_and(|g| g.enabled) {
+        return Ok(Language::Markdown.to_string());
+    }
+
+    if let Some(ref generator) = config.plugins.generator {
+        return Ok(generator.clone());
+    }
+
     anyhow::bail!("No generator enabled. Enable at least one generator in the configuration.");
 }
 
@@ -284,6 +342,22 @@ async fn build_type_graph(
     type_graph.ok_or_else(|| anyhow::anyhow!("No FHIR packages specified in configuration"))
 }
 
+/// Build the type graph for the packages of a configuration file
+///
+/// `additional_packages` are added to the configured ones, as with `generate --package`.
+pub async fn load_type_graph(
+    config_path: &Path,
+    additional_packages: &[String],
+    formatter: &OutputFormatter,
+) -> Result<TypeGraph> {
+    let mut config =
+        CodegenConfig::from_file(config_path).context("Failed to load configuration file")?;
+    config.fhir.packages.extend(additional_packages.iter().cloned());
+
+    let canonical_manager = initialize_canonical_manager(&config).await?;
+    build_type_graph(&config, canonical_manager, formatter).await
+}
+
 /// Merge two type graphs (simple implementation - later will be more sophisticated)
 fn merge_type_graphs(mut base: TypeGraph, other: TypeGraph) -> Result<TypeGraph> {
     // Merge resources
@@ -304,61 +378,189 @@ fn merge_type_graphs(mut base: TypeGraph, other: TypeGraph) -> Result<TypeGraph>
     Ok(base)
 }
 
-/// Generate SDK files based on configuration and language
-fn generate_sdk_files(
-    config: &CodegenConfig,
-    type_graph: &TypeGraph,
-    language: &str,
-) -> Result<HashMap<String, String>> {
-    match language {
-        "TypeScript" => generate_typescript_sdk(config, type_graph),
-        "Rust" => anyhow::bail!("Rust generator not yet implemented"),
-        "Python" => anyhow::bail!("Python generator not yet implemented"),
-        "Java" => anyhow::bail!("Java generator not yet implemented"),
-        _ => anyhow::bail!("Unknown language: {}", language),
+/// Build the generator registry from built-in generators and configured plugins
+///
+/// Plugins are loaded from the `[plugins]` section (relative to `config_dir`) and
+/// from `OCTOFHIR_PLUGIN_PATH`. Plugins that fail to load are reported as warnings.
+pub fn load_generator_registry(
+    plugins: &PluginsConfig,
+    config_dir: &Path,
+    formatter: &OutputFormatter,
+) -> GeneratorRegistry {
+    let mut registry = GeneratorRegistry::with_builtin_generators();
+
+    let (mut loaded, mut errors) = registry.load_plugins(&plugins.resolve_paths(config_dir));
+    let (env_loaded, env_errors) = registry.load_plugins_from_env();
+    loaded.extend(env_loaded);
+    errors.extend(env_errors);
+
+    for name in loaded {
+        tracing::debug!("Loaded generator plugin: {}", name);
     }
+    for error in errors {
+        formatter.warning(&error.to_string());
+    }
+
+    registry
 }
 
-/// Generate TypeScript SDK
-fn generate_typescript_sdk(
+/// Look up the generator for a language and build its validated configuration
+///
+/// The configuration is built for the generator's registered name, so display
+/// names such as "TypeScript" and aliases such as "ts" select the same options.
+fn prepare_generator(
+    registry: &GeneratorRegistry,
     config: &CodegenConfig,
-    type_graph: &TypeGraph,
-) -> Result<HashMap<String, String>> {
-    let ts_config = config
-        .generators
-        .typescript
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("TypeScript generator not configured"))?;
-
-    // Create package config
-    let package_config = PackageConfig {
-        name: ts_config
-            .module_name
-            .clone()
-            .or_else(|| Some(config.project.name.clone()))
-            .unwrap_or_else(|| "fhir-sdk".to_string()),
-        version: config.project.version.clone(),
-        description: config
+    language: &str,
+) -> Result<(Arc<dyn CodeGenerator>, GeneratorConfig)> {
+    let generator = registry.get(language).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No generator registered for language: {}. Available generators: {}",
+            language,
+            registry.names().join(", ")
+        )
+    })?;
+
+    let generator_config = build_generator_config(config, &generator.metadata().name)?;
+    generator.validate_config(&generator_config)?;
+
+    Ok((generator, generator_config))
+}
+
+/// Build the generic generator configuration from the project configuration
+fn build_generator_config(config: &CodegenConfig, generator: &str) -> Result<GeneratorConfig> {
+    let mut generator_config = GeneratorConfig {
+        output_dir: config.output.directory.clone(),
+        module_name: Some(config.project.name.clone()),
+        package_version: config.project.version.clone(),
+        clean_output: config.output.clean,
+        ..Default::default()
+    };
+
+    // Project metadata shared by all generators
+    generator_config.set_custom(
+        "description".to_string(),
+        config
             .project
             .description
             .clone()
             .unwrap_or_else(|| "FHIR SDK generated by OctoFHIR Codegen".to_string()),
-        fhir_version: config.fhir.version.clone(),
-        repository_url: config.project.repository.clone(),
-        license: config.project.license.clone().unwrap_or_else(|| "Apache-2.0".to_string()),
-    };
+    )?;
+    if let Some(ref license) = config.project.license {
+        generator_config.set_custom("license".to_string(), license)?;
+    }
+    if let Some(ref repository) = config.project.repository {
+        generator_config.set_custom("repository_url".to_string(), repository)?;
+    }
+
+    match generator {
+        "typescript" => {
+            let ts_config = config
+                .generators
+                .typescript
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("TypeScript generator not configured"))?;
 
-    // Create SDK generator
-    let generator = TypeScriptSdkGenerator::new(package_config);
+            if let Some(ref module_name) = ts_config.module_name {
+                generator_config.module_name = Some(module_name.clone());
+            }
+            generator_config.emit_validation = ts_config.emit_validation;
+            generator_config.emit_helpers = ts_config.emit_helpers;
+            generator_config.emit_tests = ts_config.emit_tests;
+            generator_config
+                .set_custom("generate_classes".to_string(), ts_config.generate_classes)?;
+            generator_config
+                .set_custom("generate_extensions".to_string(), ts_config.generate_extensions)?;
+            generator_config
+                .set_custom("generate_search".to_string(), ts_config.generate_search)?;
+            generator_config
+                .set_custom("generate_client".to_string(), ts_config.generate_client)?;
+            generator_config
+                .set_custom("validation_style".to_string(), ts_config.validation_style)?;
+            generator_config.set_custom("strict_mode".to_string(), ts_config.strict_mode)?;
+            generator_config.set_custom("target_version".to_string(), &ts_config.target_version)?;
+            if !ts_config.type_mappings.is_empty() {
+                generator_config
+                    .set_custom("type_mappings".to_string(), &ts_config.type_mappings)?;
+            }
+            if !ts_config.compiler_options.is_empty() {
+                generator_config
+                    .set_custom("compiler_options".to_string(), &ts_config.compiler_options)?;
+            }
+        }
+        "kotlin" => {
+            let kotlin_config = config
+                .generators
+                .kotlin
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Kotlin generator not configured"))?;
+
+            if let Some(ref package_name) = kotlin_config.package_name {
+                generator_config.set_custom("package_name".to_string(), package_name)?;
+            }
+            if let Some(ref group_id) = kotlin_config.group_id {
+                generator_config.set_custom("group_id".to_string(), group_id)?;
+            }
+            generator_config
+                .set_custom("kotlin_version".to_string(), &kotlin_config.kotlin_version)?;
+            generator_config.set_custom(
+                "serialization_version".to_string(),
+                &kotlin_config.serialization_version,
+            )?;
+            generator_config.set_custom("targets".to_string(), &kotlin_config.targets)?;
+        }
+        "json-schema" => {
+            let json_schema_config = config
+                .generators
+                .json_schema
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("JSON Schema generator not configured"))?;
+
+            if let Some(ref schema_id) = json_schema_config.schema_id {
+                generator_config.set_custom("schema_id".to_string(), schema_id)?;
+            }
+        }
+        "openapi" => {
+            let openapi_config = config
+                .generators
+                .openapi
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("OpenAPI generator not configured"))?;
+
+            if let Some(ref server_url) = openapi_config.server_url {
+                generator_config.set_custom("server_url".to_string(), server_url)?;
+            }
+        }
+        "markdown" => {
+            let markdown_config = config
+                .generators
+                .markdown
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Markdown generator not configured"))?;
+
+            if let Some(ref flavor) = markdown_config.flavor {
+                generator_config.set_custom("flavor".to_string(), flavor)?;
+            }
+            if let Some(ref title) = markdown_config.title {
+                generator_config.set_custom("title".to_string(), title)?;
+            }
+        }
+        plugin => {
+            if let Some(options) = config.plugins.options_for(plugin) {
+                generator_config
+                    .custom_options
+                    .extend(options.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+    }
 
-    // Generate SDK files
-    Ok(generator.generate_sdk(type_graph)?)
+    Ok(generator_config)
 }
 
 /// Write generated files to disk
 fn write_files_to_disk(
     output_dir: &Path,
-    files: &HashMap<String, String>,
+    files: &[GeneratedFile],
     formatter: &OutputFormatter,
 ) -> Result<()> {
     let pb = ProgressBar::new(files.len() as u64);
@@ -370,8 +572,8 @@ fn write_files_to_disk(
     );
     pb.set_message("Writing files");
 
-    for (rel_path, content) in files {
-        let full_path = output_dir.join(rel_path);
+    for file in files {
+        let full_path = output_dir.join(&file.path);
 
         // Create parent directories if needed
         if let Some(parent) = full_path.parent() {
@@ -380,7 +582,7 @@ fn write_files_to_disk(
         }
 
         // Write file
-        fs::write(&full_path, content)
+        fs::write(&full_path, &file.content)
             .context(format!("Failed to write file: {}", full_path.display()))?;
 
         pb.inc(1);
@@ -427,6 +629,18 @@ fn filter_type_graph(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generator::GeneratedCode;
+
+    /// Generate SDK files by dispatching to the registered generator for a language
+    async fn generate_sdk_files(
+        registry: &GeneratorRegistry,
+        config: &CodegenConfig,
+        type_graph: &TypeGraph,
+        language: &str,
+    ) -> Result<GeneratedCode> {
+        let (generator, generator_config) = prepare_generator(registry, config, language)?;
+        Ok(generator.generate(type_graph, &generator_config).await?)
+    }
 
     #[test]
     fn test_apply_cli_overrides_language() {
@@ -485,10 +699,8 @@ mod tests {
     #[test]
     fn test_determine_language_from_config() {
         let mut config = CodegenConfig::default();
-        config.generators.typescript = Some(crate::cli::config::TypeScriptGeneratorConfig {
-            enabled: true,
-            ..Default::default()
-        });
+        config.generators.typescript =
+            Some(crate::config::TypeScriptGeneratorConfig { enabled: true, ..Default::default() });
 
         let options = GenerateOptions {
             config_path: PathBuf::from("test.toml"),
@@ -503,4 +715,312 @@ mod tests {
         let lang = determine_language(&config, &options).unwrap();
         assert_eq!(lang, "TypeScript");
     }
+
+    #[test]
+    fn test_kotlin_override_normalized() {
+        let mut config = CodegenConfig::default();
+        let options = GenerateOptions {
+            config_path: PathBuf::from("test.toml"),
+            language_override: Some("kt".to_string()),
+            output_override: None,
+            fhir_version_override: None,
+            additional_packages: vec![],
+            skip_validation: false,
+            no_clean: false,
+        };
+
+        apply_cli_overrides(&mut config, &options);
+
+        assert!(config.generators.kotlin.as_ref().is_some_and(|g| g.enabled));
+        assert_eq!(determine_language(&config, &options).unwrap(), "Kotlin");
+    }
+
+    #[tokio::test]
+    async fn test_generate_kotlin_sdk_files() {
+        let mut config = CodegenConfig::template_for("kotlin").unwrap();
+        config.project.name = "fhir-kotlin".to_string();
+        let graph = TypeGraph::new(FhirVersion::R4);
+
+        let code = generate_sdk_files(
+            &GeneratorRegistry::with_builtin_generators(),
+            &config,
+            &graph,
+            "Kotlin",
+        )
+        .await
+        .unwrap();
+        let file = |path: &str| code.files.iter().find(|f| f.path == Path::new(path));
+
+        assert!(file("build.gradle.kts").is_some());
+        assert!(
+            file("settings.gradle.kts")
+                .unwrap()
+                .content
+                .contains("rootProject.name = \"fhir-kotlin\"")
+        );
+        assert!(file("src/commonMain/kotlin/com/example/fhir/r4/FhirJson.kt").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_generate_json_schema_bundle() {
+        let mut config = CodegenConfig::default();
+        config.project.name = "fhir-r4".to_string();
+        let options = GenerateOptions {
+            config_path: PathBuf::from("test.toml"),
+            language_override: Some("json-schema".to_string()),
+            output_override: None,
+            fhir_version_override: None,
+            additional_packages: vec![],
+            skip_validation: false,
+            no_clean: false,
+        };
+        apply_cli_overrides(&mut config, &options);
+        let language = determine_language(&config, &options).unwrap();
+        assert_eq!(language, "JSON Schema");
+
+        let graph = TypeGraph::new(FhirVersion::R4);
+        let code = generate_sdk_files(
+            &GeneratorRegistry::with_builtin_generators(),
+            &config,
+            &graph,
+            &language,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(code.manifest.generator.name, "json-schema");
+        assert!(code.files.iter().any(|f| f.path == Path::new("fhir-r4.schema.json")));
+    }
+
+    #[tokio::test]
+    async fn test_generate_openapi_document() {
+        let config = CodegenConfig::template_for("openapi").unwrap();
+        let graph = TypeGraph::new(FhirVersion::R4);
+
+        let code = generate_sdk_files(
+            &GeneratorRegistry::with_builtin_generators(),
+            &config,
+            &graph,
+            "OpenAPI",
+        )
+        .await
+        .unwrap();
+        let document = code
+            .files
+            .iter()
+            .find(|f| f.path == Path::new("fhir-openapi-sdk.openapi.json"))
+            .unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&document.content).unwrap();
+        assert_eq!(parsed["openapi"], "3.1.0");
+        assert_eq!(parsed["info"]["version"], "0.1.0");
+        assert_eq!(parsed["servers"][0]["url"], "https://example.com/fhir");
+    }
+
+    #[tokio::test]
+    async fn test_generate_markdown_site() {
+        let mut config = CodegenConfig::template_for("markdown").unwrap();
+        config.generators.markdown.as_mut().unwrap().flavor = Some("docusaurus".to_string());
+        let graph = TypeGraph::new(FhirVersion::R4);
+
+        let code = generate_sdk_files(
+            &GeneratorRegistry::with_builtin_generators(),
+            &config,
+            &graph,
+            "Markdown",
+        )
+        .await
+        .unwrap();
+        let index = code.files.iter().find(|f| f.path == Path::new("docs/index.md")).unwrap();
+
+        assert_eq!(code.manifest.generator.name, "markdown");
+        assert!(index.content.contains("title: \"FHIR R4 Reference\"\n"));
+        assert!(index.content.contains("sidebar_label: \"FHIR R4 Reference\"\n"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_typescript_sdk_through_registry() {
+        let mut config = CodegenConfig::template_for("typescript").unwrap();
+        config.project.license = Some("MIT".to_string());
+        let graph = TypeGraph::new(FhirVersion::R4);
+
+        let code = generate_sdk_files(
+            &GeneratorRegistry::with_builtin_generators(),
+            &config,
+            &graph,
+            "TypeScript",
+        )
+        .await
+        .unwrap();
+        let package_json = code.files.iter().find(|f| f.path == Path::new("package.json")).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&package_json.content).unwrap();
+        assert_eq!(parsed["name"], "fhir-r4");
+        assert_eq!(parsed["license"], "MIT");
+        assert_eq!(code.manifest.generator.name, "typescript");
+    }
+
+    #[tokio::test]
+    async fn test_generate_typescript_tests_from_display_name() {
+        use crate::core::ir::{Documentation, ResourceType};
+
+        let mut config = CodegenConfig::template_for("typescript").unwrap();
+        config.generators.typescript.as_mut().unwrap().emit_tests = true;
+        let registry = GeneratorRegistry::with_builtin_generators();
+
+        // determine_language reports display names; they must select the TypeScript options
+        let options = GenerateOptions {
+            config_path: PathBuf::from("test.toml"),
+            language_override: None,
+            output_override: None,
+            fhir_version_override: None,
+            additional_packages: vec![],
+            skip_validation: false,
+            no_clean: false,
+        };
+        let language = determine_language(&config, &options).unwrap();
+        assert_eq!(language, "TypeScript");
+        let (_, generator_config) = prepare_generator(&registry, &config, &language).unwrap();
+        assert!(generator_config.emit_tests);
+
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource(
+            "Patient".to_string(),
+            ResourceType {
+                name: "Patient".to_string(),
+                base: None,
+                properties: vec![],
+                constraints: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: "http://hl7.org/fhir/StructureDefinition/Patient".to_string(),
+                is_abstract: false,
+            },
+        );
+        graph.add_example(
+            "Patient".to_string(),
+            serde_json::json!({ "resourceType": "Patient", "id": "example" }),
+        );
+
+        let code = generate_sdk_files(&registry, &config, &graph, &language).await.unwrap();
+        let fixture = |path: &str| code.files.iter().any(|f| f.path == Path::new(path));
+        assert!(fixture("tests/fixtures/Patient.examples.json"));
+        assert!(fixture("tests/resources/Patient.test.ts"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_unknown_language_fails() {
+        let config = CodegenConfig::default();
+        let graph = TypeGraph::new(FhirVersion::R4);
+
+        let err = generate_sdk_files(
+            &GeneratorRegistry::with_builtin_generators(),
+            &config,
+            &graph,
+            "Rust",
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("No generator registered"));
+    }
+
+    #[test]
+    fn test_plugin_generator_config() {
+        let config = CodegenConfig::from_toml_str(
+            r#"
+            [plugins]
+            paths = ["plugins"]
+            generator = "csharp"
+
+            [plugins.options.csharp]
+            namespace = "Example.Fhir"
+            nullable = true
+        "#,
+        )
+        .unwrap();
+        let options = GenerateOptions {
+            config_path: PathBuf::from("test.toml"),
+            language_override: None,
+            output_override: None,
+            fhir_version_override: None,
+            additional_packages: vec![],
+            skip_validation: false,
+            no_clean: false,
+        };
+
+        assert_eq!(determine_language(&config, &options).unwrap(), "csharp");
+
+        let generator_config = build_generator_config(&config, "csharp").unwrap();
+        assert_eq!(
+            generator_config.get_custom::<String>("namespace"),
+            Some("Example.Fhir".to_string())
+        );
+        assert_eq!(generator_config.get_custom::<bool>("nullable"), Some(true));
+    }
+
+    #[test]
+    fn test_language_override_keeps_typescript_options() {
+        let mut config = CodegenConfig::template_for("typescript").unwrap();
+        let ts = config.generators.typescript.as_mut().unwrap();
+        ts.enabled = false;
+        ts.generate_classes = true;
+        let options = GenerateOptions {
+            config_path: PathBuf::from("test.toml"),
+            language_override: Some("ts".to_string()),
+            output_override: None,
+            fhir_version_override: None,
+            additional_packages: vec![],
+            skip_validation: false,
+            no_clean: false,
+        };
+
+        apply_cli_overrides(&mut config, &options);
+
+        let ts = config.generators.typescript.as_ref().unwrap();
+        assert!(ts.enabled);
+        assert!(ts.generate_classes);
+    }
+
+    #[test]
+    fn test_typescript_generator_config() {
+        let config = CodegenConfig::from_toml_str(
+            r#"
+            [generators.typescript]
+            emit_validation = false
+            emit_tests = true
+            generate_classes = true
+            generate_extensions = false
+            generate_search = false
+            generate_client = true
+            strict_mode = false
+            target_version = "5.4"
+
+            [generators.typescript.type_mappings]
+            dateTime = "Date"
+
+            [generators.typescript.compiler_options]
+            source_map = false
+        "#,
+        )
+        .unwrap();
+
+        let generator_config = build_generator_config(&config, "typescript").unwrap();
+        assert!(!generator_config.emit_validation);
+        assert!(generator_config.emit_tests);
+        assert_eq!(generator_config.get_custom::<bool>("generate_classes"), Some(true));
+        assert_eq!(generator_config.get_custom::<bool>("generate_extensions"), Some(false));
+        assert_eq!(generator_config.get_custom::<bool>("generate_search"), Some(false));
+        assert_eq!(generator_config.get_custom::<bool>("generate_client"), Some(true));
+        assert_eq!(generator_config.get_custom::<bool>("strict_mode"), Some(false));
+        assert_eq!(generator_config.get_custom::<String>("target_version"), Some("5.4".into()));
+        assert_eq!(
+            generator_config.custom_options["type_mappings"],
+            serde_json::json!({ "dateTime": "Date" })
+        );
+        assert_eq!(
+            generator_config.custom_options["compiler_options"],
+            serde_json::json!({ "source_map": false })
+        );
+    }
 }
@@ -1,19 +1,27 @@
 //! Command-line interface
 //!
-//! This module provides CLI infrastructure including configuration management,
-//! command parsing, and output formatting.
+//! This module provides CLI infrastructure including configuration discovery,
+//! command parsing, and output formatting. The configuration schema itself lives
+//! in [`crate::config`].
 
 pub mod commands;
-pub mod config;
 pub mod discovery;
 pub mod generate;
 pub mod output;
 
-pub use commands::{Cli, CommandResult, Commands};
-pub use config::CodegenConfig;
+pub use crate::config::CodegenConfig;
+pub use commands::{Cli, CommandResult, Commands, ConfigCommands};
 pub use discovery::{DiscoveryResult, discover_config, ensure_config_exists};
-pub use generate::{GenerateOptions, GenerationResult, execute_generate};
+pub use generate::{
+    GenerateOptions, GenerationResult, execute_generate, load_generator_registry, load_type_graph,
+};
 pub use output::{
     OutputFormat, OutputFormatter, ProgressReporter, Verbosity, create_detailed_progress_bar,
     create_multi_progress, create_progress_bar, create_spinner,
 };
+
+/// Configuration schema, now in [`crate::config`]
+#[deprecated(since = "0.1.0", note = "use `octofhir_codegen::config` instead")]
+pub mod config {
+    pub use crate::config::*;
+}
@@ -0,0 +1,470 @@
+//! Legacy (pre-versioned) configuration format
+//!
+//! Early releases used a flat layout with top-level `fhir_version` and `output_dir`
+//! keys and `type`-tagged generator tables:
+//!
+//! ```toml
+//! fhir_version = "R4"
+//! output_dir = "./generated"
+//!
+//! [generators.typescript]
+//! type = "typescript"
+//! module_name = "fhir-r4"
+//! ```
+//!
+//! These types only exist to read such files and migrate them to the current
+//! [`CodegenConfig`](super::CodegenConfig) schema.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Top-level keys that only exist in the legacy layout
+const LEGACY_TOP_LEVEL_KEYS: &[&str] = &["fhir_version", "output_dir"];
+
+/// Check whether a parsed TOML document uses the legacy layout
+///
+/// A document is legacy if it has a legacy top-level key, or a generator table
+/// tagged with `type`.
+pub fn is_legacy(document: &toml::Table) -> bool {
+    if LEGACY_TOP_LEVEL_KEYS.iter().any(|key| document.contains_key(*key)) {
+        return true;
+    }
+
+    document.get("generators").and_then(toml::Value::as_table).is_some_and(|generators| {
+        generators
+            .values()
+            .filter_map(toml::Value::as_table)
+            .any(|generator| generator.contains_key("type"))
+    })
+}
+
+/// Result of migrating a legacy configuration
+#[derive(Debug, Clone)]
+pub struct Migration {
+    /// Configuration in the current schema
+    pub config: super::CodegenConfig,
+
+    /// Notes about settings that could not be carried over exactly
+    pub notes: Vec<String>,
+}
+
+/// Legacy codegen configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LegacyConfig {
+    /// FHIR version to generate for
+    pub fhir_version: String,
+
+    /// Output directory
+    pub output_dir: String,
+
+    /// Generator-specific configurations
+    pub generators: HashMap<String, LegacyGeneratorConfig>,
+}
+
+impl Default for LegacyConfig {
+    fn default() -> Self {
+        Self {
+            fhir_version: "R4".to_string(),
+            output_dir: "./generated".to_string(),
+            generators: HashMap::new(),
+        }
+    }
+}
+
+/// Configuration for a specific language generator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum LegacyGeneratorConfig {
+    /// TypeScript generator configuration
+    #[serde(rename = "typescript")]
+    TypeScript(TypeScriptConfig),
+
+    /// Rust generator configuration (future)
+    #[serde(rename = "rust")]
+    Rust(RustConfig),
+}
+
+/// TypeScript generator configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeScriptConfig {
+    /// Whether this generator is enabled
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Module/package name
+    #[serde(default = "default_module_name")]
+    pub module_name: String,
+
+    /// Generate classes instead of interfaces
+    #[serde(default)]
+    pub generate_classes: bool,
+
+    /// Generate separate builder classes
+    #[serde(default)]
+    pub generate_builders: bool,
+
+    /// Generate extension helper methods
+    #[serde(default = "default_true")]
+    pub generate_extensions: bool,
+
+    /// Use TypeScript strict mode
+    #[serde(default = "default_true")]
+    pub strict_mode: bool,
+
+    /// Emit validation functions
+    #[serde(default = "default_true")]
+    pub emit_validation: bool,
+
+    /// Emit helper methods
+    #[serde(default = "default_true")]
+    pub emit_helpers: bool,
+
+    /// Emit test files
+    #[serde(default)]
+    pub emit_tests: bool,
+
+    /// Target TypeScript version
+    #[serde(default = "default_ts_version")]
+    pub target_version: String,
+
+    /// TypeScript compiler options
+    #[serde(default)]
+    pub compiler_options: TypeScriptCompilerOptions,
+}
+
+impl Default for TypeScriptConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            module_name: "fhir-r4".to_string(),
+            generate_classes: false,
+            generate_builders: false,
+            generate_extensions: true,
+            strict_mode: true,
+            emit_validation: true,
+            emit_helpers: true,
+            emit_tests: false,
+            target_version: "5.3".to_string(),
+            compiler_options: TypeScriptCompilerOptions::default(),
+        }
+    }
+}
+
+/// TypeScript compiler options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeScriptCompilerOptions {
+    /// Enable all strict type-checking options
+    #[serde(default = "default_true")]
+    pub strict: bool,
+
+    /// Enable ES module interop
+    #[serde(default = "default_true")]
+    pub es_module_interop: bool,
+
+    /// Generate declaration files
+    #[serde(default = "default_true")]
+    pub declaration: bool,
+
+    /// Generate source maps
+    #[serde(default)]
+    pub source_map: bool,
+
+    /// Module resolution strategy
+    #[serde(default = "default_module_resolution")]
+    pub module_resolution: String,
+}
+
+impl Default for TypeScriptCompilerOptions {
+    fn default() -> Self {
+        Self {
+            strict: true,
+            es_module_interop: true,
+            declaration: true,
+            source_map: false,
+            module_resolution: "node".to_string(),
+        }
+    }
+}
+
+/// Rust generator configuration (placeholder for future)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustConfig {
+    /// Whether this generator is enabled
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Crate name
+    #[serde(default = "default_rust_crate_name")]
+    pub crate_name: String,
+
+    /// Use serde for serialization
+    #[serde(default = "default_true")]
+    pub use_serde: bool,
+
+    /// Generate builder pattern
+    #[serde(default = "default_true")]
+    pub generate_builders: bool,
+}
+
+impl Default for RustConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            crate_name: "fhir-r4".to_string(),
+            use_serde: true,
+            generate_builders: true,
+        }
+    }
+}
+
+// Default value functions for serde
+fn default_true() -> bool {
+    true
+}
+
+fn default_module_name() -> String {
+    "fhir-r4".to_string()
+}
+
+fn default_ts_version() -> String {
+    "5.3".to_string()
+}
+
+fn default_module_resolution() -> String {
+    "node".to_string()
+}
+
+fn default_rust_crate_name() -> String {
+    "fhir-r4".to_string()
+}
+
+impl LegacyConfig {
+    /// Load configuration from TOML file
+    pub fn from_toml(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    /// Save configuration to TOML string
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+
+    /// Get TypeScript configuration if present
+    pub fn typescript_config(&self) -> Option<&TypeScriptConfig> {
+        self.generators.get("typescript").and_then(|g| {
+            if let LegacyGeneratorConfig::TypeScript(config) = g { Some(config) } else { None }
+        })
+    }
+
+    /// Get Rust configuration if present
+    pub fn rust_config(&self) -> Option<&RustConfig> {
+        self.generators.get("rust").and_then(|g| {
+            if let LegacyGeneratorConfig::Rust(config) = g { Some(config) } else { None }
+        })
+    }
+
+    /// Convert to the current configuration schema
+    pub fn migrate(self) -> Migration {
+        use super::{CodegenConfig, RustGeneratorConfig, TypeScriptGeneratorConfig};
+
+        let mut config = CodegenConfig::default();
+        let mut notes = Vec::new();
+
+        // Legacy files implied the core package of the configured version
+        match core_package(&self.fhir_version) {
+            Some(package) => {
+                config.fhir.packages = vec![package.to_string()];
+                // Legacy files accepted any case ("r4b"); the current schema expects "R4B"
+                config.fhir.version = self.fhir_version.to_ascii_uppercase();
+            }
+            None => {
+                notes.push(format!(
+                    "No core package known for FHIR version '{}'; add one to fhir.packages",
+                    self.fhir_version
+                ));
+                config.fhir.version = self.fhir_version;
+            }
+        }
+        config.output.directory = PathBuf::from(self.output_dir);
+
+        // Sort for deterministic notes
+        let mut generators: Vec<_> = self.generators.into_iter().collect();
+        generators.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (name, generator) in generators {
+            match generator {
+                LegacyGeneratorConfig::TypeScript(ts) => {
+                    if ts.generate_builders {
+                        notes.push(format!(
+                            "generators.{}.generate_builders has no equivalent and was dropped",
+                            name
+                        ));
+                    }
+                    config.project.name.clone_from(&ts.module_name);
+                    config.generators.typescript = Some(TypeScriptGeneratorConfig {
+                        enabled: ts.enabled,
+                        module_name: Some(ts.module_name),
+                        emit_validation: ts.emit_validation,
+                        validation_style: Default::default(),
+                        emit_helpers: ts.emit_helpers,
+                        emit_tests: ts.emit_tests,
+                        generate_classes: ts.generate_classes,
+                        generate_extensions: ts.generate_extensions,
+                        generate_search: true,
+                        generate_client: false,
+                        strict_mode: ts.strict_mode,
+                        target_version: ts.target_version,
+                        type_mappings: HashMap::new(),
+                        compiler_options: ts.compiler_options.into_map(),
+                    });
+                }
+                LegacyGeneratorConfig::Rust(rust) => {
+                    if !rust.use_serde {
+                        notes.push(format!(
+                            "generators.{}.use_serde = false is not supported; serde is always used",
+                            name
+                        ));
+                    }
+                    if rust.generate_builders {
+                        notes.push(format!(
+                            "generators.{}.generate_builders has no equivalent and was dropped",
+                            name
+                        ));
+                    }
+                    config.generators.rust = Some(RustGeneratorConfig {
+                        enabled: rust.enabled,
+                        crate_name: Some(rust.crate_name),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        Migration { config, notes }
+    }
+}
+
+/// Core package implied by a legacy FHIR version
+fn core_package(fhir_version: &str) -> Option<&'static str> {
+    match fhir_version.to_ascii_uppercase().as_str() {
+        "R4" => Some("hl7.fhir.r4.core@4.0.1"),
+        "R4B" => Some("hl7.fhir.r4b.core@4.3.0"),
+        "R5" => Some("hl7.fhir.r5.core@5.0.0"),
+        _ => None,
+    }
+}
+
+impl TypeScriptCompilerOptions {
+    /// Convert to the free-form compiler options map of the current schema
+    fn into_map(self) -> HashMap<String, serde_json::Value> {
+        HashMap::from([
+            ("strict".to_string(), self.strict.into()),
+            ("es_module_interop".to_string(), self.es_module_interop.into()),
+            ("declaration".to_string(), self.declaration.into()),
+            ("source_map".to_string(), self.source_map.into()),
+            ("module_resolution".to_string(), self.module_resolution.into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = LegacyConfig::default();
+        assert_eq!(config.fhir_version, "R4");
+        assert_eq!(config.output_dir, "./generated");
+    }
+
+    #[test]
+    fn test_typescript_config_defaults() {
+        let config = TypeScriptConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.module_name, "fhir-r4");
+        assert!(!config.generate_classes);
+        assert!(config.generate_extensions);
+        assert!(config.strict_mode);
+    }
+
+    #[test]
+    fn test_toml_serialization() {
+        let config = LegacyConfig::default();
+        let toml_str = config.to_toml().unwrap();
+        assert!(toml_str.contains("fhir_version"));
+        assert!(toml_str.contains("output_dir"));
+    }
+
+    #[test]
+    fn test_toml_deserialization() {
+        let toml_str = r#"
+            fhir_version = "R4"
+            output_dir = "./output"
+
+            [generators.typescript]
+            type = "typescript"
+            enabled = true
+            module_name = "my-fhir"
+            generate_classes = true
+            generate_extensions = true
+        "#;
+
+        let config: LegacyConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.fhir_version, "R4");
+        assert_eq!(config.output_dir, "./output");
+
+        let ts_config = config.typescript_config().unwrap();
+        assert_eq!(ts_config.module_name, "my-fhir");
+        assert!(ts_config.generate_classes);
+    }
+
+    #[test]
+    fn test_detect_legacy() {
+        let legacy: toml::Table = toml::from_str("fhir_version = \"R4\"").unwrap();
+        assert!(is_legacy(&legacy));
+
+        let tagged: toml::Table =
+            toml::from_str("[generators.typescript]\ntype = \"typescript\"").unwrap();
+        assert!(is_legacy(&tagged));
+
+        let current: toml::Table =
+            toml::from_str("[fhir]\nversion = \"R4\"\n[generators.typescript]\nenabled = true")
+                .unwrap();
+        assert!(!is_legacy(&current));
+    }
+
+    #[test]
+    fn test_migrate() {
+        let toml_str = r#"
+            fhir_version = "R5"
+            output_dir = "./out"
+
+            [generators.typescript]
+            type = "typescript"
+            module_name = "my-fhir"
+            generate_classes = true
+            generate_builders = true
+
+            [generators.typescript.compiler_options]
+            source_map = true
+        "#;
+
+        let config: LegacyConfig = toml::from_str(toml_str).unwrap();
+        let migration = config.migrate();
+
+        assert_eq!(migration.config.fhir.version, "R5");
+        assert_eq!(migration.config.fhir.packages, vec!["hl7.fhir.r5.core@5.0.0"]);
+        assert_eq!(migration.config.output.directory, PathBuf::from("./out"));
+        assert_eq!(migration.config.project.name, "my-fhir");
+
+        let ts = migration.config.generators.typescript.unwrap();
+        assert_eq!(ts.module_name.as_deref(), Some("my-fhir"));
+        assert!(ts.generate_classes);
+        assert_eq!(ts.compiler_options["source_map"], serde_json::json!(true));
+        assert_eq!(migration.notes.len(), 1);
+        assert!(migration.notes[0].contains("generate_builders"));
+    }
+}
@@ -1,225 +1,1222 @@
-//! Configuration for code generation
+//! Configuration management for OctoFHIR Codegen
 //!
-//! Defines configuration structures for controlling code generation behavior
-//! across different language backends.
+//! This module handles parsing and validation of the `codegen.toml` configuration file.
+//! It supports environment variable overrides and provides sensible defaults.
+//!
+//! The schema is versioned through the top-level `schema_version` key. Files written
+//! in the legacy flat layout (`fhir_version`, `output_dir` and `type`-tagged generator
+//! tables) are migrated on load; see [`legacy`].
+
+pub mod legacy;
 
+use crate::languages::typescript::ValidationStyle;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-/// Complete codegen configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Current version of the configuration schema
+pub const CONFIG_SCHEMA_VERSION: u32 = 1;
+
+/// Main configuration structure for OctoFHIR Codegen
+///
+/// This structure represents the complete configuration from `codegen.toml`.
+/// All sections are optional with sensible defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct CodegenConfig {
-    /// FHIR version to generate for
-    pub fhir_version: String,
+    /// Configuration schema version
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+
+    /// Project metadata
+    #[serde(default)]
+    pub project: ProjectConfig,
+
+    /// FHIR-specific configuration
+    #[serde(default)]
+    pub fhir: FhirConfig,
 
-    /// Output directory
-    pub output_dir: String,
+    /// Output directory and options
+    #[serde(default)]
+    pub output: OutputConfig,
 
-    /// Generator-specific configurations
-    pub generators: HashMap<String, GeneratorConfig>,
+    /// Language generator configurations
+    #[serde(default)]
+    pub generators: GeneratorsConfig,
+
+    /// Out-of-tree generator plugins
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    /// Canonical manager integration settings
+    #[serde(default)]
+    pub canonical_manager: Option<CanonicalManagerConfig>,
+
+    /// Logging configuration
+    #[serde(default)]
+    pub logging: LoggingConfig,
 }
 
 impl Default for CodegenConfig {
     fn default() -> Self {
         Self {
-            fhir_version: "R4".to_string(),
-            output_dir: "./generated".to_string(),
-            generators: HashMap::new(),
+            schema_version: CONFIG_SCHEMA_VERSION,
+            project: ProjectConfig::default(),
+            fhir: FhirConfig::default(),
+            output: OutputConfig::default(),
+            generators: GeneratorsConfig::default(),
+            plugins: PluginsConfig::default(),
+            canonical_manager: None,
+            logging: LoggingConfig::default(),
+        }
+    }
+}
+
+/// Project metadata configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Project name
+    #[serde(default = "default_project_name")]
+    pub name: String,
+
+    /// Project version (semver)
+    #[serde(default = "default_version")]
+    pub version: String,
+
+    /// Project description
+    pub description: Option<String>,
+
+    /// Authors
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+
+    /// License identifier (e.g., "MIT", "Apache-2.0")
+    pub license: Option<String>,
+
+    /// Repository URL
+    pub repository: Option<String>,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            name: default_project_name(),
+            version: default_version(),
+            description: None,
+            authors: Vec::new(),
+            license: None,
+            repository: None,
+        }
+    }
+}
+
+/// FHIR-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FhirConfig {
+    /// FHIR version to target (R4, R4B, R5, R6)
+    #[serde(default = "default_fhir_version")]
+    pub version: String,
+
+    /// FHIR packages to include (e.g., "hl7.fhir.r4.core@4.0.1")
+    #[serde(default)]
+    pub packages: Vec<String>,
+
+    /// Specific resource types to generate (if empty, generates all)
+    /// Example: ["Patient", "Observation", "Condition"]
+    #[serde(default)]
+    pub include_resources: Vec<String>,
+
+    /// Resource types to exclude from generation (applied after include_resources)
+    /// Note: If include_resources is set, this is typically not needed
+    #[serde(default)]
+    pub exclude_resources: Vec<String>,
+
+    /// Specific profiles to generate (optional, generates all if omitted)
+    #[serde(default)]
+    pub include_profiles: Vec<String>,
+
+    /// Extension URLs to include
+    #[serde(default)]
+    pub include_extensions: Vec<String>,
+
+    /// Whether to generate only core resources (no profiles or extensions)
+    #[serde(default)]
+    pub core_only: bool,
+}
+
+impl Default for FhirConfig {
+    fn default() -> Self {
+        Self {
+            version: default_fhir_version(),
+            packages: Vec::new(),
+            include_resources: Vec::new(),
+            exclude_resources: Vec::new(),
+            include_profiles: Vec::new(),
+            include_extensions: Vec::new(),
+            core_only: false,
+        }
+    }
+}
+
+/// Output directory and file management configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Output directory for generated code
+    #[serde(default = "default_output_directory")]
+    pub directory: PathBuf,
+
+    /// Clean output directory before generation
+    #[serde(default = "default_true")]
+    pub clean: bool,
+
+    /// Create directory if it doesn't exist
+    #[serde(default = "default_true")]
+    pub create_if_missing: bool,
+
+    /// Overwrite existing files
+    #[serde(default = "default_true")]
+    pub overwrite: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            directory: default_output_directory(),
+            clean: true,
+            create_if_missing: true,
+            overwrite: true,
         }
     }
 }
 
-/// Configuration for a specific language generator
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
-pub enum GeneratorConfig {
+/// Language generator configurations
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GeneratorsConfig {
     /// TypeScript generator configuration
-    #[serde(rename = "typescript")]
-    TypeScript(TypeScriptConfig),
+    pub typescript: Option<TypeScriptGeneratorConfig>,
+
+    /// Rust generator configuration
+    pub rust: Option<RustGeneratorConfig>,
+
+    /// Python generator configuration
+    pub python: Option<PythonGeneratorConfig>,
+
+    /// Java generator configuration
+    pub java: Option<JavaGeneratorConfig>,
+
+    /// Kotlin Multiplatform generator configuration
+    pub kotlin: Option<KotlinGeneratorConfig>,
+
+    /// JSON Schema generator configuration
+    pub json_schema: Option<JsonSchemaGeneratorConfig>,
 
-    /// Rust generator configuration (future)
-    #[serde(rename = "rust")]
-    Rust(RustConfig),
+    /// OpenAPI generator configuration
+    pub openapi: Option<OpenApiGeneratorConfig>,
+
+    /// Markdown documentation site generator configuration
+    pub markdown: Option<MarkdownGeneratorConfig>,
 }
 
-/// TypeScript generator configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TypeScriptConfig {
-    /// Whether this generator is enabled
+/// TypeScript generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TypeScriptGeneratorConfig {
+    /// Enable/disable TypeScript generator
     #[serde(default = "default_true")]
     pub enabled: bool,
 
     /// Module/package name
-    #[serde(default = "default_module_name")]
-    pub module_name: String,
+    pub module_name: Option<String>,
+
+    /// Generate validation functions
+    #[serde(default = "default_true")]
+    pub emit_validation: bool,
 
-    /// Generate classes instead of interfaces
+    /// Validation output: "functions" (validateX functions) or "zod" (Zod schemas)
     #[serde(default)]
-    pub generate_classes: bool,
+    pub validation_style: ValidationStyle,
+
+    /// Generate helper methods
+    #[serde(default = "default_true")]
+    pub emit_helpers: bool,
+
+    /// Generate test files
+    #[serde(default)]
+    pub emit_tests: bool,
 
-    /// Generate separate builder classes
+    /// Generate classes with fluent builders instead of interfaces
     #[serde(default)]
-    pub generate_builders: bool,
+    pub generate_classes: bool,
 
-    /// Generate extension helper methods
+    /// Generate extension helpers
     #[serde(default = "default_true")]
     pub generate_extensions: bool,
 
-    /// Use TypeScript strict mode
+    /// Generate typed search query builders
+    #[serde(default = "default_true")]
+    pub generate_search: bool,
+
+    /// Generate a typed REST client (`FhirClient`)
+    #[serde(default)]
+    pub generate_client: bool,
+
+    /// Enable TypeScript strict mode in the generated tsconfig.json
     #[serde(default = "default_true")]
     pub strict_mode: bool,
 
-    /// Emit validation functions
+    /// TypeScript version to target
+    #[serde(default = "default_ts_version")]
+    pub target_version: String,
+
+    /// Custom type mappings (FHIR type -> TypeScript type)
+    #[serde(default, serialize_with = "serialize_sorted")]
+    pub type_mappings: HashMap<String, String>,
+
+    /// Additional compiler options for generated tsconfig.json
+    #[serde(default, serialize_with = "serialize_sorted")]
+    pub compiler_options: HashMap<String, serde_json::Value>,
+}
+
+impl Default for TypeScriptGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            module_name: None,
+            emit_validation: true,
+            validation_style: ValidationStyle::default(),
+            emit_helpers: true,
+            emit_tests: false,
+            generate_classes: false,
+            generate_extensions: true,
+            generate_search: true,
+            generate_client: false,
+            strict_mode: true,
+            target_version: default_ts_version(),
+            type_mappings: HashMap::new(),
+            compiler_options: HashMap::new(),
+        }
+    }
+}
+
+/// Rust generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RustGeneratorConfig {
+    /// Enable/disable Rust generator
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Crate name
+    pub crate_name: Option<String>,
+
+    /// Generate validation implementations
     #[serde(default = "default_true")]
     pub emit_validation: bool,
 
-    /// Emit helper methods
+    /// Generate helper methods
     #[serde(default = "default_true")]
     pub emit_helpers: bool,
 
-    /// Emit test files
+    /// Generate test modules
     #[serde(default)]
     pub emit_tests: bool,
 
-    /// Target TypeScript version
-    #[serde(default = "default_ts_version")]
-    pub target_version: String,
+    /// Rust edition (2021, 2024)
+    #[serde(default = "default_rust_edition")]
+    pub edition: String,
 
-    /// TypeScript compiler options
+    /// Additional derives to include
     #[serde(default)]
-    pub compiler_options: TypeScriptCompilerOptions,
+    pub additional_derives: Vec<String>,
 }
 
-impl Default for TypeScriptConfig {
+impl Default for RustGeneratorConfig {
     fn default() -> Self {
         Self {
-            enabled: true,
-            module_name: "fhir-r4".to_string(),
-            generate_classes: false,
-            generate_builders: false,
-            generate_extensions: true,
-            strict_mode: true,
+            enabled: false,
+            crate_name: None,
             emit_validation: true,
             emit_helpers: true,
             emit_tests: false,
-            target_version: "5.3".to_string(),
-            compiler_options: TypeScriptCompilerOptions::default(),
+            edition: default_rust_edition(),
+            additional_derives: Vec::new(),
         }
     }
 }
 
-/// TypeScript compiler options
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TypeScriptCompilerOptions {
-    /// Enable all strict type-checking options
-    #[serde(default = "default_true")]
-    pub strict: bool,
+/// Python generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PythonGeneratorConfig {
+    /// Enable/disable Python generator
+    #[serde(default)]
+    pub enabled: bool,
 
-    /// Enable ES module interop
-    #[serde(default = "default_true")]
-    pub es_module_interop: bool,
+    /// Package name
+    pub package_name: Option<String>,
 
-    /// Generate declaration files
+    /// Generate Pydantic models
     #[serde(default = "default_true")]
-    pub declaration: bool,
+    pub use_pydantic: bool,
 
-    /// Generate source maps
-    #[serde(default)]
-    pub source_map: bool,
+    /// Generate type stubs (.pyi files)
+    #[serde(default = "default_true")]
+    pub generate_stubs: bool,
 
-    /// Module resolution strategy
-    #[serde(default = "default_module_resolution")]
-    pub module_resolution: String,
+    /// Python version to target
+    #[serde(default = "default_python_version")]
+    pub target_version: String,
 }
 
-impl Default for TypeScriptCompilerOptions {
+impl Default for PythonGeneratorConfig {
     fn default() -> Self {
         Self {
-            strict: true,
-            es_module_interop: true,
-            declaration: true,
-            source_map: false,
-            module_resolution: "node".to_string(),
+            enabled: false,
+            package_name: None,
+            use_pydantic: true,
+            generate_stubs: true,
+            target_version: default_python_version(),
         }
     }
 }
 
-/// Rust generator configuration (placeholder for future)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RustConfig {
-    /// Whether this generator is enabled
-    #[serde(default = "default_true")]
+/// Java generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JavaGeneratorConfig {
+    /// Enable/disable Java generator
+    #[serde(default)]
     pub enabled: bool,
 
-    /// Crate name
-    #[serde(default = "default_rust_crate_name")]
-    pub crate_name: String,
+    /// Package name (e.g., "com.example.fhir")
+    pub package_name: Option<String>,
+
+    /// Java version to target
+    #[serde(default = "default_java_version")]
+    pub target_version: String,
 
-    /// Use serde for serialization
+    /// Use Jackson for JSON serialization
     #[serde(default = "default_true")]
-    pub use_serde: bool,
+    pub use_jackson: bool,
 
-    /// Generate builder pattern
+    /// Generate validation annotations
     #[serde(default = "default_true")]
-    pub generate_builders: bool,
+    pub emit_validation: bool,
 }
 
-impl Default for RustConfig {
+impl Default for JavaGeneratorConfig {
     fn default() -> Self {
         Self {
-            enabled: true,
-            crate_name: "fhir-r4".to_string(),
-            use_serde: true,
-            generate_builders: true,
+            enabled: false,
+            package_name: None,
+            target_version: default_java_version(),
+            use_jackson: true,
+            emit_validation: true,
+        }
+    }
+}
+
+/// Kotlin Multiplatform generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KotlinGeneratorConfig {
+    /// Enable/disable Kotlin generator
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Base package name (e.g., "com.example.fhir")
+    pub package_name: Option<String>,
+
+    /// Maven group id for the generated Gradle project
+    pub group_id: Option<String>,
+
+    /// Kotlin compiler and plugin version
+    #[serde(default = "default_kotlin_version")]
+    pub kotlin_version: String,
+
+    /// kotlinx.serialization runtime version
+    #[serde(default = "default_kotlin_serialization_version")]
+    pub serialization_version: String,
+
+    /// Multiplatform targets (jvm, android, ios, js)
+    #[serde(default = "default_kotlin_targets")]
+    pub targets: Vec<String>,
+}
+
+impl Default for KotlinGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            package_name: None,
+            group_id: None,
+            kotlin_version: default_kotlin_version(),
+            serialization_version: default_kotlin_serialization_version(),
+            targets: default_kotlin_targets(),
         }
     }
 }
 
+/// JSON Schema generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct JsonSchemaGeneratorConfig {
+    /// Enable/disable JSON Schema generator
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// `$id` of the generated bundle (an absolute URI)
+    pub schema_id: Option<String>,
+}
+
+/// OpenAPI generator-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct OpenApiGeneratorConfig {
+    /// Enable/disable OpenAPI generator
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// FHIR base URL listed under `servers` (an absolute URL or a path)
+    pub server_url: Option<String>,
+}
+
+/// Markdown documentation site generator configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MarkdownGeneratorConfig {
+    /// Enable/disable Markdown generator
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Site layout: "mdbook" (default) or "docusaurus"
+    pub flavor: Option<String>,
+
+    /// Site title
+    pub title: Option<String>,
+}
+
+/// Out-of-tree generator plugin configuration
+///
+/// Plugins are external executables or dynamic libraries speaking the JSON
+/// plugin protocol (see [`crate::generator::plugin`]).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PluginsConfig {
+    /// Plugin files or directories containing plugins, relative to the config file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
+
+    /// Plugin generator to use when no built-in generator is enabled
+    pub generator: Option<String>,
+
+    /// Per-plugin options, passed to the plugin as custom generator options
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted_options"
+    )]
+    pub options: HashMap<String, HashMap<String, serde_json::Value>>,
+}
+
+impl PluginsConfig {
+    /// Plugin paths resolved against the directory containing the config file
+    pub fn resolve_paths(&self, base_dir: &Path) -> Vec<PathBuf> {
+        self.paths
+            .iter()
+            .map(|path| if path.is_absolute() { path.clone() } else { base_dir.join(path) })
+            .collect()
+    }
+
+    /// Options configured for a plugin, looked up case-insensitively
+    pub fn options_for(&self, name: &str) -> Option<&HashMap<String, serde_json::Value>> {
+        self.options.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+    }
+}
+
+/// Canonical manager integration configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CanonicalManagerConfig {
+    /// Path to canonical-manager configuration file
+    pub config_path: Option<PathBuf>,
+
+    /// Registry URL (overrides config)
+    pub registry_url: Option<String>,
+
+    /// Local cache directory
+    pub cache_dir: Option<PathBuf>,
+
+    /// Enable offline mode
+    #[serde(default)]
+    pub offline: bool,
+}
+
+/// Logging configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// Log level (trace, debug, info, warn, error)
+    #[serde(default = "default_log_level")]
+    pub level: String,
+
+    /// Log format (pretty, json, compact)
+    #[serde(default = "default_log_format")]
+    pub format: String,
+
+    /// Enable colored output
+    #[serde(default = "default_true")]
+    pub color: bool,
+
+    /// Log to file
+    pub file: Option<PathBuf>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self { level: default_log_level(), format: default_log_format(), color: true, file: None }
+    }
+}
+
+// ============================================================================
 // Default value functions for serde
-fn default_true() -> bool {
-    true
+// ============================================================================
+
+fn default_schema_version() -> u32 {
+    CONFIG_SCHEMA_VERSION
+}
+
+fn default_project_name() -> String {
+    "fhir-sdk".to_string()
 }
 
-fn default_module_name() -> String {
-    "fhir-r4".to_string()
+fn default_version() -> String {
+    "0.1.0".to_string()
+}
+
+fn default_fhir_version() -> String {
+    "R4".to_string()
+}
+
+fn default_output_directory() -> PathBuf {
+    PathBuf::from("./generated")
+}
+
+fn default_true() -> bool {
+    true
 }
 
 fn default_ts_version() -> String {
     "5.3".to_string()
 }
 
-fn default_module_resolution() -> String {
-    "node".to_string()
+fn default_rust_edition() -> String {
+    "2024".to_string()
+}
+
+fn default_python_version() -> String {
+    "3.11".to_string()
+}
+
+fn default_java_version() -> String {
+    "17".to_string()
+}
+
+fn default_kotlin_version() -> String {
+    "2.0.21".to_string()
+}
+
+fn default_kotlin_serialization_version() -> String {
+    "1.7.3".to_string()
+}
+
+fn default_kotlin_targets() -> Vec<String> {
+    vec!["jvm".to_string(), "android".to_string(), "ios".to_string()]
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
 }
 
-fn default_rust_crate_name() -> String {
-    "fhir-r4".to_string()
+fn default_log_format() -> String {
+    "pretty".to_string()
 }
 
+// ============================================================================
+// Implementation methods
+// ============================================================================
+
 impl CodegenConfig {
-    /// Load configuration from TOML file
-    pub fn from_toml(content: &str) -> Result<Self, toml::de::Error> {
-        toml::from_str(content)
+    /// Parse configuration from a TOML string
+    ///
+    /// Legacy configuration files are migrated transparently; use
+    /// [`CodegenConfig::migrate_toml_str`] to inspect the migration.
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        if let Some(migration) = Self::migrate_toml_str(content)? {
+            tracing::warn!(
+                "Configuration uses the legacy format; run 'octofhir-codegen config migrate' to update it"
+            );
+            return Ok(migration.config);
+        }
+
+        let document: toml::Table =
+            toml::from_str(content).context("Failed to parse TOML configuration")?;
+        if let Some(version) = document.get("schema_version").and_then(toml::Value::as_integer)
+            && version > i64::from(CONFIG_SCHEMA_VERSION)
+        {
+            anyhow::bail!(
+                "Configuration schema version {} is newer than the supported version {}. \
+                 Please upgrade octofhir-codegen.",
+                version,
+                CONFIG_SCHEMA_VERSION
+            );
+        }
+
+        toml::from_str(content).context("Failed to parse TOML configuration")
     }
 
-    /// Save configuration to TOML string
-    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
-        toml::to_string_pretty(self)
+    /// Migrate a legacy configuration document
+    ///
+    /// Returns `None` if the document already uses the current schema.
+    pub fn migrate_toml_str(content: &str) -> Result<Option<legacy::Migration>> {
+        let document: toml::Table =
+            toml::from_str(content).context("Failed to parse TOML configuration")?;
+        if !legacy::is_legacy(&document) {
+            return Ok(None);
+        }
+
+        let legacy: legacy::LegacyConfig =
+            toml::from_str(content).context("Failed to parse legacy configuration")?;
+        Ok(Some(legacy.migrate()))
+    }
+
+    /// Load configuration from a TOML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read config file: {:?}", path))?;
+        Self::from_toml_str(&content)
     }
 
-    /// Get TypeScript configuration if present
-    pub fn typescript_config(&self) -> Option<&TypeScriptConfig> {
-        self.generators.get("typescript").and_then(|g| {
-            if let GeneratorConfig::TypeScript(config) = g { Some(config) } else { None }
-        })
+    /// Save configuration to a TOML file
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let content = toml::to_string_pretty(self).context("Failed to serialize configuration")?;
+        std::fs::write(path, content)
+            .context(format!("Failed to write config file: {:?}", path))?;
+        Ok(())
     }
 
-    /// Get Rust configuration if present
-    pub fn rust_config(&self) -> Option<&RustConfig> {
-        self.generators
-            .get("rust")
-            .and_then(|g| if let GeneratorConfig::Rust(config) = g { Some(config) } else { None })
+    /// Serialize configuration to TOML string
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize configuration")
     }
+
+    /// Validate the configuration
+    ///
+    /// Checks for:
+    /// - Valid FHIR version
+    /// - At least one enabled generator
+    /// - Valid output directory
+    /// - Valid package specifications
+    /// - Valid log level
+    pub fn validate(&self) -> Result<Vec<String>> {
+        let mut warnings = Vec::new();
+
+        // Validate schema version
+        if self.schema_version == 0 || self.schema_version > CONFIG_SCHEMA_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported schema_version: {}. Must be between 1 and {}",
+                self.schema_version,
+                CONFIG_SCHEMA_VERSION
+            ));
+        }
+
+        // Validate FHIR version
+        match self.fhir.version.as_str() {
+            "R4" | "R4B" | "R5" | "R6" => {}
+            v => {
+                return Err(anyhow::anyhow!(
+                    "Invalid FHIR version: '{}'. Must be one of: R4, R4B, R5, R6",
+                    v
+                ));
+            }
+        }
+
+        // Check if at least one generator is enabled
+        let has_enabled_generator = self.generators.typescript.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.rust.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.python.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.java.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.kotlin.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.json_schema.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.openapi.as_ref().is_some_and(|g| g.enabled)
+            || self.generators.markdown.as_ref().is_some_and(|g| g.enabled)
+            || self.plugins.generator.is_some();
+
+        if !has_enabled_generator {
+            warnings.push("No generators are enabled. Enable at least one generator.".to_string());
+        }
+
+        // Validate output directory is not empty
+        if self.output.directory.as_os_str().is_empty() {
+            return Err(anyhow::anyhow!("Output directory cannot be empty"));
+        }
+
+        // Validate FHIR packages format
+        for package in &self.fhir.packages {
+            if !package.contains('@') && !package.is_empty() {
+                warnings.push(format!(
+                    "Package '{}' may be missing version. Expected format: 'package@version'",
+                    package
+                ));
+            }
+        }
+
+        // Validate resource filtering logic
+        if !self.fhir.include_resources.is_empty() && !self.fhir.exclude_resources.is_empty() {
+            warnings.push(
+                "Both include_resources and exclude_resources are set. \
+                 exclude_resources will be applied after include_resources. \
+                 Consider using only include_resources for clarity."
+                    .to_string(),
+            );
+        }
+
+        // Validate log level
+        match self.logging.level.to_lowercase().as_str() {
+            "trace" | "debug" | "info" | "warn" | "error" => {}
+            l => {
+                return Err(anyhow::anyhow!(
+                    "Invalid log level: '{}'. Must be one of: trace, debug, info, warn, error",
+                    l
+                ));
+            }
+        }
+
+        // Validate log format
+        match self.logging.format.to_lowercase().as_str() {
+            "pretty" | "json" | "compact" => {}
+            f => {
+                return Err(anyhow::anyhow!(
+                    "Invalid log format: '{}'. Must be one of: pretty, json, compact",
+                    f
+                ));
+            }
+        }
+
+        // Validate TypeScript configuration
+        if let Some(ref ts_config) = self.generators.typescript
+            && ts_config.enabled
+            && let Err(e) = Self::validate_semver(&ts_config.target_version)
+        {
+            warnings.push(format!("TypeScript target_version: {}", e));
+        }
+
+        // Validate Rust configuration
+        if let Some(ref rust_config) = self.generators.rust
+            && rust_config.enabled
+        {
+            match rust_config.edition.as_str() {
+                "2015" | "2018" | "2021" | "2024" => {}
+                e => {
+                    return Err(anyhow::anyhow!(
+                        "Invalid Rust edition: '{}'. Must be one of: 2015, 2018, 2021, 2024",
+                        e
+                    ));
+                }
+            }
+        }
+
+        // Validate Python configuration
+        if let Some(ref py_config) = self.generators.python
+            && py_config.enabled
+            && let Err(e) = Self::validate_semver(&py_config.target_version)
+        {
+            warnings.push(format!("Python target_version: {}", e));
+        }
+
+        // Validate Java configuration
+        if let Some(ref java_config) = self.generators.java
+            && java_config.enabled
+            && java_config.package_name.is_none()
+        {
+            warnings.push("Java generator enabled but package_name is not set".to_string());
+        }
+
+        // Validate Kotlin configuration
+        if let Some(ref kotlin_config) = self.generators.kotlin
+            && kotlin_config.enabled
+        {
+            for target in &kotlin_config.targets {
+                if !crate::languages::kotlin::SUPPORTED_TARGETS.contains(&target.as_str()) {
+                    return Err(anyhow::anyhow!(
+                        "Invalid Kotlin target: '{}'. Must be one of: {}",
+                        target,
+                        crate::languages::kotlin::SUPPORTED_TARGETS.join(", ")
+                    ));
+                }
+            }
+            if kotlin_config.targets.is_empty() {
+                warnings.push("Kotlin generator enabled but no targets are configured".to_string());
+            }
+            if kotlin_config.package_name.is_none() {
+                warnings.push("Kotlin generator enabled but package_name is not set".to_string());
+            }
+        }
+
+        // Validate JSON Schema configuration
+        if let Some(ref json_schema_config) = self.generators.json_schema
+            && json_schema_config.enabled
+            && let Some(ref schema_id) = json_schema_config.schema_id
+            && !schema_id.contains(':')
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid JSON Schema schema_id: '{}'. Must be an absolute URI",
+                schema_id
+            ));
+        }
+
+        // Validate OpenAPI configuration
+        if let Some(ref openapi_config) = self.generators.openapi
+            && openapi_config.enabled
+            && let Some(ref server_url) = openapi_config.server_url
+            && !server_url.contains("://")
+            && !server_url.starts_with('/')
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid OpenAPI server_url: '{}'. Must be an absolute URL or a path",
+                server_url
+            ));
+        }
+
+        // Validate Markdown configuration
+        if let Some(ref markdown_config) = self.generators.markdown
+            && markdown_config.enabled
+            && let Some(ref flavor) = markdown_config.flavor
+            && !crate::languages::markdown::SUPPORTED_FLAVORS.contains(&flavor.as_str())
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid Markdown flavor: '{}'. Must be one of: {}",
+                flavor,
+                crate::languages::markdown::SUPPORTED_FLAVORS.join(", ")
+            ));
+        }
+
+        Ok(warnings)
+    }
+
+    /// Apply environment variable overrides
+    ///
+    /// Supports the following environment variables:
+    /// - `OCTOFHIR_FHIR_VERSION`: Override FHIR version
+    /// - `OCTOFHIR_OUTPUT_DIR`: Override output directory
+    /// - `OCTOFHIR_LOG_LEVEL`: Override log level
+    /// - `OCTOFHIR_OFFLINE`: Enable offline mode for canonical manager
+    pub fn apply_env_overrides(&mut self) {
+        if let Ok(version) = std::env::var("OCTOFHIR_FHIR_VERSION") {
+            self.fhir.version = version;
+        }
+
+        if let Ok(output_dir) = std::env::var("OCTOFHIR_OUTPUT_DIR") {
+            self.output.directory = PathBuf::from(output_dir);
+        }
+
+        if let Ok(log_level) = std::env::var("OCTOFHIR_LOG_LEVEL") {
+            self.logging.level = log_level;
+        }
+
+        if let Ok(offline) = std::env::var("OCTOFHIR_OFFLINE")
+            && let Some(ref mut cm_config) = self.canonical_manager
+        {
+            cm_config.offline = offline.to_lowercase() == "true" || offline == "1";
+        }
+    }
+
+    /// Merge with another configuration (other takes precedence)
+    pub fn merge(&mut self, other: &CodegenConfig) {
+        // Merge FHIR packages (append)
+        self.fhir.packages.extend(other.fhir.packages.clone());
+
+        // Override primitives if set in other
+        if other.fhir.version != default_fhir_version() {
+            self.fhir.version.clone_from(&other.fhir.version);
+        }
+
+        if other.output.directory != default_output_directory() {
+            self.output.directory.clone_from(&other.output.directory);
+        }
+
+        // Merge generator configs (other takes precedence if Some)
+        if other.generators.typescript.is_some() {
+            self.generators.typescript = other.generators.typescript.clone();
+        }
+        if other.generators.rust.is_some() {
+            self.generators.rust = other.generators.rust.clone();
+        }
+        if other.generators.python.is_some() {
+            self.generators.python = other.generators.python.clone();
+        }
+        if other.generators.java.is_some() {
+            self.generators.java = other.generators.java.clone();
+        }
+        if other.generators.kotlin.is_some() {
+            self.generators.kotlin = other.generators.kotlin.clone();
+        }
+        if other.generators.json_schema.is_some() {
+            self.generators.json_schema = other.generators.json_schema.clone();
+        }
+        if other.generators.openapi.is_some() {
+            self.generators.openapi = other.generators.openapi.clone();
+        }
+        if other.generators.markdown.is_some() {
+            self.generators.markdown = other.generators.markdown.clone();
+        }
+
+        // Merge plugins (append paths, other's options take precedence)
+        self.plugins.paths.extend(other.plugins.paths.clone());
+        if other.plugins.generator.is_some() {
+            self.plugins.generator.clone_from(&other.plugins.generator);
+        }
+        self.plugins.options.extend(other.plugins.options.clone());
+    }
+
+    /// Validate semantic version format
+    fn validate_semver(version: &str) -> Result<()> {
+        let parts: Vec<&str> = version.split('.').collect();
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(anyhow::anyhow!(
+                "Invalid version format: '{}'. Expected semantic versioning (e.g., '1.0.0' or '5.3')",
+                version
+            ));
+        }
+        Ok(())
+    }
+
+    /// Create a default configuration with sensible values
+    pub fn default_with_typescript() -> Self {
+        let mut config = Self::default();
+        config.generators.typescript = Some(TypeScriptGeneratorConfig::default());
+        config
+    }
+
+    /// Create a configuration template for a specific language
+    pub fn template_for(language: &str) -> Result<Self> {
+        let mut config = Self::default();
+
+        // Set basic project metadata
+        config.project.name = format!("fhir-{}-sdk", language);
+        config.project.version = "0.1.0".to_string();
+        config.project.description =
+            Some(format!("FHIR {} SDK generated by OctoFHIR Codegen", language));
+
+        // Set FHIR configuration
+        config.fhir.version = "R4".to_string();
+        config.fhir.packages = vec!["hl7.fhir.r4.core@4.0.1".to_string()];
+
+        // Enable the appropriate generator
+        match language.to_lowercase().as_str() {
+            "typescript" | "ts" => {
+                config.generators.typescript = Some(TypeScriptGeneratorConfig {
+                    module_name: Some("fhir-r4".to_string()),
+                    ..Default::default()
+                });
+            }
+            "rust" | "rs" => {
+                config.generators.rust = Some(RustGeneratorConfig {
+                    enabled: true,
+                    crate_name: Some("fhir-r4".to_string()),
+                    ..Default::default()
+                });
+            }
+            "python" | "py" => {
+                config.generators.python = Some(PythonGeneratorConfig {
+                    enabled: true,
+                    package_name: Some("fhir_r4".to_string()),
+                    ..Default::default()
+                });
+            }
+            "java" => {
+                config.generators.java = Some(JavaGeneratorConfig {
+                    enabled: true,
+                    package_name: Some("com.example.fhir.r4".to_string()),
+                    ..Default::default()
+                });
+            }
+            "kotlin" | "kt" => {
+                config.generators.kotlin = Some(KotlinGeneratorConfig {
+                    enabled: true,
+                    package_name: Some("com.example.fhir.r4".to_string()),
+                    group_id: Some("com.example".to_string()),
+                    ..Default::default()
+                });
+            }
+            "json-schema" | "json_schema" | "jsonschema" => {
+                config.generators.json_schema = Some(JsonSchemaGeneratorConfig {
+                    enabled: true,
+                    schema_id: Some("https://example.com/fhir/r4.schema.json".to_string()),
+                });
+            }
+            "openapi" | "open-api" | "oas" => {
+                config.generators.openapi = Some(OpenApiGeneratorConfig {
+                    enabled: true,
+                    server_url: Some("https://example.com/fhir".to_string()),
+                });
+            }
+            "markdown" | "md" | "docs" => {
+                config.generators.markdown = Some(MarkdownGeneratorConfig {
+                    enabled: true,
+                    flavor: Some("mdbook".to_string()),
+                    title: Some("FHIR R4 Reference".to_string()),
+                });
+            }
+            "multi" | "all" => {
+                // Enable all generators
+                config.generators.typescript = Some(TypeScriptGeneratorConfig::default());
+                config.generators.rust =
+                    Some(RustGeneratorConfig { enabled: true, ..Default::default() });
+                config.generators.python =
+                    Some(PythonGeneratorConfig { enabled: true, ..Default::default() });
+                config.generators.java = Some(JavaGeneratorConfig {
+                    enabled: true,
+                    package_name: Some("com.example.fhir.r4".to_string()),
+                    ..Default::default()
+                });
+                config.generators.kotlin = Some(KotlinGeneratorConfig {
+                    enabled: true,
+                    package_name: Some("com.example.fhir.r4".to_string()),
+                    ..Default::default()
+                });
+                config.generators.json_schema =
+                    Some(JsonSchemaGeneratorConfig { enabled: true, ..Default::default() });
+                config.generators.openapi =
+                    Some(OpenApiGeneratorConfig { enabled: true, ..Default::default() });
+                config.generators.markdown =
+                    Some(MarkdownGeneratorConfig { enabled: true, ..Default::default() });
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unknown language template: '{}'. Available templates: typescript, rust, python, java, kotlin, json-schema, openapi, markdown, multi",
+                    language
+                ));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Generate a commented TOML configuration template
+    ///
+    /// The configuration is serialized as a whole, so every setting survives a
+    /// `config migrate`; unset optional settings are listed as commented-out
+    /// examples.
+    pub fn to_commented_toml(&self) -> Result<String> {
+        let document = self.to_toml_string()?;
+
+        let mut output = String::new();
+        output.push_str("# OctoFHIR Codegen Configuration\n");
+        output.push_str("# For full documentation, visit: https://github.com/octofhir/codegen\n\n");
+
+        let mut table = String::new();
+        let mut keys: Vec<String> = Vec::new();
+
+        for line in document.lines() {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                Self::push_template_hints(&mut output, &table, &keys);
+                table = name.to_string();
+                keys.clear();
+            } else if let Some((key, _)) = line.split_once(" = ")
+                && !line.starts_with(' ')
+            {
+                if let Some((_, _, comment)) =
+                    TEMPLATE_KEY_COMMENTS.iter().find(|(t, k, _)| *t == table && *k == key)
+                {
+                    output.push_str(&format!("# {}\n", comment));
+                }
+                keys.push(key.to_string());
+            }
+
+            output.push_str(line);
+            output.push('\n');
+        }
+        Self::push_template_hints(&mut output, &table, &keys);
+        output.truncate(output.trim_end_matches('\n').len());
+        output.push('\n');
+
+        Ok(output)
+    }
+
+    /// Append commented-out examples for the unset keys of a table
+    fn push_template_hints(output: &mut String, table: &str, keys: &[String]) {
+        let hints: Vec<&str> = TEMPLATE_HINTS
+            .iter()
+            .filter(|(t, key, _)| *t == table && !keys.iter().any(|k| k == key))
+            .map(|(_, _, hint)| *hint)
+            .collect();
+        if hints.is_empty() {
+            return;
+        }
+
+        // Keep the hints inside the table, before the blank line separating the next one
+        let trimmed = output.trim_end_matches('\n').len();
+        output.truncate(trimmed);
+        output.push('\n');
+        for hint in hints {
+            output.push_str(&format!("# {}\n", hint));
+        }
+        output.push('\n');
+    }
+}
+
+/// Examples written as comments when a key is unset, as `(table, key, example)`
+const TEMPLATE_HINTS: &[(&str, &str, &str)] = &[
+    ("project", "description", "description = \"FHIR SDK for my project\""),
+    ("project", "authors", "authors = [\"Your Name <email@example.com>\"]"),
+    ("project", "license", "license = \"MIT\""),
+    ("project", "repository", "repository = \"https://github.com/username/repo\""),
+    ("generators.typescript.type_mappings", "dateTime", "dateTime = \"Date\""),
+    ("generators.typescript.compiler_options", "source_map", "source_map = false"),
+    ("plugins", "paths", "paths = [\"plugins/\"]"),
+    ("plugins", "generator", "generator = \"my-generator\""),
+    ("logging", "file", "file = \"codegen.log\""),
+];
+
+/// Comments written above a key, as `(table, key, comment)`
+const TEMPLATE_KEY_COMMENTS: &[(&str, &str, &str)] = &[
+    ("fhir", "include_resources", "e.g. [\"Patient\", \"Observation\", \"Condition\"]"),
+    ("fhir", "exclude_resources", "e.g. [\"Binary\"]"),
+    ("generators.typescript", "validation_style", "\"functions\" or \"zod\""),
+];
+
+/// Serialize a map with sorted keys, so written configuration files are stable
+fn serialize_sorted<S, V>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// Serialize per-plugin options with sorted plugin names and option keys
+fn serialize_sorted_options<S>(
+    options: &HashMap<String, HashMap<String, serde_json::Value>>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    options
+        .iter()
+        .map(|(name, values)| (name, values.iter().collect::<BTreeMap<_, _>>()))
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
 }
 
 #[cfg(test)]
@@ -229,48 +1226,477 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = CodegenConfig::default();
-        assert_eq!(config.fhir_version, "R4");
-        assert_eq!(config.output_dir, "./generated");
+        assert_eq!(config.project.name, "fhir-sdk");
+        assert_eq!(config.fhir.version, "R4");
+        assert_eq!(config.output.directory, PathBuf::from("./generated"));
+        assert_eq!(config.logging.level, "info");
+    }
+
+    #[test]
+    fn test_parse_minimal_config() {
+        let toml = r#"
+            [project]
+            name = "my-sdk"
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        assert_eq!(config.project.name, "my-sdk");
+        assert_eq!(config.fhir.version, "R4"); // Default
+    }
+
+    #[test]
+    fn test_parse_full_config() {
+        let toml = r#"
+            [project]
+            name = "my-fhir-sdk"
+            version = "1.0.0"
+            description = "Custom FHIR SDK"
+
+            [fhir]
+            version = "R5"
+            packages = ["hl7.fhir.r5.core@5.0.0"]
+
+            [output]
+            directory = "./dist"
+            clean = true
+
+            [generators.typescript]
+            enabled = true
+            module_name = "fhir-r5"
+            emit_validation = true
+
+            [logging]
+            level = "debug"
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        assert_eq!(config.project.name, "my-fhir-sdk");
+        assert_eq!(config.fhir.version, "R5");
+        assert_eq!(config.output.directory, PathBuf::from("./dist"));
+        assert!(config.generators.typescript.is_some());
+        assert_eq!(config.logging.level, "debug");
     }
 
     #[test]
-    fn test_typescript_config_defaults() {
-        let config = TypeScriptConfig::default();
-        assert!(config.enabled);
-        assert_eq!(config.module_name, "fhir-r4");
-        assert!(!config.generate_classes);
-        assert!(config.generate_extensions);
-        assert!(config.strict_mode);
+    fn test_validation_success() {
+        let mut config = CodegenConfig::default();
+        config.generators.typescript = Some(TypeScriptGeneratorConfig::default());
+
+        let warnings = config.validate().unwrap();
+        assert!(warnings.is_empty());
     }
 
     #[test]
-    fn test_toml_serialization() {
+    fn test_validation_invalid_fhir_version() {
+        let mut config = CodegenConfig::default();
+        config.fhir.version = "R3".to_string();
+
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid FHIR version"));
+    }
+
+    #[test]
+    fn test_validation_no_generators() {
         let config = CodegenConfig::default();
-        let toml_str = config.to_toml().unwrap();
-        assert!(toml_str.contains("fhir_version"));
-        assert!(toml_str.contains("output_dir"));
+        let warnings = config.validate().unwrap();
+        assert!(!warnings.is_empty());
+        assert!(warnings[0].contains("No generators are enabled"));
+    }
+
+    #[test]
+    fn test_validation_invalid_log_level() {
+        let mut config = CodegenConfig::default();
+        config.logging.level = "invalid".to_string();
+
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid log level"));
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        unsafe {
+            std::env::set_var("OCTOFHIR_FHIR_VERSION", "R6");
+            std::env::set_var("OCTOFHIR_OUTPUT_DIR", "/tmp/output");
+            std::env::set_var("OCTOFHIR_LOG_LEVEL", "trace");
+        }
+
+        let mut config = CodegenConfig::default();
+        config.apply_env_overrides();
+
+        assert_eq!(config.fhir.version, "R6");
+        assert_eq!(config.output.directory, PathBuf::from("/tmp/output"));
+        assert_eq!(config.logging.level, "trace");
+
+        // Cleanup
+        unsafe {
+            std::env::remove_var("OCTOFHIR_FHIR_VERSION");
+            std::env::remove_var("OCTOFHIR_OUTPUT_DIR");
+            std::env::remove_var("OCTOFHIR_LOG_LEVEL");
+        }
+    }
+
+    #[test]
+    fn test_merge_configs() {
+        let mut base = CodegenConfig::default();
+        base.fhir.packages = vec!["hl7.fhir.r4.core@4.0.1".to_string()];
+
+        let mut override_config = CodegenConfig::default();
+        override_config.fhir.version = "R5".to_string();
+        override_config.fhir.packages = vec!["hl7.fhir.r5.core@5.0.0".to_string()];
+
+        base.merge(&override_config);
+
+        assert_eq!(base.fhir.version, "R5");
+        assert_eq!(base.fhir.packages.len(), 2);
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let config = CodegenConfig::default_with_typescript();
+        let toml_str = config.to_toml_string().unwrap();
+        let parsed = CodegenConfig::from_toml_str(&toml_str).unwrap();
+        assert_eq!(config, parsed);
     }
 
     #[test]
-    fn test_toml_deserialization() {
-        let toml_str = r#"
-            fhir_version = "R4"
-            output_dir = "./output"
+    fn test_type_mappings() {
+        let toml = r#"
+            [generators.typescript]
+            enabled = true
+
+            [generators.typescript.type_mappings]
+            date = "string"
+            instant = "Date"
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        let ts_config = config.generators.typescript.unwrap();
+        assert_eq!(ts_config.type_mappings.get("date"), Some(&"string".to_string()));
+        assert_eq!(ts_config.type_mappings.get("instant"), Some(&"Date".to_string()));
+    }
+
+    #[test]
+    fn test_include_resources() {
+        let toml = r#"
+            [fhir]
+            version = "R4"
+            packages = ["hl7.fhir.r4.core@4.0.1"]
+            include_resources = ["Patient", "Observation", "Condition"]
+
+            [generators.typescript]
+            enabled = true
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        assert_eq!(config.fhir.include_resources, vec!["Patient", "Observation", "Condition"]);
+
+        let warnings = config.validate().unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_include_and_exclude_resources_warning() {
+        let toml = r#"
+            [fhir]
+            version = "R4"
+            packages = ["hl7.fhir.r4.core@4.0.1"]
+            include_resources = ["Patient", "Observation"]
+            exclude_resources = ["Binary"]
 
             [generators.typescript]
-            type = "typescript"
             enabled = true
-            module_name = "my-fhir"
-            generate_classes = true
-            generate_extensions = true
         "#;
 
-        let config: CodegenConfig = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.fhir_version, "R4");
-        assert_eq!(config.output_dir, "./output");
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        let warnings = config.validate().unwrap();
+        assert!(!warnings.is_empty());
+        assert!(warnings[0].contains("include_resources and exclude_resources"));
+    }
+
+    #[test]
+    fn test_kotlin_config() {
+        let toml = r#"
+            [generators.kotlin]
+            enabled = true
+            package_name = "com.example.fhir"
+            targets = ["jvm", "js"]
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        let kotlin = config.generators.kotlin.as_ref().unwrap();
+        assert_eq!(kotlin.package_name.as_deref(), Some("com.example.fhir"));
+        assert_eq!(kotlin.targets, vec!["jvm", "js"]);
+        assert_eq!(kotlin.kotlin_version, "2.0.21");
+
+        let warnings = config.validate().unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_kotlin_invalid_target() {
+        let toml = r#"
+            [generators.kotlin]
+            enabled = true
+            package_name = "com.example.fhir"
+            targets = ["wasm"]
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid Kotlin target"));
+    }
+
+    #[test]
+    fn test_kotlin_template_roundtrip() {
+        let config = CodegenConfig::template_for("kotlin").unwrap();
+        let toml = config.to_commented_toml().unwrap();
+        assert!(toml.contains("[generators.kotlin]"));
+
+        let parsed = CodegenConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed.generators.kotlin, config.generators.kotlin);
+    }
+
+    #[test]
+    fn test_json_schema_template_roundtrip() {
+        let config = CodegenConfig::template_for("json-schema").unwrap();
+        let toml = config.to_commented_toml().unwrap();
+        assert!(toml.contains("[generators.json_schema]"));
+        assert!(toml.contains("schema_id = \"https://example.com/fhir/r4.schema.json\""));
+
+        let parsed = CodegenConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed.generators.json_schema, config.generators.json_schema);
+    }
+
+    #[test]
+    fn test_json_schema_invalid_schema_id() {
+        let toml = r#"
+            [generators.json_schema]
+            enabled = true
+            schema_id = "fhir.schema.json"
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        let result = config.validate();
+        assert!(result.unwrap_err().to_string().contains("Invalid JSON Schema schema_id"));
+    }
+
+    #[test]
+    fn test_openapi_template_roundtrip() {
+        let config = CodegenConfig::template_for("openapi").unwrap();
+        let toml = config.to_commented_toml().unwrap();
+        assert!(toml.contains("[generators.openapi]"));
+        assert!(toml.contains("server_url = \"https://example.com/fhir\""));
+
+        let parsed = CodegenConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed.generators.openapi, config.generators.openapi);
+    }
+
+    #[test]
+    fn test_markdown_template_roundtrip() {
+        let config = CodegenConfig::template_for("markdown").unwrap();
+        let toml = config.to_commented_toml().unwrap();
+        assert!(toml.contains("[generators.markdown]"));
+        assert!(toml.contains("flavor = \"mdbook\""));
+
+        let parsed = CodegenConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed.generators.markdown, config.generators.markdown);
+    }
+
+    #[test]
+    fn test_markdown_invalid_flavor() {
+        let toml = r#"
+            [generators.markdown]
+            enabled = true
+            flavor = "hugo"
+        "#;
 
-        let ts_config = config.typescript_config().unwrap();
-        assert_eq!(ts_config.module_name, "my-fhir");
-        assert!(ts_config.generate_classes);
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        let result = config.validate();
+        assert!(result.unwrap_err().to_string().contains("Invalid Markdown flavor"));
+    }
+
+    #[test]
+    fn test_plugins_config() {
+        let toml = r#"
+            [plugins]
+            paths = ["plugins", "/opt/octofhir/octofhir-gen-csharp"]
+            generator = "csharp"
+
+            [plugins.options.CSharp]
+            namespace = "Example.Fhir"
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            config.plugins.resolve_paths(Path::new("/project")),
+            vec![
+                PathBuf::from("/project/plugins"),
+                PathBuf::from("/opt/octofhir/octofhir-gen-csharp")
+            ]
+        );
+        assert_eq!(
+            config.plugins.options_for("csharp").unwrap()["namespace"],
+            serde_json::json!("Example.Fhir")
+        );
+
+        // A plugin generator counts as an enabled generator
+        let warnings = config.validate().unwrap();
+        assert!(!warnings.iter().any(|w| w.contains("No generators are enabled")));
+    }
+
+    #[test]
+    fn test_plugins_reject_unknown_keys() {
+        let toml = r#"
+            [plugins]
+            path = ["plugins"]
+        "#;
+
+        assert!(CodegenConfig::from_toml_str(toml).is_err());
+    }
+
+    #[test]
+    fn test_typescript_rejects_unknown_keys() {
+        let toml = r#"
+            [generators.typescript]
+            enabled = true
+            generate_builders = true
+        "#;
+
+        let err = CodegenConfig::from_toml_str(toml).unwrap_err();
+        assert!(format!("{:#}", err).contains("generate_builders"));
+    }
+
+    #[test]
+    fn test_every_section_rejects_unknown_keys() {
+        let sections = [
+            ("project", "licence = \"MIT\""),
+            ("fhir", "package = [\"hl7.fhir.r4.core\"]"),
+            ("output", "dir = \"out\""),
+            ("generators", "typscript = {}"),
+            ("generators.rust", "crate = \"fhir\""),
+            ("generators.python", "package = \"fhir\""),
+            ("generators.java", "package = \"fhir\""),
+            ("generators.kotlin", "package = \"fhir\""),
+            ("canonical_manager", "registry = \"https://example.com\""),
+            ("logging", "levl = \"debug\""),
+        ];
+
+        for (section, entry) in sections {
+            let toml = format!("[{}]\n{}\n", section, entry);
+            let key = entry.split(' ').next().unwrap();
+            let err = CodegenConfig::from_toml_str(&toml).unwrap_err();
+            assert!(format!("{:#}", err).contains(key), "[{}] accepted {}", section, key);
+        }
+    }
+
+    #[test]
+    fn test_typescript_template_roundtrip() {
+        let mut config = CodegenConfig::template_for("typescript").unwrap();
+        let ts = config.generators.typescript.as_mut().unwrap();
+        ts.generate_classes = true;
+        ts.validation_style = ValidationStyle::Zod;
+        let toml = config.to_commented_toml().unwrap();
+        assert!(toml.contains("generate_classes = true"));
+        assert!(toml.contains("validation_style = \"zod\""));
+        assert!(toml.contains("strict_mode = true"));
+
+        let parsed = CodegenConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed.generators.typescript, config.generators.typescript);
+    }
+
+    #[test]
+    fn test_shipped_examples_parse() {
+        for content in [
+            include_str!("../../examples/codegen.toml"),
+            include_str!("../../examples/codegen-simple.toml"),
+        ] {
+            assert!(CodegenConfig::migrate_toml_str(content).unwrap().is_none());
+            let config = CodegenConfig::from_toml_str(content).unwrap();
+            assert_eq!(config.schema_version, CONFIG_SCHEMA_VERSION);
+            config.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_legacy_config_is_migrated() {
+        let toml = r#"
+            fhir_version = "R5"
+            output_dir = "./legacy-out"
+
+            [generators.typescript]
+            type = "typescript"
+            module_name = "legacy-sdk"
+        "#;
+
+        let config = CodegenConfig::from_toml_str(toml).unwrap();
+        assert_eq!(config.fhir.version, "R5");
+        assert_eq!(config.output.directory, PathBuf::from("./legacy-out"));
+        assert_eq!(
+            config.generators.typescript.unwrap().module_name.as_deref(),
+            Some("legacy-sdk")
+        );
+
+        let migration = CodegenConfig::migrate_toml_str(toml).unwrap().unwrap();
+        let rewritten = migration.config.to_commented_toml().unwrap();
+        assert!(CodegenConfig::migrate_toml_str(&rewritten).unwrap().is_none());
+        assert_eq!(CodegenConfig::from_toml_str(&rewritten).unwrap().fhir.version, "R5");
+    }
+
+    #[test]
+    fn test_migrated_r4b_config_validates() {
+        let toml = r#"
+            fhir_version = "r4b"
+            output_dir = "./legacy-out"
+
+            [generators.typescript]
+            type = "typescript"
+            module_name = "legacy-sdk"
+        "#;
+
+        let migration = CodegenConfig::migrate_toml_str(toml).unwrap().unwrap();
+        assert_eq!(migration.config.fhir.version, "R4B");
+        assert_eq!(migration.config.fhir.packages, vec!["hl7.fhir.r4b.core@4.3.0"]);
+        migration.config.validate().unwrap();
+
+        let rewritten = migration.config.to_commented_toml().unwrap();
+        CodegenConfig::from_toml_str(&rewritten).unwrap().validate().unwrap();
+    }
+
+    #[test]
+    fn test_commented_toml_keeps_every_setting() {
+        let mut config = CodegenConfig::template_for("markdown").unwrap();
+        config.project.authors = vec!["Jane Doe <jane@example.com>".to_string()];
+        config.project.license = Some("MIT".to_string());
+        config.fhir.exclude_resources = vec!["Binary".to_string()];
+        config.canonical_manager = Some(CanonicalManagerConfig {
+            config_path: None,
+            registry_url: Some("https://packages.example.com".to_string()),
+            cache_dir: None,
+            offline: true,
+        });
+        config.plugins.options.insert(
+            "csharp".to_string(),
+            HashMap::from([("namespace".to_string(), serde_json::json!("Example.Fhir"))]),
+        );
+        config.logging.file = Some(PathBuf::from("codegen.log"));
+        config.generators.markdown.as_mut().unwrap().title =
+            Some("FHIR \"R4\" \\ Reference".to_string());
+
+        let toml = config.to_commented_toml().unwrap();
+        assert!(toml.contains("# repository = \"https://github.com/username/repo\""));
+        assert!(!toml.contains("# license"));
+
+        let parsed = CodegenConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_newer_schema_version_rejected() {
+        let toml = format!("schema_version = {}", CONFIG_SCHEMA_VERSION + 1);
+        let err = CodegenConfig::from_toml_str(&toml).unwrap_err();
+        assert!(err.to_string().contains("newer than the supported version"));
     }
 }
@@ -44,6 +44,10 @@ pub enum Error {
     #[error("Template error: {0}")]
     Template(String),
 
+    /// Plugin loading or protocol error
+    #[error("Plugin error: {0}")]
+    Plugin(String),
+
     /// Other errors
     #[error("{0}")]
     Other(String),
@@ -0,0 +1,768 @@
+//! FHIRPath expression parser
+//!
+//! Parses FHIRPath expressions (as used in `ElementDefinition.constraint.expression`)
+//! into a language-agnostic AST. Language backends translate the AST into native
+//! checks; the parser accepts the full operator grammar so that backends can report
+//! precisely which parts of an invariant they do not support.
+
+use crate::core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// FHIRPath expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Expression {
+    /// Literal value (`true`, `'text'`, `42`, `{}`)
+    Literal {
+        /// Literal value
+        value: Literal,
+    },
+
+    /// Navigation from the current focus (e.g. `name`)
+    Identifier {
+        /// Element name
+        name: String,
+    },
+
+    /// Special variable (`$this`, `$index`, `$total`)
+    Variable {
+        /// Variable name without the `$`
+        name: String,
+    },
+
+    /// Environment variable (`%resource`, `%context`, `%ucum`)
+    External {
+        /// Variable name without the `%`
+        name: String,
+    },
+
+    /// Member navigation (e.g. `name.given`)
+    Member {
+        /// Expression being navigated
+        target: Box<Expression>,
+        /// Element name
+        name: String,
+    },
+
+    /// Function invocation, either on a target (`name.exists()`) or on the focus (`exists()`)
+    Function {
+        /// Target the function is invoked on (`None` for the current focus)
+        target: Option<Box<Expression>>,
+        /// Function name
+        name: String,
+        /// Function arguments
+        args: Vec<Expression>,
+    },
+
+    /// Indexer (e.g. `name[0]`)
+    Index {
+        /// Indexed expression
+        target: Box<Expression>,
+        /// Index expression
+        index: Box<Expression>,
+    },
+
+    /// Unary polarity (`-x`, `+x`)
+    Unary {
+        /// Operator
+        op: UnaryOperator,
+        /// Operand
+        operand: Box<Expression>,
+    },
+
+    /// Binary operation (e.g. `a and b`, `count() > 1`)
+    Binary {
+        /// Operator
+        op: BinaryOperator,
+        /// Left operand
+        left: Box<Expression>,
+        /// Right operand
+        right: Box<Expression>,
+    },
+
+    /// Type operation (`x is Quantity`, `x as string`)
+    Type {
+        /// Operator
+        op: TypeOperator,
+        /// Operand
+        operand: Box<Expression>,
+        /// Type specifier (e.g. "Quantity", "FHIR.string")
+        type_name: String,
+    },
+}
+
+/// Literal value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum Literal {
+    /// Empty collection `{}`
+    Empty,
+    /// Boolean literal
+    Boolean(bool),
+    /// String literal (escapes resolved)
+    String(String),
+    /// Number literal, kept as written
+    Number(String),
+    /// Date, date-time or time literal, without the leading `@`
+    DateTime(String),
+    /// Quantity literal (e.g. `4 'mg'`, `2 days`)
+    Quantity {
+        /// Numeric value as written
+        value: String,
+        /// Unit
+        unit: String,
+    },
+}
+
+/// Unary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnaryOperator {
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+}
+
+/// Binary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryOperator {
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `div`
+    Div,
+    /// `mod`
+    Mod,
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `&`
+    Concatenate,
+    /// `|`
+    Union,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterOrEqual,
+    /// `=`
+    Equals,
+    /// `~`
+    Equivalent,
+    /// `!=`
+    NotEquals,
+    /// `!~`
+    NotEquivalent,
+    /// `in`
+    In,
+    /// `contains`
+    Contains,
+    /// `and`
+    And,
+    /// `or`
+    Or,
+    /// `xor`
+    Xor,
+    /// `implies`
+    Implies,
+}
+
+impl BinaryOperator {
+    /// Operator as written in FHIRPath
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Div => "div",
+            BinaryOperator::Mod => "mod",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Concatenate => "&",
+            BinaryOperator::Union => "|",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessOrEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterOrEqual => ">=",
+            BinaryOperator::Equals => "=",
+            BinaryOperator::Equivalent => "~",
+            BinaryOperator::NotEquals => "!=",
+            BinaryOperator::NotEquivalent => "!~",
+            BinaryOperator::In => "in",
+            BinaryOperator::Contains => "contains",
+            BinaryOperator::And => "and",
+            BinaryOperator::Or => "or",
+            BinaryOperator::Xor => "xor",
+            BinaryOperator::Implies => "implies",
+        }
+    }
+
+    /// Binding power; higher binds tighter
+    ///
+    /// Follows the operator precedence table of the FHIRPath specification.
+    fn precedence(self) -> u8 {
+        match self {
+            BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Div
+            | BinaryOperator::Mod => 10,
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Concatenate => 9,
+            BinaryOperator::Union => 7,
+            BinaryOperator::LessThan
+            | BinaryOperator::LessOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterOrEqual => 6,
+            BinaryOperator::Equals
+            | BinaryOperator::Equivalent
+            | BinaryOperator::NotEquals
+            | BinaryOperator::NotEquivalent => 5,
+            BinaryOperator::In | BinaryOperator::Contains => 4,
+            BinaryOperator::And => 3,
+            BinaryOperator::Or | BinaryOperator::Xor => 2,
+            BinaryOperator::Implies => 1,
+        }
+    }
+}
+
+/// Type operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeOperator {
+    /// `is`
+    Is,
+    /// `as`
+    As,
+}
+
+/// Precedence of the `is`/`as` type operators
+const TYPE_OPERATOR_PRECEDENCE: u8 = 8;
+
+/// Parse a FHIRPath expression
+pub fn parse(expression: &str) -> Result<Expression> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser { tokens, position: 0, source: expression };
+    let parsed = parser.parse_expression(0)?;
+    match parser.peek() {
+        None => Ok(parsed),
+        Some(token) => Err(parser.error(&format!("unexpected {}", token.describe()))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    /// Backtick-delimited identifier; never treated as a keyword
+    DelimitedIdentifier(String),
+    String(String),
+    Number(String),
+    DateTime(String),
+    Variable(String),
+    External(String),
+    Symbol(&'static str),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Identifier(name) | Token::DelimitedIdentifier(name) => {
+                format!("identifier '{}'", name)
+            }
+            Token::String(value) => format!("string '{}'", value),
+            Token::Number(value) => format!("number {}", value),
+            Token::DateTime(value) => format!("date/time @{}", value),
+            Token::Variable(name) => format!("variable ${}", name),
+            Token::External(name) => format!("variable %{}", name),
+            Token::Symbol(symbol) => format!("'{}'", symbol),
+        }
+    }
+}
+
+const SYMBOLS: &[&str] = &[
+    "<=", ">=", "!=", "!~", "(", ")", "[", "]", "{", "}", ".", ",", "+", "-", "*", "/", "&", "|",
+    "<", ">", "=", "~",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let error = |message: String| {
+        Error::Parser(format!("Invalid FHIRPath expression '{}': {}", expression, message))
+    };
+    let take_while = |mut i: usize, predicate: &dyn Fn(char) -> bool| {
+        while i < chars.len() && predicate(chars[i]) {
+            i += 1;
+        }
+        i
+    };
+    let is_identifier_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            i = take_while(i, &|c| c != '\n');
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let end = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .ok_or_else(|| error("unterminated comment".to_string()))?;
+            i = end + 2;
+        } else if c == '\'' || c == '`' {
+            let (value, end) = read_quoted(&chars, i).map_err(error)?;
+            tokens.push(if c == '\'' {
+                Token::String(value)
+            } else {
+                Token::DelimitedIdentifier(value)
+            });
+            i = end;
+        } else if c.is_ascii_digit() {
+            let mut end = take_while(i, &|c| c.is_ascii_digit());
+            if chars.get(end) == Some(&'.') && chars.get(end + 1).is_some_and(char::is_ascii_digit)
+            {
+                end = take_while(end + 1, &|c| c.is_ascii_digit());
+            }
+            tokens.push(Token::Number(chars[i..end].iter().collect()));
+            i = end;
+        } else if c == '@' {
+            let end = take_while(i + 1, &|c| {
+                c.is_ascii_digit() || matches!(c, '-' | ':' | 'T' | '.' | '+' | 'Z')
+            });
+            tokens.push(Token::DateTime(chars[i + 1..end].iter().collect()));
+            i = end;
+        } else if c == '$' || c == '%' {
+            let (name, end) = if chars.get(i + 1) == Some(&'`') || chars.get(i + 1) == Some(&'\'') {
+                read_quoted(&chars, i + 1).map_err(error)?
+            } else {
+                // Environment variables may name value sets, e.g. `%vs-name`
+                let end =
+                    take_while(i + 1, &|ch| is_identifier_char(ch) || (c == '%' && ch == '-'));
+                (chars[i + 1..end].iter().collect(), end)
+            };
+            if name.is_empty() {
+                return Err(error(format!("expected a variable name after '{}'", c)));
+            }
+            tokens.push(if c == '$' { Token::Variable(name) } else { Token::External(name) });
+            i = end;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = take_while(i, &is_identifier_char);
+            tokens.push(Token::Identifier(chars[i..end].iter().collect()));
+            i = end;
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| {
+            symbol.chars().enumerate().all(|(offset, s)| chars.get(i + offset) == Some(&s))
+        }) {
+            tokens.push(Token::Symbol(symbol));
+            i += symbol.len();
+        } else {
+            return Err(error(format!("unexpected character '{}'", c)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Read a quoted string or delimited identifier starting at `start`
+fn read_quoted(chars: &[char], start: usize) -> std::result::Result<(String, usize), String> {
+    let quote = chars[start];
+    let mut value = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        match chars[i] {
+            c if c == quote => return Ok((value, i + 1)),
+            '\\' => {
+                let escaped = chars.get(i + 1).ok_or("unterminated escape sequence")?;
+                match escaped {
+                    'n' => value.push('\n'),
+                    'r' => value.push('\r'),
+                    't' => value.push('\t'),
+                    'f' => value.push('\u{c}'),
+                    'u' => {
+                        let hex: String =
+                            chars.get(i + 2..i + 6).unwrap_or_default().iter().collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid unicode escape '\\u{}'", hex))?;
+                        value.push(code);
+                        i += 4;
+                    }
+                    other => value.push(*other),
+                }
+                i += 2;
+            }
+            c => {
+                value.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Err(format!("unterminated {}", if quote == '\'' { "string" } else { "identifier" }))
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    source: &'a str,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> Error {
+        Error::Parser(format!("Invalid FHIRPath expression '{}': {}", self.source, message))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol)
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.is_symbol(symbol) {
+            self.position += 1;
+            Ok(())
+        } else {
+            let found = self.peek().map(Token::describe).unwrap_or_else(|| "end of input".into());
+            Err(self.error(&format!("expected '{}', found {}", symbol, found)))
+        }
+    }
+
+    /// Binary operator at the current position, if any
+    fn peek_binary_operator(&self) -> Option<BinaryOperator> {
+        let op = match self.peek()? {
+            Token::Symbol(symbol) => match *symbol {
+                "*" => BinaryOperator::Multiply,
+                "/" => BinaryOperator::Divide,
+                "+" => BinaryOperator::Add,
+                "-" => BinaryOperator::Subtract,
+                "&" => BinaryOperator::Concatenate,
+                "|" => BinaryOperator::Union,
+                "<" => BinaryOperator::LessThan,
+                "<=" => BinaryOperator::LessOrEqual,
+                ">" => BinaryOperator::GreaterThan,
+                ">=" => BinaryOperator::GreaterOrEqual,
+                "=" => BinaryOperator::Equals,
+                "~" => BinaryOperator::Equivalent,
+                "!=" => BinaryOperator::NotEquals,
+                "!~" => BinaryOperator::NotEquivalent,
+                _ => return None,
+            },
+            Token::Identifier(keyword) => match keyword.as_str() {
+                "div" => BinaryOperator::Div,
+                "mod" => BinaryOperator::Mod,
+                "in" => BinaryOperator::In,
+                "contains" => BinaryOperator::Contains,
+                "and" => BinaryOperator::And,
+                "or" => BinaryOperator::Or,
+                "xor" => BinaryOperator::Xor,
+                "implies" => BinaryOperator::Implies,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    /// Precedence-climbing parse of an expression whose operators bind tighter than `min`
+    fn parse_expression(&mut self, min: u8) -> Result<Expression> {
+        let mut left = self.parse_unary()?;
+
+        loop {
+            if let Some(Token::Identifier(keyword)) = self.peek()
+                && (keyword == "is" || keyword == "as")
+                && TYPE_OPERATOR_PRECEDENCE > min
+            {
+                let op = if keyword == "is" { TypeOperator::Is } else { TypeOperator::As };
+                self.position += 1;
+                let type_name = self.parse_type_specifier()?;
+                left = Expression::Type { op, operand: Box::new(left), type_name };
+                continue;
+            }
+
+            let Some(op) = self.peek_binary_operator() else { break };
+            let precedence = op.precedence();
+            if precedence <= min {
+                break;
+            }
+            self.position += 1;
+            // All binary operators are left-associative
+            let right = self.parse_expression(precedence)?;
+            left = Expression::Binary { op, left: Box::new(left), right: Box::new(right) };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_type_specifier(&mut self) -> Result<String> {
+        let mut parts = vec![self.parse_identifier("a type name")?];
+        while self.is_symbol(".") {
+            self.position += 1;
+            parts.push(self.parse_identifier("a type name")?);
+        }
+        Ok(parts.join("."))
+    }
+
+    fn parse_identifier(&mut self, expected: &str) -> Result<String> {
+        match self.next() {
+            Some(Token::Identifier(name)) | Some(Token::DelimitedIdentifier(name)) => Ok(name),
+            other => {
+                let found = other.map(|t| t.describe()).unwrap_or_else(|| "end of input".into());
+                Err(self.error(&format!("expected {}, found {}", expected, found)))
+            }
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression> {
+        let op = if self.is_symbol("-") {
+            Some(UnaryOperator::Minus)
+        } else if self.is_symbol("+") {
+            Some(UnaryOperator::Plus)
+        } else {
+            None
+        };
+
+        match op {
+            Some(op) => {
+                self.position += 1;
+                let operand = self.parse_unary()?;
+                Ok(Expression::Unary { op, operand: Box::new(operand) })
+            }
+            None => self.parse_postfix(),
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_term()?;
+
+        loop {
+            if self.is_symbol(".") {
+                self.position += 1;
+                let name = self.parse_identifier("a member name")?;
+                expression = if self.is_symbol("(") {
+                    let args = self.parse_arguments()?;
+                    Expression::Function { target: Some(Box::new(expression)), name, args }
+                } else {
+                    Expression::Member { target: Box::new(expression), name }
+                };
+            } else if self.is_symbol("[") {
+                self.position += 1;
+                let index = self.parse_expression(0)?;
+                self.expect_symbol("]")?;
+                expression =
+                    Expression::Index { target: Box::new(expression), index: Box::new(index) };
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>> {
+        self.expect_symbol("(")?;
+        let mut args = Vec::new();
+        if !self.is_symbol(")") {
+            loop {
+                args.push(self.parse_expression(0)?);
+                if !self.is_symbol(",") {
+                    break;
+                }
+                self.position += 1;
+            }
+        }
+        self.expect_symbol(")")?;
+        Ok(args)
+    }
+
+    fn parse_term(&mut self) -> Result<Expression> {
+        let token = self.next().ok_or_else(|| self.error("unexpected end of input"))?;
+
+        let literal = |value| Ok(Expression::Literal { value });
+        match token {
+            Token::Symbol("(") => {
+                let expression = self.parse_expression(0)?;
+                self.expect_symbol(")")?;
+                Ok(expression)
+            }
+            Token::Symbol("{") => {
+                self.expect_symbol("}")?;
+                literal(Literal::Empty)
+            }
+            Token::String(value) => literal(Literal::String(value)),
+            Token::DateTime(value) => literal(Literal::DateTime(value)),
+            Token::Number(value) => match self.peek() {
+                // Quantity literals: `4 'mg'` or calendar durations such as `2 days`
+                Some(Token::String(unit)) => {
+                    let unit = unit.clone();
+                    self.position += 1;
+                    literal(Literal::Quantity { value, unit })
+                }
+                Some(Token::Identifier(unit)) if is_calendar_unit(unit) => {
+                    let unit = unit.clone();
+                    self.position += 1;
+                    literal(Literal::Quantity { value, unit })
+                }
+                _ => literal(Literal::Number(value)),
+            },
+            Token::Variable(name) => Ok(Expression::Variable { name }),
+            Token::External(name) => Ok(Expression::External { name }),
+            Token::Identifier(name) if name == "true" || name == "false" => {
+                literal(Literal::Boolean(name == "true"))
+            }
+            Token::Identifier(name) | Token::DelimitedIdentifier(name) => {
+                if self.is_symbol("(") {
+                    let args = self.parse_arguments()?;
+                    Ok(Expression::Function { target: None, name, args })
+                } else {
+                    Ok(Expression::Identifier { name })
+                }
+            }
+            other => Err(self.error(&format!("unexpected {}", other.describe()))),
+        }
+    }
+}
+
+fn is_calendar_unit(unit: &str) -> bool {
+    matches!(
+        unit,
+        "year"
+            | "years"
+            | "month"
+            | "months"
+            | "week"
+            | "weeks"
+            | "day"
+            | "days"
+            | "hour"
+            | "hours"
+            | "minute"
+            | "minutes"
+            | "second"
+            | "seconds"
+            | "millisecond"
+            | "milliseconds"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(name: &str) -> Expression {
+        Expression::Identifier { name: name.to_string() }
+    }
+
+    fn function(target: Option<Expression>, name: &str, args: Vec<Expression>) -> Expression {
+        Expression::Function { target: target.map(Box::new), name: name.to_string(), args }
+    }
+
+    #[test]
+    fn test_parse_member_function() {
+        let parsed = parse("name.given.exists()").unwrap();
+        let expected = function(
+            Some(Expression::Member {
+                target: Box::new(identifier("name")),
+                name: "given".to_string(),
+            }),
+            "exists",
+            vec![],
+        );
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        // `and` binds tighter than `or`, which binds tighter than `implies`
+        let parsed = parse("a or b and c implies d").unwrap();
+        let Expression::Binary { op: BinaryOperator::Implies, left, .. } = parsed else {
+            panic!("expected implies at the root");
+        };
+        let Expression::Binary { op: BinaryOperator::Or, right, .. } = *left else {
+            panic!("expected or under implies");
+        };
+        assert!(matches!(*right, Expression::Binary { op: BinaryOperator::And, .. }));
+    }
+
+    #[test]
+    fn test_parse_comparison_with_count() {
+        let parsed = parse("name.count() > 1").unwrap();
+        assert!(matches!(
+            parsed,
+            Expression::Binary { op: BinaryOperator::GreaterThan, ref right, .. }
+                if **right == Expression::Literal { value: Literal::Number("1".to_string()) }
+        ));
+    }
+
+    #[test]
+    fn test_parse_pat_1() {
+        let parsed =
+            parse("name.exists() or telecom.exists() or address.exists() or organization.exists()")
+                .unwrap();
+        assert!(matches!(parsed, Expression::Binary { op: BinaryOperator::Or, .. }));
+    }
+
+    #[test]
+    fn test_parse_where_with_this_and_strings() {
+        let parsed = parse("code.where($this = 'it\\'s').empty()").unwrap();
+        let Expression::Function { target: Some(target), name, .. } = parsed else {
+            panic!("expected empty()");
+        };
+        assert_eq!(name, "empty");
+        let Expression::Function { name, args, .. } = *target else {
+            panic!("expected where()");
+        };
+        assert_eq!(name, "where");
+        assert_eq!(
+            args[0],
+            Expression::Binary {
+                op: BinaryOperator::Equals,
+                left: Box::new(Expression::Variable { name: "this".to_string() }),
+                right: Box::new(Expression::Literal { value: Literal::String("it's".to_string()) }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_index_type_and_literals() {
+        let parsed = parse("name[0] is HumanName").unwrap();
+        assert!(matches!(parsed, Expression::Type { op: TypeOperator::Is, ref type_name, .. }
+            if type_name == "HumanName"));
+
+        assert_eq!(parse("{}").unwrap(), Expression::Literal { value: Literal::Empty });
+        assert_eq!(
+            parse("4.5 'mg'").unwrap(),
+            Expression::Literal {
+                value: Literal::Quantity { value: "4.5".to_string(), unit: "mg".to_string() }
+            }
+        );
+        assert!(matches!(parse("-1").unwrap(), Expression::Unary { op: UnaryOperator::Minus, .. }));
+        assert_eq!(
+            parse("%resource").unwrap(),
+            Expression::External { name: "resource".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("name.exists(").is_err());
+        assert!(parse("name.").is_err());
+        assert!(parse("'unterminated").is_err());
+        assert!(parse("a b").is_err());
+        assert!(parse("name # given").is_err());
+    }
+}
@@ -5,7 +5,10 @@
 use crate::core::ir::*;
 use crate::core::parser::{StructureDefinitionParser, StructureKind};
 use crate::core::resolver::SchemaResolver;
+use crate::core::terminology::ValueSetExpander;
 use crate::core::{Error, Result};
+use heck::ToLowerCamelCase;
+use indexmap::IndexSet;
 use octofhir_canonical_manager::CanonicalManager;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -89,9 +92,21 @@ impl TypeGraphBuilder {
             }
         }
 
+        // Parse extension definitions and attach them to the resources they apply to
+        Self::add_extensions(&mut graph, &structure_defs);
+
+        // Point profiled reference targets at the resource types they constrain
+        Self::resolve_reference_targets(&mut graph, &Self::types_by_url(&structure_defs));
+
         // Add search parameters
         self.add_search_parameters(&mut graph).await?;
 
+        // Add operation definitions
+        self.add_operations(&mut graph).await?;
+
+        // Expand value sets bound to coded elements
+        self.add_value_sets(&mut graph).await?;
+
         // Update metadata
         graph.metadata.source_packages = self.get_installed_packages().await?;
 
@@ -124,6 +139,11 @@ impl TypeGraphBuilder {
         for sd in structure_defs {
             let name = sd.get("name").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
 
+            // Extension definitions constrain Extension rather than define a datatype
+            if Self::is_extension_definition(sd) {
+                continue;
+            }
+
             let kind = sd.get("kind").and_then(|v| v.as_str());
 
             match kind {
@@ -154,13 +174,13 @@ impl TypeGraphBuilder {
             return Err(Error::Parser(format!("{} is not a primitive type", parsed.name)));
         }
 
-        // Extract pattern from elements if available
-        let pattern =
-            parsed.elements.iter().find(|e| e.path == format!("{}.value", parsed.name)).and(
-                // Try to get pattern from element
-                // This is a simplified version - real implementation would parse constraints
-                None,
-            );
+        // The value element's type carries the regex extension
+        let value_path = format!("{}.value", parsed.name);
+        let pattern = parsed
+            .elements
+            .iter()
+            .find(|e| e.path == value_path)
+            .and_then(|e| e.types.iter().find_map(|t| t.regex.clone()));
 
         Ok(PrimitiveType {
             name: parsed.name.clone(),
@@ -235,6 +255,127 @@ impl TypeGraphBuilder {
         Ok(())
     }
 
+    /// Expand the value sets of `required` and `extensible` bindings on `code` elements
+    ///
+    /// Value sets that cannot be expanded locally (missing code systems,
+    /// filter-based includes) are skipped with a warning.
+    async fn add_value_sets(&self, graph: &mut TypeGraph) -> Result<()> {
+        let mut urls = IndexSet::new();
+        for resource in graph.resources.values() {
+            Self::collect_code_bindings(&resource.properties, &mut urls);
+        }
+        for datatype in graph.datatypes.values() {
+            Self::collect_code_bindings(&datatype.properties, &mut urls);
+        }
+        debug!("Expanding {} bound value sets", urls.len());
+
+        let mut expander = ValueSetExpander::new();
+        let mut pending: Vec<String> = urls.iter().cloned().collect();
+        while let Some(url) = pending.pop() {
+            if expander.has_value_set(&url) {
+                continue;
+            }
+            let Some(value_set) = self.resolver.resolve_value_set(&url).await? else {
+                continue;
+            };
+
+            let (systems, nested) = ValueSetExpander::references(&value_set);
+            expander.add_value_set(value_set);
+            pending.extend(nested);
+
+            for system in systems {
+                if expander.has_code_system(&system) {
+                    continue;
+                }
+                if let Some(code_system) = self.resolver.resolve_code_system(&system).await? {
+                    expander.add_code_system(code_system);
+                }
+            }
+        }
+
+        for url in urls {
+            match expander.expand(&url) {
+                Ok(expansion) => graph.add_value_set(url, expansion),
+                Err(e) => warn!("Skipping value set {}: {}", url, e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Map each StructureDefinition's versionless URL to the type it defines or constrains
+    fn types_by_url(structure_defs: &[Value]) -> HashMap<String, String> {
+        structure_defs
+            .iter()
+            .filter_map(|sd| {
+                let url = sd.get("url").and_then(|v| v.as_str())?;
+                let type_name = sd.get("type").and_then(|v| v.as_str())?;
+                Some((versionless_url(url).to_string(), type_name.to_string()))
+            })
+            .collect()
+    }
+
+    /// Resolve `Reference` target types from their target profile URLs
+    ///
+    /// The parser only sees the URL, so a target like `us-core-patient` is
+    /// replaced by the type its profile constrains (`Patient`). The profile
+    /// URLs themselves are kept.
+    fn resolve_reference_targets(graph: &mut TypeGraph, types_by_url: &HashMap<String, String>) {
+        fn resolve(properties: &mut [Property], types_by_url: &HashMap<String, String>) {
+            for property in properties {
+                match &mut property.property_type {
+                    PropertyType::Reference { target_types, target_profiles } => {
+                        let mut resolved: Vec<String> = Vec::new();
+                        for url in target_profiles.iter() {
+                            let type_name = match types_by_url.get(versionless_url(url)) {
+                                Some(type_name) => type_name.clone(),
+                                None => StructureDefinitionParser::reference_target_types(
+                                    std::slice::from_ref(url),
+                                )
+                                .remove(0),
+                            };
+                            if !resolved.contains(&type_name) {
+                                resolved.push(type_name);
+                            }
+                        }
+                        if !resolved.is_empty() {
+                            *target_types = resolved;
+                        }
+                    }
+                    PropertyType::BackboneElement { properties } => {
+                        resolve(properties, types_by_url);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for resource in graph.resources.values_mut() {
+            resolve(&mut resource.properties, types_by_url);
+        }
+        for datatype in graph.datatypes.values_mut() {
+            resolve(&mut datatype.properties, types_by_url);
+        }
+    }
+
+    /// Collect versionless value set URLs bound to `code` properties
+    fn collect_code_bindings(properties: &[Property], urls: &mut IndexSet<String>) {
+        for property in properties {
+            if let Some(binding) = &property.binding
+                && matches!(
+                    binding.strength,
+                    BindingStrength::Required | BindingStrength::Extensible
+                )
+                && matches!(&property.property_type, PropertyType::Primitive { type_name } if type_name == "code")
+            {
+                urls.insert(versionless_url(&binding.value_set).to_string());
+            }
+            if let PropertyType::BackboneElement { properties } = &property.property_type {
+                Self::collect_code_bindings(properties, urls);
+            }
+        }
+    }
+
     /// Parse a SearchParameter resource
     fn parse_search_parameter(&self, content: &Value) -> Result<SearchParameter> {
         let code = content
@@ -248,7 +389,31 @@ impl TypeGraphBuilder {
             .and_then(|v| v.as_str())
             .ok_or_else(|| Error::Parser("SearchParameter missing type".to_string()))?;
 
-        let param_type = match param_type {
+        let param_type = Self::parse_search_param_type(param_type).ok_or_else(|| {
+            Error::Parser(format!("Unknown search parameter type: {}", param_type))
+        })?;
+
+        let description =
+            content.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+        let expression = content.get("expression").and_then(|v| v.as_str()).map(String::from);
+
+        let target_types = if param_type == SearchParamType::Reference {
+            content
+                .get("target")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+
+        Ok(SearchParameter { code, param_type, description, expression, target_types })
+    }
+
+    /// Map a SearchParameter `type` code to its IR type
+    fn parse_search_param_type(code: &str) -> Option<SearchParamType> {
+        Some(match code {
             "number" => SearchParamType::Number,
             "date" => SearchParamType::Date,
             "string" => SearchParamType::String,
@@ -258,30 +423,351 @@ impl TypeGraphBuilder {
             "quantity" => SearchParamType::Quantity,
             "uri" => SearchParamType::Uri,
             "special" => SearchParamType::Special,
-            _ => {
+            _ => return None,
+        })
+    }
+
+    /// Whether a StructureDefinition defines an extension (a constraint on `Extension`)
+    pub fn is_extension_definition(sd: &Value) -> bool {
+        sd.get("type").and_then(|v| v.as_str()) == Some("Extension")
+            && sd.get("derivation").and_then(|v| v.as_str()) == Some("constraint")
+    }
+
+    /// Add extension definitions to the graph
+    ///
+    /// Each extension is also attached to the resources named by its contexts,
+    /// skipping extensions whose name another extension of the resource already uses.
+    fn add_extensions(graph: &mut TypeGraph, structure_defs: &[Value]) {
+        for sd in structure_defs.iter().filter(|sd| Self::is_extension_definition(sd)) {
+            match Self::parse_extension_definition(sd) {
+                Ok(extension) => graph.add_extension(extension.url.clone(), extension),
+                Err(e) => warn!("Skipping extension definition: {}", e),
+            }
+        }
+
+        for extension in graph.extensions.values() {
+            for target in &extension.target_types {
+                let Some(resource) = graph.resources.get_mut(target) else { continue };
+                if resource.extensions.iter().any(|existing| existing.name == extension.name) {
+                    debug!("Skipping duplicate extension {} on {}", extension.name, target);
+                    continue;
+                }
+                resource.extensions.push(extension.clone());
+            }
+        }
+    }
+
+    /// Parse an extension StructureDefinition into an [`Extension`]
+    ///
+    /// Value types come from `Extension.value[x]` and sub-extensions from the
+    /// `Extension.extension` slices, recursively.
+    fn parse_extension_definition(sd: &Value) -> Result<Extension> {
+        let url = sd
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Parser("Extension definition missing url".to_string()))?;
+
+        let elements = sd
+            .pointer("/snapshot/element")
+            .or_else(|| sd.pointer("/differential/element"))
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| Error::Parser(format!("Extension {} has no elements", url)))?;
+
+        // R4 contexts are objects with an expression; STU3 used plain strings
+        let target_types = sd
+            .get("context")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|context| {
+                        context
+                            .as_str()
+                            .or_else(|| context.get("expression").and_then(|v| v.as_str()))
+                            .map(String::from)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let name = sd.get("name").and_then(|v| v.as_str()).unwrap_or("extension");
+        let name = name.to_lower_camel_case();
+        let name = match name.strip_suffix("Extension") {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => name,
+        };
+
+        let mut extension = Self::parse_extension_elements(elements, "Extension", url, name);
+        extension.target_types = target_types;
+        let text = |key: &str| sd.get(key).and_then(|v| v.as_str()).map(String::from);
+        if let Some(title) = text("title") {
+            extension.documentation.short = title;
+        }
+        if let Some(description) = text("description") {
+            extension.documentation.definition = description;
+        }
+        extension.documentation.url = Some(url.to_string());
+
+        Ok(extension)
+    }
+
+    /// Build an extension from the elements below the element with id `prefix`
+    fn parse_extension_elements(
+        elements: &[Value],
+        prefix: &str,
+        url: &str,
+        name: String,
+    ) -> Extension {
+        let element_id = |element: &Value| {
+            element
+                .get("id")
+                .or_else(|| element.get("path"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let find = |id: &str| elements.iter().find(|element| element_id(element) == id);
+        let text = |element: Option<&Value>, key: &str| {
+            element.and_then(|e| e.get(key)).and_then(|v| v.as_str()).map(String::from)
+        };
+
+        let root = find(prefix);
+        let min = root.and_then(|e| e.get("min")).and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        let max = match root.and_then(|e| e.get("max")).and_then(|v| v.as_str()) {
+            Some("*") | None => None,
+            Some(max) => Some(max.parse().unwrap_or(1)),
+        };
+
+        // A value[x] with max 0 marks a complex extension
+        let value = find(&format!("{}.value[x]", prefix))
+            .filter(|e| e.get("max").and_then(|v| v.as_str()) != Some("0"));
+        let value_types: Vec<String> = value
+            .and_then(|e| e.get("type"))
+            .and_then(|v| v.as_array())
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(|t| t.get("code").and_then(|v| v.as_str()).map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let slice_prefix = format!("{}.extension:", prefix);
+        let sub_extensions: Vec<Extension> = elements
+            .iter()
+            .map(element_id)
+            .filter_map(|id| {
+                let slice = id.strip_prefix(&slice_prefix)?;
+                (!slice.contains('.')).then(|| (id.clone(), slice.to_string()))
+            })
+            .filter(|(id, _)| {
+                find(id).and_then(|e| e.get("max")).and_then(|v| v.as_str()) != Some("0")
+            })
+            .map(|(id, slice)| {
+                // Sub-extension URLs are fixed on their `url` element, usually to the slice name
+                let sub_url = find(&format!("{}.url", id))
+                    .and_then(|e| e.get("fixedUri"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| slice.clone());
+                Self::parse_extension_elements(elements, &id, &sub_url, slice)
+            })
+            .collect();
+
+        Extension {
+            url: url.to_string(),
+            name,
+            value_types,
+            target_types: vec![],
+            cardinality: CardinalityRange { min, max },
+            documentation: Documentation {
+                short: text(root, "short").unwrap_or_default(),
+                definition: text(root, "definition").unwrap_or_default(),
+                ..Default::default()
+            },
+            is_complex: !sub_extensions.is_empty(),
+            sub_extensions,
+        }
+    }
+
+    /// Add operation definitions, keyed by canonical URL
+    async fn add_operations(&self, graph: &mut TypeGraph) -> Result<()> {
+        debug!("Loading operation definitions");
+
+        let operations =
+            self.resolver.get_resources_by_type("OperationDefinition", Some(1000)).await?;
+
+        debug!("Found {} operation definitions", operations.len());
+
+        for op_resource in operations {
+            match self.parse_operation_definition(&op_resource.resource.content) {
+                Ok(operation) => graph.add_operation(operation.url.clone(), operation),
+                Err(e) => warn!("Skipping operation definition: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse an OperationDefinition resource
+    fn parse_operation_definition(&self, content: &Value) -> Result<OperationDefinition> {
+        let url = content
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Parser("OperationDefinition missing url".to_string()))?;
+
+        let code = content
+            .get("code")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Parser(format!("OperationDefinition {} missing code", url)))?
+            .to_string();
+
+        let flag = |name: &str| content.get(name).and_then(|v| v.as_bool()).unwrap_or(false);
+        let text = |name: &str| {
+            content.get(name).and_then(|v| v.as_str()).map(String::from).unwrap_or_default()
+        };
+
+        let kind = match content.get("kind").and_then(|v| v.as_str()) {
+            Some("query") => OperationKind::Query,
+            _ => OperationKind::Operation,
+        };
+
+        let resources = content
+            .get("resource")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        let parameters = content
+            .get("parameter")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().map(Self::parse_operation_parameter).collect::<Result<_>>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let name = content.get("name").and_then(|v| v.as_str()).unwrap_or(&code).to_string();
+
+        Ok(OperationDefinition {
+            url: versionless_url(url).to_string(),
+            name,
+            code,
+            kind,
+            affects_state: flag("affectsState"),
+            resources,
+            system: flag("system"),
+            type_level: flag("type"),
+            instance: flag("instance"),
+            parameters,
+            documentation: Documentation {
+                short: text("title"),
+                definition: text("description"),
+                comments: content.get("comment").and_then(|v| v.as_str()).map(String::from),
+                url: Some(url.to_string()),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Parse an `OperationDefinition.parameter`, including its parts
+    fn parse_operation_parameter(content: &Value) -> Result<OperationParameter> {
+        let name = content
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Parser("Operation parameter missing name".to_string()))?
+            .to_string();
+
+        let usage = match content.get("use").and_then(|v| v.as_str()) {
+            Some("in") => OperationParameterUse::In,
+            Some("out") => OperationParameterUse::Out,
+            other => {
                 return Err(Error::Parser(format!(
-                    "Unknown search parameter type: {}",
-                    param_type
+                    "Operation parameter {} has invalid use {:?}",
+                    name, other
                 )));
             }
         };
 
-        let description =
-            content.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
-
-        let expression = content.get("expression").and_then(|v| v.as_str()).map(String::from);
+        let min = content.get("min").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        let max = match content.get("max").and_then(|v| v.as_str()) {
+            Some("*") => None,
+            Some(max) => Some(max.parse().unwrap_or(1)),
+            None => Some(1),
+        };
 
-        let target_types = if param_type == SearchParamType::Reference {
-            content
-                .get("target")
+        let binding = content.get("binding").and_then(|binding| {
+            let strength = match binding.get("strength")?.as_str()? {
+                "required" => BindingStrength::Required,
+                "extensible" => BindingStrength::Extensible,
+                "preferred" => BindingStrength::Preferred,
+                "example" => BindingStrength::Example,
+                _ => return None,
+            };
+            // R4 uses valueSet; STU3 used valueSetReference/valueSetUri
+            let value_set = binding
+                .get("valueSet")
+                .and_then(|v| v.as_str())
+                .or_else(|| {
+                    binding.pointer("/valueSetReference/reference").and_then(|v| v.as_str())
+                })
+                .or_else(|| binding.get("valueSetUri").and_then(|v| v.as_str()))?;
+            Some(ValueSetBinding {
+                strength,
+                value_set: value_set.to_string(),
+                description: binding.get("description").and_then(|v| v.as_str()).map(String::from),
+            })
+        });
+
+        let parts = content
+            .get("part")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().map(Self::parse_operation_parameter).collect::<Result<_>>())
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(OperationParameter {
+            name,
+            usage,
+            cardinality: CardinalityRange { min, max },
+            type_name: content.get("type").and_then(|v| v.as_str()).map(String::from),
+            target_profiles: content
+                .get("targetProfile")
                 .and_then(|v| v.as_array())
                 .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-                .unwrap_or_default()
-        } else {
-            vec![]
-        };
+                .unwrap_or_default(),
+            search_type: content
+                .get("searchType")
+                .and_then(|v| v.as_str())
+                .and_then(Self::parse_search_param_type),
+            binding,
+            documentation: content
+                .get("documentation")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            parts,
+        })
+    }
 
-        Ok(SearchParameter { code, param_type, description, expression, target_types })
+    /// Load example instances of the graph's concrete resources from installed packages
+    ///
+    /// At most `per_type` examples are kept for each resource type.
+    pub async fn add_examples(&self, graph: &mut TypeGraph, per_type: usize) -> Result<()> {
+        let resource_types: Vec<String> = graph
+            .resources
+            .values()
+            .filter(|resource| !resource.is_abstract)
+            .map(|resource| resource.name.clone())
+            .collect();
+
+        for resource_type in resource_types {
+            let examples = self.resolver.get_instances_by_type(&resource_type, per_type).await?;
+            debug!("Found {} examples of {}", examples.len(), resource_type);
+
+            for example in examples {
+                graph.add_example(resource_type.clone(), example);
+            }
+        }
+
+        Ok(())
     }
 
     /// Get list of installed packages
@@ -338,6 +824,61 @@ mod tests {
         // Just verify it creates successfully
     }
 
+    #[test]
+    fn test_resolve_reference_targets() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource(
+            "Observation".to_string(),
+            ResourceType {
+                name: "Observation".to_string(),
+                base: Some("DomainResource".to_string()),
+                properties: vec![Property {
+                    name: "subject".to_string(),
+                    path: "Observation.subject".to_string(),
+                    property_type: PropertyType::Reference {
+                        target_types: vec!["us-core-patient".to_string(), "Group".to_string()],
+                        target_profiles: vec![
+                            "http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient"
+                                .to_string(),
+                            "http://hl7.org/fhir/StructureDefinition/Group".to_string(),
+                        ],
+                    },
+                    cardinality: CardinalityRange::optional(),
+                    is_choice: false,
+                    choice_types: vec![],
+                    is_modifier: false,
+                    is_summary: true,
+                    binding: None,
+                    constraints: vec![],
+                    short_description: String::new(),
+                    definition: String::new(),
+                    comments: None,
+                    examples: vec![],
+                }],
+                constraints: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: "http://hl7.org/fhir/StructureDefinition/Observation".to_string(),
+                is_abstract: false,
+            },
+        );
+        let types_by_url = TypeGraphBuilder::types_by_url(&[serde_json::json!({
+            "url": "http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient",
+            "type": "Patient"
+        })]);
+
+        TypeGraphBuilder::resolve_reference_targets(&mut graph, &types_by_url);
+
+        let PropertyType::Reference { target_types, target_profiles } =
+            &graph.resources["Observation"].properties[0].property_type
+        else {
+            panic!("Expected Reference type");
+        };
+        assert_eq!(target_types, &["Patient", "Group"]);
+        assert_eq!(target_profiles.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_categorize_empty() {
         let builder = create_test_builder().await;
@@ -380,6 +921,139 @@ mod tests {
         assert!(primitives.contains_key("string"));
     }
 
+    #[tokio::test]
+    async fn test_process_primitive_extracts_regex() {
+        let builder = create_test_builder().await;
+        let sd = serde_json::json!({
+            "resourceType": "StructureDefinition",
+            "url": "http://hl7.org/fhir/StructureDefinition/id",
+            "name": "id",
+            "kind": "primitive-type",
+            "baseDefinition": "http://hl7.org/fhir/StructureDefinition/string",
+            "snapshot": {
+                "element": [
+                    { "path": "id", "min": 0, "max": "*" },
+                    {
+                        "path": "id.value",
+                        "min": 0,
+                        "max": "1",
+                        "type": [{
+                            "code": "http://hl7.org/fhirpath/System.String",
+                            "extension": [{
+                                "url": "http://hl7.org/fhir/StructureDefinition/regex",
+                                "valueString": "[A-Za-z0-9\\-\\.]{1,64}"
+                            }]
+                        }]
+                    }
+                ]
+            }
+        });
+
+        let primitive = builder.process_primitive(&sd).await.unwrap();
+
+        assert_eq!(primitive.name, "id");
+        assert_eq!(primitive.pattern.as_deref(), Some("[A-Za-z0-9\\-\\.]{1,64}"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_operation_definition() {
+        let builder = create_test_builder().await;
+        let operation = serde_json::json!({
+            "resourceType": "OperationDefinition",
+            "url": "http://hl7.org/fhir/OperationDefinition/Patient-everything|4.0.1",
+            "name": "Everything",
+            "kind": "operation",
+            "code": "everything",
+            "resource": ["Patient"],
+            "system": false,
+            "type": true,
+            "instance": true,
+            "parameter": [
+                { "name": "start", "use": "in", "min": 0, "max": "1", "type": "date" },
+                {
+                    "name": "_type", "use": "in", "min": 0, "max": "*", "type": "code",
+                    "binding": {
+                        "strength": "required",
+                        "valueSet": "http://hl7.org/fhir/ValueSet/resource-types|4.0.1"
+                    }
+                },
+                {
+                    "name": "match", "use": "out", "min": 0, "max": "*",
+                    "part": [
+                        { "name": "resource", "use": "out", "min": 1, "max": "1", "type": "Resource" }
+                    ]
+                },
+                { "name": "return", "use": "out", "min": 1, "max": "1", "type": "Bundle" }
+            ]
+        });
+
+        let operation = builder.parse_operation_definition(&operation).unwrap();
+
+        assert_eq!(operation.url, "http://hl7.org/fhir/OperationDefinition/Patient-everything");
+        assert_eq!(operation.code, "everything");
+        assert_eq!(operation.kind, OperationKind::Operation);
+        assert!(operation.type_level && operation.instance && !operation.system);
+        assert_eq!(operation.input_parameters().count(), 2);
+        let type_param = &operation.parameters[1];
+        assert!(type_param.cardinality.is_array());
+        assert_eq!(type_param.binding.as_ref().unwrap().strength, BindingStrength::Required);
+        let match_param = &operation.parameters[2];
+        assert_eq!(match_param.type_name, None);
+        assert_eq!(match_param.parts[0].type_name.as_deref(), Some("Resource"));
+        // Two outputs, so the response is a Parameters resource
+        assert_eq!(operation.return_resource(), None);
+    }
+
+    #[test]
+    fn test_parse_extension_definition() {
+        let race = serde_json::json!({
+            "resourceType": "StructureDefinition",
+            "url": "http://hl7.org/fhir/us/core/StructureDefinition/us-core-race",
+            "name": "USCoreRaceExtension",
+            "title": "US Core Race Extension",
+            "kind": "complex-type",
+            "type": "Extension",
+            "derivation": "constraint",
+            "context": [{ "type": "element", "expression": "Patient" }],
+            "snapshot": {
+                "element": [
+                    { "id": "Extension", "path": "Extension", "min": 0, "max": "1" },
+                    { "id": "Extension.extension:ombCategory", "path": "Extension.extension", "min": 0, "max": "5" },
+                    {
+                        "id": "Extension.extension:ombCategory.url", "path": "Extension.extension.url",
+                        "fixedUri": "ombCategory"
+                    },
+                    {
+                        "id": "Extension.extension:ombCategory.value[x]", "path": "Extension.extension.value[x]",
+                        "min": 1, "max": "1", "type": [{ "code": "Coding" }]
+                    },
+                    { "id": "Extension.extension:text", "path": "Extension.extension", "min": 1, "max": "1" },
+                    {
+                        "id": "Extension.extension:text.value[x]", "path": "Extension.extension.value[x]",
+                        "min": 1, "max": "1", "type": [{ "code": "string" }]
+                    },
+                    { "id": "Extension.value[x]", "path": "Extension.value[x]", "min": 0, "max": "0" }
+                ]
+            }
+        });
+
+        assert!(TypeGraphBuilder::is_extension_definition(&race));
+        let extension = TypeGraphBuilder::parse_extension_definition(&race).unwrap();
+
+        assert_eq!(extension.name, "usCoreRace");
+        assert_eq!(extension.target_types, vec!["Patient".to_string()]);
+        assert!(extension.value_types.is_empty());
+        assert!(extension.is_complex);
+        assert_eq!(extension.sub_extensions.len(), 2);
+        let omb = &extension.sub_extensions[0];
+        assert_eq!(omb.url, "ombCategory");
+        assert_eq!(omb.value_types, vec!["Coding".to_string()]);
+        assert_eq!(omb.cardinality.max, Some(5));
+        let text = &extension.sub_extensions[1];
+        assert!(text.cardinality.is_required());
+        assert_eq!(text.value_types, vec!["string".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_build_empty_graph() {
         let builder = create_test_builder().await;
@@ -5,7 +5,7 @@
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Complete type graph representing all FHIR definitions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,6 +22,24 @@ pub struct TypeGraph {
     /// Profile definitions (e.g., USCorePatient)
     pub profiles: IndexMap<String, ProfileType>,
 
+    /// Expanded value sets for required and extensible bindings, keyed by versionless URL
+    #[serde(default)]
+    pub value_sets: IndexMap<String, ValueSetExpansion>,
+
+    /// Operation definitions (e.g., `$everything`, `$validate`), keyed by canonical URL
+    #[serde(default)]
+    pub operations: IndexMap<String, OperationDefinition>,
+
+    /// Extension definitions, keyed by canonical URL
+    #[serde(default)]
+    pub extensions: IndexMap<String, Extension>,
+
+    /// Example instances shipped with the packages, keyed by resource type
+    ///
+    /// Only loaded when generated tests are requested.
+    #[serde(default)]
+    pub examples: IndexMap<String, Vec<serde_json::Value>>,
+
     /// FHIR version this graph represents
     pub fhir_version: FhirVersion,
 
@@ -37,6 +55,10 @@ impl TypeGraph {
             datatypes: IndexMap::new(),
             primitives: IndexMap::new(),
             profiles: IndexMap::new(),
+            value_sets: IndexMap::new(),
+            operations: IndexMap::new(),
+            extensions: IndexMap::new(),
+            examples: IndexMap::new(),
             fhir_version,
             metadata: GraphMetadata::default(),
         }
@@ -62,6 +84,111 @@ impl TypeGraph {
         self.profiles.insert(name, profile);
     }
 
+    /// Add an expanded value set to the graph
+    pub fn add_value_set(&mut self, url: String, value_set: ValueSetExpansion) {
+        self.value_sets.insert(url, value_set);
+    }
+
+    /// Add an operation definition to the graph
+    pub fn add_operation(&mut self, url: String, operation: OperationDefinition) {
+        self.operations.insert(url, operation);
+    }
+
+    /// Add an extension definition to the graph
+    pub fn add_extension(&mut self, url: String, extension: Extension) {
+        self.extensions.insert(url, extension);
+    }
+
+    /// Add an example instance of a resource type to the graph
+    pub fn add_example(&mut self, resource_type: String, example: serde_json::Value) {
+        self.examples.entry(resource_type).or_default().push(example);
+    }
+
+    /// Look up the expansion for a binding's value set, ignoring any `|version` suffix
+    pub fn value_set(&self, url: &str) -> Option<&ValueSetExpansion> {
+        self.value_sets.get(versionless_url(url))
+    }
+
+    /// Search parameters of a resource and its base resources, without duplicates
+    ///
+    /// Parameters such as `_id` and `_lastUpdated` are declared on `Resource`
+    /// and `DomainResource` and apply to every resource deriving from them.
+    pub fn search_parameters<'a>(&'a self, resource: &'a ResourceType) -> Vec<&'a SearchParameter> {
+        let mut seen_codes = HashSet::new();
+        let mut seen_types = HashSet::new();
+        let mut parameters = Vec::new();
+
+        let mut current = Some(resource);
+        while let Some(resource) = current {
+            if !seen_types.insert(resource.name.as_str()) {
+                break;
+            }
+            for parameter in &resource.search_parameters {
+                if seen_codes.insert(parameter.code.as_str()) {
+                    parameters.push(parameter);
+                }
+            }
+            current = resource.base.as_deref().and_then(|base| self.resources.get(base));
+        }
+
+        parameters
+    }
+
+    /// Properties of a resource or datatype and the types it derives from,
+    /// root type first (`Resource`, `DomainResource`, then `Patient`)
+    pub fn properties_with_base(&self, name: &str) -> Vec<&Property> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = Some(name);
+
+        while let Some(name) = current.take() {
+            if !seen.insert(name) {
+                break;
+            }
+            if let Some(resource) = self.resources.get(name) {
+                chain.push(&resource.properties);
+                current = resource.base.as_deref();
+            } else if let Some(datatype) = self.datatypes.get(name) {
+                chain.push(&datatype.properties);
+                current = datatype.base.as_deref();
+            }
+        }
+
+        chain.into_iter().rev().flatten().collect()
+    }
+
+    /// Root invariants of a resource or datatype and the types it derives from,
+    /// root type first and without duplicate keys
+    ///
+    /// Invariants such as `dom-2` are declared once on `DomainResource` and
+    /// apply to every resource deriving from it.
+    pub fn constraints_with_base(&self, name: &str) -> Vec<&InvariantRule> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = Some(name);
+
+        while let Some(name) = current.take() {
+            if !seen.insert(name) {
+                break;
+            }
+            if let Some(resource) = self.resources.get(name) {
+                chain.push(&resource.constraints);
+                current = resource.base.as_deref();
+            } else if let Some(datatype) = self.datatypes.get(name) {
+                chain.push(&datatype.constraints);
+                current = datatype.base.as_deref();
+            }
+        }
+
+        let mut seen_keys = HashSet::new();
+        chain
+            .into_iter()
+            .rev()
+            .flatten()
+            .filter(|rule| seen_keys.insert(rule.key.as_str()))
+            .collect()
+    }
+
     /// Get total number of types in graph
     pub fn total_types(&self) -> usize {
         self.resources.len() + self.datatypes.len() + self.primitives.len() + self.profiles.len()
@@ -131,6 +258,10 @@ pub struct ResourceType {
     /// Properties/elements of this resource
     pub properties: Vec<Property>,
 
+    /// Invariants declared on the resource itself (e.g., "obs-6")
+    #[serde(default)]
+    pub constraints: Vec<InvariantRule>,
+
     /// Search parameters defined for this resource
     pub search_parameters: Vec<SearchParameter>,
 
@@ -159,6 +290,10 @@ pub struct DataType {
     /// Properties of this datatype
     pub properties: Vec<Property>,
 
+    /// Invariants declared on the datatype itself
+    #[serde(default)]
+    pub constraints: Vec<InvariantRule>,
+
     /// Documentation
     pub documentation: Documentation,
 
@@ -210,6 +345,30 @@ pub struct ProfileType {
     pub url: String,
 }
 
+impl ProfileType {
+    /// Constraint narrowing a top-level element of the base type, if any
+    ///
+    /// `element` is the element name without `[x]` (`value` for `Observation.value[x]`).
+    /// Only the first constraint on the element that changes cardinality, types,
+    /// binding or value is returned.
+    pub fn element_constraint(&self, element: &str) -> Option<&PropertyConstraint> {
+        self.property_constraints.iter().find(|constraint| {
+            constraint.top_level_element(&self.base) == Some(element) && constraint.is_narrowing()
+        })
+    }
+
+    /// Narrowing constraint on an element at any depth (`Patient.name.family`)
+    ///
+    /// `[x]` markers are ignored on both sides, so `Observation.value` matches a
+    /// constraint on `Observation.value[x]`.
+    pub fn constraint_at(&self, path: &str) -> Option<&PropertyConstraint> {
+        let path = path.replace("[x]", "");
+        self.property_constraints.iter().find(|constraint| {
+            constraint.path.replace("[x]", "") == path && constraint.is_narrowing()
+        })
+    }
+}
+
 /// Property constraint in a profile
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PropertyConstraint {
@@ -225,10 +384,68 @@ pub struct PropertyConstraint {
     /// New binding (if changed)
     pub binding: Option<ValueSetBinding>,
 
+    /// Value the element must have (`fixed[x]` or `pattern[x]`), as FHIR JSON
+    #[serde(default)]
+    pub fixed_value: Option<serde_json::Value>,
+
     /// Must support flag
     pub must_support: bool,
 }
 
+impl PropertyConstraint {
+    /// Element name of the path directly below `base` (`Patient.value[x]` -> `value`)
+    ///
+    /// Returns `None` for the root path and nested paths (`Patient.name.family`).
+    pub fn top_level_element(&self, base: &str) -> Option<&str> {
+        let element = self.path.strip_prefix(base)?.strip_prefix('.')?;
+        let element = element.trim_end_matches("[x]");
+        (!element.is_empty() && !element.contains('.')).then_some(element)
+    }
+
+    /// Whether the constraint changes cardinality, types, binding or value
+    pub fn is_narrowing(&self) -> bool {
+        self.cardinality.is_some()
+            || !self.type_constraints.is_empty()
+            || self.binding.is_some()
+            || self.fixed_value.is_some()
+    }
+
+    /// Apply the constraint to the base property it narrows
+    ///
+    /// Cardinality and binding are replaced. Type constraints restrict the types
+    /// of a choice element, or the targets of a reference.
+    pub fn apply(&self, property: &Property) -> Property {
+        let mut narrowed = property.clone();
+
+        if let Some(cardinality) = &self.cardinality {
+            narrowed.cardinality = cardinality.clone();
+        }
+        if self.binding.is_some() {
+            narrowed.binding.clone_from(&self.binding);
+        }
+
+        let allowed = &self.type_constraints;
+        if !allowed.is_empty() {
+            if property.is_choice {
+                narrowed.choice_types.retain(|type_code| allowed.contains(type_code));
+                if let PropertyType::Choice { types } = &mut narrowed.property_type {
+                    types.retain(|type_code| allowed.contains(type_code));
+                }
+            } else if let PropertyType::Reference { target_types, .. } = &mut narrowed.property_type
+            {
+                // Reference(Any) or a wider target list narrows to the named resources
+                let targets: Vec<String> =
+                    allowed.iter().filter(|t| t.as_str() != "Reference").cloned().collect();
+                if !targets.is_empty() {
+                    *target_types = targets;
+                }
+            }
+        }
+
+        narrowed
+    }
+}
+
 /// A property (field/element) in a resource or datatype
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Property {
@@ -275,6 +492,73 @@ pub struct Property {
     pub examples: Vec<Example>,
 }
 
+impl Property {
+    /// Name of the JSON sibling holding a primitive's `id` and extensions
+    ///
+    /// FHIR JSON keeps a primitive's value inline (`"birthDate": "1970-01-01"`)
+    /// and its element data in `_birthDate`. Returns `None` for non-primitive
+    /// properties and for `xhtml`, which cannot carry extensions.
+    pub fn primitive_element_name(&self) -> Option<String> {
+        match &self.property_type {
+            PropertyType::Primitive { type_name } if type_name != "xhtml" => {
+                Some(format!("_{}", self.name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Concrete properties of a choice element, one per allowed type
+    ///
+    /// FHIR JSON never carries `value[x]` itself; each type gets its own
+    /// optional key (`valueQuantity`, `valueString`) of which at most one is
+    /// present. Type codes starting with a lowercase letter are primitives.
+    /// Returns an empty list for non-choice properties.
+    pub fn choice_variants(&self) -> Vec<Property> {
+        if !self.is_choice {
+            return vec![];
+        }
+
+        let base = self.name.trim_end_matches("[x]");
+        self.choice_types
+            .iter()
+            .map(|type_code| {
+                let mut chars = type_code.chars();
+                let suffix = match chars.next() {
+                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                    None => String::new(),
+                };
+                let property_type = if type_code.starts_with(|c: char| c.is_ascii_lowercase()) {
+                    PropertyType::Primitive { type_name: type_code.clone() }
+                } else {
+                    PropertyType::Complex { type_name: type_code.clone() }
+                };
+
+                Property {
+                    name: format!("{}{}", base, suffix),
+                    path: self.path.replace("[x]", &suffix),
+                    property_type,
+                    cardinality: CardinalityRange::optional(),
+                    is_choice: false,
+                    choice_types: vec![],
+                    ..self.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Properties as keyed in FHIR JSON, with choice elements expanded into
+    /// their variants
+    pub fn json_properties(properties: &[Property]) -> Vec<Property> {
+        properties
+            .iter()
+            .flat_map(|property| {
+                let variants = property.choice_variants();
+                if variants.is_empty() { vec![property.clone()] } else { variants }
+            })
+            .collect()
+    }
+}
+
 /// Type of a property
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "kind")]
@@ -293,8 +577,11 @@ pub enum PropertyType {
 
     /// Reference to another resource
     Reference {
-        /// Allowed target types (empty = any resource)
+        /// Allowed target resource types (empty = any resource)
         target_types: Vec<String>,
+        /// Target profile canonical URLs as declared, including non-core profiles
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        target_profiles: Vec<String>,
     },
 
     /// Backbone element (inline complex type)
@@ -381,6 +668,40 @@ pub struct ValueSetBinding {
     pub description: Option<String>,
 }
 
+/// Strip the `|version` suffix from a canonical URL
+pub fn versionless_url(url: &str) -> &str {
+    url.split('|').next().unwrap_or(url)
+}
+
+/// Locally expanded ValueSet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValueSetExpansion {
+    /// ValueSet canonical URL (without version)
+    pub url: String,
+
+    /// Computer-friendly name (e.g., "AdministrativeGender")
+    pub name: String,
+
+    /// Human-friendly title
+    pub title: Option<String>,
+
+    /// Codes in the expansion, in definition order
+    pub concepts: Vec<CodeConcept>,
+}
+
+/// Single code from a value set expansion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CodeConcept {
+    /// CodeSystem canonical URL
+    pub system: String,
+
+    /// Code value
+    pub code: String,
+
+    /// Display text
+    pub display: Option<String>,
+}
+
 /// Binding strength
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum BindingStrength {
@@ -394,6 +715,18 @@ pub enum BindingStrength {
     Example,
 }
 
+impl BindingStrength {
+    /// FHIR `binding-strength` code
+    pub fn code(&self) -> &'static str {
+        match self {
+            BindingStrength::Required => "required",
+            BindingStrength::Extensible => "extensible",
+            BindingStrength::Preferred => "preferred",
+            BindingStrength::Example => "example",
+        }
+    }
+}
+
 /// Invariant constraint rule
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct InvariantRule {
@@ -496,19 +829,148 @@ pub enum SearchParamType {
     Special,
 }
 
+impl SearchParamType {
+    /// FHIR `search-param-type` code
+    pub fn code(&self) -> &'static str {
+        match self {
+            SearchParamType::Number => "number",
+            SearchParamType::Date => "date",
+            SearchParamType::String => "string",
+            SearchParamType::Token => "token",
+            SearchParamType::Reference => "reference",
+            SearchParamType::Composite => "composite",
+            SearchParamType::Quantity => "quantity",
+            SearchParamType::Uri => "uri",
+            SearchParamType::Special => "special",
+        }
+    }
+}
+
+/// FHIR OperationDefinition (e.g., `Patient/$everything`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OperationDefinition {
+    /// Canonical URL
+    pub url: String,
+
+    /// Computer-friendly name (e.g., "Everything")
+    pub name: String,
+
+    /// Code used in the URL, without the `$` (e.g., "everything")
+    pub code: String,
+
+    /// Operation or named query
+    pub kind: OperationKind,
+
+    /// Whether invoking the operation changes server state (requires POST)
+    pub affects_state: bool,
+
+    /// Resource types the operation is defined on (empty for system-only operations)
+    pub resources: Vec<String>,
+
+    /// Invocable at the system level (`[base]/$code`)
+    pub system: bool,
+
+    /// Invocable at the type level (`[base]/Patient/$code`)
+    pub type_level: bool,
+
+    /// Invocable at the instance level (`[base]/Patient/123/$code`)
+    pub instance: bool,
+
+    /// Input and output parameters, in definition order
+    pub parameters: Vec<OperationParameter>,
+
+    /// Documentation
+    pub documentation: Documentation,
+}
+
+impl OperationDefinition {
+    /// Parameters sent to the operation
+    pub fn input_parameters(&self) -> impl Iterator<Item = &OperationParameter> {
+        self.parameters.iter().filter(|p| p.usage == OperationParameterUse::In)
+    }
+
+    /// Parameters returned by the operation
+    pub fn output_parameters(&self) -> impl Iterator<Item = &OperationParameter> {
+        self.parameters.iter().filter(|p| p.usage == OperationParameterUse::Out)
+    }
+
+    /// Type of the resource returned directly instead of a `Parameters` resource
+    ///
+    /// An operation whose only output is a single resource parameter called
+    /// `return` responds with that resource itself.
+    pub fn return_resource(&self) -> Option<&str> {
+        let mut outputs = self.output_parameters();
+        let output = outputs.next()?;
+        if outputs.next().is_some() || output.name != "return" || output.cardinality.is_array() {
+            return None;
+        }
+        output.type_name.as_deref()
+    }
+}
+
+/// Whether an OperationDefinition is an operation or a named query
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OperationKind {
+    /// Invoked with `$code`
+    Operation,
+    /// Invoked as a search with `_query=code`
+    Query,
+}
+
+/// Direction of an operation parameter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OperationParameterUse {
+    /// Input parameter
+    In,
+    /// Output parameter
+    Out,
+}
+
+/// Parameter of an OperationDefinition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OperationParameter {
+    /// Parameter name as it appears in `Parameters.parameter.name`
+    pub name: String,
+
+    /// Input or output
+    pub usage: OperationParameterUse,
+
+    /// Cardinality
+    pub cardinality: CardinalityRange,
+
+    /// FHIR type of the value (e.g., "boolean", "Coding", "Bundle"); `None` for
+    /// parameters made of `parts`
+    pub type_name: Option<String>,
+
+    /// Allowed profiles for resource and Reference values
+    pub target_profiles: Vec<String>,
+
+    /// Search type for parameters that are also search parameters
+    pub search_type: Option<SearchParamType>,
+
+    /// Terminology binding for coded values
+    pub binding: Option<ValueSetBinding>,
+
+    /// Description
+    pub documentation: String,
+
+    /// Nested parameters (for tuple parameters without a type)
+    pub parts: Vec<OperationParameter>,
+}
+
 /// FHIR Extension definition
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Extension {
     /// Extension canonical URL
     pub url: String,
 
-    /// Extension name (derived from URL or id)
+    /// Extension name (from the StructureDefinition name, or the slice name of a sub-extension)
     pub name: String,
 
     /// Value type(s) allowed for this extension
     pub value_types: Vec<String>,
 
-    /// Target resource/datatype this extension applies to
+    /// Contexts this extension applies to (e.g., `Patient`, `Patient.name`)
     pub target_types: Vec<String>,
 
     /// Cardinality (0..1 or 0..*)
@@ -542,6 +1004,7 @@ mod tests {
         let resource = ResourceType {
             name: "Patient".to_string(),
             base: Some("DomainResource".to_string()),
+            constraints: vec![],
             properties: Vec::new(),
             search_parameters: Vec::new(),
             extensions: Vec::new(),
@@ -555,6 +1018,42 @@ mod tests {
         assert!(graph.resources.contains_key("Patient"));
     }
 
+    #[test]
+    fn test_constraints_with_base() {
+        let rule = |key: &str| InvariantRule {
+            key: key.to_string(),
+            severity: ConstraintSeverity::Error,
+            human: String::new(),
+            expression: None,
+            xpath: None,
+        };
+        let resource = |name: &str, base: Option<&str>, constraints| ResourceType {
+            name: name.to_string(),
+            base: base.map(str::to_string),
+            constraints,
+            properties: Vec::new(),
+            search_parameters: Vec::new(),
+            extensions: Vec::new(),
+            documentation: Documentation::default(),
+            url: String::new(),
+            is_abstract: base.is_none(),
+        };
+
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource(
+            "DomainResource".to_string(),
+            resource("DomainResource", None, vec![rule("dom-2"), rule("dom-6")]),
+        );
+        graph.add_resource(
+            "Patient".to_string(),
+            resource("Patient", Some("DomainResource"), vec![rule("pat-1"), rule("dom-6")]),
+        );
+
+        let keys: Vec<&str> =
+            graph.constraints_with_base("Patient").iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, ["dom-2", "dom-6", "pat-1"]);
+    }
+
     #[test]
     fn test_cardinality_required() {
         let card = CardinalityRange::required();
@@ -603,10 +1102,76 @@ mod tests {
         let complex = PropertyType::Complex { type_name: "HumanName".to_string() };
         assert_eq!(complex.type_name(), Some("HumanName"));
 
-        let reference = PropertyType::Reference { target_types: vec!["Patient".to_string()] };
+        let reference = PropertyType::Reference {
+            target_types: vec!["Patient".to_string()],
+            target_profiles: vec![],
+        };
         assert_eq!(reference.type_name(), None);
     }
 
+    #[test]
+    fn test_primitive_element_name() {
+        let mut property = Property {
+            name: "birthDate".to_string(),
+            path: "Patient.birthDate".to_string(),
+            property_type: PropertyType::Primitive { type_name: "date".to_string() },
+            cardinality: CardinalityRange::optional(),
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        };
+        assert_eq!(property.primitive_element_name().as_deref(), Some("_birthDate"));
+
+        property.property_type = PropertyType::Primitive { type_name: "xhtml".to_string() };
+        assert_eq!(property.primitive_element_name(), None);
+
+        property.property_type = PropertyType::Complex { type_name: "HumanName".to_string() };
+        assert_eq!(property.primitive_element_name(), None);
+    }
+
+    #[test]
+    fn test_choice_variants() {
+        let property = Property {
+            name: "deceased".to_string(),
+            path: "Patient.deceased[x]".to_string(),
+            property_type: PropertyType::Choice {
+                types: vec!["boolean".to_string(), "dateTime".to_string()],
+            },
+            cardinality: CardinalityRange::optional(),
+            is_choice: true,
+            choice_types: vec!["boolean".to_string(), "dateTime".to_string()],
+            is_modifier: true,
+            is_summary: true,
+            binding: None,
+            constraints: vec![],
+            short_description: "Indicates if the individual is deceased or not".to_string(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        };
+
+        let variants = property.choice_variants();
+        let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["deceasedBoolean", "deceasedDateTime"]);
+        assert_eq!(variants[1].path, "Patient.deceasedDateTime");
+        assert_eq!(
+            variants[1].property_type,
+            PropertyType::Primitive { type_name: "dateTime".to_string() }
+        );
+        assert!(variants.iter().all(|v| !v.is_choice && v.is_modifier));
+
+        let expanded = Property::json_properties(&[property]);
+        assert_eq!(expanded.len(), 2);
+        assert!(Property::json_properties(&variants).iter().all(|v| !v.is_choice));
+    }
+
     #[test]
     fn test_fhir_version_display() {
         assert_eq!(FhirVersion::R4.to_string(), "R4");
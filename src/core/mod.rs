@@ -1,10 +1,14 @@
 //! Core functionality for codegen
 
 pub mod error;
+pub mod fhirpath;
 pub mod graph_builder;
 pub mod ir;
 pub mod parser;
 pub mod resolver;
+pub mod synth;
+pub mod terminology;
+pub mod validate;
 
 pub use error::{Error, Result};
 pub use graph_builder::TypeGraphBuilder;
@@ -10,6 +10,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::debug;
 
+/// Extension carrying the regular expression for a primitive type's value
+const REGEX_EXTENSION_URL: &str = "http://hl7.org/fhir/StructureDefinition/regex";
+
 /// Parser for FHIR StructureDefinitions
 pub struct StructureDefinitionParser {
     /// Cache of parsed structures
@@ -38,7 +41,7 @@ impl StructureDefinitionParser {
         }
 
         // Extract the last segment from URL
-        let type_name = url_or_name.split('/').last().unwrap_or(url_or_name);
+        let type_name = url_or_name.split('/').next_back().unwrap_or(url_or_name);
 
         // Handle FHIRPath System types
         if type_name.starts_with("System.") {
@@ -238,17 +241,15 @@ impl StructureDefinitionParser {
         // Extract type name from URL if needed
         let code = Self::extract_type_name(code_raw);
 
-        // Handle Reference types with targetProfile
+        // Handle Reference types with targetProfile (canonical URLs kept as declared)
         let target_profiles = if code == "Reference" {
             type_obj
                 .get("targetProfile")
                 .and_then(|v| match v {
-                    Value::String(s) => Some(vec![Self::extract_type_name(s)]),
-                    Value::Array(arr) => Some(
-                        arr.iter()
-                            .filter_map(|v| v.as_str().map(|s| Self::extract_type_name(s)))
-                            .collect(),
-                    ),
+                    Value::String(s) => Some(vec![s.clone()]),
+                    Value::Array(arr) => {
+                        Some(arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                    }
                     _ => None,
                 })
                 .unwrap_or_default()
@@ -256,7 +257,19 @@ impl StructureDefinitionParser {
             vec![]
         };
 
-        Ok(ElementType { code, target_profiles })
+        let regex = type_obj
+            .get("extension")
+            .and_then(|v| v.as_array())
+            .and_then(|extensions| {
+                extensions.iter().find(|ext| {
+                    ext.get("url").and_then(|u| u.as_str()) == Some(REGEX_EXTENSION_URL)
+                })
+            })
+            .and_then(|ext| ext.get("valueString"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        Ok(ElementType { code, target_profiles, regex })
     }
 
     /// Parse value set binding
@@ -294,7 +307,61 @@ impl StructureDefinitionParser {
 
         let xpath = constraint.get("xpath").and_then(|v| v.as_str()).map(String::from);
 
-        Ok(Constraint { key, severity, human, expression, xpath })
+        let source = constraint.get("source").and_then(|v| v.as_str()).map(String::from);
+
+        Ok(Constraint { key, severity, human, expression, xpath, source })
+    }
+
+    /// Convert the constraints declared by a structure into IR invariants
+    ///
+    /// Snapshots repeat every inherited invariant (e.g. `ele-1` on each element);
+    /// only those whose `source` is the structure itself are kept, so each
+    /// invariant is attached to the type that declares it.
+    fn to_invariant_rules(constraints: &[Constraint], owner_url: &str) -> Vec<InvariantRule> {
+        constraints
+            .iter()
+            .filter(|c| c.source.as_deref().is_none_or(|source| source == owner_url))
+            .map(|c| InvariantRule {
+                key: c.key.clone(),
+                severity: if c.severity == "warning" {
+                    ConstraintSeverity::Warning
+                } else {
+                    ConstraintSeverity::Error
+                },
+                human: c.human.clone(),
+                expression: c.expression.clone(),
+                xpath: c.xpath.clone(),
+            })
+            .collect()
+    }
+
+    /// Convert a parsed binding into an IR binding
+    ///
+    /// Bindings without a value set carry no codes and are dropped.
+    fn to_value_set_binding(binding: &Binding) -> Option<ValueSetBinding> {
+        let strength = match binding.strength.as_str() {
+            "required" => BindingStrength::Required,
+            "extensible" => BindingStrength::Extensible,
+            "preferred" => BindingStrength::Preferred,
+            "example" => BindingStrength::Example,
+            _ => return None,
+        };
+
+        Some(ValueSetBinding {
+            strength,
+            value_set: binding.value_set.clone()?,
+            description: binding.description.clone(),
+        })
+    }
+
+    /// Invariants declared on the root element of a structure
+    fn root_invariant_rules(parsed: &ParsedStructure) -> Vec<InvariantRule> {
+        parsed
+            .elements
+            .iter()
+            .find(|elem| elem.path == parsed.name)
+            .map(|root| Self::to_invariant_rules(&root.constraints, &parsed.url))
+            .unwrap_or_default()
     }
 
     /// Convert parsed structure to IR ResourceType
@@ -304,7 +371,7 @@ impl StructureDefinitionParser {
         }
 
         // Convert elements to properties
-        let properties = self.elements_to_properties(&parsed.elements, &parsed.name)?;
+        let properties = self.elements_to_properties(parsed)?;
 
         // Extract type name from base URL if present
         let base = parsed.base_definition.as_ref().map(|url| Self::extract_type_name(url));
@@ -313,6 +380,7 @@ impl StructureDefinitionParser {
             name: parsed.name.clone(),
             base,
             properties,
+            constraints: Self::root_invariant_rules(parsed),
             search_parameters: vec![], // Will be populated by resolver
             extensions: vec![],        // Will be populated by resolver
             documentation: Documentation {
@@ -334,7 +402,7 @@ impl StructureDefinitionParser {
             return Err(Error::Parser(format!("{} is not a complex type", parsed.name)));
         }
 
-        let properties = self.elements_to_properties(&parsed.elements, &parsed.name)?;
+        let properties = self.elements_to_properties(parsed)?;
 
         // Extract type name from base URL if present
         let base = parsed.base_definition.as_ref().map(|url| Self::extract_type_name(url));
@@ -343,6 +411,7 @@ impl StructureDefinitionParser {
             name: parsed.name.clone(),
             base,
             properties,
+            constraints: Self::root_invariant_rules(parsed),
             documentation: Documentation {
                 short: format!("FHIR {} DataType", parsed.name),
                 definition: String::new(),
@@ -357,15 +426,11 @@ impl StructureDefinitionParser {
     }
 
     /// Convert element definitions to IR properties
-    fn elements_to_properties(
-        &self,
-        elements: &[ElementDefinition],
-        type_name: &str,
-    ) -> Result<Vec<Property>> {
-        let root_path = type_name;
+    fn elements_to_properties(&self, parsed: &ParsedStructure) -> Result<Vec<Property>> {
+        let root_path = parsed.name.as_str();
         let mut properties = Vec::new();
 
-        for elem in elements {
+        for elem in &parsed.elements {
             // Skip the root element itself
             if elem.path == root_path {
                 continue;
@@ -406,11 +471,8 @@ impl StructureDefinitionParser {
                 let elem_type = &elem.types[0];
                 if elem_type.code == "Reference" && !elem_type.target_profiles.is_empty() {
                     PropertyType::Reference {
-                        target_types: elem_type
-                            .target_profiles
-                            .iter()
-                            .filter_map(|url| url.split('/').next_back().map(String::from))
-                            .collect(),
+                        target_types: Self::reference_target_types(&elem_type.target_profiles),
+                        target_profiles: elem_type.target_profiles.clone(),
                     }
                 } else if Self::is_primitive(&elem_type.code) {
                     PropertyType::Primitive { type_name: elem_type.code.clone() }
@@ -438,10 +500,10 @@ impl StructureDefinitionParser {
                 cardinality,
                 is_choice,
                 choice_types,
-                is_modifier: false,  // TODO: Extract from element
-                is_summary: false,   // TODO: Extract from element
-                binding: None,       // TODO: Convert binding
-                constraints: vec![], // TODO: Convert constraints
+                is_modifier: false, // TODO: Extract from element
+                is_summary: false,  // TODO: Extract from element
+                binding: elem.binding.as_ref().and_then(Self::to_value_set_binding),
+                constraints: Self::to_invariant_rules(&elem.constraints, &parsed.url),
                 short_description: elem.short.clone().unwrap_or_default(),
                 definition: elem.definition.clone().unwrap_or_default(),
                 comments: None,
@@ -452,6 +514,24 @@ impl StructureDefinitionParser {
         Ok(properties)
     }
 
+    /// Resource types targeted by a set of profile URLs, deduplicated
+    ///
+    /// Takes the last path segment of each canonical, which is the resource type
+    /// for core definitions. Profile URLs are resolved to their base type when
+    /// the type graph is built.
+    pub fn reference_target_types(target_profiles: &[String]) -> Vec<String> {
+        let mut types: Vec<String> = Vec::new();
+        for url in target_profiles {
+            let canonical = url.split('|').next().unwrap_or(url);
+            if let Some(name) = canonical.rsplit('/').next()
+                && !types.iter().any(|t| t == name)
+            {
+                types.push(name.to_string());
+            }
+        }
+        types
+    }
+
     /// Check if a type code represents a primitive
     fn is_primitive(type_code: &str) -> bool {
         matches!(
@@ -554,8 +634,10 @@ pub enum Cardinality {
 pub struct ElementType {
     /// Type code (e.g., "string", "Reference")
     pub code: String,
-    /// Target profiles (for Reference types)
+    /// Target profile canonical URLs (for Reference types)
     pub target_profiles: Vec<String>,
+    /// Regular expression from the `regex` type extension (primitive `value` elements)
+    pub regex: Option<String>,
 }
 
 /// Value set binding
@@ -582,6 +664,8 @@ pub struct Constraint {
     pub expression: Option<String>,
     /// XPath expression
     pub xpath: Option<String>,
+    /// Canonical URL of the structure that declared the constraint
+    pub source: Option<String>,
 }
 
 #[cfg(test)]
@@ -752,8 +836,12 @@ mod tests {
 
         let subject_prop = resource_type.properties.iter().find(|p| p.name == "subject").unwrap();
 
-        if let PropertyType::Reference { target_types } = &subject_prop.property_type {
+        if let PropertyType::Reference { target_types, target_profiles } =
+            &subject_prop.property_type
+        {
             assert_eq!(target_types.len(), 2);
+            assert_eq!(target_profiles.len(), 2);
+            assert!(target_profiles[0].starts_with("http://hl7.org/fhir/StructureDefinition/"));
             assert!(target_types.contains(&"Patient".to_string()));
             assert!(target_types.contains(&"Group".to_string()));
         } else {
@@ -813,4 +901,117 @@ mod tests {
         assert_eq!(array.cardinality.min, 0);
         assert_eq!(array.cardinality.max, None); // unbounded
     }
+
+    #[test]
+    fn test_constraints_filtered_by_source() {
+        let json = serde_json::json!({
+            "resourceType": "StructureDefinition",
+            "url": "http://example.com/TestInv",
+            "name": "TestInv",
+            "kind": "resource",
+            "snapshot": {
+                "element": [
+                    {
+                        "path": "TestInv",
+                        "min": 0,
+                        "max": "*",
+                        "constraint": [
+                            {
+                                "key": "dom-2",
+                                "severity": "error",
+                                "human": "Inherited",
+                                "expression": "contained.contained.empty()",
+                                "source": "http://hl7.org/fhir/StructureDefinition/DomainResource"
+                            },
+                            {
+                                "key": "inv-1",
+                                "severity": "warning",
+                                "human": "Own",
+                                "expression": "name.exists()",
+                                "source": "http://example.com/TestInv"
+                            }
+                        ]
+                    },
+                    {
+                        "path": "TestInv.name",
+                        "min": 0,
+                        "max": "1",
+                        "type": [{ "code": "string" }],
+                        "constraint": [
+                            {
+                                "key": "ele-1",
+                                "severity": "error",
+                                "human": "All FHIR elements must have a @value or children",
+                                "expression": "hasValue() or (children().count() > id.count())",
+                                "source": "http://hl7.org/fhir/StructureDefinition/Element"
+                            },
+                            {
+                                "key": "inv-2",
+                                "severity": "error",
+                                "human": "Name is not blank",
+                                "expression": "$this != ''"
+                            }
+                        ]
+                    }
+                ]
+            }
+        });
+
+        let mut parser = StructureDefinitionParser::new();
+        let parsed = parser.parse(&json).unwrap();
+        let resource_type = parser.to_resource_type(&parsed).unwrap();
+
+        assert_eq!(resource_type.constraints.len(), 1);
+        assert_eq!(resource_type.constraints[0].key, "inv-1");
+        assert_eq!(resource_type.constraints[0].severity, ConstraintSeverity::Warning);
+
+        let name = resource_type.properties.iter().find(|p| p.name == "name").unwrap();
+        assert_eq!(name.constraints.len(), 1);
+        assert_eq!(name.constraints[0].key, "inv-2");
+        assert_eq!(name.constraints[0].expression.as_deref(), Some("$this != ''"));
+    }
+
+    #[test]
+    fn test_parse_binding() {
+        let json = serde_json::json!({
+            "resourceType": "StructureDefinition",
+            "url": "http://example.com/TestBinding",
+            "name": "TestBinding",
+            "kind": "resource",
+            "snapshot": {
+                "element": [
+                    { "path": "TestBinding", "min": 0, "max": "*" },
+                    {
+                        "path": "TestBinding.gender",
+                        "min": 0,
+                        "max": "1",
+                        "type": [{ "code": "code" }],
+                        "binding": {
+                            "strength": "required",
+                            "valueSet": "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1"
+                        }
+                    },
+                    {
+                        "path": "TestBinding.note",
+                        "min": 0,
+                        "max": "1",
+                        "type": [{ "code": "string" }],
+                        "binding": { "strength": "example", "description": "No value set" }
+                    }
+                ]
+            }
+        });
+
+        let mut parser = StructureDefinitionParser::new();
+        let parsed = parser.parse(&json).unwrap();
+        let resource_type = parser.to_resource_type(&parsed).unwrap();
+
+        let gender = resource_type.properties.iter().find(|p| p.name == "gender").unwrap();
+        let binding = gender.binding.as_ref().unwrap();
+        assert_eq!(binding.strength, BindingStrength::Required);
+        assert_eq!(binding.value_set, "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1");
+
+        let note = resource_type.properties.iter().find(|p| p.name == "note").unwrap();
+        assert!(note.binding.is_none());
+    }
 }
@@ -145,6 +145,27 @@ impl SchemaResolver {
         }
     }
 
+    /// Resolve a code system by canonical URL
+    ///
+    /// # Arguments
+    ///
+    /// * `code_system_url` - Canonical URL of the code system
+    ///
+    /// # Returns
+    ///
+    /// CodeSystem resource if found
+    pub async fn resolve_code_system(&self, code_system_url: &str) -> Result<Option<Value>> {
+        debug!("Resolving code system: {}", code_system_url);
+
+        match self.manager.resolve(code_system_url).await {
+            Ok(resolved) => Ok(Some(resolved.resource.content)),
+            Err(e) => {
+                warn!("Failed to resolve code system {}: {}", code_system_url, e);
+                Ok(None)
+            }
+        }
+    }
+
     /// Get all resources of a specific type
     ///
     /// # Arguments
@@ -187,6 +208,30 @@ impl SchemaResolver {
         Ok(resources)
     }
 
+    /// Get the content of resources of a given type, with or without a canonical URL
+    ///
+    /// Unlike [`Self::get_resources_by_type`], this also returns instances such as
+    /// package examples, which are not addressable by canonical URL.
+    pub async fn get_instances_by_type(
+        &self,
+        resource_type: &str,
+        limit: usize,
+    ) -> Result<Vec<Value>> {
+        debug!("Searching for instances of type: {} (limit: {})", resource_type, limit);
+
+        let query = self
+            .manager
+            .search()
+            .await
+            .resource_type(resource_type)
+            .limit(limit)
+            .execute()
+            .await
+            .map_err(|e| Error::CanonicalManager(e.to_string()))?;
+
+        Ok(query.resources.into_iter().map(|result| result.resource.content).collect())
+    }
+
     /// Get all StructureDefinitions for a FHIR version
     ///
     /// # Arguments
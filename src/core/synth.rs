@@ -0,0 +1,1023 @@
+//! Synthetic FHIR instances
+//!
+//! Builds random but structurally valid FHIR JSON for any resource or profile
+//! in a [`TypeGraph`], for fixtures and load testing:
+//!
+//! - elements follow the cardinality in the IR, and choice elements carry
+//!   exactly one of their typed variants
+//! - coded elements with an expanded binding draw their codes from the expansion
+//! - string primitives match the regex of their primitive type
+//! - references point at other generated resources, generating a minimal
+//!   target when none of the referenced type exists yet
+//! - profile instances apply the profile's cardinality, type, binding and
+//!   fixed value constraints and declare the profile in `meta.profile`
+//!
+//! Output is fully determined by the graph and the seed.
+
+use crate::core::ir::{
+    CodeConcept, ProfileType, Property, PropertyType, TypeGraph, versionless_url,
+};
+use crate::core::{Error, Result};
+use indexmap::IndexMap;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+/// Seed used when none is given
+pub const DEFAULT_SEED: u64 = 0x5EED_F41B;
+
+/// Depth below which optional elements of nested datatypes are populated
+const MAX_OPTIONAL_DEPTH: usize = 3;
+
+/// Depth at which synthesis stops even for required elements
+const MAX_DEPTH: usize = 6;
+
+/// Nesting of resources generated to satisfy references
+const MAX_REFERENCE_DEPTH: usize = 2;
+
+/// Elements never synthesized: extensions need a real URL, contained
+/// resources are out of scope, and resource ids are assigned per instance
+const SKIPPED_ELEMENTS: &[&str] = &["id", "extension", "modifierExtension", "contained"];
+
+/// Words used for free-text strings
+const WORDS: &[&str] = &[
+    "amber", "bright", "cedar", "delta", "ember", "falcon", "granite", "harbor", "ivory",
+    "juniper", "kestrel", "lumen", "meadow", "north", "orchid", "pioneer", "quartz", "river",
+    "summit", "timber", "umber", "vale", "willow", "zephyr",
+];
+
+/// Which optional elements a synthetic instance populates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Coverage {
+    /// Required elements only
+    Required,
+    /// Every element, up to a nesting depth
+    All,
+    /// A random selection of optional elements
+    #[default]
+    Sampled,
+}
+
+/// Generator of synthetic FHIR instances from a [`TypeGraph`]
+pub struct Synthesizer<'a> {
+    graph: &'a TypeGraph,
+    rng: Rng,
+    coverage: Coverage,
+    /// Ids of the resources generated so far, by resource type
+    generated: IndexMap<String, Vec<String>>,
+    /// Resources generated to satisfy references, returned after the requested ones
+    referenced: Vec<Value>,
+    /// Nesting of resources being generated for references
+    reference_depth: usize,
+    /// Parsed primitive patterns by primitive type
+    patterns: HashMap<String, Option<Pattern>>,
+}
+
+impl<'a> Synthesizer<'a> {
+    /// Create a synthesizer with the default seed and sampled coverage
+    pub fn new(graph: &'a TypeGraph) -> Self {
+        Self {
+            graph,
+            rng: Rng::new(DEFAULT_SEED),
+            coverage: Coverage::default(),
+            generated: IndexMap::new(),
+            referenced: Vec::new(),
+            reference_depth: 0,
+            patterns: HashMap::new(),
+        }
+    }
+
+    /// Seed the random choices; the same seed yields the same instances
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Select which optional elements are populated
+    pub fn with_coverage(mut self, coverage: Coverage) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    /// Generate `count` instances of a resource type or profile URL
+    ///
+    /// The requested instances come first, followed by the resources generated
+    /// so that their references resolve.
+    pub fn generate(&mut self, target: &str, count: usize) -> Result<Vec<Value>> {
+        let mut instances =
+            (0..count).map(|_| self.instance(target)).collect::<Result<Vec<_>>>()?;
+        instances.append(&mut self.referenced);
+        Ok(instances)
+    }
+
+    /// Generate a single instance of a resource type or profile URL
+    ///
+    /// Resources generated for its references are kept for [`Self::generate`].
+    pub fn instance(&mut self, target: &str) -> Result<Value> {
+        let graph = self.graph;
+
+        if let Some(resource) = graph.resources.get(target) {
+            if resource.is_abstract {
+                return Err(Error::Other(format!("Cannot synthesize abstract type {}", target)));
+            }
+            return Ok(self.resource(&resource.name, None));
+        }
+
+        let profile = graph
+            .profiles
+            .get(versionless_url(target))
+            .or_else(|| graph.profiles.values().find(|profile| profile.name == target));
+        match profile {
+            Some(profile)
+                if graph.resources.get(&profile.base).is_some_and(|base| !base.is_abstract) =>
+            {
+                Ok(self.resource(&profile.base, Some(profile)))
+            }
+            Some(profile) => Err(Error::Other(format!(
+                "Profile {} does not constrain a concrete resource",
+                profile.url
+            ))),
+            None => Err(Error::Other(format!("Unknown resource type or profile: {}", target))),
+        }
+    }
+
+    /// Generate a resource, optionally conforming to a profile on it
+    fn resource(&mut self, name: &str, profile: Option<&ProfileType>) -> Value {
+        let ids = self.generated.entry(name.to_string()).or_default();
+        let id = format!("{}-{}", name.to_lowercase(), ids.len() + 1);
+        ids.push(id.clone());
+
+        let mut object = Map::new();
+        object.insert("resourceType".to_string(), json!(name));
+        object.insert("id".to_string(), json!(id));
+
+        let mut properties: Vec<Property> =
+            self.graph.properties_with_base(name).into_iter().cloned().collect();
+
+        if let Some(profile) = profile {
+            object.insert("meta".to_string(), json!({ "profile": [profile.url] }));
+
+            for property in &mut properties {
+                let element = property.name.trim_end_matches("[x]");
+                let Some(constraint) = profile.element_constraint(element) else { continue };
+                *property = constraint.apply(property);
+
+                // Fixed and pattern values are used as-is
+                if let Some(fixed) = &constraint.fixed_value
+                    && !property.is_choice
+                {
+                    let value = if property.cardinality.is_array() && !fixed.is_array() {
+                        json!([fixed])
+                    } else {
+                        fixed.clone()
+                    };
+                    object.insert(property.name.clone(), value);
+                }
+            }
+        }
+
+        self.fill(&mut object, &properties, 0);
+        Value::Object(object)
+    }
+
+    /// Populate `object` with values for `properties`, leaving present keys alone
+    fn fill(&mut self, object: &mut Map<String, Value>, properties: &[Property], depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        for property in properties {
+            if SKIPPED_ELEMENTS.contains(&property.name.as_str())
+                || property.cardinality.max == Some(0)
+            {
+                continue;
+            }
+
+            let variants = property.choice_variants();
+            let present = if variants.is_empty() {
+                object.contains_key(&property.name)
+            } else {
+                variants.iter().any(|variant| object.contains_key(&variant.name))
+            };
+            if present || (!property.cardinality.is_required() && !self.include_optional(depth)) {
+                continue;
+            }
+
+            // A choice element carries exactly one of its typed variants
+            let property = if variants.is_empty() {
+                property.clone()
+            } else {
+                variants[self.rng.below(variants.len())].clone()
+            };
+
+            let value = if property.cardinality.is_array() {
+                let min = property.cardinality.min.max(1) as usize;
+                let count = match self.coverage {
+                    Coverage::Sampled => {
+                        let max = property.cardinality.max.map_or(3, |max| max as usize);
+                        // Up to three items, never fewer than the minimum
+                        let upper = max.max(min).min(min.max(3));
+                        min + self.rng.below(upper - min + 1)
+                    }
+                    _ => min,
+                };
+                let items: Vec<Value> =
+                    (0..count).filter_map(|_| self.value(&property, depth)).collect();
+                (!items.is_empty()).then_some(Value::Array(items))
+            } else {
+                self.value(&property, depth)
+            };
+
+            if let Some(value) = value {
+                object.insert(property.name.clone(), value);
+            }
+        }
+    }
+
+    /// Whether an optional element is populated at `depth`
+    fn include_optional(&mut self, depth: usize) -> bool {
+        depth < MAX_OPTIONAL_DEPTH
+            && match self.coverage {
+                Coverage::Required => false,
+                Coverage::All => true,
+                Coverage::Sampled => self.rng.chance(),
+            }
+    }
+
+    /// A value for a single occurrence of a property
+    fn value(&mut self, property: &Property, depth: usize) -> Option<Value> {
+        if let Some(concept) = self.bound_concept(property) {
+            match &property.property_type {
+                PropertyType::Primitive { .. } => return Some(json!(concept.code)),
+                PropertyType::Complex { type_name } if type_name == "Coding" => {
+                    return Some(coding(&concept));
+                }
+                PropertyType::Complex { type_name } if type_name == "CodeableConcept" => {
+                    return Some(json!({ "coding": [coding(&concept)] }));
+                }
+                _ => {}
+            }
+        }
+
+        match &property.property_type {
+            PropertyType::Primitive { type_name } => Some(self.primitive(type_name)),
+            PropertyType::Complex { type_name } => self.complex(type_name, depth + 1),
+            PropertyType::Reference { target_types, .. } => Some(self.reference(target_types)),
+            PropertyType::BackboneElement { properties } => {
+                let mut object = Map::new();
+                self.fill(&mut object, properties, depth + 1);
+                self.ensure_children(&mut object, properties, depth + 1);
+                Some(Value::Object(object))
+            }
+            PropertyType::Choice { .. } => None,
+        }
+    }
+
+    /// A random code from the expansion of the property's binding
+    fn bound_concept(&mut self, property: &Property) -> Option<CodeConcept> {
+        let binding = property.binding.as_ref()?;
+        let value_set = self.graph.value_set(&binding.value_set)?;
+        if value_set.concepts.is_empty() {
+            return None;
+        }
+        Some(value_set.concepts[self.rng.below(value_set.concepts.len())].clone())
+    }
+
+    /// A value of a complex datatype
+    fn complex(&mut self, type_name: &str, depth: usize) -> Option<Value> {
+        let graph = self.graph;
+        if depth > MAX_DEPTH || type_name == "Extension" || !graph.datatypes.contains_key(type_name)
+        {
+            return None;
+        }
+
+        let properties: Vec<Property> =
+            graph.properties_with_base(type_name).into_iter().cloned().collect();
+        let mut object = Map::new();
+        self.fill(&mut object, &properties, depth);
+        self.ensure_children(&mut object, &properties, depth);
+        Some(Value::Object(object))
+    }
+
+    /// FHIR elements must have a value or children: populate one if none were
+    fn ensure_children(
+        &mut self,
+        object: &mut Map<String, Value>,
+        properties: &[Property],
+        depth: usize,
+    ) {
+        if !object.is_empty() {
+            return;
+        }
+
+        let primitive = properties.iter().find(|property| {
+            !SKIPPED_ELEMENTS.contains(&property.name.as_str())
+                && property.cardinality.max != Some(0)
+                && matches!(property.property_type, PropertyType::Primitive { .. })
+        });
+        if let Some(property) = primitive
+            && let Some(value) = self.value(property, depth)
+        {
+            let value = if property.cardinality.is_array() { json!([value]) } else { value };
+            object.insert(property.name.clone(), value);
+        }
+    }
+
+    /// A reference to a generated resource of one of the target types
+    ///
+    /// Existing resources are reused half of the time; otherwise a minimal
+    /// target is generated. Past the reference depth, or when no target type
+    /// is a concrete resource, the reference carries a display only.
+    fn reference(&mut self, target_types: &[String]) -> Value {
+        let graph = self.graph;
+        let targets: Vec<&String> = target_types
+            .iter()
+            .filter(|target| graph.resources.get(*target).is_some_and(|r| !r.is_abstract))
+            .collect();
+        if targets.is_empty() {
+            return json!({ "display": self.words(2) });
+        }
+        let target = targets[self.rng.below(targets.len())].clone();
+
+        let existing = self.generated.get(&target).filter(|ids| !ids.is_empty()).cloned();
+        let can_generate = self.reference_depth < MAX_REFERENCE_DEPTH;
+        let id = match existing {
+            Some(ids) if !can_generate || self.rng.chance() => {
+                ids[self.rng.below(ids.len())].clone()
+            }
+            _ if can_generate => {
+                let coverage = std::mem::replace(&mut self.coverage, Coverage::Required);
+                self.reference_depth += 1;
+                let resource = self.resource(&target, None);
+                self.reference_depth -= 1;
+                self.coverage = coverage;
+
+                let id = resource["id"].as_str().unwrap_or_default().to_string();
+                self.referenced.push(resource);
+                id
+            }
+            _ => return json!({ "display": self.words(2) }),
+        };
+
+        json!({ "reference": format!("{}/{}", target, id) })
+    }
+
+    /// A value of a FHIR primitive type
+    fn primitive(&mut self, type_name: &str) -> Value {
+        match type_name {
+            "boolean" => json!(self.rng.chance()),
+            "integer" => json!(self.rng.below(2001) as i64 - 1000),
+            "positiveInt" => json!(self.rng.below(1000) + 1),
+            "unsignedInt" => json!(self.rng.below(1001)),
+            "decimal" => json!(self.rng.below(100_000) as f64 / 100.0),
+            "integer64" => json!(self.rng.below(1_000_000_000).to_string()),
+            "string" | "markdown" => {
+                let count = 1 + self.rng.below(3);
+                json!(self.words(count))
+            }
+            "xhtml" => json!(format!(
+                "<div xmlns=\"http://www.w3.org/1999/xhtml\">{}</div>",
+                self.words(3)
+            )),
+            "uri" | "url" | "canonical" => {
+                json!(format!("http://example.org/fhir/{}", self.words(1)))
+            }
+            "date" => {
+                let (year, month, day) = self.date();
+                json!(format!("{:04}-{:02}-{:02}", year, month, day))
+            }
+            "dateTime" | "instant" => {
+                let (year, month, day) = self.date();
+                let (hour, minute, second) = self.time();
+                json!(format!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                    year, month, day, hour, minute, second
+                ))
+            }
+            "time" => {
+                let (hour, minute, second) = self.time();
+                json!(format!("{:02}:{:02}:{:02}", hour, minute, second))
+            }
+            "base64Binary" => {
+                const ALPHABET: &[u8] =
+                    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+                let length = 4 * (1 + self.rng.below(4));
+                let encoded: String =
+                    (0..length).map(|_| ALPHABET[self.rng.below(ALPHABET.len())] as char).collect();
+                json!(encoded)
+            }
+            _ => match self.pattern(type_name) {
+                Some(pattern) => json!(pattern.generate(&mut self.rng)),
+                None => json!(self.words(1)),
+            },
+        }
+    }
+
+    /// The parsed regex of a primitive type, if it has one we understand
+    fn pattern(&mut self, type_name: &str) -> Option<Pattern> {
+        let graph = self.graph;
+        self.patterns
+            .entry(type_name.to_string())
+            .or_insert_with(|| {
+                graph
+                    .primitives
+                    .get(type_name)
+                    .and_then(|primitive| primitive.pattern.as_deref())
+                    .and_then(Pattern::parse)
+            })
+            .clone()
+    }
+
+    /// `count` random words separated by spaces
+    fn words(&mut self, count: usize) -> String {
+        (0..count).map(|_| WORDS[self.rng.below(WORDS.len())]).collect::<Vec<_>>().join(" ")
+    }
+
+    /// A random calendar date between 1950 and 2024
+    fn date(&mut self) -> (usize, usize, usize) {
+        (1950 + self.rng.below(75), 1 + self.rng.below(12), 1 + self.rng.below(28))
+    }
+
+    /// A random time of day
+    fn time(&mut self) -> (usize, usize, usize) {
+        (self.rng.below(24), self.rng.below(60), self.rng.below(60))
+    }
+}
+
+/// FHIR JSON of a Coding for an expansion concept
+fn coding(concept: &CodeConcept) -> Value {
+    let mut coding = json!({ "system": concept.system, "code": concept.code });
+    if let Some(display) = &concept.display {
+        coding["display"] = json!(display);
+    }
+    coding
+}
+
+/// SplitMix64 pseudo-random generator
+#[derive(Debug, Clone)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`; `bound` must be positive
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn chance(&mut self) -> bool {
+        self.next_u64() & 1 == 0
+    }
+}
+
+/// Printable ASCII, the alphabet for `.` and negated character classes
+const PRINTABLE: (char, char) = (' ', '~');
+
+/// A regular expression, parsed for generating matching strings
+///
+/// Supports the subset used by FHIR primitive patterns: literals, escapes,
+/// character classes (`[A-Z]`, `[^\s]`, `\d`, `\s`, `\S`, `\w`), groups with
+/// alternation and the `?`, `*`, `+` and `{n,m}` quantifiers.
+#[derive(Debug, Clone)]
+struct Pattern {
+    alternatives: Vec<Vec<Node>>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(char),
+    Class { ranges: Vec<(char, char)>, negated: bool },
+    Group(Vec<Vec<Node>>),
+    Repeat { node: Box<Node>, min: usize, max: Option<usize> },
+}
+
+impl Pattern {
+    /// Parse a pattern, or `None` if it uses unsupported syntax
+    fn parse(pattern: &str) -> Option<Self> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut position = 0;
+        let alternatives = Self::parse_alternatives(&chars, &mut position)?;
+        (position == chars.len()).then_some(Self { alternatives })
+    }
+
+    /// A random string matching the pattern
+    fn generate(&self, rng: &mut Rng) -> String {
+        let mut output = String::new();
+        Self::generate_alternatives(&self.alternatives, rng, &mut output);
+        output
+    }
+
+    fn parse_alternatives(chars: &[char], position: &mut usize) -> Option<Vec<Vec<Node>>> {
+        let mut alternatives = vec![Vec::new()];
+
+        while let Some(&c) = chars.get(*position) {
+            let node = match c {
+                ')' => break,
+                '|' => {
+                    *position += 1;
+                    alternatives.push(Vec::new());
+                    continue;
+                }
+                '^' | '$' => {
+                    *position += 1;
+                    continue;
+                }
+                '(' => {
+                    *position += 1;
+                    if chars.get(*position..*position + 2) == Some(&['?', ':']) {
+                        *position += 2;
+                    }
+                    let group = Self::parse_alternatives(chars, position)?;
+                    if chars.get(*position) != Some(&')') {
+                        return None;
+                    }
+                    *position += 1;
+                    Node::Group(group)
+                }
+                '[' => {
+                    *position += 1;
+                    Self::parse_class(chars, position)?
+                }
+                '.' => {
+                    *position += 1;
+                    Node::Class { ranges: vec![PRINTABLE], negated: false }
+                }
+                '\\' => {
+                    let escaped = *chars.get(*position + 1)?;
+                    *position += 2;
+                    Self::escape(escaped)
+                }
+                '?' | '*' | '+' | '{' => {
+                    let sequence = alternatives.last_mut()?;
+                    match sequence.pop() {
+                        Some(node) => {
+                            let (min, max) = Self::parse_quantifier(chars, position)?;
+                            Node::Repeat { node: Box::new(node), min, max }
+                        }
+                        // A leading quantifier character is taken literally
+                        None => {
+                            *position += 1;
+                            Node::Literal(c)
+                        }
+                    }
+                }
+                _ => {
+                    *position += 1;
+                    Node::Literal(c)
+                }
+            };
+            alternatives.last_mut()?.push(node);
+        }
+
+        Some(alternatives)
+    }
+
+    /// Parse a quantifier at `position` into its bounds
+    fn parse_quantifier(chars: &[char], position: &mut usize) -> Option<(usize, Option<usize>)> {
+        let c = chars[*position];
+        *position += 1;
+        match c {
+            '?' => Some((0, Some(1))),
+            '*' => Some((0, None)),
+            '+' => Some((1, None)),
+            _ => {
+                let end = chars[*position..].iter().position(|&c| c == '}')? + *position;
+                let body: String = chars[*position..end].iter().collect();
+                *position = end + 1;
+                match body.split_once(',') {
+                    Some((min, "")) => Some((min.parse().ok()?, None)),
+                    Some((min, max)) => {
+                        // `{n,m}` with m < n matches nothing; treat the pattern as unsupported
+                        let (min, max) = (min.parse().ok()?, max.parse().ok()?);
+                        (max >= min).then_some((min, Some(max)))
+                    }
+                    None => {
+                        let exact = body.parse().ok()?;
+                        Some((exact, Some(exact)))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Parse a character class after its opening `[`
+    fn parse_class(chars: &[char], position: &mut usize) -> Option<Node> {
+        let negated = chars.get(*position) == Some(&'^');
+        if negated {
+            *position += 1;
+        }
+
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let c = *chars.get(*position)?;
+            *position += 1;
+            match c {
+                ']' if !first => break,
+                '\\' => {
+                    let escaped = *chars.get(*position)?;
+                    *position += 1;
+                    match Self::escape(escaped) {
+                        Node::Class { ranges: escaped, .. } => ranges.extend(escaped),
+                        Node::Literal(literal) => ranges.push((literal, literal)),
+                        _ => return None,
+                    }
+                }
+                _ => {
+                    let is_range = chars.get(*position) == Some(&'-')
+                        && chars.get(*position + 1).is_some_and(|&end| end != ']');
+                    if is_range {
+                        ranges.push((c, chars[*position + 1]));
+                        *position += 2;
+                    } else {
+                        ranges.push((c, c));
+                    }
+                }
+            }
+            first = false;
+        }
+
+        Some(Node::Class { ranges, negated })
+    }
+
+    /// Node for an escape sequence (`\d`, `\s`, `\.`)
+    fn escape(c: char) -> Node {
+        let class = |ranges: Vec<(char, char)>| Node::Class { ranges, negated: false };
+        match c {
+            'd' => class(vec![('0', '9')]),
+            's' => class(vec![(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')]),
+            'S' => class(vec![('!', '~')]),
+            'w' => class(vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')]),
+            'n' => Node::Literal('\n'),
+            'r' => Node::Literal('\r'),
+            't' => Node::Literal('\t'),
+            _ => Node::Literal(c),
+        }
+    }
+
+    fn generate_alternatives(alternatives: &[Vec<Node>], rng: &mut Rng, output: &mut String) {
+        for node in &alternatives[rng.below(alternatives.len())] {
+            Self::generate_node(node, rng, output);
+        }
+    }
+
+    fn generate_node(node: &Node, rng: &mut Rng, output: &mut String) {
+        match node {
+            Node::Literal(c) => output.push(*c),
+            Node::Group(alternatives) => Self::generate_alternatives(alternatives, rng, output),
+            Node::Repeat { node, min, max } => {
+                // Keep unbounded and long repetitions short
+                let upper = max.map_or(min + 3, |max| max.min(min + 8)).max(*min);
+                for _ in 0..*min + rng.below(upper - min + 1) {
+                    Self::generate_node(node, rng, output);
+                }
+            }
+            Node::Class { ranges, negated: false } => {
+                let size: usize = ranges.iter().map(|&(start, end)| range_size(start, end)).sum();
+                let mut index = rng.below(size.max(1));
+                for &(start, end) in ranges {
+                    let length = range_size(start, end);
+                    if index < length {
+                        output.push(char::from_u32(start as u32 + index as u32).unwrap_or(start));
+                        return;
+                    }
+                    index -= length;
+                }
+            }
+            Node::Class { ranges, negated: true } => {
+                let candidates: Vec<char> = (PRINTABLE.0..=PRINTABLE.1)
+                    .filter(|c| !ranges.iter().any(|&(start, end)| (start..=end).contains(c)))
+                    .collect();
+                if !candidates.is_empty() {
+                    output.push(candidates[rng.below(candidates.len())]);
+                }
+            }
+        }
+    }
+}
+
+/// Number of characters in an inclusive range
+fn range_size(start: char, end: char) -> usize {
+    (end as usize).saturating_sub(start as usize) + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{
+        BindingStrength, CardinalityRange, DataType, FhirVersion, PrimitiveType,
+        PropertyConstraint, ResourceType, ValueSetBinding, ValueSetExpansion,
+    };
+
+    fn property(
+        name: &str,
+        property_type: PropertyType,
+        cardinality: CardinalityRange,
+    ) -> Property {
+        Property {
+            name: name.to_string(),
+            path: name.to_string(),
+            property_type,
+            cardinality,
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        }
+    }
+
+    fn primitive(name: &str) -> PropertyType {
+        PropertyType::Primitive { type_name: name.to_string() }
+    }
+
+    fn resource(name: &str, properties: Vec<Property>) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: None,
+            properties,
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Default::default(),
+            url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+            is_abstract: false,
+        }
+    }
+
+    fn graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+
+        graph.add_primitive(
+            "id".to_string(),
+            PrimitiveType {
+                name: "id".to_string(),
+                base: None,
+                pattern: Some("[A-Za-z0-9\\-\\.]{1,64}".to_string()),
+                documentation: Default::default(),
+                url: String::new(),
+            },
+        );
+        graph.add_datatype(
+            "Coding".to_string(),
+            DataType {
+                name: "Coding".to_string(),
+                base: None,
+                properties: vec![
+                    property("system", primitive("uri"), CardinalityRange::optional()),
+                    property("code", primitive("code"), CardinalityRange::optional()),
+                ],
+                constraints: vec![],
+                documentation: Default::default(),
+                url: String::new(),
+                is_abstract: false,
+            },
+        );
+        graph.add_value_set(
+            "http://hl7.org/fhir/ValueSet/observation-status".to_string(),
+            ValueSetExpansion {
+                url: "http://hl7.org/fhir/ValueSet/observation-status".to_string(),
+                name: "ObservationStatus".to_string(),
+                title: None,
+                concepts: ["final", "amended"]
+                    .iter()
+                    .map(|code| CodeConcept {
+                        system: "http://hl7.org/fhir/observation-status".to_string(),
+                        code: code.to_string(),
+                        display: None,
+                    })
+                    .collect(),
+            },
+        );
+
+        graph.add_resource(
+            "Patient".to_string(),
+            resource(
+                "Patient",
+                vec![
+                    property("active", primitive("boolean"), CardinalityRange::optional()),
+                    property("birthDate", primitive("date"), CardinalityRange::optional()),
+                ],
+            ),
+        );
+
+        let mut status = property("status", primitive("code"), CardinalityRange::required());
+        status.binding = Some(ValueSetBinding {
+            strength: BindingStrength::Required,
+            value_set: "http://hl7.org/fhir/ValueSet/observation-status|4.0.1".to_string(),
+            description: None,
+        });
+        let mut value = property(
+            "value[x]",
+            PropertyType::Choice { types: vec!["string".to_string(), "boolean".to_string()] },
+            CardinalityRange::optional(),
+        );
+        value.is_choice = true;
+        value.choice_types = vec!["string".to_string(), "boolean".to_string()];
+        graph.add_resource(
+            "Observation".to_string(),
+            resource(
+                "Observation",
+                vec![
+                    status,
+                    property("identifier", primitive("id"), CardinalityRange::optional_array()),
+                    property(
+                        "code",
+                        PropertyType::Complex { type_name: "Coding".to_string() },
+                        CardinalityRange::required(),
+                    ),
+                    value,
+                    property(
+                        "subject",
+                        PropertyType::Reference {
+                            target_types: vec!["Patient".to_string()],
+                            target_profiles: vec![],
+                        },
+                        CardinalityRange::required(),
+                    ),
+                ],
+            ),
+        );
+
+        graph.add_profile(
+            "http://example.org/StructureDefinition/amended-observation".to_string(),
+            ProfileType {
+                name: "AmendedObservation".to_string(),
+                base: "Observation".to_string(),
+                property_constraints: vec![
+                    PropertyConstraint {
+                        path: "Observation.status".to_string(),
+                        cardinality: None,
+                        type_constraints: vec![],
+                        binding: None,
+                        fixed_value: Some(json!("amended")),
+                        must_support: true,
+                    },
+                    PropertyConstraint {
+                        path: "Observation.value[x]".to_string(),
+                        cardinality: Some(CardinalityRange::required()),
+                        type_constraints: vec!["boolean".to_string()],
+                        binding: None,
+                        fixed_value: None,
+                        must_support: true,
+                    },
+                ],
+                new_properties: vec![],
+                documentation: Default::default(),
+                url: "http://example.org/StructureDefinition/amended-observation".to_string(),
+            },
+        );
+
+        graph
+    }
+
+    #[test]
+    fn test_generate_follows_cardinality_bindings_and_references() {
+        let graph = graph();
+        let instances = Synthesizer::new(&graph)
+            .with_seed(7)
+            .with_coverage(Coverage::Required)
+            .generate("Observation", 3)
+            .unwrap();
+
+        // Three observations, then the patients their subjects point at
+        let (observations, patients) = instances.split_at(3);
+        assert!(!patients.is_empty());
+        assert!(patients.iter().all(|patient| patient["resourceType"] == "Patient"));
+        assert_eq!(patients[0]["id"], "patient-1");
+
+        for observation in observations {
+            let status = observation["status"].as_str().unwrap();
+            assert!(["final", "amended"].contains(&status));
+            assert!(observation["code"].as_object().is_some_and(|code| !code.is_empty()));
+            assert!(observation.get("identifier").is_none());
+
+            let reference = observation["subject"]["reference"].as_str().unwrap();
+            assert!(
+                patients
+                    .iter()
+                    .any(|patient| format!("Patient/{}", patient["id"].as_str().unwrap())
+                        == reference)
+            );
+        }
+        assert_eq!(observations[0]["id"], "observation-1");
+        assert_eq!(observations[2]["id"], "observation-3");
+    }
+
+    #[test]
+    fn test_generate_fills_large_minimum_cardinality() {
+        let mut graph = graph();
+        let cardinality = |max| CardinalityRange { min: 5, max };
+        graph.add_resource(
+            "Roster".to_string(),
+            resource(
+                "Roster",
+                vec![
+                    property("member", primitive("string"), cardinality(None)),
+                    property("seat", primitive("string"), cardinality(Some(6))),
+                ],
+            ),
+        );
+
+        for seed in 0..10 {
+            let roster = Synthesizer::new(&graph)
+                .with_seed(seed)
+                .with_coverage(Coverage::Sampled)
+                .instance("Roster")
+                .unwrap();
+            assert_eq!(roster["member"].as_array().unwrap().len(), 5);
+            assert_eq!(roster["seat"].as_array().unwrap().len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_generate_is_deterministic_per_seed() {
+        let graph = graph();
+        let generate = |seed| Synthesizer::new(&graph).with_seed(seed).generate("Observation", 5);
+
+        assert_eq!(generate(1).unwrap(), generate(1).unwrap());
+        assert_ne!(generate(1).unwrap(), generate(2).unwrap());
+    }
+
+    #[test]
+    fn test_choice_and_patterns() {
+        let graph = graph();
+        let mut synthesizer = Synthesizer::new(&graph).with_coverage(Coverage::All);
+
+        for _ in 0..10 {
+            let observation = synthesizer.instance("Observation").unwrap();
+            let variants = ["valueString", "valueBoolean"]
+                .iter()
+                .filter(|key| observation.get(**key).is_some())
+                .count();
+            assert_eq!(variants, 1);
+
+            for identifier in observation["identifier"].as_array().unwrap() {
+                let identifier = identifier.as_str().unwrap();
+                assert!((1..=64).contains(&identifier.len()));
+                assert!(identifier.chars().all(|c| c.is_ascii_alphanumeric() || "-.".contains(c)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_profile_instance() {
+        let graph = graph();
+        let observation = Synthesizer::new(&graph)
+            .with_coverage(Coverage::Required)
+            .instance("http://example.org/StructureDefinition/amended-observation|1.0.0")
+            .unwrap();
+
+        assert_eq!(
+            observation["meta"]["profile"],
+            json!(["http://example.org/StructureDefinition/amended-observation"])
+        );
+        assert_eq!(observation["status"], "amended");
+        assert!(observation["valueBoolean"].is_boolean());
+        assert!(observation.get("valueString").is_none());
+
+        assert!(Synthesizer::new(&graph).instance("AmendedObservation").is_ok());
+        assert!(Synthesizer::new(&graph).instance("Unknown").is_err());
+    }
+
+    #[test]
+    fn test_pattern_generation() {
+        let mut rng = Rng::new(3);
+        let cases = [
+            ("urn:oid:[0-2](\\.(0|[1-9][0-9]*))+", "urn:oid:"),
+            ("[^\\s]+(\\s[^\\s]+)*", ""),
+            ("urn:uuid:[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}", "urn:uuid:"),
+        ];
+
+        for (pattern, prefix) in cases {
+            let parsed = Pattern::parse(pattern).unwrap();
+            for _ in 0..20 {
+                let value = parsed.generate(&mut rng);
+                assert!(value.starts_with(prefix), "{} from {}", value, pattern);
+                assert!(!value.is_empty() && !value.starts_with(' '));
+            }
+        }
+
+        let uuid = Pattern::parse(cases[2].0).unwrap().generate(&mut rng);
+        assert_eq!(uuid.len(), "urn:uuid:".len() + 36);
+        assert!(Pattern::parse("(unclosed").is_none());
+        assert!(Pattern::parse("[0-9]{3,1}").is_none());
+        assert_eq!(Pattern::parse("a{2,2}").unwrap().generate(&mut rng), "aa");
+    }
+}
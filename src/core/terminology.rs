@@ -0,0 +1,425 @@
+//! Local ValueSet expansion
+//!
+//! Expands ValueSets from their `compose` definitions using CodeSystems loaded
+//! from packages, without a terminology server.
+
+use crate::core::ir::{CodeConcept, ValueSetExpansion, versionless_url};
+use crate::core::{Error, Result};
+use heck::ToPascalCase;
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Maximum depth of nested `include.valueSet` references
+const MAX_VALUE_SET_DEPTH: usize = 8;
+
+/// Expanded concepts keyed by `(system, code)`, so display text never affects
+/// membership
+type Concepts = IndexMap<(String, String), CodeConcept>;
+
+/// Expands ValueSets from locally available CodeSystems and ValueSets
+#[derive(Debug, Default)]
+pub struct ValueSetExpander {
+    /// ValueSet resources by versionless URL
+    value_sets: HashMap<String, Value>,
+
+    /// CodeSystem resources by versionless URL
+    code_systems: HashMap<String, Value>,
+}
+
+impl ValueSetExpander {
+    /// Create an empty expander
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a ValueSet resource
+    pub fn add_value_set(&mut self, value_set: Value) {
+        if let Some(url) = value_set.get("url").and_then(|v| v.as_str()) {
+            self.value_sets.insert(versionless_url(url).to_string(), value_set);
+        }
+    }
+
+    /// Register a CodeSystem resource
+    pub fn add_code_system(&mut self, code_system: Value) {
+        if let Some(url) = code_system.get("url").and_then(|v| v.as_str()) {
+            self.code_systems.insert(versionless_url(url).to_string(), code_system);
+        }
+    }
+
+    /// Whether a ValueSet with this URL has been registered
+    pub fn has_value_set(&self, url: &str) -> bool {
+        self.value_sets.contains_key(versionless_url(url))
+    }
+
+    /// Whether a CodeSystem with this URL has been registered
+    pub fn has_code_system(&self, url: &str) -> bool {
+        self.code_systems.contains_key(versionless_url(url))
+    }
+
+    /// Canonical URLs a ValueSet depends on: `(code systems, value sets)`
+    ///
+    /// Used to fetch everything needed before calling [`Self::expand`].
+    pub fn references(value_set: &Value) -> (Vec<String>, Vec<String>) {
+        let mut systems = Vec::new();
+        let mut value_sets = Vec::new();
+
+        let compose = value_set.get("compose");
+        for key in ["include", "exclude"] {
+            let entries = compose.and_then(|c| c.get(key)).and_then(|v| v.as_array());
+            for entry in entries.into_iter().flatten() {
+                if let Some(system) = entry.get("system").and_then(|v| v.as_str()) {
+                    systems.push(versionless_url(system).to_string());
+                }
+                let nested = entry.get("valueSet").and_then(|v| v.as_array());
+                for url in nested.into_iter().flatten().filter_map(|v| v.as_str()) {
+                    value_sets.push(versionless_url(url).to_string());
+                }
+            }
+        }
+
+        (systems, value_sets)
+    }
+
+    /// Expand a registered ValueSet
+    ///
+    /// Uses a pre-computed `expansion` when the ValueSet has one, otherwise
+    /// evaluates `compose.include`/`compose.exclude`. Includes listing explicit
+    /// concepts and includes of a whole CodeSystem are supported; filter-based
+    /// includes are not and produce an error.
+    pub fn expand(&self, url: &str) -> Result<ValueSetExpansion> {
+        let value_set = self.value_set(url)?;
+        let concepts = self.expand_concepts(value_set, 0)?;
+
+        let url = versionless_url(url).to_string();
+        let name = value_set
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or_else(|| url.rsplit('/').next().unwrap_or("ValueSet"))
+            .to_pascal_case();
+        let title = value_set.get("title").and_then(|v| v.as_str()).map(String::from);
+
+        Ok(ValueSetExpansion { url, name, title, concepts: concepts.into_values().collect() })
+    }
+
+    fn value_set(&self, url: &str) -> Result<&Value> {
+        self.value_sets
+            .get(versionless_url(url))
+            .ok_or_else(|| Error::Other(format!("ValueSet {} not found", url)))
+    }
+
+    fn expand_concepts(&self, value_set: &Value, depth: usize) -> Result<Concepts> {
+        if depth > MAX_VALUE_SET_DEPTH {
+            return Err(Error::Other("ValueSet includes are nested too deeply".to_string()));
+        }
+
+        if let Some(contains) =
+            value_set.get("expansion").and_then(|e| e.get("contains")).and_then(|v| v.as_array())
+        {
+            let mut concepts = Concepts::new();
+            Self::collect_contains(contains, &mut concepts);
+            return Ok(concepts);
+        }
+
+        let compose = value_set.get("compose").ok_or_else(|| {
+            Error::Other("ValueSet has neither an expansion nor a compose".to_string())
+        })?;
+
+        let mut concepts = Concepts::new();
+        for include in compose.get("include").and_then(|v| v.as_array()).into_iter().flatten() {
+            for (key, concept) in self.expand_set(include, depth)? {
+                concepts.entry(key).or_insert(concept);
+            }
+        }
+        for exclude in compose.get("exclude").and_then(|v| v.as_array()).into_iter().flatten() {
+            let excluded = self.expand_set(exclude, depth)?;
+            concepts.retain(|key, _| !excluded.contains_key(key));
+        }
+
+        Ok(concepts)
+    }
+
+    /// Expand a single `compose.include` or `compose.exclude` entry
+    fn expand_set(&self, set: &Value, depth: usize) -> Result<Concepts> {
+        if set.get("filter").and_then(|v| v.as_array()).is_some_and(|f| !f.is_empty()) {
+            return Err(Error::Other("filter-based includes are not supported".to_string()));
+        }
+
+        let system = set.get("system").and_then(|v| v.as_str()).map(versionless_url);
+
+        let mut concepts = match (system, set.get("concept").and_then(|v| v.as_array())) {
+            (Some(system), Some(listed)) => {
+                let code_system = self.code_systems.get(system);
+                let mut concepts = Concepts::new();
+                for concept in listed {
+                    let Some(code) = concept.get("code").and_then(|v| v.as_str()) else {
+                        continue;
+                    };
+                    let display = concept
+                        .get("display")
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                        .or_else(|| code_system.and_then(|cs| Self::find_display(cs, code)));
+                    Self::insert_concept(&mut concepts, system, code, display);
+                }
+                concepts
+            }
+            (Some(system), None) => {
+                let code_system = self
+                    .code_systems
+                    .get(system)
+                    .ok_or_else(|| Error::Other(format!("CodeSystem {} not found", system)))?;
+                let content = code_system.get("content").and_then(|v| v.as_str());
+                if content.is_some_and(|content| content != "complete") {
+                    return Err(Error::Other(format!(
+                        "CodeSystem {} does not define its complete content",
+                        system
+                    )));
+                }
+                let mut concepts = Concepts::new();
+                let defined = code_system.get("concept").and_then(|v| v.as_array());
+                Self::collect_code_system(system, defined.into_iter().flatten(), &mut concepts);
+                concepts
+            }
+            (None, _) => Concepts::new(),
+        };
+
+        // Nested value sets intersect with the system part (or stand alone without one)
+        let nested = set.get("valueSet").and_then(|v| v.as_array());
+        for (i, url) in nested.into_iter().flatten().filter_map(|v| v.as_str()).enumerate() {
+            let expanded = self.expand_concepts(self.value_set(url)?, depth + 1)?;
+            if system.is_none() && i == 0 {
+                concepts = expanded;
+            } else {
+                concepts.retain(|key, _| expanded.contains_key(key));
+            }
+        }
+
+        Ok(concepts)
+    }
+
+    /// Flatten a (possibly hierarchical) CodeSystem concept list
+    fn collect_code_system<'a>(
+        system: &str,
+        defined: impl Iterator<Item = &'a Value>,
+        concepts: &mut Concepts,
+    ) {
+        for concept in defined {
+            if let Some(code) = concept.get("code").and_then(|v| v.as_str()) {
+                let display = concept.get("display").and_then(|v| v.as_str()).map(String::from);
+                Self::insert_concept(concepts, system, code, display);
+            }
+            let children = concept.get("concept").and_then(|v| v.as_array());
+            Self::collect_code_system(system, children.into_iter().flatten(), concepts);
+        }
+    }
+
+    /// Flatten a pre-computed `expansion.contains` tree, skipping abstract entries
+    fn collect_contains(contains: &[Value], concepts: &mut Concepts) {
+        for entry in contains {
+            let is_abstract = entry.get("abstract").and_then(|v| v.as_bool()).unwrap_or(false);
+            if let (false, Some(system), Some(code)) = (
+                is_abstract,
+                entry.get("system").and_then(|v| v.as_str()),
+                entry.get("code").and_then(|v| v.as_str()),
+            ) {
+                let display = entry.get("display").and_then(|v| v.as_str()).map(String::from);
+                Self::insert_concept(concepts, versionless_url(system), code, display);
+            }
+            if let Some(nested) = entry.get("contains").and_then(|v| v.as_array()) {
+                Self::collect_contains(nested, concepts);
+            }
+        }
+    }
+
+    /// Add a concept unless its `(system, code)` is already present
+    fn insert_concept(concepts: &mut Concepts, system: &str, code: &str, display: Option<String>) {
+        concepts.entry((system.to_string(), code.to_string())).or_insert_with(|| CodeConcept {
+            system: system.to_string(),
+            code: code.to_string(),
+            display,
+        });
+    }
+
+    /// Find the display text for a code anywhere in a CodeSystem hierarchy
+    fn find_display(code_system: &Value, code: &str) -> Option<String> {
+        fn search(concepts: &[Value], code: &str) -> Option<String> {
+            concepts.iter().find_map(|concept| {
+                if concept.get("code").and_then(|v| v.as_str()) == Some(code) {
+                    return concept.get("display").and_then(|v| v.as_str()).map(String::from);
+                }
+                search(concept.get("concept").and_then(|v| v.as_array())?, code)
+            })
+        }
+        search(code_system.get("concept").and_then(|v| v.as_array())?, code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn gender_code_system() -> Value {
+        json!({
+            "resourceType": "CodeSystem",
+            "url": "http://hl7.org/fhir/administrative-gender",
+            "content": "complete",
+            "concept": [
+                { "code": "male", "display": "Male" },
+                { "code": "female", "display": "Female" },
+                { "code": "other", "display": "Other", "concept": [
+                    { "code": "nonbinary", "display": "Non-binary" }
+                ] },
+                { "code": "unknown", "display": "Unknown" }
+            ]
+        })
+    }
+
+    fn expander() -> ValueSetExpander {
+        let mut expander = ValueSetExpander::new();
+        expander.add_code_system(gender_code_system());
+        expander.add_value_set(json!({
+            "resourceType": "ValueSet",
+            "url": "http://hl7.org/fhir/ValueSet/administrative-gender",
+            "name": "AdministrativeGender",
+            "title": "AdministrativeGender",
+            "compose": { "include": [{ "system": "http://hl7.org/fhir/administrative-gender" }] }
+        }));
+        expander
+    }
+
+    #[test]
+    fn test_expand_whole_system() {
+        let expansion =
+            expander().expand("http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1").unwrap();
+
+        assert_eq!(expansion.name, "AdministrativeGender");
+        assert_eq!(expansion.url, "http://hl7.org/fhir/ValueSet/administrative-gender");
+        let codes: Vec<_> = expansion.concepts.iter().map(|c| c.code.as_str()).collect();
+        assert_eq!(codes, ["male", "female", "other", "nonbinary", "unknown"]);
+        assert_eq!(expansion.concepts[0].display.as_deref(), Some("Male"));
+    }
+
+    #[test]
+    fn test_expand_concepts_and_exclude() {
+        let mut expander = expander();
+        expander.add_value_set(json!({
+            "url": "http://example.org/ValueSet/binary-gender",
+            "name": "binary-gender",
+            "compose": {
+                "include": [
+                    {
+                        "system": "http://hl7.org/fhir/administrative-gender",
+                        "concept": [{ "code": "male" }, { "code": "female" }, { "code": "unknown" }]
+                    }
+                ],
+                "exclude": [
+                    {
+                        "system": "http://hl7.org/fhir/administrative-gender",
+                        "concept": [{ "code": "unknown" }]
+                    }
+                ]
+            }
+        }));
+
+        let expansion = expander.expand("http://example.org/ValueSet/binary-gender").unwrap();
+
+        assert_eq!(expansion.name, "BinaryGender");
+        assert_eq!(expansion.concepts.len(), 2);
+        // Display falls back to the CodeSystem definition
+        assert_eq!(expansion.concepts[1].display.as_deref(), Some("Female"));
+    }
+
+    #[test]
+    fn test_expand_matches_codes_without_display() {
+        let mut expander = expander();
+        expander.add_value_set(json!({
+            "url": "http://example.org/ValueSet/answers",
+            "name": "Answers",
+            "compose": {
+                "include": [{
+                    "system": "http://example.org/answers",
+                    "concept": [
+                        { "code": "Y", "display": "Yes" },
+                        { "code": "N", "display": "No" },
+                        { "code": "U", "display": "Unknown" }
+                    ]
+                }],
+                "exclude": [{ "system": "http://example.org/answers", "concept": [{ "code": "U" }] }]
+            }
+        }));
+        expander.add_value_set(json!({
+            "url": "http://example.org/ValueSet/yes",
+            "name": "Yes",
+            "compose": {
+                "include": [{
+                    "system": "http://example.org/answers",
+                    "concept": [{ "code": "Y", "display": "Affirmative" }, { "code": "Y" }],
+                    "valueSet": ["http://example.org/ValueSet/answers"]
+                }]
+            }
+        }));
+
+        let answers = expander.expand("http://example.org/ValueSet/answers").unwrap();
+        let codes: Vec<_> = answers.concepts.iter().map(|c| c.code.as_str()).collect();
+        assert_eq!(codes, ["Y", "N"]);
+
+        let yes = expander.expand("http://example.org/ValueSet/yes").unwrap();
+        assert_eq!(yes.concepts.len(), 1);
+        assert_eq!(yes.concepts[0].display.as_deref(), Some("Affirmative"));
+    }
+
+    #[test]
+    fn test_expand_nested_value_set() {
+        let mut expander = expander();
+        expander.add_value_set(json!({
+            "url": "http://example.org/ValueSet/wrapped",
+            "name": "Wrapped",
+            "compose": {
+                "include": [{ "valueSet": ["http://hl7.org/fhir/ValueSet/administrative-gender"] }]
+            }
+        }));
+
+        let expansion = expander.expand("http://example.org/ValueSet/wrapped").unwrap();
+        assert_eq!(expansion.concepts.len(), 5);
+    }
+
+    #[test]
+    fn test_expand_unsupported() {
+        let mut expander = expander();
+        expander.add_value_set(json!({
+            "url": "http://example.org/ValueSet/filtered",
+            "compose": {
+                "include": [{
+                    "system": "http://loinc.org",
+                    "filter": [{ "property": "CLASS", "op": "=", "value": "CHEM" }]
+                }]
+            }
+        }));
+        expander.add_value_set(json!({
+            "url": "http://example.org/ValueSet/missing",
+            "compose": { "include": [{ "system": "http://example.org/unknown" }] }
+        }));
+
+        assert!(expander.expand("http://example.org/ValueSet/filtered").is_err());
+        assert!(expander.expand("http://example.org/ValueSet/missing").is_err());
+        assert!(expander.expand("http://example.org/ValueSet/absent").is_err());
+    }
+
+    #[test]
+    fn test_references() {
+        let value_set = json!({
+            "compose": {
+                "include": [
+                    { "system": "http://hl7.org/fhir/administrative-gender|4.0.1" },
+                    { "valueSet": ["http://example.org/ValueSet/other"] }
+                ]
+            }
+        });
+
+        let (systems, value_sets) = ValueSetExpander::references(&value_set);
+        assert_eq!(systems, ["http://hl7.org/fhir/administrative-gender"]);
+        assert_eq!(value_sets, ["http://example.org/ValueSet/other"]);
+    }
+}
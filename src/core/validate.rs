@@ -0,0 +1,1014 @@
+//! Validation of FHIR JSON instances
+//!
+//! Checks a resource against the same [`TypeGraph`] the generators use, so the
+//! server and the generated SDKs agree on what a valid instance is:
+//!
+//! - every key is an element of the type or the `_` sibling of a primitive
+//! - elements occur within their cardinality, as arrays only when repeating
+//! - at most one variant of each choice element is present
+//! - primitives have the right JSON type and match the regex of their type
+//! - codes bound with required strength to an expanded value set are in the expansion
+//! - references point at one of the allowed resource types
+//! - profile constraints narrow cardinality, types and bindings and fix values,
+//!   on top-level and nested elements alike
+//!
+//! All problems are collected and reported as an `OperationOutcome`.
+
+use crate::core::ir::{
+    BindingStrength, ProfileType, Property, PropertyType, TypeGraph, versionless_url,
+};
+use crate::core::{Error, Result};
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
+
+/// Elements any element may carry, even when the graph does not declare them
+const IMPLICIT_ELEMENTS: &[&str] = &["id", "extension", "modifierExtension"];
+
+/// Severity of a validation issue (`OperationOutcome.issue.severity`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSeverity {
+    /// The instance could not be processed
+    Fatal,
+    /// The instance is invalid
+    Error,
+    /// The instance is valid but questionable
+    Warning,
+    /// Informational note
+    Information,
+}
+
+impl IssueSeverity {
+    /// FHIR `issue-severity` code
+    pub fn code(&self) -> &'static str {
+        match self {
+            IssueSeverity::Fatal => "fatal",
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Information => "information",
+        }
+    }
+}
+
+/// Kind of a validation issue (`OperationOutcome.issue.code`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueType {
+    /// Unknown element or wrong JSON shape
+    Structure,
+    /// Required element missing
+    Required,
+    /// Element value not allowed
+    Value,
+    /// Rule between elements broken
+    Invariant,
+    /// Code not in the bound value set
+    CodeInvalid,
+    /// Referenced type or profile not known
+    NotFound,
+    /// No problem found
+    Informational,
+}
+
+impl IssueType {
+    /// FHIR `issue-type` code
+    pub fn code(&self) -> &'static str {
+        match self {
+            IssueType::Structure => "structure",
+            IssueType::Required => "required",
+            IssueType::Value => "value",
+            IssueType::Invariant => "invariant",
+            IssueType::CodeInvalid => "code-invalid",
+            IssueType::NotFound => "not-found",
+            IssueType::Informational => "informational",
+        }
+    }
+}
+
+/// Single problem found in an instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// How serious the problem is
+    pub severity: IssueSeverity,
+
+    /// Kind of problem
+    pub code: IssueType,
+
+    /// Human-readable description
+    pub diagnostics: String,
+
+    /// FHIRPath location of the problem (e.g., `Patient.name[0].given`)
+    pub expression: String,
+}
+
+/// Outcome of validating one instance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Problems in the order they were found
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Whether no error or fatal issue was found
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Error and fatal issues
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| matches!(issue.severity, IssueSeverity::Fatal | IssueSeverity::Error))
+    }
+
+    /// The report as a FHIR `OperationOutcome` resource
+    ///
+    /// An outcome needs at least one issue, so a clean report yields a single
+    /// informational one.
+    pub fn to_operation_outcome(&self) -> Value {
+        let issues: Vec<Value> = if self.issues.is_empty() {
+            vec![json!({
+                "severity": IssueSeverity::Information.code(),
+                "code": IssueType::Informational.code(),
+                "diagnostics": "No issues detected",
+            })]
+        } else {
+            self.issues
+                .iter()
+                .map(|issue| {
+                    json!({
+                        "severity": issue.severity.code(),
+                        "code": issue.code.code(),
+                        "diagnostics": issue.diagnostics,
+                        "expression": [issue.expression],
+                    })
+                })
+                .collect()
+        };
+
+        json!({ "resourceType": "OperationOutcome", "issue": issues })
+    }
+}
+
+/// Validator of FHIR JSON against a [`TypeGraph`]
+pub struct Validator<'a> {
+    graph: &'a TypeGraph,
+    /// Compiled primitive patterns by primitive type, anchored to the whole value
+    patterns: HashMap<&'a str, Regex>,
+}
+
+impl<'a> Validator<'a> {
+    /// Create a validator, compiling the regex of every primitive type
+    ///
+    /// Patterns the regex engine does not support are skipped.
+    pub fn new(graph: &'a TypeGraph) -> Self {
+        let patterns = graph
+            .primitives
+            .iter()
+            .filter_map(|(name, primitive)| {
+                let pattern = primitive.pattern.as_deref()?;
+                match Regex::new(&format!("^(?:{})$", pattern)) {
+                    Ok(regex) => Some((name.as_str(), regex)),
+                    Err(e) => {
+                        tracing::debug!("Skipping pattern of primitive {}: {}", name, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { graph, patterns }
+    }
+
+    /// Validate a resource against the definition of its `resourceType`
+    pub fn validate(&self, instance: &Value) -> ValidationReport {
+        let mut run = Run { validator: self, issues: Vec::new() };
+        run.resource(instance, "", None);
+        ValidationReport { issues: run.issues }
+    }
+
+    /// Validate a resource against a profile, given by URL or name
+    ///
+    /// Fails if the profile is not in the graph.
+    pub fn validate_profile(&self, instance: &Value, profile: &str) -> Result<ValidationReport> {
+        let graph = self.graph;
+        let profile = graph
+            .profiles
+            .get(versionless_url(profile))
+            .or_else(|| graph.profiles.values().find(|candidate| candidate.name == profile))
+            .ok_or_else(|| Error::Validation(format!("Unknown profile: {}", profile)))?;
+
+        let mut run = Run { validator: self, issues: Vec::new() };
+        run.resource(instance, "", Some(profile));
+        Ok(ValidationReport { issues: run.issues })
+    }
+}
+
+/// State of a single validation
+struct Run<'v, 'a> {
+    validator: &'v Validator<'a>,
+    issues: Vec<Issue>,
+}
+
+impl<'a> Run<'_, 'a> {
+    fn error(&mut self, code: IssueType, expression: &str, diagnostics: String) {
+        self.issues.push(Issue {
+            severity: IssueSeverity::Error,
+            code,
+            diagnostics,
+            expression: expression.to_string(),
+        });
+    }
+
+    /// Validate a resource, at the root or nested (`contained`, `Bundle.entry.resource`)
+    fn resource(&mut self, value: &Value, expression: &str, profile: Option<&'a ProfileType>) {
+        let graph = self.validator.graph;
+
+        let Some(object) = value.as_object() else {
+            self.error(IssueType::Structure, expression, "Expected a resource object".to_string());
+            return;
+        };
+        let Some(resource_type) = object.get("resourceType").and_then(Value::as_str) else {
+            self.error(IssueType::Required, expression, "Missing resourceType".to_string());
+            return;
+        };
+        let expression = if expression.is_empty() { resource_type } else { expression };
+
+        let Some(resource) = graph.resources.get(resource_type) else {
+            self.issues.push(Issue {
+                severity: IssueSeverity::Fatal,
+                code: IssueType::NotFound,
+                diagnostics: format!("Unknown resource type {}", resource_type),
+                expression: expression.to_string(),
+            });
+            return;
+        };
+        if resource.is_abstract {
+            self.error(
+                IssueType::Structure,
+                expression,
+                format!("Resource type {} is abstract", resource_type),
+            );
+            return;
+        }
+        if let Some(profile) = profile
+            && profile.base != resource_type
+        {
+            self.error(
+                IssueType::Structure,
+                expression,
+                format!(
+                    "Profile {} constrains {}, not {}",
+                    profile.url, profile.base, resource_type
+                ),
+            );
+            return;
+        }
+
+        let properties = graph.properties_with_base(resource_type);
+        self.element(object, &properties, resource_type, expression, profile, true);
+    }
+
+    /// Validate the keys of an object against the properties of its type
+    ///
+    /// `schema_path` is the element path without indices (`Patient.name`) used
+    /// to find profile constraints; `expression` locates the object in the instance.
+    fn element(
+        &mut self,
+        object: &Map<String, Value>,
+        properties: &[&Property],
+        schema_path: &str,
+        expression: &str,
+        profile: Option<&'a ProfileType>,
+        is_resource: bool,
+    ) {
+        let mut known: HashSet<String> = HashSet::new();
+        if is_resource {
+            known.insert("resourceType".to_string());
+        }
+
+        for property in properties {
+            let element_path = format!("{}.{}", schema_path, property.name.trim_end_matches("[x]"));
+            let constraint = profile.and_then(|profile| profile.constraint_at(&element_path));
+            let narrowed = constraint.map(|constraint| constraint.apply(property));
+            let effective = narrowed.as_ref().unwrap_or(property);
+            let fixed = constraint.and_then(|constraint| constraint.fixed_value.as_ref());
+
+            if !property.is_choice {
+                known.insert(property.name.clone());
+                known.extend(property.primitive_element_name());
+                self.occurrences(object, effective, &element_path, expression, profile, fixed);
+                continue;
+            }
+
+            let variants = property.choice_variants();
+            let allowed = effective.choice_variants();
+            let present: Vec<&Property> = variants
+                .iter()
+                .filter(|variant| {
+                    object.contains_key(&variant.name)
+                        || variant.primitive_element_name().is_some_and(|n| object.contains_key(&n))
+                })
+                .collect();
+            for variant in &variants {
+                known.insert(variant.name.clone());
+                known.extend(variant.primitive_element_name());
+            }
+
+            let choice_expression =
+                format!("{}.{}", expression, property.name.trim_end_matches("[x]"));
+            if present.len() > 1 {
+                let names: Vec<&str> =
+                    present.iter().map(|variant| variant.name.as_str()).collect();
+                self.error(
+                    IssueType::Invariant,
+                    &choice_expression,
+                    format!("Only one of {} may be present", names.join(", ")),
+                );
+            }
+            let allowed_present =
+                present.iter().any(|variant| allowed.iter().any(|a| a.name == variant.name));
+            if !allowed_present && effective.cardinality.is_required() {
+                self.error(
+                    IssueType::Required,
+                    &choice_expression,
+                    format!("Missing required element {}", property.name),
+                );
+            }
+
+            for variant in present {
+                let Some(variant) = allowed.iter().find(|allowed| allowed.name == variant.name)
+                else {
+                    let type_code = variant.property_type.type_name().unwrap_or_default();
+                    self.error(
+                        IssueType::Structure,
+                        &format!("{}.{}", expression, variant.name),
+                        format!("Type {} is not allowed for {}", type_code, property.name),
+                    );
+                    continue;
+                };
+                self.occurrences(object, variant, &element_path, expression, profile, fixed);
+            }
+        }
+
+        for key in object.keys() {
+            if !known.contains(key) && !IMPLICIT_ELEMENTS.contains(&key.as_str()) {
+                self.error(
+                    IssueType::Structure,
+                    &format!("{}.{}", expression, key),
+                    format!("Unknown element '{}'", key),
+                );
+            }
+        }
+    }
+
+    /// Check the cardinality of an element and validate each of its values
+    fn occurrences(
+        &mut self,
+        object: &Map<String, Value>,
+        property: &Property,
+        element_path: &str,
+        expression: &str,
+        profile: Option<&'a ProfileType>,
+        fixed: Option<&Value>,
+    ) {
+        let value = object.get(&property.name);
+        let element_name = property.primitive_element_name().unwrap_or_default();
+        let element = object.get(&element_name);
+        let expression = format!("{}.{}", expression, property.name);
+        let cardinality = &property.cardinality;
+
+        if value.is_none() && element.is_none() {
+            if cardinality.is_required() {
+                self.error(
+                    IssueType::Required,
+                    &expression,
+                    format!("Missing required element {}", property.name),
+                );
+            }
+            return;
+        }
+
+        let is_array = cardinality.is_array();
+        let mut values: Vec<Option<&Value>> = Vec::new();
+        let mut elements: Vec<Option<&Value>> = Vec::new();
+        for (target, item, name) in [
+            (&mut values, value, property.name.as_str()),
+            (&mut elements, element, element_name.as_str()),
+        ] {
+            match (item, is_array) {
+                (None, _) => {}
+                (Some(Value::Array(items)), true) => {
+                    if items.is_empty() {
+                        self.error(
+                            IssueType::Structure,
+                            &expression,
+                            format!("Array {} must not be empty", name),
+                        );
+                    }
+                    target.extend(items.iter().map(|item| Some(item).filter(|i| !i.is_null())));
+                }
+                (Some(_), true) => {
+                    self.error(
+                        IssueType::Structure,
+                        &expression,
+                        format!("Element {} must be an array", name),
+                    );
+                    return;
+                }
+                (Some(Value::Array(_)), false) => {
+                    self.error(
+                        IssueType::Structure,
+                        &expression,
+                        format!("Element {} must not be an array", name),
+                    );
+                    return;
+                }
+                (Some(item), false) => target.push(Some(item)),
+            }
+        }
+
+        let count = values.len().max(elements.len());
+        if (count as u64) < u64::from(cardinality.min) {
+            self.error(
+                IssueType::Required,
+                &expression,
+                format!(
+                    "Element {} requires at least {} values, found {}",
+                    property.name, cardinality.min, count
+                ),
+            );
+        }
+        if let Some(max) = cardinality.max
+            && count as u64 > u64::from(max)
+        {
+            self.error(
+                IssueType::Structure,
+                &expression,
+                format!("Element {} allows at most {} values, found {}", property.name, max, count),
+            );
+        }
+
+        for index in 0..count {
+            let item_expression =
+                if is_array { format!("{}[{}]", expression, index) } else { expression.clone() };
+            let item = values.get(index).copied().flatten();
+            let element = elements.get(index).copied().flatten();
+
+            if item.is_none() && element.is_none() {
+                self.error(
+                    IssueType::Structure,
+                    &item_expression,
+                    "Array entries must not be null".to_string(),
+                );
+                continue;
+            }
+            if let Some(element) = element {
+                match element.as_object() {
+                    Some(element) => self.element(element, &[], "", &item_expression, None, false),
+                    None => self.error(
+                        IssueType::Structure,
+                        &item_expression,
+                        "Primitive element data must be an object".to_string(),
+                    ),
+                }
+            }
+            if let Some(item) = item {
+                self.value(property, item, element_path, &item_expression, profile);
+                if let Some(fixed) = fixed
+                    && !matches_pattern(item, fixed)
+                {
+                    self.error(
+                        IssueType::Value,
+                        &item_expression,
+                        format!(
+                            "Value does not match the value required by the profile: {}",
+                            fixed
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Validate a single value of an element
+    fn value(
+        &mut self,
+        property: &Property,
+        value: &Value,
+        element_path: &str,
+        expression: &str,
+        profile: Option<&'a ProfileType>,
+    ) {
+        let graph = self.validator.graph;
+
+        match &property.property_type {
+            PropertyType::Primitive { type_name } => {
+                self.primitive(type_name, value, expression);
+                if let Some(code) = value.as_str()
+                    && type_name == "code"
+                {
+                    self.binding(property, &[(None, code)], expression);
+                }
+            }
+            PropertyType::Complex { type_name } if graph.resources.contains_key(type_name) => {
+                self.resource(value, expression, None);
+            }
+            PropertyType::Complex { type_name } => {
+                self.complex(type_name, value, element_path, expression, profile);
+                match type_name.as_str() {
+                    "Coding" => self.binding(property, &codings(value), expression),
+                    "CodeableConcept" => {
+                        let codings: Vec<_> = value
+                            .get("coding")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                            .flat_map(codings)
+                            .collect();
+                        self.binding(property, &codings, expression);
+                    }
+                    _ => {}
+                }
+            }
+            PropertyType::Reference { target_types, .. } => {
+                self.complex("Reference", value, element_path, expression, profile);
+                self.reference(target_types, value, expression);
+            }
+            PropertyType::BackboneElement { properties } => {
+                let Some(object) = value.as_object() else {
+                    self.error(IssueType::Structure, expression, "Expected an object".to_string());
+                    return;
+                };
+                let properties: Vec<&Property> = properties.iter().collect();
+                self.element(object, &properties, element_path, expression, profile, false);
+            }
+            // Choice elements are validated through their variants
+            PropertyType::Choice { .. } => {}
+        }
+    }
+
+    /// Validate a datatype value; types missing from the graph only need to be objects
+    fn complex(
+        &mut self,
+        type_name: &str,
+        value: &Value,
+        element_path: &str,
+        expression: &str,
+        profile: Option<&'a ProfileType>,
+    ) {
+        let Some(object) = value.as_object() else {
+            self.error(
+                IssueType::Structure,
+                expression,
+                format!("Expected an object for type {}", type_name),
+            );
+            return;
+        };
+        if self.validator.graph.datatypes.contains_key(type_name) {
+            let properties = self.validator.graph.properties_with_base(type_name);
+            self.element(object, &properties, element_path, expression, profile, false);
+        }
+    }
+
+    /// Check the JSON type, range and regex of a primitive value
+    fn primitive(&mut self, type_name: &str, value: &Value, expression: &str) {
+        let (valid, expected) = match type_name {
+            "boolean" => (value.is_boolean(), "a boolean"),
+            "integer" | "unsignedInt" | "positiveInt" => {
+                (value.is_i64() || value.is_u64(), "an integer")
+            }
+            "decimal" => (value.is_number(), "a number"),
+            _ => (value.is_string(), "a string"),
+        };
+        if !valid {
+            self.error(
+                IssueType::Structure,
+                expression,
+                format!("Expected {} for {}, found {}", expected, type_name, value),
+            );
+            return;
+        }
+
+        let in_range = match (type_name, value.as_i64()) {
+            ("integer", Some(number)) => i32::try_from(number).is_ok(),
+            ("unsignedInt", Some(number)) => (0..=i64::from(i32::MAX)).contains(&number),
+            ("positiveInt", Some(number)) => (1..=i64::from(i32::MAX)).contains(&number),
+            ("integer" | "unsignedInt" | "positiveInt", None) => false,
+            _ => true,
+        };
+        if !in_range {
+            self.error(
+                IssueType::Value,
+                expression,
+                format!("Value {} is out of range for {}", value, type_name),
+            );
+        }
+
+        if let Some(text) = value.as_str()
+            && let Some(pattern) = self.validator.patterns.get(type_name)
+            && !pattern.is_match(text)
+        {
+            self.error(
+                IssueType::Value,
+                expression,
+                format!("Value '{}' is not a valid {}", text, type_name),
+            );
+        }
+    }
+
+    /// Check codes against a required binding with a local expansion
+    ///
+    /// `codes` are `(system, code)` pairs; one of them must be in the expansion.
+    fn binding(&mut self, property: &Property, codes: &[(Option<&str>, &str)], expression: &str) {
+        let Some(binding) = &property.binding else { return };
+        if binding.strength != BindingStrength::Required {
+            return;
+        }
+        let Some(expansion) = self.validator.graph.value_set(&binding.value_set) else { return };
+
+        let found = codes.iter().any(|(system, code)| {
+            expansion.concepts.iter().any(|concept| {
+                concept.code == *code && system.is_none_or(|system| concept.system == system)
+            })
+        });
+        if !found {
+            let codes: Vec<&str> = codes.iter().map(|(_, code)| *code).collect();
+            let diagnostics = if codes.is_empty() {
+                format!("A code from value set {} is required", expansion.url)
+            } else {
+                format!("Code '{}' is not in value set {}", codes.join("', '"), expansion.url)
+            };
+            self.error(IssueType::CodeInvalid, expression, diagnostics);
+        }
+    }
+
+    /// Check that a literal reference points at an allowed resource type
+    ///
+    /// Contained (`#id`), `urn:` and unrecognized references are not checked.
+    fn reference(&mut self, target_types: &[String], value: &Value, expression: &str) {
+        if target_types.is_empty() || target_types.iter().any(|target| target == "Resource") {
+            return;
+        }
+        let Some(reference) = value.get("reference").and_then(Value::as_str) else { return };
+        if reference.starts_with('#') || reference.starts_with("urn:") {
+            return;
+        }
+
+        let graph = self.validator.graph;
+        let path = reference.split("/_history/").next().unwrap_or(reference);
+        let Some(target) =
+            path.rsplit('/').nth(1).filter(|segment| graph.resources.contains_key(*segment))
+        else {
+            return;
+        };
+        if !target_types.iter().any(|allowed| allowed == target) {
+            self.error(
+                IssueType::Value,
+                &format!("{}.reference", expression),
+                format!(
+                    "Reference to {} is not allowed, expected {}",
+                    target,
+                    target_types.join(" | ")
+                ),
+            );
+        }
+    }
+}
+
+/// `(system, code)` of a Coding, if it has a code
+fn codings(value: &Value) -> Vec<(Option<&str>, &str)> {
+    value
+        .get("code")
+        .and_then(Value::as_str)
+        .map(|code| (value.get("system").and_then(Value::as_str), code))
+        .into_iter()
+        .collect()
+}
+
+/// Whether a value contains everything in a pattern
+///
+/// Objects must carry every key of the pattern, arrays must contain a match for
+/// every pattern entry, and other values must be equal.
+fn matches_pattern(value: &Value, pattern: &Value) -> bool {
+    match (value, pattern) {
+        (Value::Object(value), Value::Object(pattern)) => pattern
+            .iter()
+            .all(|(key, expected)| value.get(key).is_some_and(|v| matches_pattern(v, expected))),
+        (Value::Array(value), Value::Array(pattern)) => {
+            pattern.iter().all(|expected| value.iter().any(|v| matches_pattern(v, expected)))
+        }
+        _ => value == pattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{
+        CardinalityRange, CodeConcept, DataType, FhirVersion, PrimitiveType, PropertyConstraint,
+        ResourceType, ValueSetBinding, ValueSetExpansion,
+    };
+
+    fn property(
+        name: &str,
+        property_type: PropertyType,
+        cardinality: CardinalityRange,
+    ) -> Property {
+        Property {
+            name: name.to_string(),
+            path: name.to_string(),
+            property_type,
+            cardinality,
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        }
+    }
+
+    fn primitive(name: &str) -> PropertyType {
+        PropertyType::Primitive { type_name: name.to_string() }
+    }
+
+    fn resource(name: &str, properties: Vec<Property>) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: None,
+            properties,
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Default::default(),
+            url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+            is_abstract: false,
+        }
+    }
+
+    fn graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+
+        graph.add_primitive(
+            "date".to_string(),
+            PrimitiveType {
+                name: "date".to_string(),
+                base: None,
+                pattern: Some(
+                    "([0-9]{4})(-(0[1-9]|1[0-2])(-(0[1-9]|[1-2][0-9]|3[0-1]))?)?".to_string(),
+                ),
+                documentation: Default::default(),
+                url: String::new(),
+            },
+        );
+        graph.add_datatype(
+            "HumanName".to_string(),
+            DataType {
+                name: "HumanName".to_string(),
+                base: None,
+                properties: vec![
+                    property("family", primitive("string"), CardinalityRange::optional()),
+                    property("given", primitive("string"), CardinalityRange::optional_array()),
+                ],
+                constraints: vec![],
+                documentation: Default::default(),
+                url: String::new(),
+                is_abstract: false,
+            },
+        );
+        graph.add_value_set(
+            "http://hl7.org/fhir/ValueSet/observation-status".to_string(),
+            ValueSetExpansion {
+                url: "http://hl7.org/fhir/ValueSet/observation-status".to_string(),
+                name: "ObservationStatus".to_string(),
+                title: None,
+                concepts: ["final", "amended"]
+                    .iter()
+                    .map(|code| CodeConcept {
+                        system: "http://hl7.org/fhir/observation-status".to_string(),
+                        code: code.to_string(),
+                        display: None,
+                    })
+                    .collect(),
+            },
+        );
+
+        graph.add_resource(
+            "Patient".to_string(),
+            resource(
+                "Patient",
+                vec![
+                    property("active", primitive("boolean"), CardinalityRange::optional()),
+                    property("birthDate", primitive("date"), CardinalityRange::optional()),
+                    property(
+                        "name",
+                        PropertyType::Complex { type_name: "HumanName".to_string() },
+                        CardinalityRange::optional_array(),
+                    ),
+                ],
+            ),
+        );
+
+        let mut status = property("status", primitive("code"), CardinalityRange::required());
+        status.binding = Some(ValueSetBinding {
+            strength: BindingStrength::Required,
+            value_set: "http://hl7.org/fhir/ValueSet/observation-status|4.0.1".to_string(),
+            description: None,
+        });
+        let mut value = property(
+            "value[x]",
+            PropertyType::Choice { types: vec!["string".to_string(), "boolean".to_string()] },
+            CardinalityRange::optional(),
+        );
+        value.is_choice = true;
+        value.choice_types = vec!["string".to_string(), "boolean".to_string()];
+        graph.add_resource(
+            "Observation".to_string(),
+            resource(
+                "Observation",
+                vec![
+                    status,
+                    value,
+                    property(
+                        "subject",
+                        PropertyType::Reference {
+                            target_types: vec!["Patient".to_string()],
+                            target_profiles: vec![],
+                        },
+                        CardinalityRange::required(),
+                    ),
+                ],
+            ),
+        );
+
+        let constraint = |path: &str| PropertyConstraint {
+            path: path.to_string(),
+            cardinality: None,
+            type_constraints: vec![],
+            binding: None,
+            fixed_value: None,
+            must_support: true,
+        };
+        graph.add_profile(
+            "http://example.org/StructureDefinition/amended-observation".to_string(),
+            ProfileType {
+                name: "AmendedObservation".to_string(),
+                base: "Observation".to_string(),
+                property_constraints: vec![
+                    PropertyConstraint {
+                        fixed_value: Some(json!("amended")),
+                        ..constraint("Observation.status")
+                    },
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange::required()),
+                        type_constraints: vec!["boolean".to_string()],
+                        ..constraint("Observation.value[x]")
+                    },
+                ],
+                new_properties: vec![],
+                documentation: Default::default(),
+                url: "http://example.org/StructureDefinition/amended-observation".to_string(),
+            },
+        );
+        graph.add_profile(
+            "http://example.org/StructureDefinition/named-patient".to_string(),
+            ProfileType {
+                name: "NamedPatient".to_string(),
+                base: "Patient".to_string(),
+                property_constraints: vec![
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange::required_array()),
+                        ..constraint("Patient.name")
+                    },
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange::required()),
+                        ..constraint("Patient.name.family")
+                    },
+                ],
+                new_properties: vec![],
+                documentation: Default::default(),
+                url: "http://example.org/StructureDefinition/named-patient".to_string(),
+            },
+        );
+
+        graph
+    }
+
+    fn expressions(report: &ValidationReport) -> Vec<&str> {
+        report.errors().map(|issue| issue.expression.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_instance() {
+        let graph = graph();
+        let report = Validator::new(&graph).validate(&json!({
+            "resourceType": "Observation",
+            "id": "obs-1",
+            "status": "final",
+            "_status": { "extension": [] },
+            "valueBoolean": true,
+            "subject": { "reference": "Patient/p1" },
+        }));
+
+        assert!(report.is_valid(), "{:?}", report.issues);
+        let outcome = report.to_operation_outcome();
+        assert_eq!(outcome["resourceType"], "OperationOutcome");
+        assert_eq!(outcome["issue"][0]["severity"], "information");
+    }
+
+    #[test]
+    fn test_structure_cardinality_and_primitives() {
+        let graph = graph();
+        let report = Validator::new(&graph).validate(&json!({
+            "resourceType": "Patient",
+            "active": "yes",
+            "birthDate": "1970-13-01",
+            "name": { "family": "Doe" },
+            "nickname": "JD",
+        }));
+
+        assert_eq!(
+            expressions(&report),
+            vec!["Patient.active", "Patient.birthDate", "Patient.name", "Patient.nickname"]
+        );
+        assert_eq!(report.issues[0].code, IssueType::Structure);
+        assert_eq!(report.issues[1].code, IssueType::Value);
+
+        let report = Validator::new(&graph).validate(&json!({
+            "resourceType": "Patient",
+            "name": [{ "given": ["Jane", null] }],
+        }));
+        assert_eq!(expressions(&report), vec!["Patient.name[0].given[1]"]);
+    }
+
+    #[test]
+    fn test_choice_binding_and_reference() {
+        let graph = graph();
+        let report = Validator::new(&graph).validate(&json!({
+            "resourceType": "Observation",
+            "status": "draft",
+            "valueString": "high",
+            "valueBoolean": true,
+            "subject": { "reference": "Observation/o1" },
+        }));
+
+        let codes: Vec<IssueType> = report.issues.iter().map(|issue| issue.code).collect();
+        assert_eq!(codes, vec![IssueType::CodeInvalid, IssueType::Invariant, IssueType::Value]);
+        assert_eq!(
+            expressions(&report),
+            vec!["Observation.status", "Observation.value", "Observation.subject.reference"]
+        );
+
+        let report = Validator::new(&graph).validate(&json!({ "resourceType": "Observation" }));
+        assert_eq!(expressions(&report), vec!["Observation.status", "Observation.subject"]);
+        assert!(report.issues.iter().all(|issue| issue.code == IssueType::Required));
+    }
+
+    #[test]
+    fn test_profile_constraints() {
+        let graph = graph();
+        let validator = Validator::new(&graph);
+
+        let report = validator
+            .validate_profile(
+                &json!({
+                    "resourceType": "Observation",
+                    "status": "final",
+                    "valueString": "high",
+                    "subject": { "reference": "Patient/p1" },
+                }),
+                "http://example.org/StructureDefinition/amended-observation|1.0.0",
+            )
+            .unwrap();
+        assert_eq!(
+            expressions(&report),
+            vec!["Observation.status", "Observation.value", "Observation.valueString"]
+        );
+
+        let report = validator
+            .validate_profile(
+                &json!({ "resourceType": "Patient", "name": [{ "given": ["Jane"] }] }),
+                "NamedPatient",
+            )
+            .unwrap();
+        assert_eq!(expressions(&report), vec!["Patient.name[0].family"]);
+
+        let report = validator
+            .validate_profile(&json!({ "resourceType": "Patient" }), "AmendedObservation")
+            .unwrap();
+        assert!(!report.is_valid());
+        assert!(validator.validate_profile(&json!({}), "http://example.org/unknown").is_err());
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        let coding = json!({ "coding": [{ "system": "s", "code": "a" }, { "code": "b" }] });
+        assert!(matches_pattern(&coding, &json!({ "coding": [{ "code": "b" }] })));
+        assert!(!matches_pattern(&coding, &json!({ "coding": [{ "system": "x" }] })));
+        assert!(matches_pattern(&json!("x"), &json!("x")));
+    }
+}
@@ -1,7 +1,11 @@
 //! Code generation traits and utilities
 
+pub mod plugin;
+pub mod registry;
 pub mod traits;
 
+pub use plugin::{ExecutablePlugin, PluginRequest, PluginResponse};
+pub use registry::GeneratorRegistry;
 pub use traits::{
     CodeGenerator, FileType, GeneratedCode, GeneratedFile, GenerationManifest,
     GenerationStatistics, GeneratorCapabilities, GeneratorConfig, GeneratorMetadata,
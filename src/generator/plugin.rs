@@ -0,0 +1,472 @@
+//! Out-of-tree generator plugins
+//!
+//! Plugins let teams ship private generators without forking this crate. Two
+//! plugin kinds are supported, both speaking the same JSON protocol:
+//!
+//! - **Executables**: the plugin is spawned once per request, reads a single
+//!   [`PluginRequest`] from stdin and writes a single [`PluginResponse`] to stdout.
+//! - **Dynamic libraries** (feature `dylib-plugins`): the library exports
+//!   `octofhir_plugin_call`, which takes a NUL-terminated JSON request and returns
+//!   a NUL-terminated JSON response, and `octofhir_plugin_free`, which releases
+//!   the returned string.
+//!
+//! ```json
+//! {"protocol_version": 1, "command": "metadata"}
+//! {"protocol_version": 1, "command": "generate", "graph": {...}, "config": {...}}
+//! ```
+
+use crate::core::ir::TypeGraph;
+use crate::core::{Error, Result};
+use crate::generator::{
+    CodeGenerator, GeneratedCode, GeneratorConfig, GeneratorMetadata, Language,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// Version of the plugin JSON protocol
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Request sent to a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginRequest {
+    /// Protocol version spoken by the host
+    pub protocol_version: u32,
+
+    /// Command to execute
+    #[serde(flatten)]
+    pub command: PluginCommand,
+}
+
+impl PluginRequest {
+    /// Request generator metadata
+    pub fn metadata() -> Self {
+        Self { protocol_version: PLUGIN_PROTOCOL_VERSION, command: PluginCommand::Metadata }
+    }
+
+    /// Request code generation
+    pub fn generate(graph: &TypeGraph, config: &GeneratorConfig) -> Self {
+        Self {
+            protocol_version: PLUGIN_PROTOCOL_VERSION,
+            command: PluginCommand::Generate {
+                graph: Box::new(graph.clone()),
+                config: config.clone(),
+            },
+        }
+    }
+}
+
+/// Plugin command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum PluginCommand {
+    /// Return the generator's metadata
+    Metadata,
+
+    /// Generate code for a type graph
+    Generate {
+        /// Type graph to generate from
+        graph: Box<TypeGraph>,
+        /// Generator configuration
+        config: GeneratorConfig,
+    },
+}
+
+/// Response returned by a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PluginResponse {
+    /// Generator metadata
+    Metadata {
+        /// Metadata describing the generator
+        metadata: GeneratorMetadata,
+    },
+
+    /// Generated code
+    Generated {
+        /// Generated files and manifest
+        code: Box<GeneratedCode>,
+    },
+
+    /// The plugin failed to handle the request
+    Error {
+        /// Human-readable error message
+        message: String,
+    },
+}
+
+impl PluginResponse {
+    fn into_metadata(self, plugin: &Path) -> Result<GeneratorMetadata> {
+        match self {
+            PluginResponse::Metadata { metadata } => Ok(metadata),
+            other => Err(Self::unexpected(plugin, "metadata", other)),
+        }
+    }
+
+    fn into_code(self, plugin: &Path) -> Result<GeneratedCode> {
+        match self {
+            PluginResponse::Generated { code } => Ok(*code),
+            other => Err(Self::unexpected(plugin, "generated", other)),
+        }
+    }
+
+    fn unexpected(plugin: &Path, expected: &str, response: PluginResponse) -> Error {
+        match response {
+            PluginResponse::Error { message } => {
+                Error::Plugin(format!("{} failed: {}", plugin.display(), message))
+            }
+            _ => Error::Plugin(format!(
+                "{} returned an unexpected response (expected '{}')",
+                plugin.display(),
+                expected
+            )),
+        }
+    }
+}
+
+/// Whether a path looks like a dynamic library for the current platform
+pub fn is_dynamic_library(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+}
+
+/// Whether a path is a file the current user can execute
+///
+/// On Windows, where there is no executable bit, `.exe` files count as executable.
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata().is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(std::env::consts::EXE_EXTENSION))
+    }
+}
+
+/// Load a plugin from a path, choosing the loader by file type
+pub fn load_plugin(path: &Path) -> Result<Box<dyn CodeGenerator>> {
+    if is_dynamic_library(path) {
+        #[cfg(feature = "dylib-plugins")]
+        {
+            return Ok(Box::new(DylibPlugin::load(path)?));
+        }
+        #[cfg(not(feature = "dylib-plugins"))]
+        {
+            return Err(Error::Plugin(format!(
+                "{}: dynamic library plugins require the 'dylib-plugins' feature",
+                path.display()
+            )));
+        }
+    }
+
+    Ok(Box::new(ExecutablePlugin::load(path)?))
+}
+
+/// Generator backed by an external executable speaking the JSON protocol
+pub struct ExecutablePlugin {
+    path: PathBuf,
+    metadata: GeneratorMetadata,
+}
+
+impl ExecutablePlugin {
+    /// Load an executable plugin, querying its metadata
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let metadata =
+            Self::call_blocking(&path, &PluginRequest::metadata())?.into_metadata(&path)?;
+        Ok(Self { path, metadata })
+    }
+
+    /// Path to the plugin executable
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn call_blocking(path: &Path, request: &PluginRequest) -> Result<PluginResponse> {
+        use std::io::Write;
+
+        let input = serde_json::to_vec(request)?;
+        let mut child = std::process::Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::Plugin(format!("Failed to start {}: {}", path.display(), e)))?;
+
+        // Write the request while stdout and stderr are drained, so a plugin
+        // producing output before it has read all of its input cannot deadlock
+        let stdin = child.stdin.take();
+        let writer = std::thread::spawn(move || match stdin {
+            Some(mut stdin) => stdin.write_all(&input),
+            None => Ok(()),
+        });
+
+        let output = child.wait_with_output()?;
+        let written =
+            writer.join().unwrap_or_else(|_| Err(std::io::Error::other("stdin writer panicked")));
+        Self::finish(path, output, written)
+    }
+
+    async fn call(&self, request: &PluginRequest) -> Result<PluginResponse> {
+        use tokio::io::AsyncWriteExt;
+
+        let input = serde_json::to_vec(request)?;
+        let mut child = tokio::process::Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                Error::Plugin(format!("Failed to start {}: {}", self.path.display(), e))
+            })?;
+
+        // Write the request while stdout and stderr are drained; dropping stdin
+        // at the end closes the pipe so the plugin sees EOF
+        let stdin = child.stdin.take();
+        let writer = tokio::spawn(async move {
+            match stdin {
+                Some(mut stdin) => stdin.write_all(&input).await,
+                None => Ok(()),
+            }
+        });
+
+        let output = child.wait_with_output().await?;
+        let written = writer.await.unwrap_or_else(|e| Err(std::io::Error::other(e)));
+        Self::finish(&self.path, output, written)
+    }
+
+    /// Parse the response, then report a failure to send the request
+    ///
+    /// A plugin that exits without reading all of its input closes the pipe
+    /// early; its own response or error says more than the broken pipe does.
+    fn finish(
+        path: &Path,
+        output: std::process::Output,
+        written: std::io::Result<()>,
+    ) -> Result<PluginResponse> {
+        let response = Self::parse_output(path, output)?;
+        match written {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                Err(Error::Plugin(format!("Failed to send request to {}: {}", path.display(), e)))
+            }
+            _ => Ok(response),
+        }
+    }
+
+    fn parse_output(path: &Path, output: std::process::Output) -> Result<PluginResponse> {
+        if !output.status.success() {
+            return Err(Error::Plugin(format!(
+                "{} exited with {}: {}",
+                path.display(),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        serde_json::from_slice(&output.stdout)
+            .map_err(|e| Error::Plugin(format!("{} returned invalid JSON: {}", path.display(), e)))
+    }
+}
+
+#[async_trait]
+impl CodeGenerator for ExecutablePlugin {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        self.call(&PluginRequest::generate(graph, config)).await?.into_code(&self.path)
+    }
+
+    fn language(&self) -> Language {
+        self.metadata.language.clone()
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        self.metadata.clone()
+    }
+}
+
+/// Generator backed by a dynamic library exporting the plugin C ABI
+#[cfg(feature = "dylib-plugins")]
+pub struct DylibPlugin {
+    path: PathBuf,
+    library: std::sync::Arc<libloading::Library>,
+    metadata: GeneratorMetadata,
+}
+
+#[cfg(feature = "dylib-plugins")]
+impl DylibPlugin {
+    /// Symbol handling a JSON request: `fn(*const c_char) -> *mut c_char`
+    pub const CALL_SYMBOL: &'static [u8] = b"octofhir_plugin_call";
+
+    /// Symbol releasing a response string: `fn(*mut c_char)`
+    pub const FREE_SYMBOL: &'static [u8] = b"octofhir_plugin_free";
+
+    /// Load a dynamic library plugin, querying its metadata
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        // SAFETY: loading a library runs its initializers; plugins are trusted code
+        // explicitly configured by the user.
+        let library = unsafe { libloading::Library::new(&path) }
+            .map_err(|e| Error::Plugin(format!("Failed to load {}: {}", path.display(), e)))?;
+
+        let mut plugin = Self {
+            path,
+            library: std::sync::Arc::new(library),
+            metadata: GeneratorMetadata {
+                name: String::new(),
+                version: String::new(),
+                language: Language::TypeScript,
+                description: String::new(),
+                author: String::new(),
+                capabilities: Default::default(),
+            },
+        };
+        plugin.metadata = plugin.call(&PluginRequest::metadata())?.into_metadata(&plugin.path)?;
+
+        Ok(plugin)
+    }
+
+    /// Path to the plugin library
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn call(&self, request: &PluginRequest) -> Result<PluginResponse> {
+        Self::call_library(&self.library, &self.path, request)
+    }
+
+    /// Send a request through the library's call symbol, blocking until it returns
+    fn call_library(
+        library: &libloading::Library,
+        path: &Path,
+        request: &PluginRequest,
+    ) -> Result<PluginResponse> {
+        use std::ffi::{CStr, CString, c_char};
+
+        type CallFn = unsafe extern "C" fn(*const c_char) -> *mut c_char;
+        type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+        let symbol_error = |e: libloading::Error| {
+            Error::Plugin(format!("{}: missing plugin symbol: {}", path.display(), e))
+        };
+
+        let input = CString::new(serde_json::to_vec(request)?)
+            .map_err(|e| Error::Plugin(format!("Request contains NUL byte: {}", e)))?;
+
+        // SAFETY: the symbols follow the documented plugin ABI. The returned pointer is
+        // owned by the plugin and released through its own free function.
+        unsafe {
+            let call: libloading::Symbol<CallFn> =
+                library.get(Self::CALL_SYMBOL).map_err(symbol_error)?;
+            let free: libloading::Symbol<FreeFn> =
+                library.get(Self::FREE_SYMBOL).map_err(symbol_error)?;
+
+            let raw = call(input.as_ptr());
+            if raw.is_null() {
+                return Err(Error::Plugin(format!("{} returned a null response", path.display())));
+            }
+
+            let response = serde_json::from_slice(CStr::from_ptr(raw).to_bytes());
+            free(raw);
+
+            response.map_err(|e| {
+                Error::Plugin(format!("{} returned invalid JSON: {}", path.display(), e))
+            })
+        }
+    }
+}
+
+#[cfg(feature = "dylib-plugins")]
+#[async_trait]
+impl CodeGenerator for DylibPlugin {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        // Generation can take a while; keep it off the async worker threads
+        let library = std::sync::Arc::clone(&self.library);
+        let path = self.path.clone();
+        let request = PluginRequest::generate(graph, config);
+        let response =
+            tokio::task::spawn_blocking(move || Self::call_library(&library, &path, &request))
+                .await
+                .map_err(|e| Error::Plugin(format!("{} panicked: {}", self.path.display(), e)))??;
+
+        response.into_code(&self.path)
+    }
+
+    fn language(&self) -> Language {
+        self.metadata.language.clone()
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        self.metadata.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::FhirVersion;
+
+    #[test]
+    fn test_request_serialization() {
+        let request = PluginRequest::metadata();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["protocol_version"], 1);
+        assert_eq!(json["command"], "metadata");
+
+        let graph = TypeGraph::new(FhirVersion::R4);
+        let request = PluginRequest::generate(&graph, &GeneratorConfig::default());
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["command"], "generate");
+        assert_eq!(json["graph"]["fhir_version"], "R4");
+        assert!(json["config"]["output_dir"].is_string());
+    }
+
+    #[test]
+    fn test_error_response() {
+        let response: PluginResponse =
+            serde_json::from_str(r#"{"status": "error", "message": "boom"}"#).unwrap();
+        let err = response.into_code(Path::new("my-plugin")).unwrap_err();
+        assert!(err.to_string().contains("my-plugin failed: boom"));
+    }
+
+    #[test]
+    fn test_metadata_response_with_other_language() {
+        let metadata = GeneratorMetadata {
+            name: "csharp".to_string(),
+            version: "1.0.0".to_string(),
+            language: Language::Other("csharp".to_string()),
+            description: "C# SDK".to_string(),
+            author: "Example".to_string(),
+            capabilities: Default::default(),
+        };
+        let json = serde_json::to_value(PluginResponse::Metadata { metadata }).unwrap();
+        assert_eq!(json["metadata"]["language"], "csharp");
+
+        let response: PluginResponse = serde_json::from_value(json).unwrap();
+        let metadata = response.into_metadata(Path::new("octofhir-gen-csharp")).unwrap();
+        assert_eq!(metadata.language, Language::Other("csharp".to_string()));
+        assert_eq!(metadata.language.to_string(), "csharp");
+
+        let kotlin: Language = serde_json::from_str(r#""kotlin""#).unwrap();
+        assert_eq!(kotlin, Language::Kotlin);
+    }
+
+    #[test]
+    fn test_missing_executable() {
+        let result = ExecutablePlugin::load("/nonexistent/octofhir-plugin");
+        assert!(matches!(result, Err(Error::Plugin(_))));
+    }
+
+    #[test]
+    fn test_dynamic_library_detection() {
+        let lib = format!("libplugin.{}", std::env::consts::DLL_EXTENSION);
+        assert!(is_dynamic_library(Path::new(&lib)));
+        assert!(!is_dynamic_library(Path::new("octofhir-gen-csharp")));
+    }
+}
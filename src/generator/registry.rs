@@ -0,0 +1,358 @@
+//! Registry of available code generators
+
+use crate::core::{Error, Result};
+use crate::generator::plugin;
+use crate::generator::{CodeGenerator, GeneratorMetadata, Language};
+use crate::languages::json_schema::JsonSchemaGenerator;
+use crate::languages::kotlin::KotlinSdkGenerator;
+use crate::languages::markdown::MarkdownSiteGenerator;
+use crate::languages::openapi::OpenApiGenerator;
+use crate::languages::typescript::TypeScriptSdkGenerator;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Environment variable listing additional plugin paths
+pub const PLUGIN_PATH_ENV: &str = "OCTOFHIR_PLUGIN_PATH";
+
+/// Registry mapping generator names to [`CodeGenerator`] implementations
+///
+/// Generators are keyed by their metadata name (e.g., "typescript"). Lookups
+/// are case-insensitive and also accept language aliases such as "ts" or "kt".
+#[derive(Clone, Default)]
+pub struct GeneratorRegistry {
+    generators: IndexMap<String, Arc<dyn CodeGenerator>>,
+}
+
+impl GeneratorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with all built-in generators registered
+    pub fn with_builtin_generators() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(TypeScriptSdkGenerator::with_defaults()));
+        registry.register(Arc::new(KotlinSdkGenerator::with_defaults()));
+        registry.register(Arc::new(JsonSchemaGenerator::new()));
+        registry.register(Arc::new(OpenApiGenerator::new()));
+        registry.register(Arc::new(MarkdownSiteGenerator::new()));
+        registry
+    }
+
+    /// Register a generator, replacing any existing generator with the same name
+    pub fn register(&mut self, generator: Arc<dyn CodeGenerator>) {
+        let name = generator.metadata().name.to_lowercase();
+        self.generators.insert(name, generator);
+    }
+
+    /// Load an out-of-tree generator plugin and register it
+    ///
+    /// Paths with the platform's dynamic library extension are loaded as
+    /// libraries; anything else is treated as an executable speaking the JSON
+    /// plugin protocol. Returns the registered generator name.
+    ///
+    /// A plugin may not take a name or language alias already in use, so it
+    /// can never silently replace a built-in or previously loaded generator.
+    pub fn load_plugin(&mut self, path: &Path) -> Result<String> {
+        let generator: Arc<dyn CodeGenerator> = Arc::from(plugin::load_plugin(path)?);
+        let name = generator.metadata().name.to_lowercase();
+        if name.trim().is_empty() {
+            return Err(Error::Plugin(format!("{} reported an empty name", path.display())));
+        }
+        if let Some(existing) = self.get(&name) {
+            return Err(Error::Plugin(format!(
+                "{} reported the name '{}', which is already taken by the {} generator",
+                path.display(),
+                name,
+                existing.metadata().name
+            )));
+        }
+
+        self.register(generator);
+        Ok(name)
+    }
+
+    /// Load plugins from files or directories
+    ///
+    /// Directories are scanned non-recursively. Loading continues past failures;
+    /// the names of loaded generators and the errors encountered are returned.
+    pub fn load_plugins<P: AsRef<Path>>(&mut self, paths: &[P]) -> (Vec<String>, Vec<Error>) {
+        let mut loaded = Vec::new();
+        let mut errors = Vec::new();
+
+        for path in paths {
+            match plugin_candidates(path.as_ref()) {
+                Ok(candidates) => {
+                    for candidate in candidates {
+                        match self.load_plugin(&candidate) {
+                            Ok(name) => loaded.push(name),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        (loaded, errors)
+    }
+
+    /// Load plugins listed in the `OCTOFHIR_PLUGIN_PATH` environment variable
+    pub fn load_plugins_from_env(&mut self) -> (Vec<String>, Vec<Error>) {
+        let paths: Vec<PathBuf> = std::env::var_os(PLUGIN_PATH_ENV)
+            .map(|value| std::env::split_paths(&value).collect())
+            .unwrap_or_default();
+        self.load_plugins(&paths)
+    }
+
+    /// Look up a generator by name or language alias
+    pub fn get(&self, name: &str) -> Option<Arc<dyn CodeGenerator>> {
+        let key = name.to_lowercase();
+        if let Some(generator) = self.generators.get(&key) {
+            return Some(Arc::clone(generator));
+        }
+
+        let language = key.parse::<Language>().ok()?;
+        self.get_by_language(language)
+    }
+
+    /// Look up the first generator registered for a language
+    pub fn get_by_language(&self, language: Language) -> Option<Arc<dyn CodeGenerator>> {
+        self.generators.values().find(|g| g.language() == language).cloned()
+    }
+
+    /// Check whether a generator is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Names of all registered generators, in registration order
+    pub fn names(&self) -> Vec<String> {
+        self.generators.keys().cloned().collect()
+    }
+
+    /// Metadata for all registered generators, in registration order
+    pub fn list(&self) -> Vec<GeneratorMetadata> {
+        self.generators.values().map(|g| g.metadata()).collect()
+    }
+
+    /// Number of registered generators
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+}
+
+/// Expand a plugin path into the files to load
+///
+/// Files named explicitly are always loaded. In a directory, only executables
+/// and, with the `dylib-plugins` feature, dynamic libraries are picked up, so
+/// READMEs and manifests next to the plugins are skipped.
+fn plugin_candidates(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut candidates = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        let hidden =
+            entry_path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+        let loadable = if plugin::is_dynamic_library(&entry_path) {
+            cfg!(feature = "dylib-plugins") && entry_path.is_file()
+        } else {
+            plugin::is_executable(&entry_path)
+        };
+        if loadable && !hidden {
+            candidates.push(entry_path);
+        }
+    }
+    candidates.sort();
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_generators() {
+        let registry = GeneratorRegistry::with_builtin_generators();
+
+        assert_eq!(
+            registry.names(),
+            vec!["typescript", "kotlin", "json-schema", "openapi", "markdown"]
+        );
+        assert!(registry.contains("TypeScript"));
+        assert!(registry.contains("kotlin"));
+    }
+
+    #[test]
+    fn test_lookup_by_alias() {
+        let registry = GeneratorRegistry::with_builtin_generators();
+
+        assert_eq!(registry.get("ts").unwrap().language(), Language::TypeScript);
+        assert_eq!(registry.get("kt").unwrap().language(), Language::Kotlin);
+        assert_eq!(registry.get("JSON Schema").unwrap().language(), Language::JsonSchema);
+        assert_eq!(registry.get("oas").unwrap().language(), Language::OpenApi);
+        assert_eq!(registry.get("docs").unwrap().language(), Language::Markdown);
+        assert!(registry.get("rust").is_none());
+        assert!(registry.get("unknown").is_none());
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let registry = GeneratorRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn test_load_missing_plugin() {
+        let mut registry = GeneratorRegistry::new();
+        let (loaded, errors) = registry.load_plugins(&["/nonexistent/octofhir-gen-foo"]);
+
+        assert!(loaded.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(registry.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_load_executable_plugin() {
+        use crate::core::ir::{FhirVersion, TypeGraph};
+        use crate::generator::plugin::PluginResponse;
+        use crate::generator::{
+            FileType, GeneratedCode, GeneratedFile, GenerationManifest, GenerationStatistics,
+            GeneratorCapabilities, GeneratorConfig,
+        };
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixtures = tempfile::tempdir().unwrap();
+        let metadata = GeneratorMetadata {
+            name: "Echo".to_string(),
+            version: "0.1.0".to_string(),
+            language: Language::Go,
+            description: "Echo plugin".to_string(),
+            author: "Tests".to_string(),
+            capabilities: GeneratorCapabilities::default(),
+        };
+        let files = vec![GeneratedFile {
+            path: PathBuf::from("echo.go"),
+            content: "package echo\n".to_string(),
+            file_type: FileType::Other,
+        }];
+        let manifest = GenerationManifest {
+            generated_at: "2024-01-01T00:00:00Z".to_string(),
+            generator: metadata.clone(),
+            config: GeneratorConfig::default(),
+            statistics: GenerationStatistics::from_files(&files),
+            warnings: vec![],
+        };
+        let code = GeneratedCode::new(files, manifest);
+        for (file, response) in [
+            ("metadata.json", PluginResponse::Metadata { metadata }),
+            ("generated.json", PluginResponse::Generated { code: Box::new(code) }),
+        ] {
+            std::fs::write(fixtures.path().join(file), serde_json::to_string(&response).unwrap())
+                .unwrap();
+        }
+
+        let plugins = tempfile::tempdir().unwrap();
+        let script = plugins.path().join("octofhir-gen-echo");
+        // Logging more than a pipe buffer before reading stdin must not deadlock
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\nhead -c 262144 /dev/zero >&2\ninput=$(cat)\ncase \"$input\" in\n  *'\"command\":\"metadata\"'*) cat {0}/metadata.json ;;\n  *) cat {0}/generated.json ;;\nesac\n",
+                fixtures.path().display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut registry = GeneratorRegistry::with_builtin_generators();
+        let (loaded, errors) = registry.load_plugins(&[plugins.path()]);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(loaded, vec!["echo"]);
+
+        let generator = registry.get("echo").unwrap();
+        assert_eq!(generator.language(), Language::Go);
+        assert_eq!(registry.get("go").unwrap().metadata().name, "Echo");
+
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.metadata.source_packages.push("x".repeat(262144));
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        assert_eq!(code.files.len(), 1);
+        assert_eq!(code.files[0].content, "package echo\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_candidates_skip_non_executables() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let plugins = tempfile::tempdir().unwrap();
+        for file in ["README.md", "octofhir-gen-echo.json", "octofhir-gen-echo", ".hidden"] {
+            std::fs::write(plugins.path().join(file), "").unwrap();
+        }
+        for file in ["octofhir-gen-echo", ".hidden"] {
+            std::fs::set_permissions(
+                plugins.path().join(file),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+        }
+        let library = format!("libplugin.{}", std::env::consts::DLL_EXTENSION);
+        std::fs::write(plugins.path().join(&library), "").unwrap();
+
+        let candidates = plugin_candidates(plugins.path()).unwrap();
+        let mut expected = vec![plugins.path().join("octofhir-gen-echo")];
+        if cfg!(feature = "dylib-plugins") {
+            expected.insert(0, plugins.path().join(&library));
+        }
+        assert_eq!(candidates, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_cannot_shadow_builtin() {
+        use crate::generator::GeneratorCapabilities;
+        use crate::generator::plugin::PluginResponse;
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixtures = tempfile::tempdir().unwrap();
+        let response = PluginResponse::Metadata {
+            metadata: GeneratorMetadata {
+                name: "TypeScript".to_string(),
+                version: "0.1.0".to_string(),
+                language: Language::TypeScript,
+                description: "Impostor".to_string(),
+                author: "Tests".to_string(),
+                capabilities: GeneratorCapabilities::default(),
+            },
+        };
+        let metadata = fixtures.path().join("metadata.json");
+        std::fs::write(&metadata, serde_json::to_string(&response).unwrap()).unwrap();
+        let script = fixtures.path().join("octofhir-gen-typescript");
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\ncat > /dev/null\ncat {}\n", metadata.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut registry = GeneratorRegistry::with_builtin_generators();
+        let error = registry.load_plugin(&script).unwrap_err();
+
+        assert!(error.to_string().contains("already taken"), "{}", error);
+        assert_ne!(registry.get("typescript").unwrap().metadata().description, "Impostor");
+    }
+}
@@ -52,6 +52,19 @@ pub trait LanguageBackend: Send + Sync {
     /// - Rust: `PropertyType::Primitive("string")` → `"String"`
     fn map_type(&self, property_type: &crate::core::ir::PropertyType) -> String;
 
+    /// Map a property to its target language type, honoring its terminology binding
+    ///
+    /// Defaults to [`Self::map_type`]; backends that generate value set types
+    /// override this to use them for bound coded elements.
+    fn map_property_type(&self, property: &crate::core::ir::Property) -> String {
+        self.map_type(&property.property_type)
+    }
+
+    /// Name of the generated value set type used by a property, if any
+    fn value_set_type(&self, _property: &crate::core::ir::Property) -> Option<String> {
+        None
+    }
+
     /// Generate import/using statements for dependencies
     ///
     /// # Example
@@ -132,7 +145,10 @@ impl TemplateContext {
 }
 
 /// Supported target languages
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+///
+/// Plugins generating for a language without a built-in generator report it as
+/// [`Language::Other`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
     /// TypeScript
@@ -145,6 +161,18 @@ pub enum Language {
     Java,
     /// Go
     Go,
+    /// Kotlin
+    Kotlin,
+    /// JSON Schema (draft 2020-12)
+    #[serde(rename = "json-schema")]
+    JsonSchema,
+    /// OpenAPI 3.1
+    OpenApi,
+    /// Markdown documentation site
+    Markdown,
+    /// Any other target, named by the generator (e.g. "csharp")
+    #[serde(untagged)]
+    Other(String),
 }
 
 impl std::fmt::Display for Language {
@@ -155,6 +183,11 @@ impl std::fmt::Display for Language {
             Language::Python => write!(f, "Python"),
             Language::Java => write!(f, "Java"),
             Language::Go => write!(f, "Go"),
+            Language::Kotlin => write!(f, "Kotlin"),
+            Language::JsonSchema => write!(f, "JSON Schema"),
+            Language::OpenApi => write!(f, "OpenAPI"),
+            Language::Markdown => write!(f, "Markdown"),
+            Language::Other(name) => write!(f, "{}", name),
         }
     }
 }
@@ -169,6 +202,12 @@ impl std::str::FromStr for Language {
             "python" | "py" => Ok(Language::Python),
             "java" => Ok(Language::Java),
             "go" | "golang" => Ok(Language::Go),
+            "kotlin" | "kt" => Ok(Language::Kotlin),
+            "json-schema" | "json_schema" | "jsonschema" | "json schema" => {
+                Ok(Language::JsonSchema)
+            }
+            "openapi" | "open-api" | "oas" => Ok(Language::OpenApi),
+            "markdown" | "md" | "docs" => Ok(Language::Markdown),
             _ => Err(Error::Generator(format!("Unknown language: {}", s))),
         }
     }
@@ -337,6 +376,8 @@ pub enum FileType {
     Primitive,
     /// Profile definition
     Profile,
+    /// Code types from a ValueSet expansion
+    ValueSet,
     /// Helper/utility code
     Helper,
     /// Validation code
@@ -397,6 +438,28 @@ pub struct GenerationStatistics {
     pub generation_time_ms: u64,
 }
 
+impl GenerationStatistics {
+    /// Compute statistics from a set of generated files
+    ///
+    /// Type counts are derived from each file's [`FileType`], which suits
+    /// generators writing one file per type. Generators that bundle several
+    /// types into a file overwrite the counts from the [`TypeGraph`](crate::core::ir::TypeGraph).
+    /// The generation time is left at zero for the caller to fill in.
+    pub fn from_files(files: &[GeneratedFile]) -> Self {
+        let count = |file_type: FileType| files.iter().filter(|f| f.file_type == file_type).count();
+
+        Self {
+            resources: count(FileType::Resource),
+            datatypes: count(FileType::DataType),
+            primitives: count(FileType::Primitive),
+            profiles: count(FileType::Profile),
+            total_files: files.len(),
+            total_lines: files.iter().map(|f| f.content.lines().count()).sum(),
+            generation_time_ms: 0,
+        }
+    }
+}
+
 /// Identifier context (for formatting)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IdentifierContext {
@@ -421,6 +484,10 @@ mod tests {
         assert_eq!(Language::TypeScript.to_string(), "TypeScript");
         assert_eq!(Language::Rust.to_string(), "Rust");
         assert_eq!(Language::Python.to_string(), "Python");
+        assert_eq!(Language::Kotlin.to_string(), "Kotlin");
+        assert_eq!(Language::JsonSchema.to_string(), "JSON Schema");
+        assert_eq!(Language::OpenApi.to_string(), "OpenAPI");
+        assert_eq!(Language::Markdown.to_string(), "Markdown");
     }
 
     #[test]
@@ -429,9 +496,33 @@ mod tests {
         assert_eq!("ts".parse::<Language>().unwrap(), Language::TypeScript);
         assert_eq!("rust".parse::<Language>().unwrap(), Language::Rust);
         assert_eq!("rs".parse::<Language>().unwrap(), Language::Rust);
+        assert_eq!("kotlin".parse::<Language>().unwrap(), Language::Kotlin);
+        assert_eq!("kt".parse::<Language>().unwrap(), Language::Kotlin);
+        assert_eq!("json-schema".parse::<Language>().unwrap(), Language::JsonSchema);
+        assert_eq!(
+            Language::JsonSchema.to_string().parse::<Language>().unwrap(),
+            Language::JsonSchema
+        );
+        assert_eq!("OpenAPI".parse::<Language>().unwrap(), Language::OpenApi);
+        assert_eq!("docs".parse::<Language>().unwrap(), Language::Markdown);
         assert!("unknown".parse::<Language>().is_err());
     }
 
+    #[test]
+    fn test_statistics_from_files() {
+        let files = vec![
+            GeneratedFile::new(PathBuf::from("a.ts"), "a\nb".to_string(), FileType::Resource),
+            GeneratedFile::new(PathBuf::from("b.ts"), "c".to_string(), FileType::DataType),
+            GeneratedFile::new(PathBuf::from("c.json"), "{}".to_string(), FileType::Manifest),
+        ];
+
+        let stats = GenerationStatistics::from_files(&files);
+        assert_eq!(stats.resources, 1);
+        assert_eq!(stats.datatypes, 1);
+        assert_eq!(stats.total_files, 3);
+        assert_eq!(stats.total_lines, 4);
+    }
+
     #[test]
     fn test_generator_config_default() {
         let config = GeneratorConfig::default();
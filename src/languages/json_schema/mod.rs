@@ -0,0 +1,6 @@
+//! JSON Schema (draft 2020-12) generation
+
+/// Conversion of the type graph into a JSON Schema bundle
+pub mod schema_generator;
+
+pub use schema_generator::{JSON_SCHEMA_DIALECT, JsonSchemaGenerator};
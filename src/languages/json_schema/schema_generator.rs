@@ -0,0 +1,530 @@
+use crate::core::ir::{
+    BindingStrength, DataType, PrimitiveType, Property, PropertyType, ResourceType, TypeGraph,
+};
+use crate::core::{Error, Result};
+use crate::generator::{
+    CodeGenerator, FileType, GeneratedCode, GeneratedFile, GenerationManifest,
+    GenerationStatistics, GeneratorCapabilities, GeneratorConfig, GeneratorMetadata, Language,
+};
+use async_trait::async_trait;
+use heck::ToUpperCamelCase;
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Dialect URI written to `$schema`
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// `$defs` entry holding the polymorphic union of all concrete resources
+const RESOURCE_DEF: &str = "Resource";
+
+/// Generator that converts a [`TypeGraph`] into a single JSON Schema bundle
+///
+/// Every primitive, datatype and resource gets an entry in `$defs`. Abstract
+/// resources (and `Resource` itself) become a `oneOf` over their concrete
+/// descendants with an OpenAPI-style `discriminator` on `resourceType`, and
+/// the bundle root validates any resource through `#/$defs/Resource`.
+#[derive(Debug, Clone, Default)]
+pub struct JsonSchemaGenerator {
+    schema_id: Option<String>,
+}
+
+impl JsonSchemaGenerator {
+    /// Create a generator that emits a bundle without an `$id`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `$id` of the generated bundle
+    pub fn with_schema_id(mut self, schema_id: impl Into<String>) -> Self {
+        self.schema_id = Some(schema_id.into());
+        self
+    }
+
+    /// Name of the bundle file for a module name
+    pub fn file_name(module_name: Option<&str>) -> String {
+        format!("{}.schema.json", module_name.unwrap_or("fhir"))
+    }
+
+    /// Build the JSON Schema bundle for a type graph
+    pub fn generate_bundle(&self, graph: &TypeGraph) -> Value {
+        let mut defs = Map::new();
+
+        for (name, primitive) in &graph.primitives {
+            defs.insert(name.clone(), Self::primitive_schema(primitive));
+        }
+        for (name, datatype) in &graph.datatypes {
+            defs.insert(name.clone(), Self::datatype_schema(graph, datatype));
+        }
+        for (name, resource) in &graph.resources {
+            defs.insert(name.clone(), Self::resource_schema(graph, resource));
+        }
+
+        let has_resources = graph.resources.values().any(|r| !r.is_abstract);
+        if has_resources && !graph.resources.contains_key(RESOURCE_DEF) {
+            defs.insert(
+                RESOURCE_DEF.to_string(),
+                Self::polymorphic_schema(graph, RESOURCE_DEF, "Any FHIR resource"),
+            );
+        }
+
+        let mut bundle = Map::new();
+        bundle.insert("$schema".to_string(), json!(JSON_SCHEMA_DIALECT));
+        if let Some(ref id) = self.schema_id {
+            bundle.insert("$id".to_string(), json!(id));
+        }
+        bundle.insert("title".to_string(), json!(format!("FHIR {}", graph.fhir_version)));
+        if has_resources {
+            bundle.insert("$ref".to_string(), Self::def_ref(RESOURCE_DEF)["$ref"].clone());
+        }
+        bundle.insert("$defs".to_string(), Value::Object(defs));
+
+        Value::Object(bundle)
+    }
+
+    /// Schema for a primitive type, anchoring its FHIR regex when it has one
+    fn primitive_schema(primitive: &PrimitiveType) -> Value {
+        let mut schema = Self::json_primitive(&primitive.name);
+        if !primitive.documentation.short.is_empty() {
+            schema["description"] = json!(primitive.documentation.short);
+        }
+        // `pattern` only constrains strings; booleans and numbers keep their JSON type
+        if let Some(ref pattern) = primitive.pattern
+            && schema["type"] == "string"
+        {
+            schema["pattern"] = json!(format!("^(?:{})$", pattern));
+        }
+        schema
+    }
+
+    /// Schema for a datatype
+    fn datatype_schema(graph: &TypeGraph, datatype: &DataType) -> Value {
+        Self::object_schema(graph, &datatype.properties, &datatype.documentation.short, None)
+    }
+
+    /// Schema for a resource
+    ///
+    /// Abstract resources with concrete descendants validate as any of them.
+    fn resource_schema(graph: &TypeGraph, resource: &ResourceType) -> Value {
+        let description = &resource.documentation.short;
+        if resource.is_abstract && !Self::descendants(graph, &resource.name).is_empty() {
+            return Self::polymorphic_schema(graph, &resource.name, description);
+        }
+
+        let resource_type = (!resource.is_abstract).then_some(resource.name.as_str());
+        Self::object_schema(graph, &resource.properties, description, resource_type)
+    }
+
+    /// `oneOf` over the concrete descendants of an abstract resource
+    fn polymorphic_schema(graph: &TypeGraph, name: &str, description: &str) -> Value {
+        let descendants = Self::descendants(graph, name);
+        let mut mapping = Map::new();
+        for descendant in &descendants {
+            mapping.insert(descendant.to_string(), Self::def_ref(descendant)["$ref"].clone());
+        }
+
+        let mut schema = json!({
+            "oneOf": descendants.iter().map(|d| Self::def_ref(d)).collect::<Vec<_>>(),
+            "discriminator": { "propertyName": "resourceType", "mapping": mapping },
+        });
+        if !description.is_empty() {
+            schema["description"] = json!(description);
+        }
+        schema
+    }
+
+    /// Concrete resources deriving (directly or indirectly) from `ancestor`
+    ///
+    /// Every concrete resource is a descendant of `Resource`, even when the
+    /// graph does not contain the full base chain.
+    fn descendants<'a>(graph: &'a TypeGraph, ancestor: &str) -> Vec<&'a str> {
+        graph
+            .resources
+            .values()
+            .filter(|resource| !resource.is_abstract)
+            .filter(|resource| {
+                ancestor == RESOURCE_DEF || Self::derives_from(graph, resource, ancestor)
+            })
+            .map(|resource| resource.name.as_str())
+            .collect()
+    }
+
+    /// Walk the base chain of a resource looking for `ancestor`
+    fn derives_from(graph: &TypeGraph, resource: &ResourceType, ancestor: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut base = resource.base.as_deref();
+        while let Some(name) = base {
+            if name == ancestor {
+                return true;
+            }
+            if !seen.insert(name) {
+                return false;
+            }
+            base = graph.resources.get(name).and_then(|r| r.base.as_deref());
+        }
+        false
+    }
+
+    /// Object schema for a set of properties
+    ///
+    /// Choice elements expand to one property per type (`valueString`,
+    /// `valueQuantity`, ...) plus an `allOf` entry allowing at most one of them,
+    /// or exactly one when the element is required.
+    fn object_schema(
+        graph: &TypeGraph,
+        properties: &[Property],
+        description: &str,
+        resource_type: Option<&str>,
+    ) -> Value {
+        let mut schema_properties = Map::new();
+        let mut required = Vec::new();
+        let mut all_of = Vec::new();
+
+        if let Some(resource_type) = resource_type {
+            schema_properties.insert("resourceType".to_string(), json!({ "const": resource_type }));
+            required.push(json!("resourceType"));
+        }
+
+        for property in properties {
+            if let PropertyType::Choice { types } = &property.property_type {
+                let names: Vec<String> = types
+                    .iter()
+                    .map(|t| format!("{}{}", property.name, t.to_upper_camel_case()))
+                    .collect();
+                for (choice_type, name) in types.iter().zip(&names) {
+                    let schema = Self::choice_type_schema(graph, choice_type);
+                    schema_properties
+                        .insert(name.clone(), Self::with_cardinality(property, schema));
+                }
+
+                let variants: Vec<Value> =
+                    names.iter().map(|n| json!({ "required": [n] })).collect();
+                if property.cardinality.is_required() {
+                    all_of.push(json!({ "oneOf": variants }));
+                } else if variants.len() > 1 {
+                    all_of.push(json!({ "not": { "anyOf": Self::pairs(&variants) } }));
+                }
+                continue;
+            }
+
+            let schema = Self::type_schema(graph, &property.property_type);
+            let schema = Self::with_binding(graph, property, schema);
+            schema_properties
+                .insert(property.name.clone(), Self::with_cardinality(property, schema));
+            if property.cardinality.is_required() {
+                required.push(json!(property.name));
+            }
+        }
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("object"));
+        if !description.is_empty() {
+            schema.insert("description".to_string(), json!(description));
+        }
+        schema.insert("properties".to_string(), Value::Object(schema_properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        if !all_of.is_empty() {
+            schema.insert("allOf".to_string(), Value::Array(all_of));
+        }
+        Value::Object(schema)
+    }
+
+    /// Every pair of `required` variants, combined with `allOf`
+    fn pairs(variants: &[Value]) -> Vec<Value> {
+        let mut pairs = Vec::new();
+        for (i, first) in variants.iter().enumerate() {
+            for second in &variants[i + 1..] {
+                pairs.push(json!({ "allOf": [first, second] }));
+            }
+        }
+        pairs
+    }
+
+    /// Schema for a single value of a property type
+    fn type_schema(graph: &TypeGraph, property_type: &PropertyType) -> Value {
+        match property_type {
+            PropertyType::Primitive { type_name } => Self::primitive_ref(graph, type_name),
+            PropertyType::Complex { type_name } => Self::complex_ref(graph, type_name),
+            PropertyType::Reference { .. } => Self::complex_ref(graph, "Reference"),
+            PropertyType::BackboneElement { properties } => {
+                Self::object_schema(graph, properties, "", None)
+            }
+            PropertyType::Choice { types } => json!({
+                "oneOf": types
+                    .iter()
+                    .map(|t| Self::choice_type_schema(graph, t))
+                    .collect::<Vec<_>>()
+            }),
+        }
+    }
+
+    /// Schema for one type of a choice element
+    ///
+    /// FHIR primitive type names start with a lowercase letter.
+    fn choice_type_schema(graph: &TypeGraph, type_name: &str) -> Value {
+        if graph.primitives.contains_key(type_name)
+            || type_name.starts_with(|c: char| c.is_ascii_lowercase())
+        {
+            Self::primitive_ref(graph, type_name)
+        } else {
+            Self::complex_ref(graph, type_name)
+        }
+    }
+
+    /// Reference a primitive definition, or inline its JSON type if the graph lacks it
+    fn primitive_ref(graph: &TypeGraph, type_name: &str) -> Value {
+        if graph.primitives.contains_key(type_name) {
+            Self::def_ref(type_name)
+        } else {
+            Self::json_primitive(type_name)
+        }
+    }
+
+    /// Reference a datatype or resource definition, or accept any object if unknown
+    fn complex_ref(graph: &TypeGraph, type_name: &str) -> Value {
+        let known = graph.datatypes.contains_key(type_name)
+            || graph.resources.contains_key(type_name)
+            || (type_name == RESOURCE_DEF && !graph.resources.is_empty());
+        if known { Self::def_ref(type_name) } else { json!({ "type": "object" }) }
+    }
+
+    /// Restrict a coded property to the codes of its required value set
+    fn with_binding(graph: &TypeGraph, property: &Property, mut schema: Value) -> Value {
+        let Some(binding) = &property.binding else {
+            return schema;
+        };
+        if binding.strength != BindingStrength::Required {
+            return schema;
+        }
+        let Some(value_set) = graph.value_set(&binding.value_set) else {
+            return schema;
+        };
+        if value_set.concepts.is_empty() {
+            return schema;
+        }
+
+        // Codes can appear in several systems; the enum only needs each once
+        let mut seen = HashSet::new();
+        let codes: Vec<Value> = value_set
+            .concepts
+            .iter()
+            .filter(|concept| seen.insert(concept.code.as_str()))
+            .map(|concept| json!(concept.code))
+            .collect();
+        schema["enum"] = Value::Array(codes);
+        schema
+    }
+
+    /// Wrap a value schema in an array when the property repeats, and document it
+    fn with_cardinality(property: &Property, schema: Value) -> Value {
+        let cardinality = &property.cardinality;
+        let mut schema = if cardinality.is_array() {
+            let mut array = json!({ "type": "array", "items": schema });
+            if cardinality.min > 0 {
+                array["minItems"] = json!(cardinality.min);
+            }
+            if let Some(max) = cardinality.max {
+                array["maxItems"] = json!(max);
+            }
+            array
+        } else {
+            schema
+        };
+
+        if !property.short_description.is_empty() {
+            schema["description"] = json!(property.short_description);
+        }
+        schema
+    }
+
+    /// JSON representation of a FHIR primitive
+    fn json_primitive(type_name: &str) -> Value {
+        match type_name {
+            "boolean" => json!({ "type": "boolean" }),
+            "integer" => json!({ "type": "integer" }),
+            "positiveInt" => json!({ "type": "integer", "minimum": 1 }),
+            "unsignedInt" => json!({ "type": "integer", "minimum": 0 }),
+            "decimal" => json!({ "type": "number" }),
+            _ => json!({ "type": "string" }),
+        }
+    }
+
+    /// `$ref` to a `$defs` entry
+    fn def_ref(name: &str) -> Value {
+        json!({ "$ref": format!("#/$defs/{}", name) })
+    }
+}
+
+#[async_trait]
+impl CodeGenerator for JsonSchemaGenerator {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        let started = std::time::Instant::now();
+
+        let mut generator = self.clone();
+        if let Some(schema_id) = config.get_custom::<String>("schema_id") {
+            generator.schema_id = Some(schema_id);
+        }
+
+        let bundle = generator.generate_bundle(graph);
+        let content = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| Error::Generator(format!("Failed to serialize JSON Schema: {}", e)))?;
+        let files = vec![GeneratedFile::new(
+            PathBuf::from(Self::file_name(config.module_name.as_deref())),
+            content + "\n",
+            FileType::Index,
+        )];
+
+        let mut statistics = GenerationStatistics::from_files(&files);
+        statistics.resources = graph.resources.len();
+        statistics.datatypes = graph.datatypes.len();
+        statistics.primitives = graph.primitives.len();
+        statistics.generation_time_ms = started.elapsed().as_millis() as u64;
+
+        let manifest = GenerationManifest {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            generator: self.metadata(),
+            config: config.clone(),
+            statistics,
+            warnings: vec![],
+        };
+
+        Ok(GeneratedCode::new(files, manifest))
+    }
+
+    fn language(&self) -> Language {
+        Language::JsonSchema
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        GeneratorMetadata {
+            name: "json-schema".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            language: Language::JsonSchema,
+            description: "JSON Schema (draft 2020-12) bundle with a $defs entry per type"
+                .to_string(),
+            author: "OctoFHIR Team".to_string(),
+            capabilities: GeneratorCapabilities { validation: true, ..Default::default() },
+        }
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Result<()> {
+        if let Some(schema_id) = config.get_custom::<String>("schema_id")
+            && !schema_id.contains(':')
+        {
+            return Err(Error::Config(format!(
+                "Invalid JSON Schema id: '{}'. Must be an absolute URI",
+                schema_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion};
+    use std::path::Path;
+
+    fn resource(name: &str, base: Option<&str>, is_abstract: bool) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: base.map(String::from),
+            properties: vec![],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+            is_abstract,
+        }
+    }
+
+    fn create_test_graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource("DomainResource".to_string(), resource("DomainResource", None, true));
+        graph.add_resource(
+            "Patient".to_string(),
+            resource("Patient", Some("DomainResource"), false),
+        );
+        graph.add_resource("Bundle".to_string(), resource("Bundle", Some("Resource"), false));
+        graph
+    }
+
+    #[test]
+    fn test_abstract_resources_are_polymorphic() {
+        let bundle = JsonSchemaGenerator::new().generate_bundle(&create_test_graph());
+
+        assert_eq!(bundle["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(bundle["$ref"], "#/$defs/Resource");
+        assert_eq!(
+            bundle["$defs"]["Resource"]["oneOf"],
+            json!([{ "$ref": "#/$defs/Patient" }, { "$ref": "#/$defs/Bundle" }])
+        );
+        assert_eq!(
+            bundle["$defs"]["DomainResource"]["discriminator"],
+            json!({
+                "propertyName": "resourceType",
+                "mapping": { "Patient": "#/$defs/Patient" }
+            })
+        );
+        assert_eq!(bundle["$defs"]["Patient"]["properties"]["resourceType"]["const"], "Patient");
+    }
+
+    #[test]
+    fn test_primitive_pattern_is_anchored() {
+        let primitive = PrimitiveType {
+            name: "id".to_string(),
+            base: None,
+            pattern: Some("[A-Za-z0-9\\-\\.]{1,64}".to_string()),
+            documentation: Documentation::default(),
+            url: String::new(),
+        };
+        let boolean = PrimitiveType {
+            name: "boolean".to_string(),
+            pattern: Some("true|false".to_string()),
+            ..primitive.clone()
+        };
+
+        let schema = JsonSchemaGenerator::primitive_schema(&primitive);
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], "^(?:[A-Za-z0-9\\-\\.]{1,64})$");
+
+        let schema = JsonSchemaGenerator::primitive_schema(&boolean);
+        assert_eq!(schema, json!({ "type": "boolean" }));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_uses_custom_options() {
+        let generator = JsonSchemaGenerator::new();
+        let config =
+            GeneratorConfig { module_name: Some("fhir-r4".to_string()), ..Default::default() };
+        let mut with_id = config.clone();
+        with_id.set_custom("schema_id".to_string(), "https://example.com/r4.json").unwrap();
+
+        generator.validate_config(&with_id).unwrap();
+        let code = generator.generate(&create_test_graph(), &with_id).await.unwrap();
+
+        assert_eq!(code.files.len(), 1);
+        let file = &code.files[0];
+        assert_eq!(file.path, Path::new("fhir-r4.schema.json"));
+        let bundle: Value = serde_json::from_str(&file.content).unwrap();
+        assert_eq!(bundle["$id"], "https://example.com/r4.json");
+        assert_eq!(code.manifest.statistics.resources, 3);
+        assert_eq!(code.manifest.generator.language, Language::JsonSchema);
+
+        let code = generator.generate(&create_test_graph(), &config).await.unwrap();
+        assert!(!code.files[0].content.contains("\"$id\""));
+    }
+
+    #[test]
+    fn test_validate_config_rejects_relative_schema_id() {
+        let generator = JsonSchemaGenerator::new();
+        let mut config = GeneratorConfig::default();
+        config.set_custom("schema_id".to_string(), "r4.schema.json").unwrap();
+
+        assert!(generator.validate_config(&config).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Kotlin language backend
+
+use crate::core::ir::{Documentation, PropertyType};
+use crate::generator::{IdentifierContext, LanguageBackend};
+use heck::{ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase};
+
+/// Kotlin hard keywords that must be escaped when used as identifiers
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Kotlin language backend
+#[derive(Clone)]
+pub struct KotlinBackend {
+    /// Base package for generated sources (e.g., "org.octofhir.fhir.r4")
+    package_name: String,
+}
+
+impl KotlinBackend {
+    /// Create new Kotlin backend for the given base package
+    pub fn new(package_name: impl Into<String>) -> Self {
+        Self { package_name: package_name.into() }
+    }
+
+    /// Base package for generated sources
+    pub fn package_name(&self) -> &str {
+        &self.package_name
+    }
+
+    /// Package holding resource classes
+    pub fn resources_package(&self) -> String {
+        format!("{}.resources", self.package_name)
+    }
+
+    /// Package holding datatype classes
+    pub fn datatypes_package(&self) -> String {
+        format!("{}.datatypes", self.package_name)
+    }
+
+    /// Source directory for a package inside `src/commonMain/kotlin`
+    pub fn source_dir(package: &str) -> String {
+        format!("src/commonMain/kotlin/{}", package.replace('.', "/"))
+    }
+
+    /// Escape an identifier that collides with a Kotlin hard keyword
+    pub fn escape_identifier(name: &str) -> String {
+        if KOTLIN_KEYWORDS.contains(&name) { format!("`{}`", name) } else { name.to_string() }
+    }
+
+    /// Map FHIR primitive to Kotlin type
+    pub fn map_primitive_type(fhir_type: &str) -> String {
+        match fhir_type {
+            "boolean" => "Boolean",
+            "integer" | "positiveInt" | "unsignedInt" => "Int",
+            "integer64" => "Long",
+            "decimal" => "Double",
+            _ => "String",
+        }
+        .to_string()
+    }
+}
+
+impl Default for KotlinBackend {
+    fn default() -> Self {
+        Self::new("org.octofhir.fhir.r4")
+    }
+}
+
+impl LanguageBackend for KotlinBackend {
+    fn map_type(&self, property_type: &PropertyType) -> String {
+        match property_type {
+            PropertyType::Primitive { type_name } => Self::map_primitive_type(type_name),
+            PropertyType::Complex { type_name } => type_name.to_pascal_case(),
+            PropertyType::Reference { .. } => "Reference".to_string(),
+            PropertyType::BackboneElement { .. } => "BackboneElement".to_string(),
+            // Choice elements get a sealed class named after their owner from the class
+            // generator; a standalone mapping can only fall back to the common supertype.
+            PropertyType::Choice { .. } => "Any".to_string(),
+        }
+    }
+
+    fn generate_imports(&self, dependencies: &[String]) -> Vec<String> {
+        dependencies.iter().map(|dep| format!("import {}", dep)).collect()
+    }
+
+    fn format_identifier(&self, name: &str, context: IdentifierContext) -> String {
+        match context {
+            IdentifierContext::TypeName => name.to_pascal_case(),
+            IdentifierContext::FieldName
+            | IdentifierContext::FunctionName
+            | IdentifierContext::VariableName => {
+                Self::escape_identifier(&name.to_lower_camel_case())
+            }
+            IdentifierContext::ConstantName => name.to_shouty_snake_case(),
+        }
+    }
+
+    fn generate_doc_comment(&self, doc: &Documentation) -> Vec<String> {
+        let mut lines = vec!["/**".to_string()];
+
+        if !doc.short.is_empty() {
+            lines.push(format!(" * {}", doc.short));
+        }
+
+        if !doc.definition.is_empty() && doc.definition != doc.short {
+            lines.push(" *".to_string());
+            lines.push(format!(" * {}", doc.definition));
+        }
+
+        if let Some(url) = &doc.url {
+            lines.push(" *".to_string());
+            lines.push(format!(" * @see <a href=\"{}\">{}</a>", url, url));
+        }
+
+        lines.push(" */".to_string());
+
+        lines
+    }
+
+    fn file_extension(&self) -> &str {
+        "kt"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_primitive_types() {
+        assert_eq!(KotlinBackend::map_primitive_type("boolean"), "Boolean");
+        assert_eq!(KotlinBackend::map_primitive_type("positiveInt"), "Int");
+        assert_eq!(KotlinBackend::map_primitive_type("integer64"), "Long");
+        assert_eq!(KotlinBackend::map_primitive_type("decimal"), "Double");
+        assert_eq!(KotlinBackend::map_primitive_type("dateTime"), "String");
+    }
+
+    #[test]
+    fn test_escape_keywords() {
+        assert_eq!(KotlinBackend::escape_identifier("class"), "`class`");
+        assert_eq!(KotlinBackend::escape_identifier("when"), "`when`");
+        assert_eq!(KotlinBackend::escape_identifier("status"), "status");
+    }
+
+    #[test]
+    fn test_packages() {
+        let backend = KotlinBackend::new("com.example.fhir");
+        assert_eq!(backend.resources_package(), "com.example.fhir.resources");
+        assert_eq!(backend.datatypes_package(), "com.example.fhir.datatypes");
+        assert_eq!(
+            KotlinBackend::source_dir("com.example.fhir"),
+            "src/commonMain/kotlin/com/example/fhir"
+        );
+    }
+
+    #[test]
+    fn test_format_identifier() {
+        let backend = KotlinBackend::default();
+        assert_eq!(
+            backend.format_identifier("human_name", IdentifierContext::TypeName),
+            "HumanName"
+        );
+        assert_eq!(
+            backend.format_identifier("birth_date", IdentifierContext::FieldName),
+            "birthDate"
+        );
+        assert_eq!(
+            backend.format_identifier("max_size", IdentifierContext::ConstantName),
+            "MAX_SIZE"
+        );
+    }
+}
@@ -0,0 +1,736 @@
+//! Kotlin data class generation for FHIR resources and datatypes
+
+use crate::core::Result;
+use crate::core::ir::{DataType, Documentation, Property, PropertyType, ResourceType, TypeGraph};
+use crate::generator::LanguageBackend;
+use crate::languages::kotlin::backend::KotlinBackend;
+use crate::templates::genco_engine::GencoTemplateEngine;
+use genco::prelude::*;
+use heck::ToPascalCase;
+use std::collections::HashSet;
+
+/// Language-neutral view over a resource or datatype definition
+struct TypeView<'a> {
+    name: &'a str,
+    properties: &'a [Property],
+    documentation: &'a Documentation,
+    is_abstract: bool,
+}
+
+impl<'a> From<&'a ResourceType> for TypeView<'a> {
+    fn from(resource: &'a ResourceType) -> Self {
+        Self {
+            name: &resource.name,
+            properties: &resource.properties,
+            documentation: &resource.documentation,
+            is_abstract: resource.is_abstract,
+        }
+    }
+}
+
+impl<'a> From<&'a DataType> for TypeView<'a> {
+    fn from(datatype: &'a DataType) -> Self {
+        Self {
+            name: &datatype.name,
+            properties: &datatype.properties,
+            documentation: &datatype.documentation,
+            is_abstract: datatype.is_abstract,
+        }
+    }
+}
+
+/// Generator for Kotlin `@Serializable` classes
+///
+/// Abstract resources (Resource, DomainResource) become interfaces so that the
+/// polymorphic `Resource` serializer can dispatch on `resourceType`. Concrete
+/// resources and all datatypes become data classes carrying the flattened set
+/// of inherited properties. Choice elements (`value[x]`) are modelled as a
+/// property holding a sealed class with one subclass per allowed type; the
+/// owning class is serialized through `ChoiceSerializer`, which maps the sealed
+/// value to and from the `valueQuantity`-style JSON keys.
+pub struct KotlinClassGenerator {
+    /// Language backend for type mapping
+    backend: KotlinBackend,
+}
+
+impl KotlinClassGenerator {
+    /// Create a new class generator
+    pub fn new(backend: KotlinBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Generate the Kotlin source file for a resource
+    pub fn generate_resource_file(
+        &self,
+        resource: &ResourceType,
+        graph: &TypeGraph,
+    ) -> Result<String> {
+        let ancestors = Self::resource_ancestors(resource, graph);
+        let view = TypeView::from(resource);
+
+        let mut tokens = kotlin::Tokens::new();
+        let imports: &[&str] = if view.is_abstract {
+            &[]
+        } else {
+            &["kotlinx.serialization.SerialName", "kotlinx.serialization.Serializable"]
+        };
+        let has_choices = !view.is_abstract && Self::has_choices(&view, &ancestors);
+        self.append_file_header(
+            &mut tokens,
+            &self.backend.resources_package(),
+            &self.backend.datatypes_package(),
+            imports,
+            has_choices,
+        );
+
+        if view.is_abstract {
+            self.append_interface(&mut tokens, &view, &ancestors);
+        } else {
+            self.append_data_class(&mut tokens, &view, &ancestors, true);
+        }
+
+        GencoTemplateEngine::format_kotlin(&tokens)
+    }
+
+    /// Generate the Kotlin source file for a complex datatype
+    ///
+    /// Datatypes are always emitted as concrete data classes, including abstract
+    /// ones such as `BackboneElement`, since they are used directly as field types.
+    pub fn generate_datatype_file(&self, datatype: &DataType, graph: &TypeGraph) -> Result<String> {
+        let ancestors: Vec<TypeView<'_>> =
+            Self::datatype_ancestors(datatype, graph).into_iter().map(TypeView::from).collect();
+        let view = TypeView::from(datatype);
+
+        let mut tokens = kotlin::Tokens::new();
+        let has_choices = Self::has_choices(&view, &ancestors);
+        let imports: &[&str] = if has_choices {
+            &["kotlinx.serialization.SerialName", "kotlinx.serialization.Serializable"]
+        } else {
+            &["kotlinx.serialization.Serializable"]
+        };
+        self.append_file_header(
+            &mut tokens,
+            &self.backend.datatypes_package(),
+            &self.backend.resources_package(),
+            imports,
+            has_choices,
+        );
+        self.append_data_class(&mut tokens, &view, &ancestors, false);
+
+        GencoTemplateEngine::format_kotlin(&tokens)
+    }
+
+    /// Collect resource ancestors, root first
+    fn resource_ancestors<'g>(resource: &ResourceType, graph: &'g TypeGraph) -> Vec<TypeView<'g>> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = resource.base.as_deref();
+
+        while let Some(base) = current {
+            if !seen.insert(base.to_string()) {
+                break;
+            }
+            match graph.resources.get(base) {
+                Some(parent) => {
+                    chain.push(TypeView::from(parent));
+                    current = parent.base.as_deref();
+                }
+                None => break,
+            }
+        }
+
+        chain.reverse();
+        chain
+    }
+
+    /// Collect datatype ancestors, root first
+    fn datatype_ancestors<'g>(datatype: &DataType, graph: &'g TypeGraph) -> Vec<&'g DataType> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = datatype.base.as_deref();
+
+        while let Some(base) = current {
+            if !seen.insert(base.to_string()) {
+                break;
+            }
+            match graph.datatypes.get(base) {
+                Some(parent) => {
+                    chain.push(parent);
+                    current = parent.base.as_deref();
+                }
+                None => break,
+            }
+        }
+
+        chain.reverse();
+        chain
+    }
+
+    /// Whether the flattened properties of a type include a choice element
+    fn has_choices(view: &TypeView<'_>, ancestors: &[TypeView<'_>]) -> bool {
+        Self::flatten_properties(view, ancestors).iter().any(|p| p.is_choice)
+    }
+
+    /// Append the file banner, package declaration and imports
+    ///
+    /// Files with choice elements also opt in to the experimental serialization
+    /// APIs their sealed classes and `ChoiceSerializer` rely on.
+    fn append_file_header(
+        &self,
+        tokens: &mut kotlin::Tokens,
+        package: &str,
+        sibling_package: &str,
+        imports: &[&str],
+        has_choices: bool,
+    ) {
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+        if has_choices {
+            tokens.append("@file:OptIn(ExperimentalSerializationApi::class)");
+            tokens.line();
+        }
+        tokens.append(format!("package {}", package));
+        tokens.line();
+
+        let choice_imports = [
+            "kotlinx.serialization.ExperimentalSerializationApi".to_string(),
+            "kotlinx.serialization.KeepGeneratedSerializer".to_string(),
+            "kotlinx.serialization.json.JsonClassDiscriminator".to_string(),
+            format!("{}.ChoiceSerializer", self.backend.package_name()),
+        ];
+        let mut all_imports: Vec<String> = imports.iter().map(|i| i.to_string()).collect();
+        if has_choices {
+            all_imports.extend(choice_imports);
+        }
+        all_imports.sort();
+
+        for import in all_imports {
+            tokens.append(format!("import {}", import));
+            tokens.push();
+        }
+        tokens.append(format!("import {}.*", sibling_package));
+        tokens.line();
+    }
+
+    /// Append an interface for an abstract resource
+    fn append_interface(
+        &self,
+        tokens: &mut kotlin::Tokens,
+        view: &TypeView<'_>,
+        ancestors: &[TypeView<'_>],
+    ) {
+        let inherited: HashSet<&str> =
+            ancestors.iter().flat_map(|a| a.properties.iter()).map(|p| p.name.as_str()).collect();
+
+        self.append_doc(tokens, view.documentation);
+
+        let type_name = Self::type_name(view.name);
+        tokens.append(format!("interface {}", type_name));
+        if let Some(parent) = ancestors.iter().rev().find(|a| a.is_abstract) {
+            tokens.append(format!(" : {}", Self::type_name(parent.name)));
+        }
+        tokens.append(" {");
+        tokens.push();
+
+        if ancestors.is_empty() {
+            tokens.append(
+                "    /** The type of the resource, used as the polymorphic discriminator */",
+            );
+            tokens.push();
+            tokens.append("    val resourceType: String");
+            tokens.push();
+        }
+
+        let mut declared = HashSet::new();
+        for property in view.properties {
+            if property.is_choice
+                || inherited.contains(property.name.as_str())
+                || !declared.insert(property.name.as_str())
+            {
+                continue;
+            }
+            tokens.append(format!(
+                "    val {}: {}",
+                KotlinBackend::escape_identifier(&property.name),
+                self.property_type(property)
+            ));
+            tokens.push();
+        }
+
+        tokens.append("}");
+        tokens.push();
+    }
+
+    /// Append a data class (plus choice sealed classes and serializer) for a concrete type
+    fn append_data_class(
+        &self,
+        tokens: &mut kotlin::Tokens,
+        view: &TypeView<'_>,
+        ancestors: &[TypeView<'_>],
+        is_resource: bool,
+    ) {
+        let type_name = Self::type_name(view.name);
+
+        // Properties declared by interfaces this class implements must be overridden
+        let interface_props: HashSet<&str> = ancestors
+            .iter()
+            .filter(|a| is_resource && a.is_abstract)
+            .flat_map(|a| a.properties.iter())
+            .filter(|p| !p.is_choice)
+            .map(|p| p.name.as_str())
+            .collect();
+
+        let properties = Self::flatten_properties(view, ancestors);
+        let choices: Vec<&Property> = properties.iter().copied().filter(|p| p.is_choice).collect();
+
+        self.append_doc(tokens, view.documentation);
+        if choices.is_empty() {
+            tokens.append("@Serializable");
+        } else {
+            tokens.append(format!("@Serializable(with = {}Serializer::class)", type_name));
+            tokens.push();
+            tokens.append("@KeepGeneratedSerializer");
+        }
+        tokens.push();
+        if is_resource {
+            tokens.append(format!("@SerialName(\"{}\")", view.name));
+            tokens.push();
+        }
+
+        if properties.is_empty() {
+            tokens.append(format!("class {}", type_name));
+        } else {
+            tokens.append(format!("data class {}(", type_name));
+            tokens.push();
+            for property in &properties {
+                self.append_constructor_params(tokens, &type_name, property, &interface_props);
+            }
+            tokens.append(")");
+        }
+
+        let parent = ancestors.iter().rev().find(|a| is_resource && a.is_abstract);
+        if let Some(parent) = parent {
+            tokens.append(format!(" : {}", Self::type_name(parent.name)));
+        }
+
+        if is_resource && parent.is_some() {
+            tokens.append(" {");
+            tokens.push();
+            tokens
+                .append(format!("    override val resourceType: String get() = \"{}\"", view.name));
+            tokens.push();
+            tokens.append("}");
+        }
+        tokens.push();
+
+        if !choices.is_empty() {
+            for choice in &choices {
+                tokens.line();
+                self.append_choice_sealed_class(tokens, &type_name, choice);
+            }
+            tokens.line();
+            self.append_choice_serializer(tokens, &type_name, &choices);
+        }
+    }
+
+    /// Flatten ancestor and own properties, root first, dropping duplicates
+    fn flatten_properties<'v>(
+        view: &TypeView<'v>,
+        ancestors: &[TypeView<'v>],
+    ) -> Vec<&'v Property> {
+        let mut seen = HashSet::new();
+        ancestors
+            .iter()
+            .flat_map(|a| a.properties.iter())
+            .chain(view.properties.iter())
+            .filter(|p| seen.insert(p.name.as_str()))
+            .collect()
+    }
+
+    /// Append constructor parameter(s) for a property
+    fn append_constructor_params(
+        &self,
+        tokens: &mut kotlin::Tokens,
+        owner: &str,
+        property: &Property,
+        interface_props: &HashSet<&str>,
+    ) {
+        self.append_param_doc(tokens, property);
+
+        if property.is_choice {
+            tokens.append(format!(
+                "    val {}: {}? = null,",
+                KotlinBackend::escape_identifier(&property.name),
+                Self::choice_class_name(owner, &property.name)
+            ));
+            tokens.push();
+            return;
+        }
+
+        let modifier =
+            if interface_props.contains(property.name.as_str()) { "override val" } else { "val" };
+        let default = if property.cardinality.is_required() { "" } else { " = null" };
+        tokens.append(format!(
+            "    {} {}: {}{},",
+            modifier,
+            KotlinBackend::escape_identifier(&property.name),
+            self.property_type(property),
+            default
+        ));
+        tokens.push();
+    }
+
+    /// Append the sealed class modelling a choice element
+    ///
+    /// Each subclass serializes as `{"type": "<Type>", "value": ...}`, the shape
+    /// `ChoiceSerializer` maps to the `<name><Type>` JSON key.
+    fn append_choice_sealed_class(
+        &self,
+        tokens: &mut kotlin::Tokens,
+        owner: &str,
+        property: &Property,
+    ) {
+        let class_name = Self::choice_class_name(owner, &property.name);
+
+        tokens.append(format!("/** Allowed types for `{}.{}[x]` */", owner, property.name));
+        tokens.push();
+        tokens.append("@Serializable");
+        tokens.push();
+        tokens.append("@JsonClassDiscriminator(\"type\")");
+        tokens.push();
+        tokens.append(format!("sealed class {} {{", class_name));
+        tokens.push();
+        for (i, (suffix, field_type)) in Self::choice_types(property).into_iter().enumerate() {
+            if i > 0 {
+                tokens.line();
+            }
+            tokens.append("    @Serializable");
+            tokens.push();
+            tokens.append(format!("    @SerialName(\"{}\")", suffix));
+            tokens.push();
+            tokens.append(format!(
+                "    data class {}{}(val value: {}) : {}()",
+                Self::capitalize(&property.name),
+                suffix,
+                field_type,
+                class_name
+            ));
+            tokens.push();
+        }
+        tokens.append("}");
+        tokens.push();
+    }
+
+    /// Append the serializer mapping the choice elements of a class to their JSON keys
+    fn append_choice_serializer(
+        &self,
+        tokens: &mut kotlin::Tokens,
+        owner: &str,
+        choices: &[&Property],
+    ) {
+        tokens.append(format!("/** Serializes `{}` with its choice elements flattened */", owner));
+        tokens.push();
+        tokens.append(format!("object {}Serializer : ChoiceSerializer<{}>(", owner, owner));
+        tokens.push();
+        tokens.append(format!("    {}.generatedSerializer(),", owner));
+        tokens.push();
+        tokens.append("    mapOf(");
+        tokens.push();
+        for choice in choices {
+            let suffixes: Vec<String> = Self::choice_types(choice)
+                .into_iter()
+                .map(|(suffix, _)| format!("\"{}\"", suffix))
+                .collect();
+            tokens.append(format!(
+                "        \"{}\" to listOf({}),",
+                choice.name,
+                suffixes.join(", ")
+            ));
+            tokens.push();
+        }
+        tokens.append("    ),");
+        tokens.push();
+        tokens.append(")");
+        tokens.push();
+    }
+
+    /// Append a KDoc line for a constructor parameter
+    fn append_param_doc(&self, tokens: &mut kotlin::Tokens, property: &Property) {
+        if !property.short_description.is_empty() {
+            tokens.append(format!("    /** {} */", Self::escape_doc(&property.short_description)));
+            tokens.push();
+        }
+    }
+
+    /// Append a KDoc block for a type
+    fn append_doc(&self, tokens: &mut kotlin::Tokens, doc: &Documentation) {
+        let escaped = Documentation {
+            short: Self::escape_doc(&doc.short),
+            definition: Self::escape_doc(&doc.definition),
+            ..doc.clone()
+        };
+        if escaped.short.is_empty() && escaped.definition.is_empty() && escaped.url.is_none() {
+            return;
+        }
+
+        for line in self.backend.generate_doc_comment(&escaped) {
+            tokens.append(line);
+            tokens.push();
+        }
+    }
+
+    /// Kotlin type for a non-choice property, including nullability and arrays
+    fn property_type(&self, property: &Property) -> String {
+        let base = self.backend.map_type(&property.property_type);
+        let base = if property.cardinality.is_array() { format!("List<{}>", base) } else { base };
+        if property.cardinality.is_required() { base } else { format!("{}?", base) }
+    }
+
+    /// Expand a choice property into `(typeSuffix, kotlinType)` pairs
+    fn choice_types(property: &Property) -> Vec<(String, String)> {
+        let types = match &property.property_type {
+            PropertyType::Choice { types } => types.as_slice(),
+            _ => property.choice_types.as_slice(),
+        };
+
+        types
+            .iter()
+            .map(|fhir_type| (Self::capitalize(fhir_type), Self::choice_type(fhir_type)))
+            .collect()
+    }
+
+    /// Map a single choice type code to a Kotlin type
+    ///
+    /// FHIR primitive type codes start with a lowercase letter; complex types do not.
+    fn choice_type(fhir_type: &str) -> String {
+        if fhir_type.starts_with(|c: char| c.is_ascii_lowercase()) {
+            KotlinBackend::map_primitive_type(fhir_type)
+        } else {
+            Self::type_name(fhir_type)
+        }
+    }
+
+    /// Name of the sealed class for a choice element (e.g., `ObservationValue`)
+    fn choice_class_name(owner: &str, property_name: &str) -> String {
+        format!("{}{}", owner, Self::capitalize(property_name))
+    }
+
+    /// Kotlin type name for a FHIR type name
+    fn type_name(name: &str) -> String {
+        if name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Self::capitalize(name)
+        } else {
+            name.to_pascal_case()
+        }
+    }
+
+    /// Uppercase the first character, keeping the rest intact
+    fn capitalize(name: &str) -> String {
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// Prevent documentation text from terminating the KDoc block
+    fn escape_doc(text: &str) -> String {
+        text.replace("*/", "*&#47;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{CardinalityRange, FhirVersion};
+
+    fn property(
+        name: &str,
+        property_type: PropertyType,
+        cardinality: CardinalityRange,
+    ) -> Property {
+        let is_choice = matches!(property_type, PropertyType::Choice { .. });
+        Property {
+            name: name.to_string(),
+            path: name.to_string(),
+            property_type,
+            cardinality,
+            is_choice,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        }
+    }
+
+    fn resource(
+        name: &str,
+        base: Option<&str>,
+        is_abstract: bool,
+        props: Vec<Property>,
+    ) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: base.map(String::from),
+            constraints: vec![],
+            properties: props,
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+            is_abstract,
+        }
+    }
+
+    fn test_graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource(
+            "Resource".to_string(),
+            resource(
+                "Resource",
+                None,
+                true,
+                vec![property(
+                    "id",
+                    PropertyType::Primitive { type_name: "id".to_string() },
+                    CardinalityRange::optional(),
+                )],
+            ),
+        );
+        graph.add_resource(
+            "Observation".to_string(),
+            resource(
+                "Observation",
+                Some("Resource"),
+                false,
+                vec![
+                    property(
+                        "status",
+                        PropertyType::Primitive { type_name: "code".to_string() },
+                        CardinalityRange::required(),
+                    ),
+                    property(
+                        "value",
+                        PropertyType::Choice {
+                            types: vec!["Quantity".to_string(), "string".to_string()],
+                        },
+                        CardinalityRange::optional(),
+                    ),
+                    property(
+                        "note",
+                        PropertyType::Complex { type_name: "Annotation".to_string() },
+                        CardinalityRange::optional_array(),
+                    ),
+                ],
+            ),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_abstract_resource_is_interface() {
+        let graph = test_graph();
+        let generator = KotlinClassGenerator::new(KotlinBackend::default());
+        let output =
+            generator.generate_resource_file(&graph.resources["Resource"], &graph).unwrap();
+
+        assert!(output.contains("package org.octofhir.fhir.r4.resources"));
+        assert!(output.contains("interface Resource {"));
+        assert!(output.contains("val resourceType: String"));
+        assert!(output.contains("val id: String?"));
+    }
+
+    #[test]
+    fn test_concrete_resource_data_class() {
+        let graph = test_graph();
+        let generator = KotlinClassGenerator::new(KotlinBackend::default());
+        let output =
+            generator.generate_resource_file(&graph.resources["Observation"], &graph).unwrap();
+
+        assert!(output.contains("@Serializable"));
+        assert!(output.contains("@SerialName(\"Observation\")"));
+        assert!(output.contains("data class Observation("));
+        assert!(output.contains("override val id: String? = null,"));
+        assert!(output.contains("val status: String,"));
+        assert!(output.contains("val note: List<Annotation>? = null,"));
+        assert!(output.contains(") : Resource {"));
+        assert!(output.contains("override val resourceType: String get() = \"Observation\""));
+    }
+
+    #[test]
+    fn test_choice_expansion() {
+        let graph = test_graph();
+        let generator = KotlinClassGenerator::new(KotlinBackend::default());
+        let output =
+            generator.generate_resource_file(&graph.resources["Observation"], &graph).unwrap();
+
+        assert!(output.contains("val value: ObservationValue? = null,"));
+        assert!(!output.contains("valueQuantity"));
+        assert!(output.contains("@Serializable(with = ObservationSerializer::class)"));
+        assert!(output.contains("sealed class ObservationValue {"));
+        assert!(output.contains("@SerialName(\"Quantity\")"));
+        assert!(
+            output.contains("data class ValueQuantity(val value: Quantity) : ObservationValue()")
+        );
+        assert!(output.contains("\"value\" to listOf(\"Quantity\", \"String\"),"));
+        assert!(output.contains("import org.octofhir.fhir.r4.ChoiceSerializer"));
+    }
+
+    #[test]
+    fn test_datatype_data_class() {
+        let graph = TypeGraph::new(FhirVersion::R4);
+        let datatype = DataType {
+            name: "Period".to_string(),
+            base: Some("Element".to_string()),
+            constraints: vec![],
+            properties: vec![property(
+                "start",
+                PropertyType::Primitive { type_name: "dateTime".to_string() },
+                CardinalityRange::optional(),
+            )],
+            documentation: Documentation {
+                short: "Time range defined by start and end date/time".to_string(),
+                ..Default::default()
+            },
+            url: "http://hl7.org/fhir/StructureDefinition/Period".to_string(),
+            is_abstract: false,
+        };
+
+        let generator = KotlinClassGenerator::new(KotlinBackend::default());
+        let output = generator.generate_datatype_file(&datatype, &graph).unwrap();
+
+        assert!(output.contains("package org.octofhir.fhir.r4.datatypes"));
+        assert!(output.contains("data class Period("));
+        assert!(output.contains("val start: String? = null,"));
+        assert!(!output.contains("@SerialName"));
+        assert!(output.contains("Time range defined by start and end date/time"));
+    }
+
+    #[test]
+    fn test_keyword_field_is_escaped() {
+        let graph = TypeGraph::new(FhirVersion::R4);
+        let datatype = DataType {
+            name: "Coding".to_string(),
+            base: None,
+            constraints: vec![],
+            properties: vec![property(
+                "class",
+                PropertyType::Primitive { type_name: "code".to_string() },
+                CardinalityRange::optional(),
+            )],
+            documentation: Documentation::default(),
+            url: String::new(),
+            is_abstract: false,
+        };
+
+        let generator = KotlinClassGenerator::new(KotlinBackend::default());
+        let output = generator.generate_datatype_file(&datatype, &graph).unwrap();
+
+        assert!(output.contains("val `class`: String? = null,"));
+    }
+}
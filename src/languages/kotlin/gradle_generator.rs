@@ -0,0 +1,205 @@
+//! Gradle build file generation for Kotlin Multiplatform
+
+use crate::core::Result;
+
+/// Kotlin Multiplatform targets supported by the generated build
+pub const SUPPORTED_TARGETS: &[&str] = &["jvm", "android", "ios", "js"];
+
+/// Configuration for the generated Gradle project
+#[derive(Debug, Clone)]
+pub struct GradleConfig {
+    /// Gradle project name (e.g., "fhir-r4")
+    pub project_name: String,
+    /// Maven group id
+    pub group_id: String,
+    /// Artifact version
+    pub version: String,
+    /// Base Kotlin package (used as the Android namespace)
+    pub package_name: String,
+    /// Kotlin compiler/plugin version
+    pub kotlin_version: String,
+    /// kotlinx.serialization runtime version
+    pub serialization_version: String,
+    /// Multiplatform targets to configure
+    pub targets: Vec<String>,
+}
+
+impl Default for GradleConfig {
+    fn default() -> Self {
+        Self {
+            project_name: "fhir-r4".to_string(),
+            group_id: "org.octofhir".to_string(),
+            version: "0.1.0".to_string(),
+            package_name: "org.octofhir.fhir.r4".to_string(),
+            kotlin_version: "2.0.21".to_string(),
+            serialization_version: "1.7.3".to_string(),
+            targets: vec!["jvm".to_string(), "android".to_string(), "ios".to_string()],
+        }
+    }
+}
+
+/// Generator for Gradle build scripts
+pub struct GradleGenerator {
+    /// Build configuration
+    config: GradleConfig,
+}
+
+impl GradleGenerator {
+    /// Create a new Gradle generator
+    pub fn new(config: GradleConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether a target is enabled
+    fn has_target(&self, target: &str) -> bool {
+        self.config.targets.iter().any(|t| t == target)
+    }
+
+    /// Generate `build.gradle.kts`
+    pub fn generate_build_gradle(&self) -> Result<String> {
+        let cfg = &self.config;
+        let mut lines = vec!["plugins {".to_string()];
+        lines.push(format!("    kotlin(\"multiplatform\") version \"{}\"", cfg.kotlin_version));
+        lines.push(format!(
+            "    kotlin(\"plugin.serialization\") version \"{}\"",
+            cfg.kotlin_version
+        ));
+        if self.has_target("android") {
+            lines.push("    id(\"com.android.library\") version \"8.5.2\"".to_string());
+        }
+        lines.push("}".to_string());
+        lines.push(String::new());
+
+        lines.push(format!("group = \"{}\"", cfg.group_id));
+        lines.push(format!("version = \"{}\"", cfg.version));
+        lines.push(String::new());
+
+        lines.push("repositories {".to_string());
+        lines.push("    google()".to_string());
+        lines.push("    mavenCentral()".to_string());
+        lines.push("}".to_string());
+        lines.push(String::new());
+
+        lines.push("kotlin {".to_string());
+        if self.has_target("jvm") {
+            lines.push("    jvm()".to_string());
+        }
+        if self.has_target("android") {
+            lines.push("    androidTarget {".to_string());
+            lines.push("        publishLibraryVariants(\"release\")".to_string());
+            lines.push("    }".to_string());
+        }
+        if self.has_target("ios") {
+            lines.push("    iosX64()".to_string());
+            lines.push("    iosArm64()".to_string());
+            lines.push("    iosSimulatorArm64()".to_string());
+        }
+        if self.has_target("js") {
+            lines.push("    js(IR) {".to_string());
+            lines.push("        browser()".to_string());
+            lines.push("        nodejs()".to_string());
+            lines.push("    }".to_string());
+        }
+        lines.push(String::new());
+        lines.push("    sourceSets {".to_string());
+        lines.push("        commonMain.dependencies {".to_string());
+        lines.push(format!(
+            "            implementation(\"org.jetbrains.kotlinx:kotlinx-serialization-json:{}\")",
+            cfg.serialization_version
+        ));
+        lines.push("        }".to_string());
+        lines.push("        commonTest.dependencies {".to_string());
+        lines.push("            implementation(kotlin(\"test\"))".to_string());
+        lines.push("        }".to_string());
+        lines.push("    }".to_string());
+        lines.push("}".to_string());
+
+        if self.has_target("android") {
+            lines.push(String::new());
+            lines.push("android {".to_string());
+            lines.push(format!("    namespace = \"{}\"", cfg.package_name));
+            lines.push("    compileSdk = 34".to_string());
+            lines.push("    defaultConfig {".to_string());
+            lines.push("        minSdk = 24".to_string());
+            lines.push("    }".to_string());
+            lines.push("}".to_string());
+        }
+
+        lines.push(String::new());
+        Ok(lines.join("\n"))
+    }
+
+    /// Generate `settings.gradle.kts`
+    pub fn generate_settings_gradle(&self) -> Result<String> {
+        let lines = [
+            "pluginManagement {".to_string(),
+            "    repositories {".to_string(),
+            "        google()".to_string(),
+            "        gradlePluginPortal()".to_string(),
+            "        mavenCentral()".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+            String::new(),
+            format!("rootProject.name = \"{}\"", self.config.project_name),
+            String::new(),
+        ];
+        Ok(lines.join("\n"))
+    }
+
+    /// Generate `gradle.properties`
+    pub fn generate_gradle_properties(&self) -> Result<String> {
+        let mut lines =
+            vec!["kotlin.code.style=official".to_string(), "org.gradle.jvmargs=-Xmx4g".to_string()];
+        if self.has_target("android") {
+            lines.push("android.useAndroidX=true".to_string());
+        }
+        lines.push(String::new());
+        Ok(lines.join("\n"))
+    }
+
+    /// Generate `.gitignore`
+    pub fn generate_gitignore(&self) -> Result<String> {
+        Ok([".gradle/", "build/", ".idea/", "*.iml", "local.properties", ".kotlin/", ""].join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_build_gradle() {
+        let generator = GradleGenerator::new(GradleConfig::default());
+        let build = generator.generate_build_gradle().unwrap();
+
+        assert!(build.contains("kotlin(\"multiplatform\") version \"2.0.21\""));
+        assert!(build.contains("kotlin(\"plugin.serialization\")"));
+        assert!(build.contains("kotlinx-serialization-json:1.7.3"));
+        assert!(build.contains("jvm()"));
+        assert!(build.contains("androidTarget"));
+        assert!(build.contains("iosArm64()"));
+        assert!(build.contains("namespace = \"org.octofhir.fhir.r4\""));
+        assert!(!build.contains("js(IR)"));
+    }
+
+    #[test]
+    fn test_jvm_only_build() {
+        let config = GradleConfig { targets: vec!["jvm".to_string()], ..Default::default() };
+        let generator = GradleGenerator::new(config);
+        let build = generator.generate_build_gradle().unwrap();
+
+        assert!(build.contains("jvm()"));
+        assert!(!build.contains("com.android.library"));
+        assert!(!build.contains("android {"));
+        assert!(!build.contains("iosX64()"));
+    }
+
+    #[test]
+    fn test_settings_gradle() {
+        let config = GradleConfig { project_name: "my-fhir".to_string(), ..Default::default() };
+        let generator = GradleGenerator::new(config);
+        let settings = generator.generate_settings_gradle().unwrap();
+
+        assert!(settings.contains("rootProject.name = \"my-fhir\""));
+    }
+}
@@ -0,0 +1,17 @@
+//! Kotlin Multiplatform code generation
+
+mod backend;
+/// Kotlin data class generation with kotlinx.serialization annotations
+pub mod class_generator;
+/// Gradle build script generation for Kotlin Multiplatform
+pub mod gradle_generator;
+/// Complete Kotlin SDK generation orchestrator
+pub mod sdk_generator;
+/// Polymorphic serializer module generation
+pub mod serialization_generator;
+
+pub use backend::KotlinBackend;
+pub use class_generator::KotlinClassGenerator;
+pub use gradle_generator::{GradleConfig, GradleGenerator, SUPPORTED_TARGETS};
+pub use sdk_generator::KotlinSdkGenerator;
+pub use serialization_generator::SerializationGenerator;
@@ -0,0 +1,308 @@
+//! Kotlin Multiplatform SDK generation orchestrator
+
+use crate::core::ir::TypeGraph;
+use crate::core::{Error, Result};
+use crate::generator::{
+    CodeGenerator, FileType, GeneratedCode, GeneratedFile, GenerationManifest,
+    GenerationStatistics, GeneratorCapabilities, GeneratorConfig, GeneratorMetadata, Language,
+};
+use crate::languages::kotlin::{
+    GradleConfig, GradleGenerator, KotlinBackend, KotlinClassGenerator, SUPPORTED_TARGETS,
+    SerializationGenerator,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Complete Kotlin Multiplatform SDK generator that orchestrates all component generators
+pub struct KotlinSdkGenerator {
+    backend: KotlinBackend,
+    class_generator: KotlinClassGenerator,
+    serialization_generator: SerializationGenerator,
+    gradle_generator: GradleGenerator,
+    config: GradleConfig,
+}
+
+impl KotlinSdkGenerator {
+    /// Create a new SDK generator with custom Gradle config
+    pub fn new(config: GradleConfig) -> Self {
+        let backend = KotlinBackend::new(config.package_name.clone());
+        Self {
+            class_generator: KotlinClassGenerator::new(backend.clone()),
+            serialization_generator: SerializationGenerator::new(backend.clone()),
+            gradle_generator: GradleGenerator::new(config.clone()),
+            backend,
+            config,
+        }
+    }
+
+    /// Create SDK generator with default configuration
+    pub fn with_defaults() -> Self {
+        Self::new(GradleConfig::default())
+    }
+
+    /// Generate a complete Kotlin Multiplatform SDK from a type graph
+    ///
+    /// Returns a map of file paths to their content
+    pub fn generate_sdk(&self, type_graph: &TypeGraph) -> Result<HashMap<String, String>> {
+        Ok(self
+            .generate_files(type_graph)?
+            .into_iter()
+            .map(|file| (file.path.to_string_lossy().into_owned(), file.content))
+            .collect())
+    }
+
+    /// Generate all SDK files from a type graph, tagged with their [`FileType`]
+    pub fn generate_files(&self, type_graph: &TypeGraph) -> Result<Vec<GeneratedFile>> {
+        let mut files = Vec::new();
+        let mut add = |path: String, content: String, file_type: FileType| {
+            files.push(GeneratedFile::new(PathBuf::from(path), content, file_type));
+        };
+
+        // Generate Gradle build files
+        add(
+            "build.gradle.kts".to_string(),
+            self.gradle_generator.generate_build_gradle()?,
+            FileType::Manifest,
+        );
+        add(
+            "settings.gradle.kts".to_string(),
+            self.gradle_generator.generate_settings_gradle()?,
+            FileType::Manifest,
+        );
+        add(
+            "gradle.properties".to_string(),
+            self.gradle_generator.generate_gradle_properties()?,
+            FileType::Manifest,
+        );
+        add(".gitignore".to_string(), self.gradle_generator.generate_gitignore()?, FileType::Other);
+
+        // Generate README.md
+        add("README.md".to_string(), self.generate_readme(type_graph), FileType::Documentation);
+
+        // Generate datatypes
+        let datatypes_dir = KotlinBackend::source_dir(&self.backend.datatypes_package());
+        for (name, datatype) in &type_graph.datatypes {
+            let file_content = self.class_generator.generate_datatype_file(datatype, type_graph)?;
+            add(format!("{}/{}.kt", datatypes_dir, name), file_content, FileType::DataType);
+        }
+
+        // Generate resources
+        let resources_dir = KotlinBackend::source_dir(&self.backend.resources_package());
+        for (name, resource) in &type_graph.resources {
+            let file_content = self.class_generator.generate_resource_file(resource, type_graph)?;
+            add(format!("{}/{}.kt", resources_dir, name), file_content, FileType::Resource);
+        }
+
+        // Generate serialization module
+        add(
+            format!("{}/FhirJson.kt", KotlinBackend::source_dir(self.backend.package_name())),
+            self.serialization_generator.generate_fhir_json(type_graph)?,
+            FileType::Helper,
+        );
+
+        Ok(files)
+    }
+
+    /// Derive the Gradle configuration for a generator config
+    ///
+    /// `module_name` and `package_version` set the project name and version; the
+    /// `package_name`, `group_id`, `kotlin_version`, `serialization_version` and
+    /// `targets` custom options override the corresponding Gradle settings.
+    fn gradle_config_for(&self, config: &GeneratorConfig) -> GradleConfig {
+        let mut gradle = self.config.clone();
+
+        if let Some(ref name) = config.module_name {
+            gradle.project_name.clone_from(name);
+        }
+        gradle.version.clone_from(&config.package_version);
+
+        if let Some(package_name) = config.get_custom::<String>("package_name") {
+            gradle.package_name = package_name;
+        }
+        if let Some(group_id) = config.get_custom::<String>("group_id") {
+            gradle.group_id = group_id;
+        }
+        if let Some(kotlin_version) = config.get_custom::<String>("kotlin_version") {
+            gradle.kotlin_version = kotlin_version;
+        }
+        if let Some(serialization_version) = config.get_custom::<String>("serialization_version") {
+            gradle.serialization_version = serialization_version;
+        }
+        if let Some(targets) = config.get_custom::<Vec<String>>("targets") {
+            gradle.targets = targets;
+        }
+
+        gradle
+    }
+
+    /// Generate the README.md file
+    fn generate_readme(&self, type_graph: &TypeGraph) -> String {
+        [
+            format!("# {}", self.config.project_name),
+            String::new(),
+            format!(
+                "Kotlin Multiplatform models for FHIR {} generated by octofhir-codegen.",
+                type_graph.fhir_version
+            ),
+            String::new(),
+            "## Installation".to_string(),
+            String::new(),
+            "```kotlin".to_string(),
+            format!(
+                "implementation(\"{}:{}:{}\")",
+                self.config.group_id, self.config.project_name, self.config.version
+            ),
+            "```".to_string(),
+            String::new(),
+            "## Usage".to_string(),
+            String::new(),
+            "```kotlin".to_string(),
+            format!("import {}.decodeResource", self.backend.package_name()),
+            format!("import {}.encodeResource", self.backend.package_name()),
+            String::new(),
+            "val resource = decodeResource(json)".to_string(),
+            "val text = encodeResource(resource)".to_string(),
+            "```".to_string(),
+            String::new(),
+        ]
+        .join("\n")
+    }
+
+    /// Get a list of all files that would be generated
+    pub fn list_generated_files(&self, type_graph: &TypeGraph) -> Result<Vec<String>> {
+        let files = self.generate_sdk(type_graph)?;
+        Ok(files.keys().cloned().collect())
+    }
+}
+
+#[async_trait]
+impl CodeGenerator for KotlinSdkGenerator {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        let started = std::time::Instant::now();
+
+        let generator = Self::new(self.gradle_config_for(config));
+        let files = generator.generate_files(graph)?;
+
+        let mut statistics = GenerationStatistics::from_files(&files);
+        statistics.generation_time_ms = started.elapsed().as_millis() as u64;
+
+        let manifest = GenerationManifest {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            generator: self.metadata(),
+            config: config.clone(),
+            statistics,
+            warnings: vec![],
+        };
+
+        Ok(GeneratedCode::new(files, manifest))
+    }
+
+    fn language(&self) -> Language {
+        Language::Kotlin
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        GeneratorMetadata {
+            name: "kotlin".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            language: Language::Kotlin,
+            description: "Kotlin Multiplatform SDK with kotlinx.serialization data classes"
+                .to_string(),
+            author: "OctoFHIR Team".to_string(),
+            capabilities: GeneratorCapabilities { documentation: true, ..Default::default() },
+        }
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Result<()> {
+        for target in self.gradle_config_for(config).targets {
+            if !SUPPORTED_TARGETS.contains(&target.as_str()) {
+                return Err(Error::Config(format!(
+                    "Invalid Kotlin target: '{}'. Must be one of: {}",
+                    target,
+                    SUPPORTED_TARGETS.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion, ResourceType};
+    use std::path::Path;
+
+    fn create_test_graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource(
+            "Patient".to_string(),
+            ResourceType {
+                name: "Patient".to_string(),
+                base: None,
+                constraints: vec![],
+                properties: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: "http://hl7.org/fhir/StructureDefinition/Patient".to_string(),
+                is_abstract: false,
+            },
+        );
+        graph
+    }
+
+    #[test]
+    fn test_generate_sdk_basic_files() {
+        let generator = KotlinSdkGenerator::with_defaults();
+        let files = generator.generate_sdk(&create_test_graph()).unwrap();
+
+        assert!(files.contains_key("build.gradle.kts"));
+        assert!(files.contains_key("settings.gradle.kts"));
+        assert!(files.contains_key("gradle.properties"));
+        assert!(files.contains_key("README.md"));
+        assert!(files.contains_key("src/commonMain/kotlin/org/octofhir/fhir/r4/FhirJson.kt"));
+        assert!(
+            files.contains_key("src/commonMain/kotlin/org/octofhir/fhir/r4/resources/Patient.kt")
+        );
+    }
+
+    #[test]
+    fn test_custom_package() {
+        let config =
+            GradleConfig { package_name: "com.example.fhir".to_string(), ..Default::default() };
+        let generator = KotlinSdkGenerator::new(config);
+        let files = generator.list_generated_files(&create_test_graph()).unwrap();
+
+        assert!(files.contains(&"src/commonMain/kotlin/com/example/fhir/FhirJson.kt".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_uses_custom_options() {
+        let generator = KotlinSdkGenerator::with_defaults();
+        let mut config = GeneratorConfig::default();
+        config.set_custom("package_name".to_string(), "com.acme.fhir").unwrap();
+        config.set_custom("targets".to_string(), vec!["jvm"]).unwrap();
+
+        generator.validate_config(&config).unwrap();
+        let code = generator.generate(&create_test_graph(), &config).await.unwrap();
+
+        let fhir_json = code
+            .files
+            .iter()
+            .find(|f| f.path == Path::new("src/commonMain/kotlin/com/acme/fhir/FhirJson.kt"))
+            .unwrap();
+        assert_eq!(fhir_json.file_type, FileType::Helper);
+        assert_eq!(code.manifest.statistics.resources, 1);
+    }
+
+    #[test]
+    fn test_validate_config_rejects_unknown_target() {
+        let generator = KotlinSdkGenerator::with_defaults();
+        let mut config = GeneratorConfig::default();
+        config.set_custom("targets".to_string(), vec!["wasm"]).unwrap();
+
+        assert!(generator.validate_config(&config).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! kotlinx.serialization configuration generation for Kotlin
+
+use crate::core::Result;
+use crate::core::ir::TypeGraph;
+use crate::languages::kotlin::backend::KotlinBackend;
+use crate::templates::genco_engine::GencoTemplateEngine;
+use genco::prelude::*;
+
+/// Generator for the shared `FhirJson` serialization entry point
+///
+/// Produces a `SerializersModule` registering every concrete resource as a
+/// polymorphic subclass of `Resource`, a preconfigured `Json` instance that uses
+/// `resourceType` as the class discriminator, encode/decode helpers, and the
+/// `ChoiceSerializer` base that classes with choice elements serialize through.
+pub struct SerializationGenerator {
+    /// Language backend for package naming
+    backend: KotlinBackend,
+}
+
+impl SerializationGenerator {
+    /// Create a new serialization generator
+    pub fn new(backend: KotlinBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Generate `FhirJson.kt`
+    pub fn generate_fhir_json(&self, graph: &TypeGraph) -> Result<String> {
+        let mut tokens = kotlin::Tokens::new();
+
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+        tokens.append(format!("package {}", self.backend.package_name()));
+        tokens.line();
+
+        for import in [
+            "kotlinx.serialization.KSerializer",
+            "kotlinx.serialization.PolymorphicSerializer",
+            "kotlinx.serialization.json.Json",
+            "kotlinx.serialization.json.JsonElement",
+            "kotlinx.serialization.json.JsonObject",
+            "kotlinx.serialization.json.JsonTransformingSerializer",
+            "kotlinx.serialization.json.buildJsonObject",
+            "kotlinx.serialization.json.contentOrNull",
+            "kotlinx.serialization.json.jsonPrimitive",
+            "kotlinx.serialization.json.put",
+            "kotlinx.serialization.modules.SerializersModule",
+            "kotlinx.serialization.modules.polymorphic",
+            "kotlinx.serialization.modules.subclass",
+        ] {
+            tokens.append(format!("import {}", import));
+            tokens.push();
+        }
+        tokens.append(format!("import {}.*", self.backend.resources_package()));
+        tokens.line();
+
+        tokens.append(
+            "/** Registers all concrete resources for polymorphic `Resource` serialization */",
+        );
+        tokens.push();
+        tokens.append("val fhirSerializersModule = SerializersModule {");
+        tokens.push();
+
+        let has_resource_base = graph.resources.contains_key("Resource");
+        if has_resource_base {
+            tokens.append("    polymorphic(Resource::class) {");
+            tokens.push();
+            for resource in graph.resources.values().filter(|r| !r.is_abstract) {
+                tokens.append(format!("        subclass({}::class)", resource.name));
+                tokens.push();
+            }
+            tokens.append("    }");
+            tokens.push();
+        }
+
+        tokens.append("}");
+        tokens.line();
+
+        tokens.append("/** JSON format configured for FHIR resources */");
+        tokens.push();
+        tokens.append("val FhirJson = Json {");
+        tokens.push();
+        for setting in [
+            "serializersModule = fhirSerializersModule",
+            "classDiscriminator = \"resourceType\"",
+            "ignoreUnknownKeys = true",
+            "explicitNulls = false",
+            "encodeDefaults = false",
+        ] {
+            tokens.append(format!("    {}", setting));
+            tokens.push();
+        }
+        tokens.append("}");
+        tokens.line();
+
+        for line in CHOICE_SERIALIZER.lines() {
+            if line.is_empty() {
+                tokens.line();
+            } else {
+                tokens.append(line);
+                tokens.push();
+            }
+        }
+
+        if has_resource_base {
+            tokens.line();
+            tokens.append("/** Decode any FHIR resource, dispatching on its `resourceType` */");
+            tokens.push();
+            tokens.append("fun decodeResource(json: String): Resource =");
+            tokens.push();
+            tokens.append(
+                "    FhirJson.decodeFromString(PolymorphicSerializer(Resource::class), json)",
+            );
+            tokens.line();
+            tokens.append("/** Encode any FHIR resource, including its `resourceType` */");
+            tokens.push();
+            tokens.append("fun encodeResource(resource: Resource): String =");
+            tokens.push();
+            tokens.append(
+                "    FhirJson.encodeToString(PolymorphicSerializer(Resource::class), resource)",
+            );
+        }
+        tokens.push();
+
+        GencoTemplateEngine::format_kotlin(&tokens)
+    }
+}
+
+/// Base serializer mapping choice elements between their `valueQuantity`-style
+/// JSON keys and the `{"type": ..., "value": ...}` shape of their sealed classes
+const CHOICE_SERIALIZER: &str = r#"/**
+ * Serializer for classes with choice elements (`value[x]`)
+ *
+ * The model holds each choice element as a sealed class; in FHIR JSON the type
+ * is part of the key instead (`valueQuantity`). [choices] maps every choice
+ * element name to the type suffixes it allows.
+ */
+open class ChoiceSerializer<T : Any>(
+    serializer: KSerializer<T>,
+    private val choices: Map<String, List<String>>,
+) : JsonTransformingSerializer<T>(serializer) {
+    override fun transformDeserialize(element: JsonElement): JsonElement {
+        if (element !is JsonObject) return element
+        return buildJsonObject {
+            for ((key, value) in element) {
+                val choice = choiceOf(key)
+                if (choice == null) {
+                    put(key, value)
+                } else {
+                    put(choice.first, buildJsonObject {
+                        put("type", choice.second)
+                        put("value", value)
+                    })
+                }
+            }
+        }
+    }
+
+    override fun transformSerialize(element: JsonElement): JsonElement {
+        if (element !is JsonObject) return element
+        return buildJsonObject {
+            for ((key, value) in element) {
+                val choice = value as? JsonObject
+                val type = choice?.get("type")?.jsonPrimitive?.contentOrNull
+                if (key in choices && choice != null && type != null) {
+                    put(key + type, choice.getValue("value"))
+                } else {
+                    put(key, value)
+                }
+            }
+        }
+    }
+
+    private fun choiceOf(key: String): Pair<String, String>? =
+        choices.entries.firstNotNullOfOrNull { (name, types) ->
+            types.firstOrNull { key == name + it }?.let { name to it }
+        }
+}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion, ResourceType};
+
+    fn resource(name: &str, is_abstract: bool) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: None,
+            constraints: vec![],
+            properties: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: String::new(),
+            is_abstract,
+        }
+    }
+
+    #[test]
+    fn test_registers_concrete_resources() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource("Resource".to_string(), resource("Resource", true));
+        graph.add_resource("DomainResource".to_string(), resource("DomainResource", true));
+        graph.add_resource("Patient".to_string(), resource("Patient", false));
+
+        let generator = SerializationGenerator::new(KotlinBackend::default());
+        let output = generator.generate_fhir_json(&graph).unwrap();
+
+        assert!(output.contains("package org.octofhir.fhir.r4"));
+        assert!(output.contains("polymorphic(Resource::class) {"));
+        assert!(output.contains("subclass(Patient::class)"));
+        assert!(!output.contains("subclass(DomainResource::class)"));
+        assert!(output.contains("classDiscriminator = \"resourceType\""));
+        assert!(output.contains("fun decodeResource(json: String): Resource"));
+    }
+
+    #[test]
+    fn test_without_resource_base() {
+        let graph = TypeGraph::new(FhirVersion::R4);
+        let generator = SerializationGenerator::new(KotlinBackend::default());
+        let output = generator.generate_fhir_json(&graph).unwrap();
+
+        assert!(output.contains("val FhirJson = Json {"));
+        assert!(output.contains("open class ChoiceSerializer<T : Any>("));
+        assert!(!output.contains("polymorphic("));
+        assert!(!output.contains("decodeResource"));
+    }
+}
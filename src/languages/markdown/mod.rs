@@ -0,0 +1,6 @@
+//! Markdown documentation site generation
+
+/// Reference pages for the types in the graph, laid out for mdBook or Docusaurus
+pub mod site_generator;
+
+pub use site_generator::{MarkdownSiteGenerator, SUPPORTED_FLAVORS, SiteFlavor};
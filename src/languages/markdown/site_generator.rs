@@ -0,0 +1,853 @@
+use crate::core::ir::{
+    CardinalityRange, Documentation, Extension, ProfileType, Property, PropertyType, ResourceType,
+    TypeGraph, ValueSetBinding, versionless_url,
+};
+use crate::core::{Error, Result};
+use crate::generator::{
+    CodeGenerator, FileType, GeneratedCode, GeneratedFile, GenerationManifest,
+    GenerationStatistics, GeneratorCapabilities, GeneratorConfig, GeneratorMetadata, Language,
+};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Site layouts accepted by the `flavor` option
+pub const SUPPORTED_FLAVORS: &[&str] = &["mdbook", "docusaurus"];
+
+/// Legend printed below property tables
+const FLAGS_LEGEND: &str = "Flags: `?!` modifier, `Σ` in summary, `MS` must support.";
+
+/// Static site generator the pages are laid out for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SiteFlavor {
+    /// mdBook: `book.toml`, pages under `src/` and navigation in `src/SUMMARY.md`
+    #[default]
+    MdBook,
+    /// Docusaurus: pages with front matter under `docs/`, sections ordered by
+    /// `_category_.json`
+    Docusaurus,
+}
+
+impl SiteFlavor {
+    /// Directory holding the pages
+    fn content_dir(self) -> &'static str {
+        match self {
+            SiteFlavor::MdBook => "src",
+            SiteFlavor::Docusaurus => "docs",
+        }
+    }
+
+    /// File name of the landing page
+    fn index_file(self) -> &'static str {
+        match self {
+            SiteFlavor::MdBook => "README.md",
+            SiteFlavor::Docusaurus => "index.md",
+        }
+    }
+}
+
+impl std::str::FromStr for SiteFlavor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mdbook" => Ok(SiteFlavor::MdBook),
+            "docusaurus" => Ok(SiteFlavor::Docusaurus),
+            _ => Err(Error::Config(format!(
+                "Invalid Markdown flavor: '{}'. Must be one of: {}",
+                s,
+                SUPPORTED_FLAVORS.join(", ")
+            ))),
+        }
+    }
+}
+
+/// Part of the site, one directory of pages each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Resources,
+    Datatypes,
+    Profiles,
+    Extensions,
+}
+
+impl Section {
+    fn directory(self) -> &'static str {
+        match self {
+            Section::Resources => "resources",
+            Section::Datatypes => "datatypes",
+            Section::Profiles => "profiles",
+            Section::Extensions => "extensions",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Section::Resources => "Resources",
+            Section::Datatypes => "Datatypes",
+            Section::Profiles => "Profiles",
+            Section::Extensions => "Extensions",
+        }
+    }
+}
+
+/// Generator for a Markdown reference site
+///
+/// Writes a page per resource, datatype, profile and extension with property
+/// tables, search parameters and links between related types, plus a landing
+/// page and the navigation the chosen [`SiteFlavor`] expects.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownSiteGenerator {
+    flavor: SiteFlavor,
+    title: Option<String>,
+}
+
+impl MarkdownSiteGenerator {
+    /// Create a generator for an mdBook site
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the site layout
+    pub fn with_flavor(mut self, flavor: SiteFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Set the site title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Build every file of the site, relative to the output directory
+    pub fn generate_site(&self, graph: &TypeGraph) -> Vec<GeneratedFile> {
+        let title =
+            self.title.clone().unwrap_or_else(|| format!("FHIR {} Reference", graph.fhir_version));
+        let site = Site::new(graph, self.flavor);
+        let dir = self.flavor.content_dir();
+        let mut files = Vec::new();
+
+        files.push(GeneratedFile::new(
+            PathBuf::from(format!("{}/{}", dir, self.flavor.index_file())),
+            site.index_page(&title),
+            FileType::Index,
+        ));
+        match self.flavor {
+            SiteFlavor::MdBook => {
+                files.push(GeneratedFile::new(
+                    PathBuf::from("book.toml"),
+                    format!("[book]\ntitle = {}\nsrc = \"src\"\n", Value::from(title.as_str())),
+                    FileType::Manifest,
+                ));
+                files.push(GeneratedFile::new(
+                    PathBuf::from("src/SUMMARY.md"),
+                    site.summary(),
+                    FileType::Index,
+                ));
+            }
+            SiteFlavor::Docusaurus => {
+                for (position, (section, _)) in site.navigation.iter().enumerate() {
+                    let category = json!({ "label": section.title(), "position": position + 2 });
+                    files.push(GeneratedFile::new(
+                        PathBuf::from(format!("docs/{}/_category_.json", section.directory())),
+                        format!("{:#}\n", category),
+                        FileType::Index,
+                    ));
+                }
+            }
+        }
+
+        for (section, entries) in &site.navigation {
+            for entry in entries {
+                let content = match section {
+                    Section::Resources => site.resource_page(&graph.resources[entry.key]),
+                    Section::Datatypes => {
+                        let datatype = &graph.datatypes[entry.key];
+                        site.type_page(
+                            &datatype.name,
+                            &datatype.documentation,
+                            &datatype.url,
+                            datatype.base.as_deref(),
+                            datatype.is_abstract,
+                            &datatype.properties,
+                            String::new(),
+                        )
+                    }
+                    Section::Profiles => site.profile_page(&graph.profiles[entry.key]),
+                    Section::Extensions => site.extension_page(&graph.extensions[entry.key]),
+                };
+                files.push(GeneratedFile::new(
+                    PathBuf::from(format!("{}/{}", dir, site.path(entry.key).unwrap_or_default())),
+                    content,
+                    FileType::Documentation,
+                ));
+            }
+        }
+
+        files
+    }
+}
+
+/// Page of the site in the navigation
+struct Entry<'a> {
+    /// Type name, or URL of a profile or extension
+    key: &'a str,
+    /// Link text
+    name: &'a str,
+    /// One-line description
+    short: &'a str,
+}
+
+/// Page locations and cross-references shared by the pages of a site
+struct Site<'a> {
+    graph: &'a TypeGraph,
+    flavor: SiteFlavor,
+    /// Pages by section, sorted by name
+    navigation: Vec<(Section, Vec<Entry<'a>>)>,
+    /// Section and file stem of each page by key
+    pages: HashMap<&'a str, (Section, String)>,
+    /// Types whose elements use a datatype or reference a resource
+    used_by: HashMap<&'a str, BTreeSet<&'a str>>,
+    /// Profiles constraining each type
+    profiles_of: HashMap<&'a str, Vec<&'a ProfileType>>,
+}
+
+impl<'a> Site<'a> {
+    fn new(graph: &'a TypeGraph, flavor: SiteFlavor) -> Self {
+        let entry = |key: &'a str, name: &'a str, documentation: &'a Documentation| Entry {
+            key,
+            name,
+            short: &documentation.short,
+        };
+        let sections = [
+            (
+                Section::Resources,
+                graph
+                    .resources
+                    .values()
+                    .map(|r| entry(&r.name, &r.name, &r.documentation))
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                Section::Datatypes,
+                graph
+                    .datatypes
+                    .values()
+                    .map(|d| entry(&d.name, &d.name, &d.documentation))
+                    .collect(),
+            ),
+            (
+                Section::Profiles,
+                graph
+                    .profiles
+                    .iter()
+                    .map(|(url, p)| entry(url, &p.name, &p.documentation))
+                    .collect(),
+            ),
+            (
+                Section::Extensions,
+                graph
+                    .extensions
+                    .iter()
+                    .map(|(url, e)| entry(url, &e.name, &e.documentation))
+                    .collect(),
+            ),
+        ];
+
+        let mut navigation = Vec::new();
+        let mut pages = HashMap::new();
+        for (section, mut entries) in sections {
+            if entries.is_empty() {
+                continue;
+            }
+            entries.sort_by_key(|entry| entry.name.to_lowercase());
+
+            let mut stems = HashSet::new();
+            for entry in &entries {
+                let base = file_stem(entry.name);
+                let mut stem = base.clone();
+                let mut suffix = 2;
+                while !stems.insert(stem.clone()) {
+                    stem = format!("{}-{}", base, suffix);
+                    suffix += 1;
+                }
+                pages.insert(entry.key, (section, stem));
+            }
+            navigation.push((section, entries));
+        }
+
+        let mut used_by: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        let owners = graph
+            .resources
+            .values()
+            .map(|r| (r.name.as_str(), &r.properties))
+            .chain(graph.datatypes.values().map(|d| (d.name.as_str(), &d.properties)));
+        for (owner, properties) in owners {
+            let mut targets = Vec::new();
+            collect_type_names(properties, &mut targets);
+            for target in targets.into_iter().filter(|target| *target != owner) {
+                used_by.entry(target).or_default().insert(owner);
+            }
+        }
+
+        let mut profiles_of: HashMap<&str, Vec<&ProfileType>> = HashMap::new();
+        for profile in graph.profiles.values() {
+            profiles_of.entry(profile.base.as_str()).or_default().push(profile);
+        }
+
+        Self { graph, flavor, navigation, pages, used_by, profiles_of }
+    }
+
+    /// Path of a page relative to the content directory
+    fn path(&self, key: &str) -> Option<String> {
+        let (section, stem) = self.pages.get(key)?;
+        Some(format!("{}/{}.md", section.directory(), stem))
+    }
+
+    /// Link from a page in a section directory, or plain text if `key` has no page
+    fn link(&self, key: &str, text: &str) -> String {
+        match self.path(key) {
+            Some(path) => format!("[{}](../{})", text, path),
+            None => text.to_string(),
+        }
+    }
+
+    fn type_link(&self, name: &str) -> String {
+        self.link(name, name)
+    }
+
+    /// Add front matter for flavors that need it
+    fn page(&self, title: &str, body: String) -> String {
+        match self.flavor {
+            SiteFlavor::MdBook => body,
+            SiteFlavor::Docusaurus => {
+                let title = yaml_string(title);
+                format!("---\ntitle: {}\nsidebar_label: {}\n---\n\n{}", title, title, body)
+            }
+        }
+    }
+
+    fn index_page(&self, title: &str) -> String {
+        let counts: Vec<String> = self
+            .navigation
+            .iter()
+            .map(|(section, entries)| {
+                format!("{} {}", entries.len(), section.title().to_lowercase())
+            })
+            .collect();
+
+        let mut out = format!("# {}\n\n", title);
+        out.push_str(&format!(
+            "Reference documentation for FHIR {} generated by OctoFHIR Codegen",
+            self.graph.fhir_version
+        ));
+        if counts.is_empty() {
+            out.push_str(".\n\n");
+        } else {
+            out.push_str(&format!(": {}.\n\n", counts.join(", ")));
+        }
+
+        for (section, entries) in &self.navigation {
+            out.push_str(&format!(
+                "## {}\n\n| Name | Description |\n| --- | --- |\n",
+                section.title()
+            ));
+            for entry in entries {
+                out.push_str(&format!(
+                    "| [{}]({}) | {} |\n",
+                    entry.name,
+                    self.path(entry.key).unwrap_or_default(),
+                    inline(entry.short)
+                ));
+            }
+            out.push('\n');
+        }
+
+        self.page(title, out)
+    }
+
+    /// mdBook navigation
+    fn summary(&self) -> String {
+        let mut out = format!("# Summary\n\n[Introduction]({})\n", self.flavor.index_file());
+        for (section, entries) in &self.navigation {
+            out.push_str(&format!("\n# {}\n\n", section.title()));
+            for entry in entries {
+                out.push_str(&format!(
+                    "- [{}]({})\n",
+                    entry.name,
+                    self.path(entry.key).unwrap_or_default()
+                ));
+            }
+        }
+        out
+    }
+
+    fn resource_page(&self, resource: &ResourceType) -> String {
+        let mut extra = String::new();
+
+        let parameters = self.graph.search_parameters(resource);
+        if !parameters.is_empty() {
+            extra.push_str("## Search parameters\n\n");
+            extra.push_str(
+                "| Name | Type | Expression | Description |\n| --- | --- | --- | --- |\n",
+            );
+            for parameter in parameters {
+                let expression = parameter
+                    .expression
+                    .as_deref()
+                    .map(|expression| format!("`{}`", expression.replace('|', "\\|")))
+                    .unwrap_or_default();
+                extra.push_str(&format!(
+                    "| `{}` | {} | {} | {} |\n",
+                    parameter.code,
+                    parameter.param_type.code(),
+                    expression,
+                    inline(&parameter.description)
+                ));
+            }
+            extra.push('\n');
+        }
+
+        if !resource.extensions.is_empty() {
+            extra.push_str("## Extensions\n\n");
+            for extension in &resource.extensions {
+                extra.push_str(&format!("- {}", self.link(&extension.url, &extension.name)));
+                if !extension.documentation.short.is_empty() {
+                    extra.push_str(&format!(": {}", inline(&extension.documentation.short)));
+                }
+                extra.push('\n');
+            }
+            extra.push('\n');
+        }
+
+        self.type_page(
+            &resource.name,
+            &resource.documentation,
+            &resource.url,
+            resource.base.as_deref(),
+            resource.is_abstract,
+            &resource.properties,
+            extra,
+        )
+    }
+
+    /// Page of a resource or datatype; `extra` holds sections specific to the kind
+    #[allow(clippy::too_many_arguments)]
+    fn type_page(
+        &self,
+        name: &str,
+        documentation: &Documentation,
+        url: &str,
+        base: Option<&str>,
+        is_abstract: bool,
+        properties: &[Property],
+        extra: String,
+    ) -> String {
+        let mut out = format!("# {}\n\n", name);
+        push_documentation(&mut out, documentation);
+
+        let mut facts = Vec::new();
+        if !url.is_empty() {
+            facts.push(format!("- **Canonical URL:** `{}`", url));
+        }
+        if let Some(base) = base {
+            facts.push(format!("- **Base type:** {}", self.type_link(base)));
+        }
+        if is_abstract {
+            facts.push("- **Abstract:** yes".to_string());
+        }
+        if !facts.is_empty() {
+            out.push_str(&format!("{}\n\n", facts.join("\n")));
+        }
+
+        out.push_str("## Properties\n\n");
+        if let Some(base) = base {
+            out.push_str(&format!("Inherits the elements of {}.\n\n", self.type_link(base)));
+        }
+        if properties.is_empty() {
+            out.push_str("No elements of its own.\n\n");
+        } else {
+            out.push_str(&self.property_table(properties));
+        }
+
+        out.push_str(&extra);
+
+        if let Some(profiles) = self.profiles_of.get(name) {
+            out.push_str("## Profiles\n\n");
+            for profile in profiles {
+                out.push_str(&format!("- {}\n", self.link(&profile.url, &profile.name)));
+            }
+            out.push('\n');
+        }
+
+        if let Some(users) = self.used_by.get(name) {
+            out.push_str("## Used by\n\n");
+            for user in users {
+                out.push_str(&format!("- {}\n", self.type_link(user)));
+            }
+            out.push('\n');
+        }
+
+        self.page(name, out)
+    }
+
+    fn profile_page(&self, profile: &ProfileType) -> String {
+        let mut out = format!("# {}\n\n", profile.name);
+        push_documentation(&mut out, &profile.documentation);
+        out.push_str(&format!(
+            "- **Canonical URL:** `{}`\n- **Constrains:** {}\n\n",
+            profile.url,
+            self.type_link(&profile.base)
+        ));
+
+        out.push_str("## Constraints\n\n");
+        if profile.property_constraints.is_empty() {
+            out.push_str("No element constraints.\n\n");
+        } else {
+            out.push_str("| Path | Flags | Card. | Type | Binding | Value |\n");
+            out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+            for constraint in &profile.property_constraints {
+                let path = constraint
+                    .path
+                    .strip_prefix(profile.base.as_str())
+                    .and_then(|path| path.strip_prefix('.'))
+                    .unwrap_or(&constraint.path);
+                let types: Vec<String> =
+                    constraint.type_constraints.iter().map(|t| self.type_link(t)).collect();
+                let value = constraint
+                    .fixed_value
+                    .as_ref()
+                    .map(|value| format!("`{}`", value.to_string().replace('|', "\\|")))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} | {} |\n",
+                    path,
+                    if constraint.must_support { "MS" } else { "" },
+                    constraint.cardinality.as_ref().map(cardinality).unwrap_or_default(),
+                    types.join(" \\| "),
+                    self.binding(constraint.binding.as_ref()),
+                    value
+                ));
+            }
+            out.push('\n');
+        }
+
+        if !profile.new_properties.is_empty() {
+            out.push_str("## Added properties\n\n");
+            out.push_str(&self.property_table(&profile.new_properties));
+        }
+
+        self.page(&profile.name, out)
+    }
+
+    fn extension_page(&self, extension: &Extension) -> String {
+        let mut out = format!("# {}\n\n", extension.name);
+        push_documentation(&mut out, &extension.documentation);
+
+        let contexts: Vec<String> = extension
+            .target_types
+            .iter()
+            .map(|context| match self.path(context) {
+                Some(_) => self.type_link(context),
+                None => format!("`{}`", context),
+            })
+            .collect();
+        let value = if extension.is_complex {
+            "complex, see the sub-extensions below".to_string()
+        } else if extension.value_types.is_empty() {
+            "none".to_string()
+        } else {
+            extension.value_types.iter().map(|t| self.type_link(t)).collect::<Vec<_>>().join(", ")
+        };
+        out.push_str(&format!("- **Canonical URL:** `{}`\n", extension.url));
+        out.push_str(&format!("- **Cardinality:** {}\n", cardinality(&extension.cardinality)));
+        if !contexts.is_empty() {
+            out.push_str(&format!("- **Context:** {}\n", contexts.join(", ")));
+        }
+        out.push_str(&format!("- **Value:** {}\n\n", value));
+
+        if !extension.sub_extensions.is_empty() {
+            out.push_str("## Sub-extensions\n\n");
+            out.push_str("| Name | URL | Card. | Value | Description |\n");
+            out.push_str("| --- | --- | --- | --- | --- |\n");
+            self.sub_extension_rows(&extension.sub_extensions, "", &mut out);
+            out.push('\n');
+        }
+
+        self.page(&extension.name, out)
+    }
+
+    fn sub_extension_rows(&self, extensions: &[Extension], prefix: &str, out: &mut String) {
+        for extension in extensions {
+            let name = format!("{}{}", prefix, extension.name);
+            let types: Vec<String> =
+                extension.value_types.iter().map(|t| self.type_link(t)).collect();
+            out.push_str(&format!(
+                "| `{}` | `{}` | {} | {} | {} |\n",
+                name,
+                extension.url,
+                cardinality(&extension.cardinality),
+                types.join(" \\| "),
+                inline(&extension.documentation.short)
+            ));
+            self.sub_extension_rows(&extension.sub_extensions, &format!("{}.", name), out);
+        }
+    }
+
+    /// Table of properties, with backbone elements expanded below their parent
+    fn property_table(&self, properties: &[Property]) -> String {
+        let mut out = String::from("| Name | Flags | Card. | Type | Binding | Description |\n");
+        out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+        self.property_rows(properties, "", &mut out);
+        out.push_str(&format!("\n{}\n\n", FLAGS_LEGEND));
+        out
+    }
+
+    fn property_rows(&self, properties: &[Property], prefix: &str, out: &mut String) {
+        for property in properties {
+            let name = format!("{}{}", prefix, property.name);
+            let mut flags = Vec::new();
+            if property.is_modifier {
+                flags.push("?!");
+            }
+            if property.is_summary {
+                flags.push("Σ");
+            }
+
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} | {} |\n",
+                name,
+                flags.join(" "),
+                cardinality(&property.cardinality),
+                self.property_type(&property.property_type),
+                self.binding(property.binding.as_ref()),
+                inline(&property.short_description)
+            ));
+
+            if let PropertyType::BackboneElement { properties } = &property.property_type {
+                self.property_rows(properties, &format!("{}.", name), out);
+            }
+        }
+    }
+
+    /// Type column of a property, linking to the pages of the types
+    fn property_type(&self, property_type: &PropertyType) -> String {
+        match property_type {
+            PropertyType::Primitive { type_name } | PropertyType::Complex { type_name } => {
+                self.type_link(type_name)
+            }
+            PropertyType::Reference { target_types, .. } if target_types.is_empty() => {
+                "Reference(Any)".to_string()
+            }
+            PropertyType::Reference { target_types, .. } => {
+                let targets: Vec<String> = target_types.iter().map(|t| self.type_link(t)).collect();
+                format!("Reference({})", targets.join(" \\| "))
+            }
+            PropertyType::BackboneElement { .. } => "BackboneElement".to_string(),
+            PropertyType::Choice { types } => {
+                types.iter().map(|t| self.type_link(t)).collect::<Vec<_>>().join(" \\| ")
+            }
+        }
+    }
+
+    /// Binding column: value set name, linked when its URL is absolute, and strength
+    fn binding(&self, binding: Option<&ValueSetBinding>) -> String {
+        let Some(binding) = binding else { return String::new() };
+        let url = versionless_url(&binding.value_set);
+        let name = match self.graph.value_set(url) {
+            Some(expansion) => expansion.name.as_str(),
+            None => url.rsplit('/').next().unwrap_or(url),
+        };
+
+        if url.contains("://") {
+            format!("[{}]({}) ({})", name, url, binding.strength.code())
+        } else {
+            format!("{} ({})", name, binding.strength.code())
+        }
+    }
+}
+
+/// Type names a list of properties refers to, including backbone elements
+fn collect_type_names<'a>(properties: &'a [Property], names: &mut Vec<&'a str>) {
+    for property in properties {
+        match &property.property_type {
+            PropertyType::Complex { type_name } => names.push(type_name),
+            PropertyType::Reference { target_types, .. } => {
+                names.extend(target_types.iter().map(String::as_str));
+            }
+            PropertyType::Choice { types } => names.extend(types.iter().map(String::as_str)),
+            PropertyType::BackboneElement { properties } => collect_type_names(properties, names),
+            PropertyType::Primitive { .. } => {}
+        }
+    }
+}
+
+/// Short description and definition as paragraphs
+fn push_documentation(out: &mut String, documentation: &Documentation) {
+    if !documentation.short.is_empty() {
+        out.push_str(&format!("{}\n\n", block(&documentation.short)));
+    }
+    if !documentation.definition.is_empty() && documentation.definition != documentation.short {
+        out.push_str(&format!("{}\n\n", block(&documentation.definition)));
+    }
+}
+
+/// Cardinality as written in FHIR (`0..*`)
+fn cardinality(cardinality: &CardinalityRange) -> String {
+    match cardinality.max {
+        Some(max) => format!("{}..{}", cardinality.min, max),
+        None => format!("{}..*", cardinality.min),
+    }
+}
+
+/// File stem for a page name, keeping letters, digits, `-` and `_`
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect()
+}
+
+/// Double-quoted YAML scalar for front matter
+///
+/// JSON string escapes are a subset of YAML's, so every title is written in
+/// the same quoted form whatever characters it contains.
+fn yaml_string(text: &str) -> String {
+    Value::from(text).to_string()
+}
+
+/// Text from FHIR definitions made safe for a table cell
+fn inline(text: &str) -> String {
+    block(text).split_whitespace().collect::<Vec<_>>().join(" ").replace('|', "\\|")
+}
+
+/// Text from FHIR definitions made safe for Markdown and MDX
+///
+/// Relative links point into the FHIR specification and would be broken on
+/// the generated site, so only their text is kept. `<` and braces are escaped
+/// because MDX reads them as JSX.
+fn block(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('[') {
+        let (before, link) = rest.split_at(start);
+        out.push_str(before);
+
+        let target = link.find("](").and_then(|middle| {
+            let close = link[middle..].find(')')? + middle;
+            Some((middle, close))
+        });
+        match target {
+            Some((middle, close)) if !link[1..middle].contains('[') => {
+                let url = &link[middle + 2..close];
+                if url.contains("://") {
+                    out.push_str(&link[..=close]);
+                } else {
+                    out.push_str(&link[1..middle]);
+                }
+                rest = &link[close + 1..];
+            }
+            _ => {
+                out.push('[');
+                rest = &link[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out.replace('<', "&lt;").replace('{', "\\{").replace('}', "\\}")
+}
+
+#[async_trait]
+impl CodeGenerator for MarkdownSiteGenerator {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        let started = std::time::Instant::now();
+
+        let mut generator = self.clone();
+        if let Some(flavor) = config.get_custom::<String>("flavor") {
+            generator.flavor = flavor.parse()?;
+        }
+        if let Some(title) = config.get_custom::<String>("title") {
+            generator.title = Some(title);
+        }
+
+        let files = generator.generate_site(graph);
+
+        let mut statistics = GenerationStatistics::from_files(&files);
+        statistics.resources = graph.resources.len();
+        statistics.datatypes = graph.datatypes.len();
+        statistics.profiles = graph.profiles.len();
+        statistics.generation_time_ms = started.elapsed().as_millis() as u64;
+
+        let manifest = GenerationManifest {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            generator: self.metadata(),
+            config: config.clone(),
+            statistics,
+            warnings: vec![],
+        };
+
+        Ok(GeneratedCode::new(files, manifest))
+    }
+
+    fn language(&self) -> Language {
+        Language::Markdown
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        GeneratorMetadata {
+            name: "markdown".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            language: Language::Markdown,
+            description:
+                "Markdown reference site with a page per resource, datatype, profile and extension"
+                    .to_string(),
+            author: "OctoFHIR Team".to_string(),
+            capabilities: GeneratorCapabilities {
+                documentation: true,
+                search_parameters: true,
+                profiles: true,
+                extensions: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Result<()> {
+        if let Some(flavor) = config.get_custom::<String>("flavor") {
+            flavor.parse::<SiteFlavor>()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_keeps_absolute_links_only() {
+        let text = "See [Patient](patient.html) and [spec](http://hl7.org/fhir) for <b>{x}</b>";
+
+        assert_eq!(
+            block(text),
+            "See Patient and [spec](http://hl7.org/fhir) for &lt;b>\\{x\\}&lt;/b>"
+        );
+        assert_eq!(inline("a | b\n  c [d"), "a \\| b c [d");
+    }
+
+    #[test]
+    fn test_yaml_string_quotes_titles() {
+        assert_eq!(yaml_string("Patient"), "\"Patient\"");
+        assert_eq!(yaml_string("a: \"b\" \\ c"), "\"a: \\\"b\\\" \\\\ c\"");
+    }
+
+    #[test]
+    fn test_flavor_from_str() {
+        assert_eq!("mdBook".parse::<SiteFlavor>().unwrap(), SiteFlavor::MdBook);
+        assert_eq!("docusaurus".parse::<SiteFlavor>().unwrap(), SiteFlavor::Docusaurus);
+        assert!("hugo".parse::<SiteFlavor>().is_err());
+    }
+}
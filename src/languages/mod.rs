@@ -2,4 +2,8 @@
 //!
 //! This module contains implementations for different target languages.
 
+pub mod json_schema;
+pub mod kotlin;
+pub mod markdown;
+pub mod openapi;
 pub mod typescript;
@@ -0,0 +1,6 @@
+//! OpenAPI 3.1 document generation
+
+/// Conversion of the type graph into an OpenAPI document for the FHIR REST API
+pub mod spec_generator;
+
+pub use spec_generator::{OPENAPI_VERSION, OpenApiGenerator};
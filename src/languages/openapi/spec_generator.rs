@@ -0,0 +1,631 @@
+use crate::core::ir::{ResourceType, SearchParamType, SearchParameter, TypeGraph};
+use crate::core::{Error, Result};
+use crate::generator::{
+    CodeGenerator, FileType, GeneratedCode, GeneratedFile, GenerationManifest,
+    GenerationStatistics, GeneratorCapabilities, GeneratorConfig, GeneratorMetadata, Language,
+};
+use crate::languages::json_schema::{JSON_SCHEMA_DIALECT, JsonSchemaGenerator};
+use async_trait::async_trait;
+use serde_json::{Map, Value, json};
+use std::path::PathBuf;
+
+/// OpenAPI version written to the `openapi` field
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Media type of FHIR JSON request and response bodies
+const FHIR_JSON: &str = "application/fhir+json";
+
+/// Comparison prefixes accepted by number, date and quantity search parameters
+const PREFIX: &str = "(eq|ne|gt|lt|ge|le|sa|eb|ap)?";
+
+/// Generator for an OpenAPI 3.1 document describing the FHIR RESTful API
+///
+/// Each concrete resource gets the type-level (`/Patient`) and instance-level
+/// (`/Patient/{id}`) interactions: read, vread, search, create, update, patch,
+/// delete and history. Component schemas are the JSON Schema `$defs` from
+/// [`JsonSchemaGenerator`], which OpenAPI 3.1 understands natively.
+#[derive(Debug, Clone, Default)]
+pub struct OpenApiGenerator {
+    title: Option<String>,
+    version: Option<String>,
+    description: Option<String>,
+    server_url: Option<String>,
+}
+
+impl OpenApiGenerator {
+    /// Create a generator with default document metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `info.title`
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set `info.version`
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set `info.description`
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a server URL (the FHIR base URL)
+    pub fn with_server_url(mut self, server_url: impl Into<String>) -> Self {
+        self.server_url = Some(server_url.into());
+        self
+    }
+
+    /// Name of the document file for a module name
+    pub fn file_name(module_name: Option<&str>) -> String {
+        format!("{}.openapi.json", module_name.unwrap_or("fhir"))
+    }
+
+    /// Build the OpenAPI document for a type graph
+    pub fn generate_document(&self, graph: &TypeGraph) -> Value {
+        let mut info = Map::new();
+        info.insert(
+            "title".to_string(),
+            json!(self.title.clone().unwrap_or_else(|| format!("FHIR {} API", graph.fhir_version))),
+        );
+        info.insert("version".to_string(), json!(self.version.as_deref().unwrap_or("1.0.0")));
+        if let Some(ref description) = self.description {
+            info.insert("description".to_string(), json!(description));
+        }
+
+        let mut paths = Map::new();
+        let mut tags = Vec::new();
+        for resource in graph.resources.values().filter(|r| !r.is_abstract) {
+            let name = &resource.name;
+            let resource_schema = Self::schema_ref(name);
+
+            paths.insert(
+                format!("/{}", name),
+                json!({
+                    "get": Self::search_operation(graph, resource),
+                    "post": Self::operation(
+                        "create",
+                        resource,
+                        format!("Create a new {}", name),
+                        vec![],
+                        Some(resource_schema.clone()),
+                        ("201", format!("{} created", name), resource_schema.clone()),
+                    ),
+                }),
+            );
+            paths.insert(
+                format!("/{}/{{id}}", name),
+                json!({
+                    "get": Self::operation(
+                        "read",
+                        resource,
+                        format!("Read the current version of a {}", name),
+                        vec![Self::parameter_ref("id")],
+                        None,
+                        ("200", format!("The {}", name), resource_schema.clone()),
+                    ),
+                    "put": Self::operation(
+                        "update",
+                        resource,
+                        format!("Update (or create) a {} by id", name),
+                        vec![Self::parameter_ref("id")],
+                        Some(resource_schema.clone()),
+                        ("200", format!("{} updated", name), resource_schema.clone()),
+                    ),
+                    "patch": Self::patch_operation(resource),
+                    "delete": Self::delete_operation(resource),
+                }),
+            );
+            paths.insert(
+                format!("/{}/{{id}}/_history", name),
+                json!({
+                    "get": Self::history_operation(
+                        graph,
+                        "history",
+                        resource,
+                        format!("History of a single {}", name),
+                        vec![Self::parameter_ref("id")],
+                    ),
+                }),
+            );
+            paths.insert(
+                format!("/{}/{{id}}/_history/{{vid}}", name),
+                json!({
+                    "get": Self::operation(
+                        "vread",
+                        resource,
+                        format!("Read a specific version of a {}", name),
+                        vec![Self::parameter_ref("id"), Self::parameter_ref("vid")],
+                        None,
+                        ("200", format!("The {} version", name), resource_schema.clone()),
+                    ),
+                }),
+            );
+            paths.insert(
+                format!("/{}/_history", name),
+                json!({
+                    "get": Self::history_operation(
+                        graph,
+                        "historyType",
+                        resource,
+                        format!("History of all {} resources", name),
+                        vec![],
+                    ),
+                }),
+            );
+
+            let mut tag = json!({ "name": name });
+            if !resource.documentation.short.is_empty() {
+                tag["description"] = json!(resource.documentation.short);
+            }
+            tags.push(tag);
+        }
+
+        let mut document = Map::new();
+        document.insert("openapi".to_string(), json!(OPENAPI_VERSION));
+        document.insert("jsonSchemaDialect".to_string(), json!(JSON_SCHEMA_DIALECT));
+        document.insert("info".to_string(), Value::Object(info));
+        if let Some(ref server_url) = self.server_url {
+            document.insert("servers".to_string(), json!([{ "url": server_url }]));
+        }
+        document.insert("tags".to_string(), Value::Array(tags));
+        document.insert("paths".to_string(), Value::Object(paths));
+        document.insert("components".to_string(), Self::components(graph));
+
+        Value::Object(document)
+    }
+
+    /// Shared schemas, parameters and responses
+    fn components(graph: &TypeGraph) -> Value {
+        let mut schemas = JsonSchemaGenerator::new().generate_bundle(graph)["$defs"].take();
+        Self::rebase_refs(&mut schemas);
+
+        let error_schema = Self::known_schema(graph, "OperationOutcome");
+        json!({
+            "schemas": schemas,
+            "parameters": {
+                "id": {
+                    "name": "id",
+                    "in": "path",
+                    "required": true,
+                    "description": "Logical id of the resource",
+                    "schema": { "type": "string", "pattern": "^[A-Za-z0-9\\-\\.]{1,64}$" },
+                },
+                "vid": {
+                    "name": "vid",
+                    "in": "path",
+                    "required": true,
+                    "description": "Version id of the resource",
+                    "schema": { "type": "string" },
+                },
+                "_count": {
+                    "name": "_count",
+                    "in": "query",
+                    "description": "Maximum number of results per page",
+                    "schema": { "type": "integer", "minimum": 0 },
+                },
+                "_since": {
+                    "name": "_since",
+                    "in": "query",
+                    "description": "Only include versions created at or after this instant",
+                    "schema": { "type": "string", "format": "date-time" },
+                },
+                "_at": {
+                    "name": "_at",
+                    "in": "query",
+                    "description": "Only include versions that were current at this time",
+                    "schema": { "type": "string" },
+                },
+            },
+            "responses": {
+                "Error": Self::response("The request failed", error_schema),
+            },
+        })
+    }
+
+    /// A plain interaction with an optional request body and one success response
+    fn operation(
+        interaction: &str,
+        resource: &ResourceType,
+        summary: String,
+        parameters: Vec<Value>,
+        request_body: Option<Value>,
+        (status, description, schema): (&str, String, Value),
+    ) -> Value {
+        let mut operation = json!({
+            "tags": [resource.name],
+            "summary": summary,
+            "operationId": format!("{}{}", interaction, resource.name),
+            "responses": {
+                status: Self::response(&description, schema),
+                "default": { "$ref": "#/components/responses/Error" },
+            },
+        });
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+        if let Some(schema) = request_body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { FHIR_JSON: { "schema": schema } },
+            });
+        }
+        operation
+    }
+
+    /// Search interaction with the resource's search parameters as query parameters
+    fn search_operation(graph: &TypeGraph, resource: &ResourceType) -> Value {
+        let mut parameters: Vec<Value> =
+            graph.search_parameters(resource).into_iter().map(Self::search_parameter).collect();
+        parameters.push(Self::parameter_ref("_count"));
+        parameters.push(json!({
+            "name": "_sort",
+            "in": "query",
+            "description": "Comma-separated search parameters to sort by; prefix with - for descending",
+            "schema": { "type": "string" },
+        }));
+
+        Self::operation(
+            "search",
+            resource,
+            format!("Search {} resources", resource.name),
+            parameters,
+            None,
+            ("200", "Search results".to_string(), Self::known_schema(graph, "Bundle")),
+        )
+    }
+
+    /// History interaction (instance or type level)
+    fn history_operation(
+        graph: &TypeGraph,
+        interaction: &str,
+        resource: &ResourceType,
+        summary: String,
+        mut parameters: Vec<Value>,
+    ) -> Value {
+        for name in ["_count", "_since", "_at"] {
+            parameters.push(Self::parameter_ref(name));
+        }
+        Self::operation(
+            interaction,
+            resource,
+            summary,
+            parameters,
+            None,
+            ("200", "History bundle".to_string(), Self::known_schema(graph, "Bundle")),
+        )
+    }
+
+    /// Patch interaction accepting a JSON Patch document
+    fn patch_operation(resource: &ResourceType) -> Value {
+        let mut operation = Self::operation(
+            "patch",
+            resource,
+            format!("Patch a {}", resource.name),
+            vec![Self::parameter_ref("id")],
+            None,
+            ("200", format!("{} patched", resource.name), Self::schema_ref(&resource.name)),
+        );
+        operation["requestBody"] = json!({
+            "required": true,
+            "content": {
+                "application/json-patch+json": {
+                    "schema": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["op", "path"],
+                            "properties": {
+                                "op": {
+                                    "enum": ["add", "remove", "replace", "move", "copy", "test"],
+                                },
+                                "path": { "type": "string" },
+                                "from": { "type": "string" },
+                                "value": {},
+                            },
+                        },
+                    },
+                },
+            },
+        });
+        operation
+    }
+
+    /// Delete interaction, which has no response body
+    fn delete_operation(resource: &ResourceType) -> Value {
+        json!({
+            "tags": [resource.name],
+            "summary": format!("Delete a {}", resource.name),
+            "operationId": format!("delete{}", resource.name),
+            "parameters": [Self::parameter_ref("id")],
+            "responses": {
+                "204": { "description": format!("{} deleted", resource.name) },
+                "default": { "$ref": "#/components/responses/Error" },
+            },
+        })
+    }
+
+    /// Query parameter for a search parameter, typed by its [`SearchParamType`]
+    fn search_parameter(parameter: &SearchParameter) -> Value {
+        let mut description = parameter.description.clone();
+        if !parameter.target_types.is_empty() {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("Targets: {}", parameter.target_types.join(", ")));
+        }
+
+        let mut query = json!({
+            "name": parameter.code,
+            "in": "query",
+            "schema": Self::search_value_schema(parameter.param_type),
+            "x-fhir-search-type": parameter.param_type.code(),
+        });
+        if !description.is_empty() {
+            query["description"] = json!(description);
+        }
+        query
+    }
+
+    /// Schema for the value of a search parameter
+    fn search_value_schema(param_type: SearchParamType) -> Value {
+        let number = r"-?\d+(\.\d+)?([eE][+-]?\d+)?";
+        match param_type {
+            SearchParamType::Number => {
+                json!({ "type": "string", "pattern": format!("^{}{}$", PREFIX, number) })
+            }
+            SearchParamType::Date => json!({
+                "type": "string",
+                "pattern": format!(r"^{}\d{{4}}(-\d{{2}}(-\d{{2}}(T[0-9:.]+(Z|[+-]\d{{2}}:\d{{2}})?)?)?)?$", PREFIX),
+            }),
+            SearchParamType::Quantity => json!({
+                "type": "string",
+                "pattern": format!(r"^{}{}(\|[^|]*\|[^|]*)?$", PREFIX, number),
+            }),
+            SearchParamType::Token => json!({
+                "type": "string",
+                "description": "[system]|[code], [code] or [system]|",
+            }),
+            SearchParamType::Reference => json!({
+                "type": "string",
+                "description": "[type]/[id], [id] or an absolute URL",
+            }),
+            SearchParamType::Uri => json!({ "type": "string", "format": "uri" }),
+            SearchParamType::String | SearchParamType::Composite | SearchParamType::Special => {
+                json!({ "type": "string" })
+            }
+        }
+    }
+
+    /// Response with a FHIR JSON body
+    fn response(description: &str, schema: Value) -> Value {
+        json!({
+            "description": description,
+            "content": { FHIR_JSON: { "schema": schema } },
+        })
+    }
+
+    /// Reference a component schema if the graph defines it, or accept any object
+    fn known_schema(graph: &TypeGraph, name: &str) -> Value {
+        if graph.resources.contains_key(name) {
+            Self::schema_ref(name)
+        } else {
+            json!({ "type": "object" })
+        }
+    }
+
+    /// `$ref` to a component schema
+    fn schema_ref(name: &str) -> Value {
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+
+    /// `$ref` to a component parameter
+    fn parameter_ref(name: &str) -> Value {
+        json!({ "$ref": format!("#/components/parameters/{}", name) })
+    }
+
+    /// Point JSON Schema `$defs` references at `components/schemas`
+    fn rebase_refs(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    if key == "$ref"
+                        && let Some(reference) = child.as_str()
+                        && let Some(name) = reference.strip_prefix("#/$defs/")
+                    {
+                        *child = json!(format!("#/components/schemas/{}", name));
+                    } else if key == "mapping"
+                        && let Value::Object(mapping) = child
+                    {
+                        for target in mapping.values_mut() {
+                            if let Some(name) =
+                                target.as_str().and_then(|t| t.strip_prefix("#/$defs/"))
+                            {
+                                *target = json!(format!("#/components/schemas/{}", name));
+                            }
+                        }
+                    } else {
+                        Self::rebase_refs(child);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(Self::rebase_refs),
+            _ => {}
+        }
+    }
+}
+
+#[async_trait]
+impl CodeGenerator for OpenApiGenerator {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        let started = std::time::Instant::now();
+
+        let mut generator = self.clone();
+        if let Some(ref module_name) = config.module_name {
+            generator.title.get_or_insert_with(|| module_name.clone());
+        }
+        generator.version.get_or_insert_with(|| config.package_version.clone());
+        if let Some(description) = config.get_custom::<String>("description") {
+            generator.description.get_or_insert(description);
+        }
+        if let Some(server_url) = config.get_custom::<String>("server_url") {
+            generator.server_url = Some(server_url);
+        }
+
+        let document = generator.generate_document(graph);
+        let content = serde_json::to_string_pretty(&document).map_err(|e| {
+            Error::Generator(format!("Failed to serialize OpenAPI document: {}", e))
+        })?;
+        let files = vec![GeneratedFile::new(
+            PathBuf::from(Self::file_name(config.module_name.as_deref())),
+            content + "\n",
+            FileType::Index,
+        )];
+
+        let mut statistics = GenerationStatistics::from_files(&files);
+        statistics.resources = graph.resources.values().filter(|r| !r.is_abstract).count();
+        statistics.datatypes = graph.datatypes.len();
+        statistics.generation_time_ms = started.elapsed().as_millis() as u64;
+
+        let manifest = GenerationManifest {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            generator: self.metadata(),
+            config: config.clone(),
+            statistics,
+            warnings: vec![],
+        };
+
+        Ok(GeneratedCode::new(files, manifest))
+    }
+
+    fn language(&self) -> Language {
+        Language::OpenApi
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        GeneratorMetadata {
+            name: "openapi".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            language: Language::OpenApi,
+            description: "OpenAPI 3.1 document for the FHIR RESTful API of each resource"
+                .to_string(),
+            author: "OctoFHIR Team".to_string(),
+            capabilities: GeneratorCapabilities {
+                search_parameters: true,
+                documentation: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Result<()> {
+        if let Some(server_url) = config.get_custom::<String>("server_url")
+            && !server_url.contains("://")
+            && !server_url.starts_with('/')
+        {
+            return Err(Error::Config(format!(
+                "Invalid OpenAPI server_url: '{}'. Must be an absolute URL or a path",
+                server_url
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion};
+
+    fn resource(name: &str, base: Option<&str>, is_abstract: bool) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: base.map(String::from),
+            properties: vec![],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: String::new(),
+            is_abstract,
+        }
+    }
+
+    fn search_parameter(code: &str, param_type: SearchParamType) -> SearchParameter {
+        SearchParameter {
+            code: code.to_string(),
+            param_type,
+            description: String::new(),
+            expression: None,
+            target_types: vec![],
+        }
+    }
+
+    #[test]
+    fn test_search_parameters_include_base_resources() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        let mut base = resource("Resource", None, true);
+        base.search_parameters.push(search_parameter("_id", SearchParamType::Token));
+        base.search_parameters.push(search_parameter("name", SearchParamType::Token));
+        graph.add_resource("Resource".to_string(), base);
+        let mut patient = resource("Patient", Some("Resource"), false);
+        patient.search_parameters.push(search_parameter("name", SearchParamType::String));
+        graph.add_resource("Patient".to_string(), patient);
+
+        let parameters = graph.search_parameters(&graph.resources["Patient"]);
+
+        let codes: Vec<_> = parameters.iter().map(|p| p.code.as_str()).collect();
+        assert_eq!(codes, vec!["name", "_id"]);
+        assert_eq!(parameters[0].param_type, SearchParamType::String);
+    }
+
+    #[test]
+    fn test_rebase_refs() {
+        let mut schema = json!({
+            "items": [{ "$ref": "#/$defs/HumanName" }],
+            "discriminator": { "mapping": { "Patient": "#/$defs/Patient" } },
+        });
+
+        OpenApiGenerator::rebase_refs(&mut schema);
+
+        assert_eq!(schema["items"][0]["$ref"], "#/components/schemas/HumanName");
+        assert_eq!(schema["discriminator"]["mapping"]["Patient"], "#/components/schemas/Patient");
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_uses_config() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource("Patient".to_string(), resource("Patient", None, false));
+        let mut config = GeneratorConfig {
+            module_name: Some("fhir-api".to_string()),
+            package_version: "2.1.0".to_string(),
+            ..Default::default()
+        };
+        config.set_custom("server_url".to_string(), "https://fhir.example.com/r4").unwrap();
+
+        let generator = OpenApiGenerator::new();
+        generator.validate_config(&config).unwrap();
+        let code = generator.generate(&graph, &config).await.unwrap();
+
+        assert_eq!(code.files[0].path, PathBuf::from("fhir-api.openapi.json"));
+        let document: Value = serde_json::from_str(&code.files[0].content).unwrap();
+        assert_eq!(document["info"]["title"], "fhir-api");
+        assert_eq!(document["info"]["version"], "2.1.0");
+        assert_eq!(document["servers"], json!([{ "url": "https://fhir.example.com/r4" }]));
+        assert_eq!(code.manifest.statistics.resources, 1);
+    }
+
+    #[test]
+    fn test_validate_config_rejects_bad_server_url() {
+        let mut config = GeneratorConfig::default();
+        config.set_custom("server_url".to_string(), "example.com").unwrap();
+
+        assert!(OpenApiGenerator::new().validate_config(&config).is_err());
+    }
+}
@@ -1,9 +1,10 @@
 //! TypeScript language backend
 
 use crate::Result;
-use crate::core::ir::{Documentation, PropertyType};
+use crate::core::ir::{BindingStrength, Documentation, Property, PropertyType, versionless_url};
 use crate::generator::{IdentifierContext, LanguageBackend};
 use heck::{ToLowerCamelCase, ToPascalCase};
+use std::collections::HashMap;
 
 /// TypeScript language backend
 #[derive(Clone)]
@@ -11,17 +12,59 @@ pub struct TypeScriptBackend {
     /// TypeScript version target
     #[allow(dead_code)]
     target_version: String,
+    /// Custom type mappings (FHIR type -> TypeScript type), checked before the defaults
+    type_mappings: HashMap<String, String>,
+    /// Generated value set types (versionless ValueSet URL -> TypeScript type name)
+    value_set_types: HashMap<String, String>,
 }
 
 impl TypeScriptBackend {
     /// Create new TypeScript backend
     pub fn new() -> Self {
-        Self { target_version: "5.9".to_string() }
+        Self::with_version("5.9".to_string())
     }
 
     /// Create with specific TypeScript version
     pub fn with_version(version: String) -> Self {
-        Self { target_version: version }
+        Self {
+            target_version: version,
+            type_mappings: HashMap::new(),
+            value_set_types: HashMap::new(),
+        }
+    }
+
+    /// Override the TypeScript type used for specific FHIR types
+    pub fn with_type_mappings(mut self, type_mappings: HashMap<String, String>) -> Self {
+        self.type_mappings = type_mappings;
+        self
+    }
+
+    /// Use generated value set types for `code` elements bound to these ValueSets
+    pub fn with_value_set_types(mut self, value_set_types: HashMap<String, String>) -> Self {
+        self.value_set_types = value_set_types;
+        self
+    }
+
+    /// Generated value set type and binding strength for a bound `code` property
+    ///
+    /// Only `required` and `extensible` bindings to a generated value set qualify.
+    pub fn bound_value_set(&self, property: &Property) -> Option<(&str, BindingStrength)> {
+        let binding = property.binding.as_ref()?;
+        if !matches!(binding.strength, BindingStrength::Required | BindingStrength::Extensible)
+            || !matches!(&property.property_type, PropertyType::Primitive { type_name } if type_name == "code")
+        {
+            return None;
+        }
+        let type_name = self.value_set_types.get(versionless_url(&binding.value_set))?;
+        Some((type_name, binding.strength))
+    }
+
+    /// Map a FHIR primitive or complex type name, honoring custom mappings
+    fn map_type_name(&self, type_name: &str) -> String {
+        match self.type_mappings.get(type_name) {
+            Some(mapped) => mapped.clone(),
+            None => Self::map_primitive_type(type_name),
+        }
     }
 
     /// Sanitize name to be a valid TypeScript identifier
@@ -30,7 +73,7 @@ impl TypeScriptBackend {
     pub fn sanitize_identifier(name: &str) -> String {
         // Replace hyphens and spaces with underscores for now
         // Later we could convert to PascalCase properly
-        name.replace('-', "_").replace(' ', "_").replace('.', "_")
+        name.replace(['-', ' ', '.'], "_")
     }
 }
 
@@ -43,10 +86,12 @@ impl Default for TypeScriptBackend {
 impl LanguageBackend for TypeScriptBackend {
     fn map_type(&self, property_type: &PropertyType) -> String {
         match property_type {
-            PropertyType::Primitive { type_name } => Self::map_primitive_type(type_name),
-            PropertyType::Complex { type_name } => type_name.clone(),
-            PropertyType::Reference { target_types } => {
-                if target_types.is_empty() {
+            PropertyType::Primitive { type_name } | PropertyType::Complex { type_name } => {
+                self.map_type_name(type_name)
+            }
+            PropertyType::Reference { target_types, .. } => {
+                // `Reference(Any)` targets the abstract Resource
+                if target_types.is_empty() || target_types.iter().any(|t| t == "Resource") {
                     "Reference".to_string()
                 } else if target_types.len() == 1 {
                     format!("Reference<\"{}\">", target_types[0])
@@ -63,20 +108,25 @@ impl LanguageBackend for TypeScriptBackend {
             PropertyType::BackboneElement { .. } => "BackboneElement".to_string(),
             PropertyType::Choice { types } => {
                 // Map each choice type (primitives to TS types, complex types as-is)
-                let mapped: Vec<String> = types
-                    .iter()
-                    .map(|t| {
-                        // Check if it's a primitive by trying to map it
-                        let mapped = Self::map_primitive_type(t);
-                        // If mapping didn't change it (not a primitive), use as-is
-                        if mapped == *t { t.clone() } else { mapped }
-                    })
-                    .collect();
+                let mapped: Vec<String> = types.iter().map(|t| self.map_type_name(t)).collect();
                 mapped.join(" | ")
             }
         }
     }
 
+    fn map_property_type(&self, property: &Property) -> String {
+        match self.bound_value_set(property) {
+            Some((type_name, BindingStrength::Required)) => type_name.to_string(),
+            // `string & {}` keeps editor completion for the known codes
+            Some((type_name, _)) => format!("{} | (string & {{}})", type_name),
+            None => self.map_type(&property.property_type),
+        }
+    }
+
+    fn value_set_type(&self, property: &Property) -> Option<String> {
+        self.bound_value_set(property).map(|(type_name, _)| type_name.to_string())
+    }
+
     fn generate_imports(&self, dependencies: &[String]) -> Vec<String> {
         if dependencies.is_empty() {
             return vec![];
@@ -273,6 +323,63 @@ mod tests {
         assert_eq!(TypeScriptBackend::map_primitive_type("dateTime"), "string");
     }
 
+    #[test]
+    fn test_map_property_type_with_value_set() {
+        use crate::core::ir::{BindingStrength, CardinalityRange, ValueSetBinding};
+
+        let backend = TypeScriptBackend::new().with_value_set_types(HashMap::from([(
+            "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+            "AdministrativeGender".to_string(),
+        )]));
+        let mut property = Property {
+            name: "gender".to_string(),
+            path: "Patient.gender".to_string(),
+            property_type: PropertyType::Primitive { type_name: "code".to_string() },
+            cardinality: CardinalityRange::optional(),
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: Some(ValueSetBinding {
+                strength: BindingStrength::Required,
+                value_set: "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1".to_string(),
+                description: None,
+            }),
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        };
+
+        assert_eq!(backend.map_property_type(&property), "AdministrativeGender");
+
+        property.binding.as_mut().unwrap().strength = BindingStrength::Extensible;
+        assert_eq!(backend.map_property_type(&property), "AdministrativeGender | (string & {})");
+
+        property.binding.as_mut().unwrap().strength = BindingStrength::Preferred;
+        assert_eq!(backend.map_property_type(&property), "string");
+        assert_eq!(backend.value_set_type(&property), None);
+    }
+
+    #[test]
+    fn test_custom_type_mappings() {
+        let backend = TypeScriptBackend::new().with_type_mappings(HashMap::from([
+            ("dateTime".to_string(), "Date".to_string()),
+            ("Quantity".to_string(), "Measurement".to_string()),
+        ]));
+
+        let date_time = PropertyType::Primitive { type_name: "dateTime".to_string() };
+        assert_eq!(backend.map_type(&date_time), "Date");
+
+        let quantity = PropertyType::Complex { type_name: "Quantity".to_string() };
+        assert_eq!(backend.map_type(&quantity), "Measurement");
+
+        let choice =
+            PropertyType::Choice { types: vec!["dateTime".to_string(), "boolean".to_string()] };
+        assert_eq!(backend.map_type(&choice), "Date | boolean");
+    }
+
     #[test]
     fn test_format_identifier() {
         let backend = TypeScriptBackend::new();
@@ -0,0 +1,551 @@
+//! Bundle utilities for TypeScript
+//!
+//! Generates `src/bundle.ts` around the `AnyResource` union: a transaction and
+//! batch builder with `urn:uuid` cross-references, entry narrowing by
+//! `resourceType`, typed `searchset` extraction and `Reference` resolution
+//! against bundle entries and contained resources.
+//!
+//! ```typescript
+//! const tx = new TransactionBuilder();
+//! const patient = tx.create({ resourceType: "Patient" });
+//! tx.create({ resourceType: "Observation", status: "final", code, subject: patient });
+//! const bundle = tx.build();
+//!
+//! const { matches } = searchsetResults(response, "Observation");
+//! ```
+
+use crate::core::Result;
+use crate::core::ir::TypeGraph;
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::resource_generator::ResourceGenerator;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+
+/// Bundle shape used when the package does not define Bundle
+const FALLBACK_BUNDLE: &str = r#"export interface Bundle {
+  resourceType: "Bundle";
+  type: string;
+  total?: number;
+  link?: Array<{ relation?: string; url?: string }>;
+  entry?: BundleEntry[];
+}"#;
+
+/// Generator for the Bundle utilities module
+pub struct BundleGenerator {
+    backend: TypeScriptBackend,
+    use_classes: bool,
+}
+
+impl BundleGenerator {
+    /// Create a bundle generator using a backend's type mappings
+    pub fn new(backend: TypeScriptBackend) -> Self {
+        Self { backend, use_classes: false }
+    }
+
+    /// Build bundles with the generated Bundle class's static `parse`
+    pub fn with_classes(mut self, use_classes: bool) -> Self {
+        self.use_classes = use_classes;
+        self
+    }
+
+    /// Generate `src/bundle.ts`
+    pub fn generate_bundle_module(&self, graph: &TypeGraph) -> Result<String> {
+        let names = ResourceGenerator::<TypeScriptBackend>::resource_union_members(graph);
+        let has_bundle = names.iter().any(|name| name == "Bundle");
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated Bundle utilities");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.line();
+
+        // Imports
+        for name in &names {
+            let keyword =
+                if self.use_classes && name == "Bundle" { "import" } else { "import type" };
+            tokens.append(format!("{} {{ {} }} from './resources/{}';", keyword, name, name));
+            tokens.push();
+        }
+        tokens.line();
+
+        // Resource union
+        tokens.append(
+            ResourceGenerator::new(self.backend.clone()).generate_resource_union(graph)?.trim_end(),
+        );
+        tokens.line();
+        tokens.append(helpers::type_alias(
+            "ResourceTypeName",
+            "AnyResource[\"resourceType\"]",
+            Some(&["`resourceType` of a resource in {@link AnyResource}".to_string()]),
+        ));
+        tokens.line();
+        tokens.append(helpers::type_alias(
+            "ResourceOfType<T extends string>",
+            "[Extract<AnyResource, { resourceType: T }>] extends [never] ? AnyResource : Extract<AnyResource, { resourceType: T }>",
+            Some(&[
+                "Resource of the union with the given `resourceType`".to_string(),
+                String::new(),
+                "Falls back to {@link AnyResource} when resources do not declare a literal `resourceType`."
+                    .to_string(),
+            ]),
+        ));
+        tokens.line();
+
+        // Entry shape and Bundle fallback
+        for line in BUNDLE_ENTRY.lines() {
+            tokens.append(line);
+            tokens.push();
+        }
+        tokens.line();
+        if !has_bundle {
+            tokens.append(helpers::jsdoc_comment(&[
+                "Minimal Bundle shape; the package does not define Bundle".to_string(),
+            ]));
+            tokens.push();
+            for line in FALLBACK_BUNDLE.lines() {
+                tokens.append(line);
+                tokens.push();
+            }
+            tokens.line();
+        }
+
+        tokens.append(helpers::jsdoc_comment(&["Entries of a bundle".to_string()]));
+        tokens.push();
+        tokens.append("function entriesOf(bundle: Bundle): BundleEntry[] {");
+        tokens.push();
+        tokens.append("  return (bundle as unknown as { entry?: BundleEntry[] }).entry ?? [];");
+        tokens.push();
+        tokens.append("}");
+        tokens.line();
+        tokens.append(helpers::jsdoc_comment(&["Bundle from plain JSON".to_string()]));
+        tokens.push();
+        tokens.append("function toBundle(json: object): Bundle {");
+        tokens.push();
+        if self.use_classes && has_bundle {
+            tokens.append("  return Bundle.parse(json);");
+        } else {
+            tokens.append("  return json as unknown as Bundle;");
+        }
+        tokens.push();
+        tokens.append("}");
+
+        let header = GencoTemplateEngine::format_typescript(&tokens)?;
+        Ok(format!("{}\n{}", header.trim_end(), BUNDLE_MODULE))
+    }
+}
+
+/// Entry shape read and written by the helpers
+const BUNDLE_ENTRY: &str = r#"/** Entry of a Bundle as read and written by these helpers */
+export interface BundleEntry {
+  fullUrl?: string;
+  resource?: AnyResource;
+  search?: { mode?: "match" | "include" | "outcome"; score?: number };
+  request?: {
+    method: "GET" | "HEAD" | "POST" | "PUT" | "DELETE" | "PATCH";
+    url: string;
+    ifNoneMatch?: string;
+    ifModifiedSince?: string;
+    ifMatch?: string;
+    ifNoneExist?: string;
+  };
+  response?: { status: string; location?: string; etag?: string; lastModified?: string };
+}"#;
+
+/// Static part of the Bundle utilities module
+const BUNDLE_MODULE: &str = r##"
+/** Check whether a value is a resource with the given `resourceType` */
+export function isResourceType<T extends ResourceTypeName>(
+  value: unknown,
+  type: T,
+): value is ResourceOfType<T> {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    (value as { resourceType?: unknown }).resourceType === type
+  );
+}
+
+/** Entries of a bundle whose resource has the given `resourceType` */
+export function bundleEntries<T extends ResourceTypeName>(
+  bundle: Bundle,
+  type: T,
+): Array<BundleEntry & { resource: ResourceOfType<T> }> {
+  return entriesOf(bundle).filter(
+    (entry): entry is BundleEntry & { resource: ResourceOfType<T> } =>
+      isResourceType(entry.resource, type),
+  );
+}
+
+/** Resources of a bundle with the given `resourceType` */
+export function bundleResources<T extends ResourceTypeName>(
+  bundle: Bundle,
+  type: T,
+): ResourceOfType<T>[] {
+  return bundleEntries(bundle, type).map((entry) => entry.resource);
+}
+
+/** Resources of a `searchset` bundle, split by search mode */
+export interface SearchsetResults<T extends ResourceTypeName> {
+  /** Resources matching the search */
+  matches: ResourceOfType<T>[];
+  /** Resources added by `_include` and `_revinclude` */
+  included: AnyResource[];
+  /** OperationOutcomes with warnings about the search */
+  outcomes: AnyResource[];
+  /** Total number of matches, when the server reports it */
+  total?: number;
+  /** URL of the next page */
+  next?: string;
+}
+
+/**
+ * Extract the results of a `searchset` bundle
+ *
+ * Entries without `search.mode` count as matches when their `resourceType`
+ * is the searched type.
+ */
+export function searchsetResults<T extends ResourceTypeName>(
+  bundle: Bundle,
+  type: T,
+): SearchsetResults<T> {
+  const results: SearchsetResults<T> = { matches: [], included: [], outcomes: [] };
+  for (const entry of entriesOf(bundle)) {
+    const resource = entry.resource;
+    if (resource === undefined) {
+      continue;
+    }
+    const mode = entry.search?.mode ?? (isResourceType(resource, type) ? "match" : "include");
+    if (mode === "match" && isResourceType(resource, type)) {
+      results.matches.push(resource);
+    } else if (mode === "outcome") {
+      results.outcomes.push(resource);
+    } else {
+      results.included.push(resource);
+    }
+  }
+  const { total, link } = bundle as unknown as {
+    total?: number;
+    link?: Array<{ relation?: string; url?: string }>;
+  };
+  if (total !== undefined) {
+    results.total = total;
+  }
+  const next = link?.find((l) => l.relation === "next")?.url;
+  if (next !== undefined) {
+    results.next = next;
+  }
+  return results;
+}
+
+/**
+ * A reference string or a Reference element
+ *
+ * A typed `Reference<T>` carries its target types in `type`, so resolving it
+ * narrows the result to those resources.
+ */
+export type ReferenceInput<T extends string = string> = string | { reference?: string; type?: T };
+
+function referenceString(reference: ReferenceInput): string | undefined {
+  return typeof reference === "string" ? reference : reference.reference;
+}
+
+/** `Type/id` of a relative or absolute RESTful reference, without `_history` */
+function typeAndId(reference: string): string | undefined {
+  const match = /(?:^|\/)([A-Z][A-Za-z]+)\/([A-Za-z0-9\-.]{1,64})(?:\/_history\/[A-Za-z0-9\-.]{1,64})?$/.exec(
+    reference,
+  );
+  return match ? `${match[1]}/${match[2]}` : undefined;
+}
+
+/**
+ * Resolve a reference against the contained resources of a resource
+ *
+ * `#id` resolves to the contained resource with that id and `#` to the
+ * resource itself.
+ */
+export function resolveContained(resource: object, reference: ReferenceInput): AnyResource | undefined {
+  const value = referenceString(reference);
+  if (value === undefined || !value.startsWith("#")) {
+    return undefined;
+  }
+  if (value === "#") {
+    return resource as AnyResource;
+  }
+  const contained = (resource as { contained?: AnyResource[] }).contained ?? [];
+  return contained.find((candidate) => (candidate as { id?: string }).id === value.slice(1));
+}
+
+/**
+ * Resolve a reference against a bundle or a resource's contained resources
+ *
+ * With a bundle, references match an entry's `fullUrl` (`urn:uuid:` and
+ * absolute URLs) or, for RESTful references, the entry resource's `Type/id`.
+ * Relative references are resolved against `from`, the `fullUrl` of the
+ * referring entry, when it is given; `#id` references then resolve against
+ * that entry's contained resources. With any other resource, `#id`
+ * references resolve against its contained resources.
+ *
+ * The result is narrowed to the target types of a `Reference<T>` (or to
+ * `options.type`), and a resource of another type resolves to `undefined`.
+ */
+export function resolveReference<T extends ResourceTypeName = ResourceTypeName>(
+  context: Bundle | object,
+  reference: ReferenceInput<T>,
+  options: { from?: string; type?: T } = {},
+): ResourceOfType<T> | undefined {
+  const value = referenceString(reference);
+  if (value === undefined) {
+    return undefined;
+  }
+
+  const isBundle = (context as { resourceType?: string }).resourceType === "Bundle";
+  let resource: AnyResource | undefined;
+  if (value.startsWith("#")) {
+    const container = isBundle
+      ? entriesOf(context as Bundle).find((entry) => options.from !== undefined && entry.fullUrl === options.from)
+          ?.resource
+      : context;
+    resource = container === undefined ? undefined : resolveContained(container, value);
+  } else if (isBundle) {
+    resource = resolveEntry(entriesOf(context as Bundle), value, options.from);
+  }
+
+  const type = options.type ?? (typeof reference === "string" ? undefined : reference.type);
+  if (resource === undefined || (type !== undefined && !isResourceType(resource, type))) {
+    return undefined;
+  }
+  return resource as ResourceOfType<T>;
+}
+
+/** Resource of the bundle entry a non-contained reference points at */
+function resolveEntry(entries: BundleEntry[], value: string, from: string | undefined): AnyResource | undefined {
+  let found = entries.find((entry) => entry.fullUrl === value);
+
+  const target = typeAndId(value);
+  if (found === undefined && target !== undefined) {
+    const base = from !== undefined && !value.includes("://") ? baseOf(from) : undefined;
+    found = entries.find((entry) => {
+      if (base !== undefined && entry.fullUrl === `${base}/${target}`) {
+        return true;
+      }
+      const resource = entry.resource as { resourceType?: string; id?: string } | undefined;
+      return resource !== undefined && `${resource.resourceType}/${resource.id}` === target;
+    });
+  }
+  return found?.resource;
+}
+
+/** Service base of an absolute RESTful URL (`http://x/fhir/Patient/1` -> `http://x/fhir`) */
+function baseOf(fullUrl: string): string | undefined {
+  const target = typeAndId(fullUrl);
+  return target !== undefined && fullUrl.includes("://") && fullUrl.endsWith(target)
+    ? fullUrl.slice(0, -target.length - 1)
+    : undefined;
+}
+
+/** Reference to an entry of a transaction being built */
+export interface EntryReference<T extends string> {
+  reference: string;
+  type: T;
+}
+
+/** Options of {@link TransactionBuilder} */
+export interface TransactionBuilderOptions {
+  /** Bundle type, `transaction` (all-or-nothing) or `batch` */
+  type?: "transaction" | "batch";
+  /** Generator of the ids in `urn:uuid:` fullUrls */
+  uuid?: () => string;
+}
+
+/**
+ * Builder of `transaction` and `batch` bundles
+ *
+ * Created resources get a `urn:uuid:` fullUrl; the returned reference can be
+ * placed in other entries and the server replaces it with the assigned id.
+ */
+export class TransactionBuilder {
+  private readonly entries: BundleEntry[] = [];
+  private readonly type: "transaction" | "batch";
+  private readonly uuid: () => string;
+
+  constructor(options: TransactionBuilderOptions = {}) {
+    this.type = options.type ?? "transaction";
+    this.uuid = options.uuid ?? randomUuid;
+  }
+
+  /** POST a new resource, optionally only if no resource matches `ifNoneExist` */
+  create<R extends AnyResource>(
+    resource: R,
+    options: { fullUrl?: string; ifNoneExist?: string } = {},
+  ): EntryReference<R["resourceType"]> {
+    const fullUrl = options.fullUrl ?? `urn:uuid:${this.uuid()}`;
+    const request: NonNullable<BundleEntry["request"]> = { method: "POST", url: resource.resourceType };
+    if (options.ifNoneExist !== undefined) {
+      request.ifNoneExist = options.ifNoneExist;
+    }
+    this.entries.push({ fullUrl, resource, request });
+    return { reference: fullUrl, type: resource.resourceType };
+  }
+
+  /** PUT a resource at `Type/id`, or at `url` for a conditional update */
+  update<R extends AnyResource>(
+    resource: R,
+    options: { url?: string; ifMatch?: string } = {},
+  ): EntryReference<R["resourceType"]> {
+    const id = (resource as { id?: string }).id;
+    const url = options.url ?? (id !== undefined ? `${resource.resourceType}/${id}` : undefined);
+    if (url === undefined) {
+      throw new Error(`update of ${resource.resourceType} needs an id or a conditional url`);
+    }
+    const request: NonNullable<BundleEntry["request"]> = { method: "PUT", url };
+    if (options.ifMatch !== undefined) {
+      request.ifMatch = options.ifMatch;
+    }
+    if (id !== undefined) {
+      this.entries.push({ resource, request });
+      return { reference: `${resource.resourceType}/${id}`, type: resource.resourceType };
+    }
+    const fullUrl = `urn:uuid:${this.uuid()}`;
+    this.entries.push({ fullUrl, resource, request });
+    return { reference: fullUrl, type: resource.resourceType };
+  }
+
+  /** DELETE `Type/id`, or the resources matching a search query */
+  delete(type: ResourceTypeName, idOrQuery: string): this {
+    const url = idOrQuery.includes("=") ? `${type}?${idOrQuery}` : `${type}/${idOrQuery}`;
+    this.entries.push({ request: { method: "DELETE", url } });
+    return this;
+  }
+
+  /** GET `Type/id` */
+  read(type: ResourceTypeName, id: string): this {
+    this.entries.push({ request: { method: "GET", url: `${type}/${id}` } });
+    return this;
+  }
+
+  /** GET a search, e.g. `search("Observation", "subject=Patient/1")` */
+  search(type: ResourceTypeName, query: string): this {
+    this.entries.push({ request: { method: "GET", url: query ? `${type}?${query}` : type } });
+    return this;
+  }
+
+  /** `urn:uuid:` references in entry resources without a matching entry */
+  unresolvedReferences(): string[] {
+    const fullUrls = new Set(this.entries.map((entry) => entry.fullUrl));
+    const unresolved = new Set<string>();
+    for (const entry of this.entries) {
+      collectReferences(entry.resource, (reference) => {
+        if (reference.startsWith("urn:uuid:") && !fullUrls.has(reference)) {
+          unresolved.add(reference);
+        }
+      });
+    }
+    return [...unresolved];
+  }
+
+  /** Build the bundle; throws when an `urn:uuid:` reference has no entry */
+  build(): Bundle {
+    const unresolved = this.unresolvedReferences();
+    if (unresolved.length > 0) {
+      throw new Error(`Unresolved bundle references: ${unresolved.join(", ")}`);
+    }
+    return toBundle({ resourceType: "Bundle", type: this.type, entry: [...this.entries] });
+  }
+}
+
+/**
+ * Map the fullUrls of a transaction's entries to the locations assigned in
+ * the server's `transaction-response`, e.g. `urn:uuid:...` -> `Patient/123`
+ */
+export function transactionLocations(request: Bundle, response: Bundle): Map<string, string> {
+  const locations = new Map<string, string>();
+  const responses = entriesOf(response);
+  entriesOf(request).forEach((entry, index) => {
+    const location = responses[index]?.response?.location;
+    const target = location !== undefined ? typeAndId(location) : undefined;
+    if (entry.fullUrl !== undefined && target !== undefined) {
+      locations.set(entry.fullUrl, target);
+    }
+  });
+  return locations;
+}
+
+/** Call `visit` with every `reference` string inside a value */
+function collectReferences(value: unknown, visit: (reference: string) => void): void {
+  if (Array.isArray(value)) {
+    value.forEach((item) => collectReferences(item, visit));
+  } else if (typeof value === "object" && value !== null) {
+    const json = "toJSON" in value && typeof value.toJSON === "function" ? value.toJSON() : value;
+    for (const [key, child] of Object.entries(json as Record<string, unknown>)) {
+      if (key === "reference" && typeof child === "string") {
+        visit(child);
+      } else {
+        collectReferences(child, visit);
+      }
+    }
+  }
+}
+
+/** Random RFC 4122 version 4 UUID */
+function randomUuid(): string {
+  return "xxxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx".replace(/[xy]/g, (c) => {
+    const r = (Math.random() * 16) | 0;
+    return (c === "x" ? r : (r & 0x3) | 0x8).toString(16);
+  });
+}
+""##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion, ResourceType};
+
+    fn graph(names: &[(&str, bool)]) -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        for (name, is_abstract) in names {
+            graph.add_resource(
+                name.to_string(),
+                ResourceType {
+                    name: name.to_string(),
+                    base: None,
+                    properties: vec![],
+                    constraints: vec![],
+                    search_parameters: vec![],
+                    extensions: vec![],
+                    documentation: Documentation::default(),
+                    url: String::new(),
+                    is_abstract: *is_abstract,
+                },
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_union_skips_abstract_resources() {
+        let graph = graph(&[("Resource", true), ("Bundle", false), ("Patient", false)]);
+        let code =
+            BundleGenerator::new(TypeScriptBackend::new()).generate_bundle_module(&graph).unwrap();
+
+        assert!(code.contains("export type AnyResource = Bundle | Patient;"));
+        assert!(!code.contains("'./resources/Resource'"));
+        assert!(code.contains("import type { Bundle } from './resources/Bundle';"));
+        assert!(!code.contains("Minimal Bundle shape"));
+    }
+
+    #[test]
+    fn test_fallback_bundle_and_classes() {
+        let code = BundleGenerator::new(TypeScriptBackend::new())
+            .generate_bundle_module(&graph(&[("Patient", false)]))
+            .unwrap();
+        assert!(code.contains("export interface Bundle {"));
+        assert!(code.contains("return json as unknown as Bundle;"));
+
+        let code = BundleGenerator::new(TypeScriptBackend::new())
+            .with_classes(true)
+            .generate_bundle_module(&graph(&[("Bundle", false), ("Patient", false)]))
+            .unwrap();
+        assert!(code.contains("import { Bundle } from './resources/Bundle';"));
+        assert!(code.contains("return Bundle.parse(json);"));
+    }
+}
@@ -1,4 +1,4 @@
-//! TypeScript choice element (discriminated union) generation
+//! TypeScript choice element (mutually exclusive variant) generation
 
 use crate::core::Result;
 use crate::core::ir::Property;
@@ -7,10 +7,9 @@ use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
 use genco::prelude::*;
 use heck::{ToPascalCase, ToSnakeCase};
 
-/// Generator for TypeScript choice element discriminated unions
+/// Generator for TypeScript choice element variant types and accessors
 pub struct ChoiceElementGenerator<B: LanguageBackend> {
     /// Language backend for type mapping
-    #[allow(dead_code)]
     backend: B,
 }
 
@@ -20,69 +19,94 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         Self { backend }
     }
 
-    /// Generate discriminated union type for a choice element
+    /// Variants of a choice property, erroring for non-choice properties
+    fn variants(property: &Property) -> Result<Vec<Property>> {
+        if !property.is_choice {
+            return Err(crate::core::Error::Generator(
+                "Property is not a choice element".to_string(),
+            ));
+        }
+        Ok(property.choice_variants())
+    }
+
+    /// Base name of a choice property (`value` for `value[x]`)
+    fn base_name(property: &Property) -> &str {
+        property.name.trim_end_matches("[x]")
+    }
+
+    /// Name of the mutual-exclusion type (`ObservationValueChoice`)
+    fn choice_name(property: &Property, resource_or_type_name: &str) -> String {
+        format!("{}{}Choice", resource_or_type_name, Self::base_name(property).to_pascal_case())
+    }
+
+    /// Name of the variant fields type (`ObservationValueFields`)
+    fn fields_name(property: &Property, resource_or_type_name: &str) -> String {
+        format!("{}{}Fields", resource_or_type_name, Self::base_name(property).to_pascal_case())
+    }
+
+    /// Name of the type guard for a variant (`hasObservationValueString`)
+    fn guard_name(variant: &Property, resource_or_type_name: &str) -> String {
+        format!("has{}{}", resource_or_type_name, variant.name.to_pascal_case())
+    }
+
+    /// Generate the variant fields and the mutual-exclusion type of a choice element
     ///
-    /// For a choice element like `value[x]` with types `string | boolean | integer`,
-    /// generates:
+    /// For a choice element like `value[x]` with types `string | boolean`, generates:
     /// ```typescript
-    /// export type ValueChoice =
-    ///   | { valueString: string }
-    ///   | { valueBoolean: boolean }
-    ///   | { valueInteger: number };
+    /// export interface ObservationValueFields {
+    ///   valueString?: string;
+    ///   valueBoolean?: boolean;
+    /// }
+    ///
+    /// export type ObservationValueChoice = ...;
     /// ```
+    /// where the choice type allows at most one of the fields to be set.
     pub fn generate_choice_union(
         &self,
         property: &Property,
         resource_or_type_name: &str,
     ) -> Result<String> {
-        if !property.is_choice {
-            return Err(crate::core::Error::Generator(
-                "Property is not a choice element".to_string(),
-            ));
-        }
+        let variants = Self::variants(property)?;
+        let base_name = Self::base_name(property);
+        let fields_name = Self::fields_name(property, resource_or_type_name);
+        let choice_name = Self::choice_name(property, resource_or_type_name);
 
         let mut tokens = js::Tokens::new();
 
-        // Extract base name (remove [x] suffix)
-        let base_name = property.name.replace("[x]", "");
-        let choice_name = format!("{}{}Choice", resource_or_type_name, base_name.to_pascal_case());
+        let fields: Vec<(String, String, bool)> = variants
+            .iter()
+            .map(|variant| (variant.name.clone(), self.backend.map_property_type(variant), true))
+            .collect();
+        let fields_doc = vec![format!("Variant fields of the {}[x] choice element", base_name)];
+        tokens.append(helpers::interface(&fields_name, None, &fields, Some(&fields_doc)));
+        tokens.line();
 
-        // JSDoc comment
         let doc = vec![
             format!("Choice type for {} property", base_name),
             String::new(),
             property.short_description.clone(),
+            String::new(),
+            "At most one of the variant fields may be set.".to_string(),
         ];
-
         tokens.append(helpers::jsdoc_comment(&doc));
         tokens.push();
 
-        // Type alias with union
-        tokens.append("export type ");
-        tokens.append(&choice_name);
-        tokens.append(" =");
+        tokens.append(format!("export type {} =", choice_name));
         tokens.push();
-
-        // Generate each variant
-        for (i, choice_type) in property.choice_types.iter().enumerate() {
-            if i > 0 {
-                tokens.push();
-            }
-
-            // Property name for this variant (e.g., valueString, valueBoolean)
-            let variant_name = format!("{}{}", base_name, choice_type.to_pascal_case());
-
-            // Map type
-            let ts_type = self.map_choice_type(choice_type);
-
-            tokens.append("  | { ");
-            tokens.append(&variant_name);
-            tokens.append(": ");
-            tokens.append(&ts_type);
-            tokens.append(" }");
-        }
-
-        tokens.append(";");
+        tokens.append("  | {");
+        tokens.push();
+        tokens.append(format!(
+            "      [K in keyof {}]-?: {{ [P in K]-?: NonNullable<{}[P]> }} & {{",
+            fields_name, fields_name
+        ));
+        tokens.push();
+        tokens.append(format!("        [P in Exclude<keyof {}, K>]?: never;", fields_name));
+        tokens.push();
+        tokens.append("      };");
+        tokens.push();
+        tokens.append(format!("    }}[keyof {}]", fields_name));
+        tokens.push();
+        tokens.append(format!("  | {{ [P in keyof {}]?: never }};", fields_name));
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
@@ -91,26 +115,25 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
     ///
     /// For each variant, generates a type guard like:
     /// ```typescript
-    /// export function hasValueString(obj: ValueChoice): obj is { valueString: string } {
-    ///   return 'valueString' in obj;
+    /// export function hasObservationValueString<T extends ObservationValueFields>(
+    ///   obj: T,
+    /// ): obj is T & { valueString: string } {
+    ///   return obj.valueString !== undefined;
     /// }
     /// ```
+    /// The guards accept any object with the variant fields, so both the
+    /// owning type and its choice type narrow.
     pub fn generate_choice_type_guards(
         &self,
         property: &Property,
         resource_or_type_name: &str,
     ) -> Result<String> {
-        if !property.is_choice {
-            return Err(crate::core::Error::Generator(
-                "Property is not a choice element".to_string(),
-            ));
-        }
+        let variants = Self::variants(property)?;
+        let base_name = Self::base_name(property);
+        let fields_name = Self::fields_name(property, resource_or_type_name);
 
         let mut tokens = js::Tokens::new();
 
-        let base_name = property.name.replace("[x]", "");
-        let choice_name = format!("{}{}Choice", resource_or_type_name, base_name.to_pascal_case());
-
         // Header comment
         tokens.append("/**");
         tokens.push();
@@ -118,18 +141,16 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         tokens.push();
         tokens.append(" */");
         tokens.push();
-        tokens.push();
+        tokens.line();
 
         // Generate a type guard for each variant
-        for (i, choice_type) in property.choice_types.iter().enumerate() {
+        for (i, variant) in variants.iter().enumerate() {
             if i > 0 {
-                tokens.push();
-                tokens.push();
+                tokens.line();
             }
 
-            let variant_name = format!("{}{}", base_name, choice_type.to_pascal_case());
-            let guard_name = format!("has{}", variant_name.to_pascal_case());
-            let ts_type = self.map_choice_type(choice_type);
+            let variant_name = &variant.name;
+            let ts_type = self.backend.map_property_type(variant);
 
             // JSDoc for this guard
             tokens.append("/**");
@@ -139,24 +160,16 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
             tokens.append(" */");
             tokens.push();
 
-            // Function signature
-            tokens.append("export function ");
-            tokens.append(&guard_name);
-            tokens.append("(obj: ");
-            tokens.append(&choice_name);
-            tokens.append("): obj is { ");
-            tokens.append(&variant_name);
-            tokens.append(": ");
-            tokens.append(&ts_type);
-            tokens.append(" } {");
+            tokens.append(format!(
+                "export function {}<T extends {}>(obj: T): obj is T & {{ {}: {} }} {{",
+                Self::guard_name(variant, resource_or_type_name),
+                fields_name,
+                variant_name,
+                ts_type
+            ));
             tokens.push();
-
-            // Implementation
-            tokens.append("  return '");
-            tokens.append(&variant_name);
-            tokens.append("' in obj;");
+            tokens.append(format!("  return obj.{} !== undefined;", variant_name));
             tokens.push();
-
             tokens.append("}");
         }
 
@@ -166,11 +179,12 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
     /// Generate a helper function to extract the value from a choice
     ///
     /// ```typescript
-    /// export function getValueChoiceValue(choice: ValueChoice): string | boolean | number {
-    ///   if (hasValueString(choice)) return choice.valueString;
-    ///   if (hasValueBoolean(choice)) return choice.valueBoolean;
-    ///   if (hasValueInteger(choice)) return choice.valueInteger;
-    ///   throw new Error('Invalid choice type');
+    /// export function getObservationValueChoiceValue(
+    ///   choice: ObservationValueFields,
+    /// ): string | boolean | undefined {
+    ///   if (hasObservationValueString(choice)) return choice.valueString;
+    ///   if (hasObservationValueBoolean(choice)) return choice.valueBoolean;
+    ///   return undefined;
     /// }
     /// ```
     pub fn generate_choice_value_extractor(
@@ -178,23 +192,24 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         property: &Property,
         resource_or_type_name: &str,
     ) -> Result<String> {
-        if !property.is_choice {
-            return Err(crate::core::Error::Generator(
-                "Property is not a choice element".to_string(),
-            ));
+        let variants = Self::variants(property)?;
+        let base_name = Self::base_name(property);
+        let fields_name = Self::fields_name(property, resource_or_type_name);
+        let choice_name = Self::choice_name(property, resource_or_type_name);
+        let function_name = format!("get{}Value", choice_name);
+
+        // Return type (union of the distinct variant types)
+        let mut return_types: Vec<String> = Vec::new();
+        for variant in &variants {
+            let ts_type = self.backend.map_property_type(variant);
+            if !return_types.contains(&ts_type) {
+                return_types.push(ts_type);
+            }
         }
+        return_types.push("undefined".to_string());
 
         let mut tokens = js::Tokens::new();
 
-        let base_name = property.name.replace("[x]", "");
-        let choice_name = format!("{}{}Choice", resource_or_type_name, base_name.to_pascal_case());
-        let function_name = format!("get{}Value", choice_name);
-
-        // Return type (union of all choice types)
-        let return_types: Vec<String> =
-            property.choice_types.iter().map(|t| self.map_choice_type(t)).collect();
-        let return_type = return_types.join(" | ");
-
         // JSDoc
         tokens.append("/**");
         tokens.push();
@@ -203,33 +218,26 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         tokens.append(" */");
         tokens.push();
 
-        // Function signature
-        tokens.append("export function ");
-        tokens.append(&function_name);
-        tokens.append("(choice: ");
-        tokens.append(&choice_name);
-        tokens.append("): ");
-        tokens.append(&return_type);
-        tokens.append(" {");
+        tokens.append(format!(
+            "export function {}(choice: {}): {} {{",
+            function_name,
+            fields_name,
+            return_types.join(" | ")
+        ));
         tokens.push();
 
         // Generate if statements for each variant
-        for choice_type in &property.choice_types {
-            let variant_name = format!("{}{}", base_name, choice_type.to_pascal_case());
-            let guard_name = format!("has{}", variant_name.to_pascal_case());
-
-            tokens.append("  if (");
-            tokens.append(&guard_name);
-            tokens.append("(choice)) return choice.");
-            tokens.append(&variant_name);
-            tokens.append(";");
+        for variant in &variants {
+            tokens.append(format!(
+                "  if ({}(choice)) return choice.{};",
+                Self::guard_name(variant, resource_or_type_name),
+                variant.name
+            ));
             tokens.push();
         }
 
-        // Fallback
-        tokens.append("  throw new Error('Invalid choice type');");
+        tokens.append("  return undefined;");
         tokens.push();
-
         tokens.append("}");
 
         GencoTemplateEngine::format_typescript(&tokens)
@@ -238,7 +246,7 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
     /// Generate complete choice element module
     ///
     /// Generates a complete TypeScript module with:
-    /// - Choice type definition
+    /// - Variant fields and mutual-exclusion choice type
     /// - Type guards
     /// - Value extractor
     pub fn generate_choice_module(
@@ -251,8 +259,11 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         // Module header
         tokens.append("/**");
         tokens.push();
-        tokens
-            .append(format!(" * Choice element for {}.{}", resource_or_type_name, &property.name));
+        tokens.append(format!(
+            " * Choice element for {}.{}[x]",
+            resource_or_type_name,
+            Self::base_name(property)
+        ));
         tokens.push();
         tokens.append(" * ");
         tokens.push();
@@ -260,44 +271,26 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         tokens.push();
         tokens.append(" */");
         tokens.push();
-        tokens.push();
+        tokens.line();
 
         // Choice type definition
         let choice_union = self.generate_choice_union(property, resource_or_type_name)?;
-        tokens.append(&choice_union);
-        tokens.push();
-        tokens.push();
+        tokens.append(choice_union.trim_end());
+        tokens.line();
 
         // Type guards
         let type_guards = self.generate_choice_type_guards(property, resource_or_type_name)?;
-        tokens.append(&type_guards);
-        tokens.push();
-        tokens.push();
+        tokens.append(type_guards.trim_end());
+        tokens.line();
 
         // Value extractor
         let value_extractor =
             self.generate_choice_value_extractor(property, resource_or_type_name)?;
-        tokens.append(&value_extractor);
+        tokens.append(value_extractor.trim_end());
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
 
-    /// Map choice type to TypeScript type
-    fn map_choice_type(&self, choice_type: &str) -> String {
-        // Use the backend's map_type for primitives
-        match choice_type {
-            "boolean" => "boolean".to_string(),
-            "integer" | "positiveInt" | "unsignedInt" => "number".to_string(),
-            "decimal" => "number".to_string(),
-            "string" | "code" | "id" | "markdown" | "uri" | "url" | "canonical" => {
-                "string".to_string()
-            }
-            "date" | "dateTime" | "instant" | "time" => "string".to_string(),
-            // Complex types - keep as-is
-            _ => choice_type.to_string(),
-        }
-    }
-
     /// Generate inline choice type for property
     ///
     /// For use directly in interface properties:
@@ -305,20 +298,12 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
     /// value?: { valueString: string } | { valueBoolean: boolean }
     /// ```
     pub fn generate_inline_choice_type(&self, property: &Property) -> Result<String> {
-        if !property.is_choice {
-            return Err(crate::core::Error::Generator(
-                "Property is not a choice element".to_string(),
-            ));
-        }
-
-        let base_name = property.name.replace("[x]", "");
-        let mut parts = Vec::new();
-
-        for choice_type in &property.choice_types {
-            let variant_name = format!("{}{}", base_name, choice_type.to_pascal_case());
-            let ts_type = self.map_choice_type(choice_type);
-            parts.push(format!("{{ {}: {} }}", variant_name, ts_type));
-        }
+        let parts: Vec<String> = Self::variants(property)?
+            .iter()
+            .map(|variant| {
+                format!("{{ {}: {} }}", variant.name, self.backend.map_property_type(variant))
+            })
+            .collect();
 
         Ok(parts.join(" | "))
     }
@@ -337,17 +322,12 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         property: &Property,
         resource_or_type_name: &str,
     ) -> Result<String> {
-        if !property.is_choice {
-            return Err(crate::core::Error::Generator(
-                "Property is not a choice element".to_string(),
-            ));
-        }
+        let variants = Self::variants(property)?;
+        let base_name = Self::base_name(property);
+        let enum_name = format!("{}{}Type", resource_or_type_name, base_name.to_pascal_case());
 
         let mut tokens = js::Tokens::new();
 
-        let base_name = property.name.replace("[x]", "");
-        let enum_name = format!("{}{}Type", resource_or_type_name, base_name.to_pascal_case());
-
         // JSDoc
         tokens.append("/**");
         tokens.push();
@@ -362,14 +342,13 @@ impl<B: LanguageBackend> ChoiceElementGenerator<B> {
         tokens.append(" = {");
         tokens.push();
 
-        for choice_type in &property.choice_types {
-            let variant_name = format!("{}{}", base_name, choice_type.to_pascal_case());
+        for (choice_type, variant) in property.choice_types.iter().zip(&variants) {
             let enum_key = choice_type.to_snake_case().to_uppercase();
 
             tokens.append("  ");
             tokens.append(&enum_key);
             tokens.append(": '");
-            tokens.append(&variant_name);
+            tokens.append(&variant.name);
             tokens.append("',");
             tokens.push();
         }
@@ -428,11 +407,13 @@ mod tests {
         assert!(result.is_ok());
 
         let output = result.unwrap();
+        assert!(output.contains("export interface ObservationValueFields"));
         assert!(output.contains("export type ObservationValueChoice"));
-        assert!(output.contains("valueString: string"));
-        assert!(output.contains("valueBoolean: boolean"));
-        assert!(output.contains("valueInteger: number"));
-        assert!(output.contains("valueQuantity: Quantity"));
+        assert!(output.contains("valueString?: string"));
+        assert!(output.contains("valueBoolean?: boolean"));
+        assert!(output.contains("valueInteger?: number"));
+        assert!(output.contains("valueQuantity?: Quantity"));
+        assert!(output.contains("[P in Exclude<keyof ObservationValueFields, K>]?: never;"));
     }
 
     #[test]
@@ -445,10 +426,10 @@ mod tests {
         assert!(result.is_ok());
 
         let output = result.unwrap();
-        assert!(output.contains("export function hasValueString"));
-        assert!(output.contains("export function hasValueBoolean"));
-        assert!(output.contains("export function hasValueInteger"));
-        assert!(output.contains("'valueString' in obj"));
+        assert!(output.contains("export function hasObservationValueString"));
+        assert!(output.contains("export function hasObservationValueBoolean"));
+        assert!(output.contains("export function hasObservationValueInteger"));
+        assert!(output.contains("return obj.valueString !== undefined;"));
     }
 
     #[test]
@@ -462,7 +443,7 @@ mod tests {
 
         let output = result.unwrap();
         assert!(output.contains("export function getObservationValueChoiceValue"));
-        assert!(output.contains("if (hasValueString(choice))"));
+        assert!(output.contains("if (hasObservationValueString(choice))"));
         assert!(output.contains("return choice.valueString"));
     }
 
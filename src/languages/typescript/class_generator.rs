@@ -4,23 +4,45 @@
 //! - Private fields with public getters/setters
 //! - Fluent API (method chaining)
 //! - Extension helper methods
-//! - JSON serialization (toJSON/parse)
+//! - JSON serialization (toJSON/parse) with primitives split into value and `_name` element
+//! - Recursive hydration of datatypes, backbone elements and contained resources,
+//!   preserving unknown fields, and canonical (definition order) toJSON output
+//! - Choice elements expanded into mutually exclusive `valueX` variants
 
 use crate::core::Result;
 use crate::core::ir::{CardinalityRange, DataType, Property, PropertyType, ResourceType};
 use crate::generator::LanguageBackend;
-use crate::languages::typescript::extension_generator::{
-    ExtensionDefinition, ExtensionGenerator, ExtensionValueType,
-};
+use crate::languages::typescript::extension_generator::{ExtensionDefinition, ExtensionGenerator};
+use crate::languages::typescript::templates::TypeScriptTemplates;
 
 /// Generator for TypeScript classes
 pub struct ClassGenerator;
 
+/// Field keeping JSON fields a class does not define, written back by `toJSON`
+const UNKNOWN_FIELDS_FIELD: &str = "  protected _unknownFields: Record<string, unknown> = {};\n";
+
+/// Functions of the serialization module (`src/serialization.ts`) used by classes
+const SERIALIZATION_FUNCTIONS: [&str; 4] =
+    ["parseResource", "parseValue", "toJSONValue", "unknownFields"];
+
+/// Abstract resource types, parsed by dispatching on `resourceType`
+const RESOURCE_BASE_TYPES: [&str; 2] = ["Resource", "DomainResource"];
+
 impl ClassGenerator {
     /// Generate a TypeScript class for a resource
     pub fn generate_resource_class<B: LanguageBackend>(
         resource: &ResourceType,
         backend: &B,
+    ) -> Result<String> {
+        Self::generate_resource_class_with_extensions(resource, backend, true)
+    }
+
+    /// Generate a TypeScript class for a resource, optionally omitting the
+    /// typed extension helper methods
+    pub fn generate_resource_class_with_extensions<B: LanguageBackend>(
+        resource: &ResourceType,
+        backend: &B,
+        include_extensions: bool,
     ) -> Result<String> {
         let mut code = String::new();
 
@@ -41,10 +63,18 @@ impl ClassGenerator {
         // Generate resourceType field for resources
         code.push_str("  resourceType: string;\n\n");
 
+        let properties = Self::json_properties(&resource.properties);
+
+        // Generate the JSON keys handled by the class
+        code.push_str(&Self::generate_json_keys(true, &properties));
+        code.push('\n');
+
         // Generate private fields
-        for prop in &resource.properties {
+        for (prop, _) in &properties {
             code.push_str(&Self::generate_private_field(prop, backend)?);
+            code.push_str(&Self::generate_element_field(prop));
         }
+        code.push_str(UNKNOWN_FIELDS_FIELD);
 
         code.push('\n');
 
@@ -54,15 +84,22 @@ impl ClassGenerator {
         code.push('\n');
 
         // Generate getters and setters
-        for prop in &resource.properties {
+        for (prop, siblings) in &properties {
             code.push_str(&Self::generate_getter(prop, backend)?);
             code.push('\n');
-            code.push_str(&Self::generate_setter(prop, backend)?);
+            code.push_str(&Self::generate_setter(prop, siblings, backend)?);
+            code.push_str(&Self::generate_element_accessors(prop));
+            code.push('\n');
+        }
+
+        // Generate choice getters returning whichever variant is set
+        for prop in resource.properties.iter().filter(|p| p.is_choice) {
+            code.push_str(&Self::generate_choice_getter(prop, backend));
             code.push('\n');
         }
 
         // Generate fluent builder methods (add/has methods)
-        for prop in &resource.properties {
+        for (prop, _) in &properties {
             if Self::is_array_property(prop) {
                 code.push_str(&Self::generate_add_method(prop, backend)?);
                 code.push('\n');
@@ -72,10 +109,11 @@ impl ClassGenerator {
         }
 
         // Generate extension helper methods if extensions are defined
-        if !resource.extensions.is_empty() {
+        if include_extensions && !resource.extensions.is_empty() {
             code.push_str("  // Extension helper methods\n\n");
-            for ext in &resource.extensions {
-                let ext_def = Self::convert_extension_to_definition(ext);
+            for ext_def in
+                resource.extensions.iter().filter_map(ExtensionDefinition::from_extension)
+            {
                 code.push_str(&ExtensionGenerator::generate_all_methods(&ext_def)?);
                 code.push('\n');
             }
@@ -108,17 +146,26 @@ impl ClassGenerator {
         }
 
         // Class declaration
-        let class_name = &datatype.name;
+        let class_name =
+            format!("{}{}", datatype.name, TypeScriptTemplates::type_parameters(&datatype.name));
         if let Some(base) = &datatype.base {
             code.push_str(&format!("export class {} extends {} {{\n", class_name, base));
         } else {
             code.push_str(&format!("export class {} {{\n", class_name));
         }
 
+        let properties = Self::json_properties(&TypeScriptTemplates::rendered_properties(datatype));
+
+        // Generate the JSON keys handled by the class
+        code.push_str(&Self::generate_json_keys(false, &properties));
+        code.push('\n');
+
         // Generate private fields
-        for prop in &datatype.properties {
+        for (prop, _) in &properties {
             code.push_str(&Self::generate_private_field(prop, backend)?);
+            code.push_str(&Self::generate_element_field(prop));
         }
+        code.push_str(UNKNOWN_FIELDS_FIELD);
 
         code.push('\n');
 
@@ -130,15 +177,22 @@ impl ClassGenerator {
         code.push_str("  }\n\n");
 
         // Generate getters and setters
-        for prop in &datatype.properties {
+        for (prop, siblings) in &properties {
             code.push_str(&Self::generate_getter(prop, backend)?);
             code.push('\n');
-            code.push_str(&Self::generate_setter(prop, backend)?);
+            code.push_str(&Self::generate_setter(prop, siblings, backend)?);
+            code.push_str(&Self::generate_element_accessors(prop));
+            code.push('\n');
+        }
+
+        // Generate choice getters returning whichever variant is set
+        for prop in datatype.properties.iter().filter(|p| p.is_choice) {
+            code.push_str(&Self::generate_choice_getter(prop, backend));
             code.push('\n');
         }
 
         // Generate fluent builder methods (add/has methods)
-        for prop in &datatype.properties {
+        for (prop, _) in &properties {
             if Self::is_array_property(prop) {
                 code.push_str(&Self::generate_add_method(prop, backend)?);
                 code.push('\n');
@@ -168,7 +222,7 @@ impl ClassGenerator {
     /// Generate private field declaration
     fn generate_private_field<B: LanguageBackend>(prop: &Property, backend: &B) -> Result<String> {
         let field_name = format!("_{}", &prop.name);
-        let ts_type = backend.map_type(&prop.property_type);
+        let ts_type = backend.map_property_type(prop);
 
         let optional = if Self::is_optional(&prop.cardinality) { "?" } else { "" };
 
@@ -194,7 +248,7 @@ impl ClassGenerator {
     fn generate_getter<B: LanguageBackend>(prop: &Property, backend: &B) -> Result<String> {
         let field_name = format!("_{}", &prop.name);
         let prop_name = &prop.name;
-        let ts_type = backend.map_type(&prop.property_type);
+        let ts_type = backend.map_property_type(prop);
 
         let optional = if Self::is_optional(&prop.cardinality) { " | undefined" } else { "" };
 
@@ -205,17 +259,74 @@ impl ClassGenerator {
     }
 
     /// Generate setter for a property
-    fn generate_setter<B: LanguageBackend>(prop: &Property, backend: &B) -> Result<String> {
+    ///
+    /// Setting a choice variant clears the other variants of its choice element.
+    fn generate_setter<B: LanguageBackend>(
+        prop: &Property,
+        siblings: &[Property],
+        backend: &B,
+    ) -> Result<String> {
         let field_name = format!("_{}", &prop.name);
         let setter_name = format!("set{}", Self::capitalize(&prop.name));
-        let ts_type = backend.map_type(&prop.property_type);
+        let ts_type = backend.map_property_type(prop);
+
+        let mut clears = String::new();
+        for sibling in siblings {
+            clears.push_str(&format!("    this._{} = undefined;\n", sibling.name));
+            if let Some(element_field) = Self::element_field_name(sibling) {
+                clears.push_str(&format!("    this.{} = undefined;\n", element_field));
+            }
+        }
 
         Ok(format!(
-            "  {}(value: {}): this {{\n    this.{} = value;\n    return this;\n  }}\n",
-            setter_name, ts_type, field_name
+            "  {}(value: {}): this {{\n{}    this.{} = value;\n    return this;\n  }}\n",
+            setter_name, ts_type, clears, field_name
         ))
     }
 
+    /// Generate a getter returning the value of whichever choice variant is set
+    fn generate_choice_getter<B: LanguageBackend>(prop: &Property, backend: &B) -> String {
+        let variants = prop.choice_variants();
+        let mut types: Vec<String> = Vec::new();
+        for variant in &variants {
+            let ts_type = backend.map_property_type(variant);
+            if !types.contains(&ts_type) {
+                types.push(ts_type);
+            }
+        }
+        let fields: Vec<String> = variants.iter().map(|v| format!("this._{}", v.name)).collect();
+
+        format!(
+            "  get {}(): {} | undefined {{\n    return {};\n  }}\n",
+            prop.name.trim_end_matches("[x]"),
+            types.join(" | "),
+            fields.join(" ?? ")
+        )
+    }
+
+    /// Properties as keyed in FHIR JSON, each paired with the other variants
+    /// of its choice element (empty outside choices)
+    fn json_properties(properties: &[Property]) -> Vec<(Property, Vec<Property>)> {
+        let mut expanded = Vec::new();
+        for prop in properties {
+            let variants = prop.choice_variants();
+            if variants.is_empty() {
+                expanded.push((prop.clone(), vec![]));
+                continue;
+            }
+            for (i, variant) in variants.iter().enumerate() {
+                let siblings = variants
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, sibling)| sibling.clone())
+                    .collect();
+                expanded.push((variant.clone(), siblings));
+            }
+        }
+        expanded
+    }
+
     /// Generate add method for array properties
     fn generate_add_method<B: LanguageBackend>(prop: &Property, backend: &B) -> Result<String> {
         let field_name = format!("_{}", &prop.name);
@@ -242,6 +353,10 @@ impl ClassGenerator {
     }
 
     /// Generate toJSON method
+    ///
+    /// Keys are written in canonical FHIR order: `resourceType`, then elements in
+    /// definition order with each `_name` sibling after its primitive, then the
+    /// preserved unknown fields.
     fn generate_to_json(resource: &ResourceType) -> Result<String> {
         let mut code = String::new();
         code.push_str("  toJSON(): any {\n");
@@ -249,15 +364,11 @@ impl ClassGenerator {
         code.push_str("      resourceType: this.resourceType\n");
         code.push_str("    };\n");
 
-        for prop in &resource.properties {
-            let prop_name = &prop.name;
-            let field_name = format!("_{}", &prop.name);
-            code.push_str(&format!(
-                "    if (this.{} !== undefined) {{\n      obj.{} = this.{};\n    }}\n",
-                field_name, prop_name, field_name
-            ));
+        for (prop, _) in Self::json_properties(&resource.properties) {
+            code.push_str(&Self::generate_to_json_property(&prop));
         }
 
+        code.push_str("    Object.assign(obj, this._unknownFields);\n");
         code.push_str("    return obj;\n");
         code.push_str("  }\n");
 
@@ -265,6 +376,9 @@ impl ClassGenerator {
     }
 
     /// Generate static parse method
+    ///
+    /// Nested datatypes, backbone elements and contained resources are hydrated
+    /// recursively, and fields missing from `jsonKeys` are kept for `toJSON`.
     fn generate_parse_method(resource: &ResourceType) -> Result<String> {
         let class_name = &resource.name;
         let mut code = String::new();
@@ -272,15 +386,14 @@ impl ClassGenerator {
         code.push_str(&format!("  static parse(json: any): {} {{\n", class_name));
         code.push_str(&format!("    const instance = new {}();\n", class_name));
 
-        for prop in &resource.properties {
-            let prop_name = &prop.name;
-            let setter_name = format!("set{}", Self::capitalize(prop_name));
-            code.push_str(&format!(
-                "    if (json.{} !== undefined) {{\n      instance.{}(json.{});\n    }}\n",
-                prop_name, setter_name, prop_name
-            ));
+        for (prop, _) in Self::json_properties(&resource.properties) {
+            code.push_str(&Self::generate_parse_property(&prop));
         }
 
+        code.push_str(&format!(
+            "    instance._unknownFields = unknownFields(json, {}.jsonKeys);\n",
+            class_name
+        ));
         code.push_str("    return instance;\n");
         code.push_str("  }\n");
 
@@ -293,15 +406,11 @@ impl ClassGenerator {
         code.push_str("  toJSON(): any {\n");
         code.push_str("    const obj: any = {};\n");
 
-        for prop in &datatype.properties {
-            let prop_name = &prop.name;
-            let field_name = format!("_{}", &prop.name);
-            code.push_str(&format!(
-                "    if (this.{} !== undefined) {{\n      obj.{} = this.{};\n    }}\n",
-                field_name, prop_name, field_name
-            ));
+        for (prop, _) in Self::json_properties(&datatype.properties) {
+            code.push_str(&Self::generate_to_json_property(&prop));
         }
 
+        code.push_str("    Object.assign(obj, this._unknownFields);\n");
         code.push_str("    return obj;\n");
         code.push_str("  }\n");
 
@@ -316,21 +425,176 @@ impl ClassGenerator {
         code.push_str(&format!("  static parse(json: any): {} {{\n", class_name));
         code.push_str(&format!("    const instance = new {}();\n", class_name));
 
-        for prop in &datatype.properties {
-            let prop_name = &prop.name;
-            let setter_name = format!("set{}", Self::capitalize(prop_name));
-            code.push_str(&format!(
-                "    if (json.{} !== undefined) {{\n      instance.{}(json.{});\n    }}\n",
-                prop_name, setter_name, prop_name
-            ));
+        for (prop, _) in Self::json_properties(&datatype.properties) {
+            code.push_str(&Self::generate_parse_property(&prop));
         }
 
+        code.push_str(&format!(
+            "    instance._unknownFields = unknownFields(json, {}.jsonKeys);\n",
+            class_name
+        ));
         code.push_str("    return instance;\n");
         code.push_str("  }\n");
 
         Ok(code)
     }
 
+    /// Generate the static list of JSON keys a class reads and writes
+    fn generate_json_keys(is_resource: bool, properties: &[(Property, Vec<Property>)]) -> String {
+        let mut keys: Vec<String> = Vec::new();
+        if is_resource {
+            keys.push("resourceType".to_string());
+        }
+        for (prop, _) in properties {
+            keys.push(prop.name.clone());
+            keys.extend(prop.primitive_element_name());
+        }
+
+        let mut code = String::from("  static readonly jsonKeys: readonly string[] = [");
+        if !keys.is_empty() {
+            code.push('\n');
+            for key in &keys {
+                code.push_str(&format!("    \"{}\",\n", key));
+            }
+            code.push_str("  ");
+        }
+        code.push_str("];\n");
+        code
+    }
+
+    /// Functions of the serialization module used by generated class code
+    ///
+    /// Class files import these from `src/serialization.ts`.
+    pub fn serialization_imports(code: &str) -> Vec<String> {
+        SERIALIZATION_FUNCTIONS
+            .iter()
+            .filter(|name| code.contains(*name))
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Parser function hydrating a JSON element of a property type
+    ///
+    /// Returns `None` for primitives, which are kept as-is. Backbone elements are
+    /// copied with their own hydrated properties.
+    fn element_parser(property_type: &PropertyType) -> Option<String> {
+        match property_type {
+            PropertyType::Primitive { .. } | PropertyType::Choice { .. } => None,
+            PropertyType::Complex { type_name }
+                if RESOURCE_BASE_TYPES.contains(&type_name.as_str()) =>
+            {
+                Some("parseResource".to_string())
+            }
+            PropertyType::Complex { type_name } => Some(format!("{}.parse", type_name)),
+            PropertyType::Reference { .. } => Some("Reference.parse".to_string()),
+            PropertyType::BackboneElement { properties } => {
+                let fields: Vec<String> = Property::json_properties(properties)
+                    .iter()
+                    .filter_map(|prop| {
+                        Self::element_parser(&prop.property_type).map(|parser| {
+                            format!("{}: parseValue(item.{}, {})", prop.name, prop.name, parser)
+                        })
+                    })
+                    .collect();
+                if fields.is_empty() {
+                    None
+                } else {
+                    Some(format!("(item: any) => ({{ ...item, {} }})", fields.join(", ")))
+                }
+            }
+        }
+    }
+
+    /// Private field holding a primitive's `_name` sibling element
+    fn generate_element_field(prop: &Property) -> String {
+        match Self::element_field_name(prop) {
+            Some(field_name) => format!(
+                "  private {}?: {};\n",
+                field_name,
+                TypeScriptTemplates::primitive_element_type(prop)
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Getter and setter for a primitive's element data (`id`, extensions)
+    fn generate_element_accessors(prop: &Property) -> String {
+        let Some(field_name) = Self::element_field_name(prop) else {
+            return String::new();
+        };
+        let ts_type = TypeScriptTemplates::primitive_element_type(prop);
+        let accessor = format!("{}Element", prop.name);
+
+        format!(
+            "\n  get {}(): {} | undefined {{\n    return this.{};\n  }}\n\n  set{}(value: {}): this {{\n    this.{} = value;\n    return this;\n  }}\n",
+            accessor,
+            ts_type,
+            field_name,
+            Self::capitalize(&accessor),
+            ts_type,
+            field_name
+        )
+    }
+
+    /// toJSON statements for a property, splitting primitives into value and `_name`
+    ///
+    /// Complex values are converted to plain JSON recursively.
+    fn generate_to_json_property(prop: &Property) -> String {
+        let prop_name = &prop.name;
+        let field_name = format!("_{}", prop_name);
+        let value = match Self::element_parser(&prop.property_type) {
+            Some(_) => format!("toJSONValue(this.{})", field_name),
+            None => format!("this.{}", field_name),
+        };
+        let mut code = format!(
+            "    if (this.{} !== undefined) {{\n      obj.{} = {};\n    }}\n",
+            field_name, prop_name, value
+        );
+
+        if let (Some(element_name), Some(element_field)) =
+            (prop.primitive_element_name(), Self::element_field_name(prop))
+        {
+            code.push_str(&format!(
+                "    if (this.{} !== undefined) {{\n      obj.{} = toJSONValue(this.{});\n    }}\n",
+                element_field, element_name, element_field
+            ));
+        }
+
+        code
+    }
+
+    /// parse statements for a property, joining primitives with their `_name` sibling
+    ///
+    /// Complex values are hydrated into their classes, element by element for arrays.
+    fn generate_parse_property(prop: &Property) -> String {
+        let prop_name = &prop.name;
+        let setter_name = format!("set{}", Self::capitalize(prop_name));
+        let value = match Self::element_parser(&prop.property_type) {
+            Some(parser) => format!("parseValue(json.{}, {})", prop_name, parser),
+            None => format!("json.{}", prop_name),
+        };
+        let mut code = format!(
+            "    if (json.{} !== undefined) {{\n      instance.{}({});\n    }}\n",
+            prop_name, setter_name, value
+        );
+
+        if let Some(element_name) = prop.primitive_element_name() {
+            code.push_str(&format!(
+                "    if (json.{} !== undefined) {{\n      instance.set{}Element(parseValue(json.{}, Element.parse));\n    }}\n",
+                element_name,
+                Self::capitalize(prop_name),
+                element_name
+            ));
+        }
+
+        code
+    }
+
+    /// Private field name for a primitive's element data (`_birthDateElement`)
+    fn element_field_name(prop: &Property) -> Option<String> {
+        prop.primitive_element_name().map(|_| format!("_{}Element", prop.name))
+    }
+
     /// Check if property is an array type
     fn is_array_property(prop: &Property) -> bool {
         // Array if max is None (unbounded) or > 1
@@ -359,60 +623,6 @@ impl ClassGenerator {
             Some(first) => first.to_uppercase().chain(chars).collect(),
         }
     }
-
-    /// Convert IR Extension to ExtensionDefinition
-    fn convert_extension_to_definition(ext: &crate::core::ir::Extension) -> ExtensionDefinition {
-        // Determine value type
-        let value_type = if ext.value_types.len() == 1 {
-            let type_name = &ext.value_types[0];
-            // Check if it's a primitive or complex type
-            if Self::is_primitive_type(type_name) {
-                ExtensionValueType::Primitive(type_name.clone())
-            } else {
-                ExtensionValueType::Complex(type_name.clone())
-            }
-        } else if ext.value_types.len() > 1 {
-            ExtensionValueType::Choice(ext.value_types.clone())
-        } else {
-            // Default to string if no value types specified
-            ExtensionValueType::Primitive("string".to_string())
-        };
-
-        ExtensionDefinition {
-            url: ext.url.clone(),
-            name: ext.name.clone(),
-            value_type,
-            target_types: ext.target_types.clone(),
-            description: ext.documentation.short.clone(),
-            is_array: ext.cardinality.is_array(),
-        }
-    }
-
-    /// Check if a type name is a FHIR primitive
-    fn is_primitive_type(type_name: &str) -> bool {
-        matches!(
-            type_name,
-            "boolean"
-                | "integer"
-                | "string"
-                | "decimal"
-                | "uri"
-                | "url"
-                | "canonical"
-                | "base64Binary"
-                | "instant"
-                | "date"
-                | "dateTime"
-                | "time"
-                | "code"
-                | "oid"
-                | "id"
-                | "markdown"
-                | "unsignedInt"
-                | "positiveInt"
-                | "uuid"
-        )
-    }
 }
 
 #[cfg(test)]
@@ -476,6 +686,245 @@ mod tests {
         assert!(!ClassGenerator::is_optional(&required));
     }
 
+    #[test]
+    fn test_primitive_element_round_trip() {
+        let backend = TypeScriptBackend::new();
+        let property = |name: &str, type_name: &str, cardinality| Property {
+            name: name.to_string(),
+            path: format!("Patient.{}", name),
+            property_type: PropertyType::Primitive { type_name: type_name.to_string() },
+            cardinality,
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: "".to_string(),
+            definition: "".to_string(),
+            comments: None,
+            examples: vec![],
+        };
+        let resource = ResourceType {
+            name: "Patient".to_string(),
+            base: Some("DomainResource".to_string()),
+            properties: vec![
+                property("birthDate", "date", CardinalityRange::optional()),
+                property("alias", "string", CardinalityRange::optional_array()),
+            ],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Default::default(),
+            url: String::new(),
+            is_abstract: false,
+        };
+
+        let code = ClassGenerator::generate_resource_class(&resource, &backend).unwrap();
+
+        assert!(code.contains("  private _birthDateElement?: Element;\n"));
+        assert!(code.contains("  private _aliasElement?: (Element | null)[];\n"));
+        assert!(code.contains("  get birthDateElement(): Element | undefined {"));
+        assert!(code.contains("  setBirthDateElement(value: Element): this {"));
+        assert!(code.contains(
+            "    if (this._birthDateElement !== undefined) {\n      obj._birthDate = toJSONValue(this._birthDateElement);\n    }\n"
+        ));
+        assert!(code.contains(
+            "    if (json._birthDate !== undefined) {\n      instance.setBirthDateElement(parseValue(json._birthDate, Element.parse));\n    }\n"
+        ));
+        assert!(code.contains(
+            "    if (json._alias !== undefined) {\n      instance.setAliasElement(parseValue(json._alias, Element.parse));\n    }\n"
+        ));
+    }
+
+    #[test]
+    fn test_choice_variants() {
+        let backend = TypeScriptBackend::new();
+        let resource = ResourceType {
+            name: "Patient".to_string(),
+            base: Some("DomainResource".to_string()),
+            properties: vec![Property {
+                name: "deceased".to_string(),
+                path: "Patient.deceased[x]".to_string(),
+                property_type: PropertyType::Choice {
+                    types: vec!["boolean".to_string(), "dateTime".to_string()],
+                },
+                cardinality: CardinalityRange::optional(),
+                is_choice: true,
+                choice_types: vec!["boolean".to_string(), "dateTime".to_string()],
+                is_modifier: true,
+                is_summary: true,
+                binding: None,
+                constraints: vec![],
+                short_description: "".to_string(),
+                definition: "".to_string(),
+                comments: None,
+                examples: vec![],
+            }],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Default::default(),
+            url: String::new(),
+            is_abstract: false,
+        };
+
+        let code = ClassGenerator::generate_resource_class(&resource, &backend).unwrap();
+
+        assert!(!code.contains("_deceased?:"));
+        assert!(code.contains("  private _deceasedBoolean?: boolean;\n"));
+        assert!(code.contains("  private _deceasedDateTimeElement?: Element;\n"));
+        assert!(code.contains(
+            "  setDeceasedBoolean(value: boolean): this {\n    this._deceasedDateTime = undefined;\n    this._deceasedDateTimeElement = undefined;\n    this._deceasedBoolean = value;\n"
+        ));
+        assert!(code.contains(
+            "  get deceased(): boolean | string | undefined {\n    return this._deceasedBoolean ?? this._deceasedDateTime;\n  }\n"
+        ));
+        assert!(code.contains("      obj.deceasedDateTime = this._deceasedDateTime;\n"));
+        assert!(code.contains("      instance.setDeceasedDateTime(json.deceasedDateTime);\n"));
+    }
+
+    #[test]
+    fn test_typed_reference_class() {
+        let backend = TypeScriptBackend::new();
+        let property = |name: &str, type_name: &str| Property {
+            name: name.to_string(),
+            path: format!("Reference.{}", name),
+            property_type: PropertyType::Primitive { type_name: type_name.to_string() },
+            cardinality: CardinalityRange::optional(),
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: true,
+            binding: None,
+            constraints: vec![],
+            short_description: "".to_string(),
+            definition: "".to_string(),
+            comments: None,
+            examples: vec![],
+        };
+        let datatype = DataType {
+            name: "Reference".to_string(),
+            base: Some("Element".to_string()),
+            properties: vec![property("reference", "string"), property("type", "uri")],
+            constraints: vec![],
+            documentation: Default::default(),
+            url: String::new(),
+            is_abstract: false,
+        };
+
+        let code = ClassGenerator::generate_datatype_class(&datatype, &backend).unwrap();
+
+        assert!(code.contains(
+            "export class Reference<T extends ResourceType = ResourceType> extends Element {\n"
+        ));
+        assert!(code.contains("  private _reference?: ReferenceString<T>;\n"));
+        assert!(code.contains("  private _type?: T;\n"));
+    }
+
+    #[test]
+    fn test_parse_hydrates_nested_values() {
+        let backend = TypeScriptBackend::new();
+        let property = |name: &str, property_type: PropertyType, cardinality| Property {
+            name: name.to_string(),
+            path: format!("Patient.{}", name),
+            property_type,
+            cardinality,
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: "".to_string(),
+            definition: "".to_string(),
+            comments: None,
+            examples: vec![],
+        };
+        let resource = ResourceType {
+            name: "Patient".to_string(),
+            base: Some("DomainResource".to_string()),
+            properties: vec![
+                property(
+                    "active",
+                    PropertyType::Primitive { type_name: "boolean".to_string() },
+                    CardinalityRange::optional(),
+                ),
+                property(
+                    "contained",
+                    PropertyType::Complex { type_name: "Resource".to_string() },
+                    CardinalityRange::optional_array(),
+                ),
+                property(
+                    "name",
+                    PropertyType::Complex { type_name: "HumanName".to_string() },
+                    CardinalityRange::optional_array(),
+                ),
+                property(
+                    "contact",
+                    PropertyType::BackboneElement {
+                        properties: vec![
+                            property(
+                                "gender",
+                                PropertyType::Primitive { type_name: "code".to_string() },
+                                CardinalityRange::optional(),
+                            ),
+                            property(
+                                "organization",
+                                PropertyType::Reference {
+                                    target_types: vec!["Organization".to_string()],
+                                    target_profiles: vec![],
+                                },
+                                CardinalityRange::optional(),
+                            ),
+                        ],
+                    },
+                    CardinalityRange::optional_array(),
+                ),
+            ],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Default::default(),
+            url: String::new(),
+            is_abstract: false,
+        };
+
+        let code = ClassGenerator::generate_resource_class(&resource, &backend).unwrap();
+
+        assert!(code.contains(
+            "  static readonly jsonKeys: readonly string[] = [\n    \"resourceType\",\n    \"active\",\n    \"_active\",\n    \"contained\",\n    \"name\",\n    \"contact\",\n  ];\n"
+        ));
+        assert!(code.contains("  protected _unknownFields: Record<string, unknown> = {};\n"));
+        assert!(code.contains("      instance.setActive(json.active);\n"));
+        assert!(
+            code.contains(
+                "      instance.setContained(parseValue(json.contained, parseResource));\n"
+            )
+        );
+        assert!(code.contains("      instance.setName(parseValue(json.name, HumanName.parse));\n"));
+        assert!(code.contains(
+            "      instance.setContact(parseValue(json.contact, (item: any) => ({ ...item, organization: parseValue(item.organization, Reference.parse) })));\n"
+        ));
+        assert!(code.contains(
+            "    instance._unknownFields = unknownFields(json, Patient.jsonKeys);\n    return instance;\n"
+        ));
+
+        assert!(code.contains("      obj.active = this._active;\n"));
+        assert!(code.contains("      obj.name = toJSONValue(this._name);\n"));
+        assert!(code.contains("    Object.assign(obj, this._unknownFields);\n    return obj;\n"));
+        // Canonical order follows the element definitions, not the setter calls
+        let active = code.find("obj.active =").unwrap();
+        let contained = code.find("obj.contained =").unwrap();
+        let contact = code.find("obj.contact =").unwrap();
+        assert!(active < contained && contained < contact);
+
+        assert_eq!(
+            ClassGenerator::serialization_imports(&code),
+            vec!["parseResource", "parseValue", "toJSONValue", "unknownFields"]
+        );
+    }
+
     #[test]
     fn test_get_element_type() {
         let backend = TypeScriptBackend::new();
@@ -0,0 +1,438 @@
+//! Typed FHIR REST client for TypeScript
+//!
+//! Generates `src/client.ts`: a `ResourceTypeMap` from resource names to their
+//! generated types, and a `FhirClient` whose `read`, `search`, `create`,
+//! `update`, `patch`, `delete`, `history` and `operation` calls are typed by it.
+//! The HTTP transport is any `fetch`-compatible function.
+
+use crate::core::Result;
+use crate::core::ir::{ResourceType, TypeGraph};
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+
+/// Minimal shapes of the resources the client itself reads, used when the
+/// package does not define them
+pub(crate) const FALLBACK_TYPES: &[(&str, &str)] = &[
+    (
+        "Bundle",
+        "interface Bundle {\n  resourceType: \"Bundle\";\n  link?: Array<{ relation?: string; url?: string }>;\n  entry?: Array<{ resource?: unknown }>;\n}",
+    ),
+    (
+        "OperationOutcome",
+        "interface OperationOutcome {\n  resourceType: \"OperationOutcome\";\n  issue?: Array<{ severity?: string; code?: string; diagnostics?: string }>;\n}",
+    ),
+    (
+        "Parameters",
+        "interface Parameters {\n  resourceType: \"Parameters\";\n  parameter?: Array<{ name: string; [value: string]: unknown }>;\n}",
+    ),
+];
+
+/// Generator for the typed REST client module
+#[derive(Debug, Clone, Default)]
+pub struct ClientGenerator {
+    use_classes: bool,
+}
+
+impl ClientGenerator {
+    /// Create a client generator for interface-based SDKs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hydrate responses into the generated classes with their static `parse`
+    pub fn with_classes(mut self, use_classes: bool) -> Self {
+        self.use_classes = use_classes;
+        self
+    }
+
+    /// Resources the client can address: every concrete resource in the graph
+    pub fn client_resources(graph: &TypeGraph) -> Vec<&ResourceType> {
+        graph.resources.values().filter(|resource| !resource.is_abstract).collect()
+    }
+
+    /// Generate `src/client.ts`
+    pub fn generate_client_module(&self, graph: &TypeGraph) -> Result<String> {
+        let resources = Self::client_resources(graph);
+        let names: Vec<String> = resources
+            .iter()
+            .map(|resource| TypeScriptBackend::sanitize_identifier(&resource.name))
+            .collect();
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated FHIR REST client");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.line();
+
+        // Imports
+        let keyword = if self.use_classes { "import" } else { "import type" };
+        for name in &names {
+            tokens.append(format!("{} {{ {} }} from './resources/{}';", keyword, name, name));
+            tokens.push();
+        }
+        tokens.line();
+
+        // Fallbacks for support resources missing from the package
+        for (name, definition) in FALLBACK_TYPES {
+            if !names.iter().any(|n| n == name) {
+                tokens.append(helpers::jsdoc_comment(&[format!(
+                    "Minimal {} shape; the package does not define {}",
+                    name, name
+                )]));
+                tokens.push();
+                for line in definition.lines() {
+                    tokens.append(line);
+                    tokens.push();
+                }
+                tokens.line();
+            }
+        }
+
+        // Resource type map
+        tokens.append(helpers::interface(
+            "ResourceTypeMap",
+            None,
+            &names.iter().map(|name| (name.clone(), name.clone(), false)).collect::<Vec<_>>(),
+            Some(&["Generated type of every resource the client can address".to_string()]),
+        ));
+        tokens.line();
+        tokens.append(helpers::type_alias(
+            "ResourceName",
+            "keyof ResourceTypeMap",
+            Some(&["Name of a resource type the client can address".to_string()]),
+        ));
+        tokens.line();
+
+        tokens.append(self.generate_hydrate(&names));
+        tokens.line();
+
+        let bundle = if names.iter().any(|name| name == "Bundle") {
+            "hydrate(\"Bundle\", json)"
+        } else {
+            "json as Bundle"
+        };
+        tokens.append("function asBundle(json: unknown): Bundle {");
+        tokens.push();
+        tokens.append(format!("  return {};", bundle));
+        tokens.push();
+        tokens.append("}");
+
+        let header = GencoTemplateEngine::format_typescript(&tokens)?;
+        Ok(format!("{}\n{}", header.trim_end(), CLIENT_MODULE))
+    }
+
+    /// Conversion of response JSON into the generated type of a resource
+    fn generate_hydrate(&self, names: &[String]) -> js::Tokens {
+        let mut tokens = js::Tokens::new();
+
+        if self.use_classes {
+            tokens.append(
+                "const parsers: { [K in ResourceName]: (json: unknown) => ResourceTypeMap[K] } = {",
+            );
+            for name in names {
+                tokens.push();
+                tokens.append(format!("  {0}: (json) => {0}.parse(json),", name));
+            }
+            tokens.push();
+            tokens.append("};");
+            tokens.line();
+        }
+
+        tokens.append(helpers::jsdoc_comment(&[
+            "Convert response JSON into the generated type of `type`".to_string(),
+        ]));
+        tokens.push();
+        let (param, body) = if self.use_classes {
+            ("type", "parsers[type](json)")
+        } else {
+            ("_type", "json as ResourceTypeMap[T]")
+        };
+        tokens.append(format!(
+            "function hydrate<T extends ResourceName>({}: T, json: unknown): ResourceTypeMap[T] {{",
+            param
+        ));
+        tokens.push();
+        tokens.append(format!("  return {};", body));
+        tokens.push();
+        tokens.append("}");
+
+        tokens
+    }
+}
+
+/// Client runtime following the generated type map and hydration functions
+const CLIENT_MODULE: &str = r#"
+/** Media type of FHIR JSON */
+const FHIR_JSON = "application/fhir+json";
+
+/** Request options passed to the fetch implementation */
+export interface FhirRequestInit {
+  method: string;
+  headers: Record<string, string>;
+  body?: string;
+}
+
+/** The parts of a fetch `Response` the client uses */
+export interface FhirResponse {
+  ok: boolean;
+  status: number;
+  statusText: string;
+  text(): Promise<string>;
+}
+
+/** A `fetch`-compatible HTTP transport */
+export type FetchLike = (url: string, init: FhirRequestInit) => Promise<FhirResponse>;
+
+/** Client configuration */
+export interface FhirClientOptions {
+  /** Base URL of the FHIR server, e.g. `https://example.com/fhir` */
+  baseUrl: string;
+  /** HTTP transport; defaults to the global `fetch` */
+  fetch?: FetchLike;
+  /** Headers sent with every request, e.g. `Authorization` */
+  headers?: Record<string, string>;
+}
+
+/** Search parameters as a query string, a parameter map or a generated search builder */
+export type SearchInput = string | Record<string, string | number | boolean> | { toQueryString(): string };
+
+/** A JSON Patch operation */
+export interface JsonPatchOperation {
+  op: "add" | "remove" | "replace" | "move" | "copy" | "test";
+  path: string;
+  value?: unknown;
+  from?: string;
+}
+
+/** Options of an `$operation` invocation */
+export interface OperationOptions {
+  /** Resource type for type- and instance-level operations */
+  type?: ResourceName;
+  /** Resource id for instance-level operations */
+  id?: string;
+  /** Input parameters, sent as the request body */
+  parameters?: Parameters;
+  /** HTTP method; defaults to `POST` */
+  method?: "GET" | "POST";
+}
+
+/** Error raised for non-2xx responses, carrying the server's OperationOutcome if any */
+export class OperationOutcomeError extends Error {
+  constructor(
+    readonly status: number,
+    message: string,
+    readonly outcome?: OperationOutcome,
+  ) {
+    super(message);
+    this.name = "OperationOutcomeError";
+  }
+}
+
+function isOperationOutcome(value: unknown): value is OperationOutcome {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    (value as { resourceType?: unknown }).resourceType === "OperationOutcome"
+  );
+}
+
+function errorFor(status: number, statusText: string, body: unknown): OperationOutcomeError {
+  if (!isOperationOutcome(body)) {
+    return new OperationOutcomeError(status, `HTTP ${status} ${statusText}`.trim());
+  }
+  const details = (body.issue ?? [])
+    .map((issue) => issue.diagnostics ?? issue.code)
+    .filter((detail) => detail !== undefined && detail !== "");
+  const message = details.length > 0 ? details.join("; ") : `HTTP ${status} ${statusText}`.trim();
+  return new OperationOutcomeError(status, message, body);
+}
+
+function queryString(query: SearchInput | undefined): string {
+  if (query === undefined) {
+    return "";
+  }
+  if (typeof query === "string") {
+    return query.replace(/^\?/, "");
+  }
+  if (typeof (query as { toQueryString?: unknown }).toQueryString === "function") {
+    return (query as { toQueryString(): string }).toQueryString();
+  }
+  return Object.entries(query as Record<string, string | number | boolean>)
+    .map(([name, value]) => `${encodeURIComponent(name)}=${encodeURIComponent(String(value))}`)
+    .join("&");
+}
+
+/** Typed client for a FHIR REST server */
+export class FhirClient {
+  private readonly baseUrl: string;
+  private readonly fetch: FetchLike;
+  private readonly headers: Record<string, string>;
+
+  constructor(options: FhirClientOptions) {
+    const fetch = options.fetch ?? (globalThis as { fetch?: FetchLike }).fetch;
+    if (fetch === undefined) {
+      throw new Error("No fetch implementation available; pass one in FhirClientOptions.fetch");
+    }
+    this.baseUrl = options.baseUrl.replace(/\/+$/, "");
+    this.fetch = fetch;
+    this.headers = options.headers ?? {};
+  }
+
+  /** Read the current version of a resource */
+  async read<T extends ResourceName>(type: T, id: string): Promise<ResourceTypeMap[T]> {
+    return hydrate(type, await this.request("GET", `${type}/${id}`));
+  }
+
+  /** Read a specific version of a resource */
+  async vread<T extends ResourceName>(type: T, id: string, versionId: string): Promise<ResourceTypeMap[T]> {
+    return hydrate(type, await this.request("GET", `${type}/${id}/_history/${versionId}`));
+  }
+
+  /** Search a resource type and return the first page */
+  async search<T extends ResourceName>(type: T, query?: SearchInput): Promise<Bundle> {
+    const parameters = queryString(query);
+    return asBundle(await this.request("GET", parameters ? `${type}?${parameters}` : type));
+  }
+
+  /** Iterate over every page of a search, following `next` links */
+  async *pages<T extends ResourceName>(type: T, query?: SearchInput): AsyncGenerator<Bundle> {
+    let bundle: Bundle | undefined = await this.search(type, query);
+    while (bundle !== undefined) {
+      yield bundle;
+      const next = bundle.link?.find((link) => link.relation === "next")?.url;
+      bundle = next ? asBundle(await this.request("GET", next)) : undefined;
+    }
+  }
+
+  /** Iterate over every match of a search across all pages, skipping included resources */
+  async *searchAll<T extends ResourceName>(type: T, query?: SearchInput): AsyncGenerator<ResourceTypeMap[T]> {
+    for await (const bundle of this.pages(type, query)) {
+      for (const entry of bundle.entry ?? []) {
+        const resource = entry.resource as { resourceType?: string } | undefined;
+        if (resource?.resourceType === type) {
+          yield hydrate(type, resource);
+        }
+      }
+    }
+  }
+
+  /** Create a resource; the server assigns its id */
+  async create<R extends ResourceTypeMap[ResourceName]>(resource: R): Promise<R> {
+    const { resourceType } = resource as { resourceType: ResourceName };
+    return hydrate(resourceType, await this.request("POST", resourceType, resource)) as R;
+  }
+
+  /** Update (or create) a resource with a known id */
+  async update<R extends ResourceTypeMap[ResourceName]>(resource: R): Promise<R> {
+    const { resourceType, id } = resource as { resourceType: ResourceName; id?: string };
+    if (!id) {
+      throw new Error(`Cannot update ${resourceType} without an id`);
+    }
+    return hydrate(resourceType, await this.request("PUT", `${resourceType}/${id}`, resource)) as R;
+  }
+
+  /** Apply a JSON Patch to a resource */
+  async patch<T extends ResourceName>(type: T, id: string, operations: JsonPatchOperation[]): Promise<ResourceTypeMap[T]> {
+    return hydrate(type, await this.request("PATCH", `${type}/${id}`, operations, "application/json-patch+json"));
+  }
+
+  /** Delete a resource */
+  async delete(type: ResourceName, id: string): Promise<void> {
+    await this.request("DELETE", `${type}/${id}`);
+  }
+
+  /** History of the whole system, a resource type, or a single resource */
+  async history(type?: ResourceName, id?: string): Promise<Bundle> {
+    const path = [type, id, "_history"].filter((segment) => segment !== undefined).join("/");
+    return asBundle(await this.request("GET", path));
+  }
+
+  /** Invoke an operation such as `$everything` or `$validate` */
+  async operation<R = Parameters>(name: string, options: OperationOptions = {}): Promise<R> {
+    const operation = name.startsWith("$") ? name : `$${name}`;
+    const path = [options.type, options.id, operation].filter((segment) => segment !== undefined).join("/");
+    const method = options.method ?? "POST";
+    const body = method === "POST" ? (options.parameters ?? { resourceType: "Parameters" }) : undefined;
+    return (await this.request(method, path, body)) as R;
+  }
+
+  /** Send a request and return the parsed JSON body, throwing on error responses */
+  private async request(method: string, path: string, body?: unknown, contentType: string = FHIR_JSON): Promise<unknown> {
+    const url = /^https?:\/\//.test(path) ? path : `${this.baseUrl}/${path}`;
+    const headers: Record<string, string> = { Accept: FHIR_JSON, ...this.headers };
+    const init: FhirRequestInit = { method, headers };
+    if (body !== undefined) {
+      headers["Content-Type"] = contentType;
+      init.body = JSON.stringify(body);
+    }
+
+    const response = await this.fetch(url, init);
+    const text = await response.text();
+    let json: unknown;
+    try {
+      json = text ? JSON.parse(text) : undefined;
+    } catch {
+      json = undefined;
+    }
+
+    if (!response.ok) {
+      throw errorFor(response.status, response.statusText, json);
+    }
+    return json;
+  }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion};
+
+    fn graph(names: &[&str]) -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        for name in names {
+            graph.add_resource(
+                name.to_string(),
+                ResourceType {
+                    name: name.to_string(),
+                    base: Some("DomainResource".to_string()),
+                    properties: vec![],
+                    constraints: vec![],
+                    search_parameters: vec![],
+                    extensions: vec![],
+                    documentation: Documentation::default(),
+                    url: String::new(),
+                    is_abstract: *name == "DomainResource",
+                },
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_fallback_types_only_for_missing_resources() {
+        let output = ClientGenerator::new()
+            .generate_client_module(&graph(&["Patient", "Bundle", "DomainResource"]))
+            .unwrap();
+
+        assert!(output.contains("import type { Bundle } from './resources/Bundle';"));
+        assert!(!output.contains("DomainResource"));
+        assert!(!output.contains("interface Bundle {"));
+        assert!(output.contains("interface OperationOutcome {"));
+        assert!(output.contains("return hydrate(\"Bundle\", json);"));
+        assert!(output.contains("interface Parameters {"));
+    }
+
+    #[test]
+    fn test_class_mode_hydrates_with_parse() {
+        let output = ClientGenerator::new()
+            .with_classes(true)
+            .generate_client_module(&graph(&["Patient"]))
+            .unwrap();
+
+        assert!(output.contains("import { Patient } from './resources/Patient';"));
+        assert!(output.contains("Patient: (json) => Patient.parse(json),"));
+        assert!(output.contains("return parsers[type](json);"));
+    }
+}
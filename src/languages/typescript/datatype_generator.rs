@@ -1,14 +1,15 @@
 //! TypeScript datatype interface generation
 
 use crate::core::Result;
-use crate::core::ir::{DataType, TypeGraph};
+use crate::core::ir::{DataType, Property, PropertyType, TypeGraph};
 use crate::generator::LanguageBackend;
 use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::choice_generator::ChoiceElementGenerator;
 use crate::languages::typescript::class_generator::ClassGenerator;
 use crate::languages::typescript::templates::TypeScriptTemplates;
 use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
 use genco::prelude::*;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 /// Generator for TypeScript datatype interfaces
 pub struct DatatypeGenerator<B: LanguageBackend> {
@@ -16,17 +17,25 @@ pub struct DatatypeGenerator<B: LanguageBackend> {
     backend: B,
     /// Whether to generate classes instead of interfaces
     use_classes: bool,
+    /// Resource type names a `Reference<T>` may target
+    resource_types: Vec<String>,
 }
 
 impl<B: LanguageBackend> DatatypeGenerator<B> {
     /// Create a new datatype generator
     pub fn new(backend: B) -> Self {
-        Self { backend, use_classes: false }
+        Self { backend, use_classes: false, resource_types: Vec::new() }
     }
 
     /// Create a new datatype generator with class generation enabled
     pub fn new_with_classes(backend: B) -> Self {
-        Self { backend, use_classes: true }
+        Self { backend, use_classes: true, resource_types: Vec::new() }
+    }
+
+    /// Set the resource type names `Reference<T>` may target
+    pub fn with_resource_types(mut self, resource_types: Vec<String>) -> Self {
+        self.resource_types = resource_types;
+        self
     }
 
     /// Generate TypeScript interface or class for a complex datatype
@@ -50,9 +59,11 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
         Ok(results)
     }
 
-    /// Generate the FhirPrimitive wrapper type
+    /// Generate the FhirPrimitive view type
     ///
-    /// Creates a type like:
+    /// Generated types keep primitive values inline and their element data in a
+    /// `_name` sibling, as FHIR JSON does. `FhirPrimitive<T>` joins the two for
+    /// code that wants a single object. Creates a type like:
     /// ```typescript
     /// export interface FhirPrimitive<T> {
     ///   value?: T;
@@ -65,9 +76,10 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
 
         // JSDoc comment
         let doc = vec![
-            "FHIR Primitive wrapper type".to_string(),
+            "A FHIR primitive value joined with its element data".to_string(),
             String::new(),
-            "Wraps primitive values to support FHIR extensions and element properties.".to_string(),
+            "FHIR JSON keeps the value inline (`birthDate`) and the element's id and".to_string(),
+            "extensions in a `_birthDate` sibling; this type combines both.".to_string(),
             String::new(),
             "@template T - The primitive value type".to_string(),
         ];
@@ -104,10 +116,10 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
 
     /// Generate primitive type aliases
     ///
-    /// Creates types like:
+    /// Creates inline types like:
     /// ```typescript
-    /// export type fhir_boolean = FhirPrimitive<boolean>;
-    /// export type fhir_string = FhirPrimitive<string>;
+    /// export type FhirBoolean = boolean;
+    /// export type date = string;
     /// ```
     pub fn generate_primitive_types(&self, graph: &TypeGraph) -> Result<String> {
         let mut tokens = js::Tokens::new();
@@ -119,8 +131,9 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
         tokens.push();
         tokens.append(" * ");
         tokens.push();
-        tokens
-            .append(" * These types wrap native TypeScript primitives to support FHIR extensions.");
+        tokens.append(" * Primitive values are inline TypeScript primitives; a primitive's id and");
+        tokens.push();
+        tokens.append(" * extensions live in the `_name` sibling element, as in FHIR JSON.");
         tokens.push();
         tokens.append(" */");
         tokens.push();
@@ -131,7 +144,7 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
         tokens.push();
         tokens.push();
 
-        // Generate FhirPrimitive wrapper
+        // Generate FhirPrimitive view type
         let wrapper = Self::generate_primitive_wrapper()?;
         tokens.append(&wrapper);
         tokens.push();
@@ -168,9 +181,9 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
                 // Type alias
                 tokens.append("export type ");
                 tokens.append(&safe_type_name);
-                tokens.append(" = FhirPrimitive<");
+                tokens.append(" = ");
                 tokens.append(&ts_type);
-                tokens.append(">;");
+                tokens.append(";");
                 tokens.push();
                 tokens.push();
             }
@@ -181,6 +194,13 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
 
     /// Map FHIR primitive name to TypeScript base type
     fn map_primitive_to_ts(&self, primitive_name: &str) -> String {
+        // Prefer the backend mapping so custom type mappings apply to primitives too
+        let primitive = PropertyType::Primitive { type_name: primitive_name.to_string() };
+        let mapped = self.backend.map_type(&primitive);
+        if mapped != primitive_name {
+            return mapped;
+        }
+
         match primitive_name {
             "boolean" => "boolean",
             "integer" | "positiveInt" | "unsignedInt" | "integer64" | "decimal" => "number",
@@ -250,13 +270,17 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
     pub fn collect_dependencies(&self, datatype: &DataType) -> HashSet<String> {
         let mut deps = HashSet::new();
 
-        for property in &datatype.properties {
+        for property in &Property::json_properties(&datatype.properties) {
             // Collect types based on property type variant
             match &property.property_type {
                 crate::core::ir::PropertyType::Primitive { type_name } => {
                     if !Self::is_primitive(type_name) {
                         deps.insert(type_name.to_string());
                     }
+                    // `_name` siblings carry the primitive's element data
+                    if property.primitive_element_name().is_some() && datatype.name != "Element" {
+                        deps.insert("Element".to_string());
+                    }
                 }
                 crate::core::ir::PropertyType::Complex { type_name } => {
                     deps.insert(type_name.to_string());
@@ -318,26 +342,63 @@ impl<B: LanguageBackend> DatatypeGenerator<B> {
     }
 
     /// Generate a complete datatype file with imports
+    ///
+    /// Choice elements get their variant types and accessors after the datatype,
+    /// and `Reference` is preceded by the `ResourceType` and `ReferenceString`
+    /// types narrowing its targets.
     pub fn generate_datatype_file(
         &self,
         datatype: &DataType,
         dependencies: &HashSet<String>,
-    ) -> Result<String> {
+    ) -> Result<String>
+    where
+        B: Clone,
+    {
         let mut tokens = js::Tokens::new();
+        let interface = self.generate_datatype(datatype)?;
 
         // Generate imports (datatypes in src/types/ import from same directory)
+        let mut modules = Vec::new();
         if !dependencies.is_empty() {
             let mut deps_vec: Vec<String> = dependencies.iter().cloned().collect();
             deps_vec.sort();
 
             // Use '.' for same directory imports
-            tokens.append(helpers::imports(&[(deps_vec, ".".to_string())]));
+            modules.push((deps_vec, ".".to_string()));
+        }
+        let value_sets: BTreeSet<String> = Property::json_properties(&datatype.properties)
+            .iter()
+            .filter_map(|p| self.backend.value_set_type(p))
+            .collect();
+        if !value_sets.is_empty() {
+            modules.push((value_sets.into_iter().collect(), "../valuesets".to_string()));
+        }
+        if self.use_classes {
+            modules.push((
+                ClassGenerator::serialization_imports(&interface),
+                "../serialization".to_string(),
+            ));
+        }
+        if modules.iter().any(|(names, _)| !names.is_empty()) {
+            tokens.append(helpers::imports(&modules));
             tokens.push();
         }
 
+        // Target types narrowing `Reference<T>`
+        if datatype.name == "Reference" {
+            tokens.append(TypeScriptTemplates::reference_target_types(&self.resource_types));
+            tokens.line();
+        }
+
         // Generate the datatype interface
-        let interface = self.generate_datatype(datatype)?;
-        tokens.append(&interface);
+        tokens.append(interface.trim_end());
+
+        // Variant types and accessors for choice elements
+        let choices = ChoiceElementGenerator::new(self.backend.clone());
+        for property in datatype.properties.iter().filter(|p| p.is_choice) {
+            tokens.line();
+            tokens.append(choices.generate_choice_module(property, &datatype.name)?.trim_end());
+        }
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
@@ -414,6 +475,7 @@ mod tests {
         DataType {
             name: "HumanName".to_string(),
             base: Some("Element".to_string()),
+            constraints: vec![],
             properties: vec![
                 Property {
                     name: "use".to_string(),
@@ -7,14 +7,15 @@
 //! - removeExtension methods for removing extensions
 
 use crate::core::Result;
+use crate::core::ir::Extension;
 
 /// Extension definition with metadata
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExtensionDefinition {
-    /// Extension canonical URL
+    /// Extension canonical URL (the slice name for sub-extensions)
     pub url: String,
 
-    /// Extension name (derived from URL)
+    /// Extension name, used in accessor method names and as the field of sub-extensions
     pub name: String,
 
     /// Value type (e.g., "string", "dateTime", "Coding")
@@ -28,6 +29,9 @@ pub struct ExtensionDefinition {
 
     /// Cardinality (0..1 or 0..*)
     pub is_array: bool,
+
+    /// Minimum cardinality is at least 1 (relevant for sub-extensions)
+    pub is_required: bool,
 }
 
 /// Extension value type
@@ -39,17 +43,23 @@ pub enum ExtensionValueType {
     Complex(String),
     /// Multiple possible types
     Choice(Vec<String>),
+    /// Complex extension made of sub-extensions instead of a value
+    Nested(Vec<ExtensionDefinition>),
 }
 
 impl ExtensionDefinition {
     /// Create extension definition from URL
+    ///
+    /// The name is guessed from the URL and the value type defaults to
+    /// `string`; prefer [`ExtensionDefinition::from_extension`] when the
+    /// extension's StructureDefinition is available.
     pub fn from_url(url: &str) -> Self {
         // Extract name from URL
         // e.g., "http://hl7.org/fhir/StructureDefinition/patient-birthTime" -> "birthTime"
         let name = url
             .rsplit('/')
             .next()
-            .and_then(|s| s.split('-').last())
+            .and_then(|s| s.split('-').next_back())
             .unwrap_or("extension")
             .to_string();
 
@@ -60,23 +70,97 @@ impl ExtensionDefinition {
             target_types: vec![],
             description: format!("{} extension", name),
             is_array: false,
+            is_required: false,
+        }
+    }
+
+    /// Create extension definition from a parsed Extension StructureDefinition
+    ///
+    /// Returns `None` when the definition allows any value type and has no
+    /// sub-extensions, as no precise accessor type exists for it.
+    pub fn from_extension(ext: &Extension) -> Option<Self> {
+        let value_type = if !ext.sub_extensions.is_empty() {
+            ExtensionValueType::Nested(
+                ext.sub_extensions.iter().filter_map(Self::from_extension).collect(),
+            )
+        } else {
+            match ext.value_types.as_slice() {
+                [] => return None,
+                [type_name] if is_primitive_type(type_name) => {
+                    ExtensionValueType::Primitive(type_name.clone())
+                }
+                [type_name] => ExtensionValueType::Complex(type_name.clone()),
+                types => ExtensionValueType::Choice(types.to_vec()),
+            }
+        };
+
+        Some(Self {
+            url: ext.url.clone(),
+            name: ext.name.clone(),
+            value_type,
+            target_types: ext.target_types.clone(),
+            description: if ext.documentation.short.is_empty() {
+                format!("{} extension", ext.name)
+            } else {
+                ext.documentation.short.clone()
+            },
+            is_array: ext.cardinality.is_array(),
+            is_required: ext.cardinality.is_required(),
+        })
+    }
+
+    /// Complex datatypes used by the accessors, including those of sub-extensions
+    pub fn complex_types(&self) -> Vec<String> {
+        match &self.value_type {
+            ExtensionValueType::Primitive(_) => vec![],
+            ExtensionValueType::Complex(type_name) => vec![type_name.clone()],
+            ExtensionValueType::Choice(types) => {
+                types.iter().filter(|t| !is_primitive_type(t)).cloned().collect()
+            }
+            ExtensionValueType::Nested(subs) => {
+                subs.iter().flat_map(|sub| sub.complex_types()).collect()
+            }
         }
     }
 
     /// Get the TypeScript type for the value
     pub fn get_typescript_type(&self) -> String {
+        self.value_type_with(false)
+    }
+
+    /// Type of the value read by the get accessor
+    pub fn get_read_type(&self) -> String {
+        self.value_type_with(true)
+    }
+
+    /// TypeScript type of the value passed to the add accessor, or returned by get if `read`
+    ///
+    /// Choice values are added as the `valueX` variant object, as in FHIR JSON,
+    /// and read as the plain value. Read sub-extension fields are all optional.
+    fn value_type_with(&self, read: bool) -> String {
         match &self.value_type {
-            ExtensionValueType::Primitive(t) => {
-                // Map FHIR primitives to TypeScript types
-                match t.as_str() {
-                    "boolean" => "boolean".to_string(),
-                    "integer" | "positiveInt" | "unsignedInt" => "number".to_string(),
-                    "decimal" => "number".to_string(),
-                    _ => "string".to_string(), // date, dateTime, time, etc. are strings
-                }
-            }
+            ExtensionValueType::Primitive(t) => map_primitive_type(t),
             ExtensionValueType::Complex(t) => t.clone(),
-            ExtensionValueType::Choice(types) => types.join(" | "),
+            ExtensionValueType::Choice(types) if read => {
+                types.iter().map(|t| map_choice_type(t)).collect::<Vec<_>>().join(" | ")
+            }
+            ExtensionValueType::Choice(types) => types
+                .iter()
+                .map(|t| format!("{{ value{}: {} }}", capitalize(t), map_choice_type(t)))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            ExtensionValueType::Nested(subs) => {
+                let fields: Vec<String> = subs
+                    .iter()
+                    .map(|sub| {
+                        let optional = if read || !sub.is_required { "?" } else { "" };
+                        let sub_type = sub.value_type_with(read);
+                        let sub_type = if sub.is_array { array_type(&sub_type) } else { sub_type };
+                        format!("{}{}: {}", sub.name, optional, sub_type)
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join("; "))
+            }
         }
     }
 
@@ -91,7 +175,77 @@ impl ExtensionDefinition {
                 let capitalized = capitalize(t);
                 format!("value{}", capitalized)
             }
-            ExtensionValueType::Choice(_) => "value".to_string(),
+            ExtensionValueType::Choice(_) | ExtensionValueType::Nested(_) => "value".to_string(),
+        }
+    }
+
+    /// Expression building the extension JSON for the value in `value`
+    fn write_extension_expression(&self, value: &str) -> String {
+        match &self.value_type {
+            ExtensionValueType::Primitive(_) | ExtensionValueType::Complex(_) => {
+                format!("{{ url: \"{}\", {}: {} }}", self.url, self.get_value_field_name(), value)
+            }
+            ExtensionValueType::Choice(_) => format!("{{ url: \"{}\", ...{} }}", self.url, value),
+            ExtensionValueType::Nested(subs) => {
+                let entries: Vec<String> = subs
+                    .iter()
+                    .map(|sub| {
+                        let field = format!("{}.{}", value, sub.name);
+                        if sub.is_array {
+                            format!(
+                                "...({} ?? []).map((v) => ({}))",
+                                field,
+                                sub.write_extension_expression("v")
+                            )
+                        } else {
+                            format!(
+                                "...({} !== undefined ? [{}] : [])",
+                                field,
+                                sub.write_extension_expression(&field)
+                            )
+                        }
+                    })
+                    .collect();
+                format!("{{ url: \"{}\", extension: [{}] }}", self.url, entries.join(", "))
+            }
+        }
+    }
+
+    /// Expression reading the value of the possibly undefined extension `ext`
+    fn read_extension_expression(&self, ext: &str) -> String {
+        match &self.value_type {
+            ExtensionValueType::Primitive(_) | ExtensionValueType::Complex(_) => {
+                format!("{}?.{}", ext, self.get_value_field_name())
+            }
+            ExtensionValueType::Choice(types) => types
+                .iter()
+                .map(|t| format!("{}?.value{}", ext, capitalize(t)))
+                .collect::<Vec<_>>()
+                .join(" ?? "),
+            ExtensionValueType::Nested(subs) => {
+                let fields: Vec<String> = subs
+                    .iter()
+                    .map(|sub| {
+                        let matching = format!("(s) => s.url === \"{}\"", sub.url);
+                        let value = if sub.is_array {
+                            format!(
+                                "({}?.extension ?? []).filter({}).map((s) => {}).filter((v): v is {} => v !== undefined)",
+                                ext,
+                                matching,
+                                sub.read_extension_expression("s"),
+                                sub.value_type_with(true)
+                            )
+                        } else {
+                            sub.read_extension_expression(&format!(
+                                "{}?.extension?.find({})",
+                                ext, matching
+                            ))
+                        };
+                        format!("{}: {}", sub.name, value)
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join(", "))
+            }
         }
     }
 }
@@ -120,10 +274,20 @@ impl ExtensionGenerator {
         code.push_str("    if (!this._extension) {\n");
         code.push_str("      this._extension = [];\n");
         code.push_str("    }\n");
-        code.push_str("    this._extension.push({\n");
-        code.push_str(&format!("      url: \"{}\",\n", ext.url));
-        code.push_str(&format!("      {}: value\n", value_field));
-        code.push_str("    });\n");
+        match ext.value_type {
+            ExtensionValueType::Primitive(_) | ExtensionValueType::Complex(_) => {
+                code.push_str("    this._extension.push({\n");
+                code.push_str(&format!("      url: \"{}\",\n", ext.url));
+                code.push_str(&format!("      {}: value\n", value_field));
+                code.push_str("    });\n");
+            }
+            ExtensionValueType::Choice(_) | ExtensionValueType::Nested(_) => {
+                code.push_str(&format!(
+                    "    this._extension.push({});\n",
+                    ext.write_extension_expression("value")
+                ));
+            }
+        }
         code.push_str("    return this;\n");
         code.push_str("  }\n");
 
@@ -133,7 +297,7 @@ impl ExtensionGenerator {
     /// Generate get extension method
     pub fn generate_get_method(ext: &ExtensionDefinition) -> Result<String> {
         let method_name = format!("get{}Extension", capitalize(&ext.name));
-        let ts_type = ext.get_typescript_type();
+        let ts_type = ext.get_read_type();
         let value_field = ext.get_value_field_name();
 
         let mut code = String::new();
@@ -149,14 +313,25 @@ impl ExtensionGenerator {
         code.push_str("   */\n");
 
         // Method signature
+        let simple = matches!(
+            ext.value_type,
+            ExtensionValueType::Primitive(_) | ExtensionValueType::Complex(_)
+        );
         if ext.is_array {
-            code.push_str(&format!("  {}(): {}[] {{\n", method_name, ts_type));
+            code.push_str(&format!("  {}(): {} {{\n", method_name, array_type(&ts_type)));
             code.push_str("    if (!this._extension) {\n");
             code.push_str("      return [];\n");
             code.push_str("    }\n");
             code.push_str("    return this._extension\n");
             code.push_str(&format!("      .filter(e => e.url === \"{}\")\n", ext.url));
-            code.push_str(&format!("      .map(e => e.{})\n", value_field));
+            if simple {
+                code.push_str(&format!("      .map(e => e.{})\n", value_field));
+            } else {
+                code.push_str(&format!(
+                    "      .map(e => {})\n",
+                    ext.read_extension_expression("e")
+                ));
+            }
             code.push_str(&format!("      .filter((v): v is {} => v !== undefined);\n", ts_type));
             code.push_str("  }\n");
         } else {
@@ -164,7 +339,14 @@ impl ExtensionGenerator {
             code.push_str("    const ext = this._extension?.find(\n");
             code.push_str(&format!("      e => e.url === \"{}\"\n", ext.url));
             code.push_str("    );\n");
-            code.push_str(&format!("    return ext?.{};\n", value_field));
+            if simple {
+                code.push_str(&format!("    return ext?.{};\n", value_field));
+            } else {
+                code.push_str(&format!(
+                    "    return ext === undefined ? undefined : {};\n",
+                    ext.read_extension_expression("ext")
+                ));
+            }
             code.push_str("  }\n");
         }
 
@@ -280,6 +462,134 @@ impl ExtensionGenerator {
 
         Ok(code)
     }
+
+    /// Generate the standalone extension utilities module (`extensions.ts`)
+    ///
+    /// Works on any element or resource with an `extension` array, so it is
+    /// usable with both interface and class output.
+    pub fn generate_extension_utilities_module() -> Result<String> {
+        let mut code = String::new();
+
+        code.push_str("/**\n");
+        code.push_str(" * FHIR extension utilities\n");
+        code.push_str(" * \n");
+        code.push_str(" * This file is auto-generated. Do not edit manually.\n");
+        code.push_str(" */\n\n");
+        code.push_str("import { Extension } from './types/Extension';\n\n");
+
+        code.push_str("/** Anything that can carry extensions */\n");
+        code.push_str("export interface HasExtensions {\n");
+        code.push_str("  extension?: Extension[];\n");
+        code.push_str("}\n\n");
+
+        code.push_str("/**\n");
+        code.push_str(" * Get the first extension with the given URL\n");
+        code.push_str(" * @param target - Element or resource to inspect\n");
+        code.push_str(" * @param url - Extension canonical URL\n");
+        code.push_str(" */\n");
+        code.push_str(
+            "export function getExtension(target: HasExtensions, url: string): Extension | undefined {\n",
+        );
+        code.push_str("  return target.extension?.find(e => e.url === url);\n");
+        code.push_str("}\n\n");
+
+        code.push_str("/**\n");
+        code.push_str(" * Get all extensions with the given URL\n");
+        code.push_str(" * @param target - Element or resource to inspect\n");
+        code.push_str(" * @param url - Extension canonical URL\n");
+        code.push_str(" */\n");
+        code.push_str(
+            "export function getExtensions(target: HasExtensions, url: string): Extension[] {\n",
+        );
+        code.push_str("  return target.extension?.filter(e => e.url === url) ?? [];\n");
+        code.push_str("}\n\n");
+
+        code.push_str("/**\n");
+        code.push_str(" * Check whether an extension with the given URL is present\n");
+        code.push_str(" * @param target - Element or resource to inspect\n");
+        code.push_str(" * @param url - Extension canonical URL\n");
+        code.push_str(" */\n");
+        code.push_str(
+            "export function hasExtension(target: HasExtensions, url: string): boolean {\n",
+        );
+        code.push_str("  return target.extension?.some(e => e.url === url) ?? false;\n");
+        code.push_str("}\n\n");
+
+        code.push_str("/**\n");
+        code.push_str(" * Return a copy of the target with the extension appended\n");
+        code.push_str(" * @param target - Element or resource to extend\n");
+        code.push_str(" * @param extension - Extension object to add\n");
+        code.push_str(" */\n");
+        code.push_str(
+            "export function addExtension<T extends HasExtensions>(target: T, extension: Extension): T {\n",
+        );
+        code.push_str(
+            "  return { ...target, extension: [...(target.extension ?? []), extension] };\n",
+        );
+        code.push_str("}\n\n");
+
+        code.push_str("/**\n");
+        code.push_str(" * Return a copy of the target without extensions with the given URL\n");
+        code.push_str(" * @param target - Element or resource to update\n");
+        code.push_str(" * @param url - Extension canonical URL\n");
+        code.push_str(" */\n");
+        code.push_str(
+            "export function removeExtension<T extends HasExtensions>(target: T, url: string): T {\n",
+        );
+        code.push_str(
+            "  return { ...target, extension: target.extension?.filter(e => e.url !== url) };\n",
+        );
+        code.push_str("}\n");
+
+        Ok(code)
+    }
+}
+
+/// Check if a type name is a FHIR primitive
+fn is_primitive_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "boolean"
+            | "integer"
+            | "integer64"
+            | "string"
+            | "decimal"
+            | "uri"
+            | "url"
+            | "canonical"
+            | "base64Binary"
+            | "instant"
+            | "date"
+            | "dateTime"
+            | "time"
+            | "code"
+            | "oid"
+            | "id"
+            | "markdown"
+            | "unsignedInt"
+            | "positiveInt"
+            | "uuid"
+    )
+}
+
+/// Map a FHIR primitive to its TypeScript type
+fn map_primitive_type(type_name: &str) -> String {
+    match type_name {
+        "boolean" => "boolean".to_string(),
+        "integer" | "integer64" | "positiveInt" | "unsignedInt" => "number".to_string(),
+        "decimal" => "number".to_string(),
+        _ => "string".to_string(), // date, dateTime, time, etc. are strings
+    }
+}
+
+/// Map a choice type code, keeping complex types as-is
+fn map_choice_type(type_name: &str) -> String {
+    if is_primitive_type(type_name) { map_primitive_type(type_name) } else { type_name.to_string() }
+}
+
+/// Array of a type, parenthesizing unions and object types
+fn array_type(type_name: &str) -> String {
+    if type_name.contains(' ') { format!("({})[]", type_name) } else { format!("{}[]", type_name) }
 }
 
 /// Capitalize first letter of a string
@@ -294,6 +604,7 @@ fn capitalize(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ir::{CardinalityRange, Documentation};
 
     #[test]
     fn test_extension_definition_from_url() {
@@ -344,6 +655,7 @@ mod tests {
             target_types: vec!["Patient".to_string()],
             description: "Birth time".to_string(),
             is_array: false,
+            is_required: false,
         };
 
         let code = ExtensionGenerator::generate_add_method(&ext).unwrap();
@@ -361,6 +673,7 @@ mod tests {
             target_types: vec!["Patient".to_string()],
             description: "Birth time".to_string(),
             is_array: false,
+            is_required: false,
         };
 
         let code = ExtensionGenerator::generate_get_method(&ext).unwrap();
@@ -377,6 +690,7 @@ mod tests {
             target_types: vec!["Patient".to_string()],
             description: "Birth time".to_string(),
             is_array: false,
+            is_required: false,
         };
 
         let code = ExtensionGenerator::generate_has_method(&ext).unwrap();
@@ -393,6 +707,7 @@ mod tests {
             target_types: vec!["Patient".to_string()],
             description: "Birth time".to_string(),
             is_array: false,
+            is_required: false,
         };
 
         let code = ExtensionGenerator::generate_remove_method(&ext).unwrap();
@@ -400,10 +715,62 @@ mod tests {
         assert!(code.contains("this"));
     }
 
+    #[test]
+    fn test_from_extension_choice_and_nested() {
+        let ext = Extension {
+            url: "http://example.org/fhir/StructureDefinition/onset".to_string(),
+            name: "onset".to_string(),
+            value_types: vec!["dateTime".to_string(), "Period".to_string()],
+            target_types: vec!["Patient".to_string()],
+            cardinality: CardinalityRange::optional(),
+            documentation: Documentation::default(),
+            is_complex: false,
+            sub_extensions: vec![],
+        };
+        let def = ExtensionDefinition::from_extension(&ext).unwrap();
+        assert_eq!(
+            def.value_type,
+            ExtensionValueType::Choice(vec!["dateTime".to_string(), "Period".to_string()])
+        );
+        assert_eq!(def.complex_types(), vec!["Period".to_string()]);
+        let code = ExtensionGenerator::generate_add_method(&def).unwrap();
+        assert!(code.contains("{ valueDateTime: string } | { valuePeriod: Period }"));
+
+        let race = Extension {
+            url: "http://hl7.org/fhir/us/core/StructureDefinition/us-core-race".to_string(),
+            name: "race".to_string(),
+            value_types: vec![],
+            is_complex: true,
+            sub_extensions: vec![Extension {
+                url: "text".to_string(),
+                name: "text".to_string(),
+                value_types: vec!["string".to_string()],
+                cardinality: CardinalityRange::required(),
+                ..ext.clone()
+            }],
+            ..ext
+        };
+        let def = ExtensionDefinition::from_extension(&race).unwrap();
+        assert!(
+            matches!(def.value_type, ExtensionValueType::Nested(ref subs) if subs[0].is_required)
+        );
+        let code = ExtensionGenerator::generate_get_method(&def).unwrap();
+        assert!(code.contains("getRaceExtension"));
+        assert!(code.contains("text?: string"));
+    }
+
     #[test]
     fn test_capitalize() {
         assert_eq!(capitalize("birthTime"), "BirthTime");
         assert_eq!(capitalize("name"), "Name");
         assert_eq!(capitalize(""), "");
     }
+
+    #[test]
+    fn test_generate_extension_utilities_module() {
+        let code = ExtensionGenerator::generate_extension_utilities_module().unwrap();
+        assert!(code.contains("import { Extension } from './types/Extension';"));
+        assert!(code.contains("export function getExtension(target: HasExtensions"));
+        assert!(code.contains("export function removeExtension<T extends HasExtensions>"));
+    }
 }
@@ -0,0 +1,475 @@
+//! Translation of FHIRPath invariants into TypeScript
+//!
+//! Expressions are translated into TypeScript expressions that evaluate to a
+//! FHIRPath collection (`unknown[]`), using the small runtime emitted as
+//! `src/fhirpath.ts`. Only a common subset of FHIRPath is supported:
+//! navigation, indexers, literals, `and`/`or`/`xor`/`implies`, equality and
+//! comparison operators, `|`, and the functions `exists`, `empty`, `all`,
+//! `where`, `count`, `not`, `hasValue` and `matches`. Anything else is
+//! reported as unsupported so callers can surface it instead of emitting a
+//! check with different semantics.
+
+use crate::core::fhirpath::{
+    self, BinaryOperator, Expression, Literal, TypeOperator, UnaryOperator,
+};
+use crate::core::{Error, Result};
+
+/// Module name the generated code imports the runtime as
+const RUNTIME: &str = "fhirpath";
+
+/// Translator from FHIRPath expressions to TypeScript
+pub struct FhirPathTranslator;
+
+/// Evaluation context of a (sub)expression
+struct Scope<'a> {
+    /// TypeScript expression of the focus collection
+    focus: &'a str,
+    /// Nesting depth of `where`/`all`/`exists` criteria, used for parameter names
+    depth: usize,
+}
+
+impl FhirPathTranslator {
+    /// Translate a FHIRPath expression evaluated against `focus`
+    ///
+    /// `focus` is a TypeScript expression of the input collection, e.g. `[resource]`.
+    /// The result is a TypeScript expression evaluating to a collection.
+    pub fn translate(expression: &str, focus: &str) -> Result<String> {
+        let parsed = fhirpath::parse(expression)?;
+        Self::translate_expression(&parsed, &Scope { focus, depth: 0 })
+    }
+
+    /// Translate an invariant into a TypeScript condition that holds when it is violated
+    ///
+    /// Per FHIRPath semantics an invariant only fails when it evaluates to `false`;
+    /// an empty result does not raise an issue.
+    pub fn translate_violation(expression: &str, focus: &str) -> Result<String> {
+        Ok(format!("{}.isFalse({})", RUNTIME, Self::translate(expression, focus)?))
+    }
+
+    fn unsupported(what: String) -> Error {
+        Error::Generator(format!("unsupported FHIRPath {}", what))
+    }
+
+    fn translate_expression(expression: &Expression, scope: &Scope) -> Result<String> {
+        match expression {
+            Expression::Literal { value } => Self::translate_literal(value),
+            Expression::Identifier { name } => {
+                Ok(format!("{}.nav({}, {})", RUNTIME, scope.focus, Self::string(name)))
+            }
+            Expression::Variable { name } if name == "this" => Ok(scope.focus.to_string()),
+            Expression::Variable { name } => Err(Self::unsupported(format!("variable ${}", name))),
+            Expression::External { name } => {
+                Err(Self::unsupported(format!("environment variable %{}", name)))
+            }
+            Expression::Member { target, name } => Ok(format!(
+                "{}.nav({}, {})",
+                RUNTIME,
+                Self::translate_expression(target, scope)?,
+                Self::string(name)
+            )),
+            Expression::Index { target, index } => Ok(format!(
+                "{}.index({}, {})",
+                RUNTIME,
+                Self::translate_expression(target, scope)?,
+                Self::translate_expression(index, scope)?
+            )),
+            Expression::Function { target, name, args } => {
+                let target = match target {
+                    Some(target) => Self::translate_expression(target, scope)?,
+                    None => scope.focus.to_string(),
+                };
+                Self::translate_function(&target, name, args, scope)
+            }
+            Expression::Unary { op, operand } => match (op, operand.as_ref()) {
+                (UnaryOperator::Minus, Expression::Literal { value: Literal::Number(n) }) => {
+                    Ok(format!("[-{}]", n))
+                }
+                (UnaryOperator::Plus, Expression::Literal { value: Literal::Number(n) }) => {
+                    Ok(format!("[{}]", n))
+                }
+                _ => Err(Self::unsupported("unary operator on a non-literal".to_string())),
+            },
+            Expression::Binary { op, left, right } => {
+                let left = Self::translate_expression(left, scope)?;
+                let right = Self::translate_expression(right, scope)?;
+                Self::translate_binary(*op, &left, &right)
+            }
+            Expression::Type { op, type_name, .. } => {
+                let op = match op {
+                    TypeOperator::Is => "is",
+                    TypeOperator::As => "as",
+                };
+                Err(Self::unsupported(format!("type operator '{} {}'", op, type_name)))
+            }
+        }
+    }
+
+    fn translate_literal(literal: &Literal) -> Result<String> {
+        match literal {
+            Literal::Empty => Ok("[]".to_string()),
+            Literal::Boolean(value) => Ok(format!("[{}]", value)),
+            Literal::String(value) => Ok(format!("[{}]", Self::string(value))),
+            Literal::Number(value) => Ok(format!("[{}]", value)),
+            Literal::DateTime(value) => Err(Self::unsupported(format!("date literal @{}", value))),
+            Literal::Quantity { value, unit } => {
+                Err(Self::unsupported(format!("quantity literal {} '{}'", value, unit)))
+            }
+        }
+    }
+
+    fn translate_binary(op: BinaryOperator, left: &str, right: &str) -> Result<String> {
+        let call = |function: &str| Ok(format!("{}.{}({}, {})", RUNTIME, function, left, right));
+        match op {
+            BinaryOperator::And => call("and"),
+            BinaryOperator::Or => call("or"),
+            BinaryOperator::Xor => call("xor"),
+            BinaryOperator::Implies => call("implies"),
+            BinaryOperator::Equals => call("equals"),
+            BinaryOperator::NotEquals => {
+                Ok(format!("{}.not({}.equals({}, {}))", RUNTIME, RUNTIME, left, right))
+            }
+            BinaryOperator::Union => call("union"),
+            BinaryOperator::LessThan
+            | BinaryOperator::LessOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterOrEqual => Ok(format!(
+                "{}.compare({}, {}, {})",
+                RUNTIME,
+                left,
+                right,
+                Self::string(op.symbol())
+            )),
+            other => Err(Self::unsupported(format!("operator '{}'", other.symbol()))),
+        }
+    }
+
+    fn translate_function(
+        target: &str,
+        name: &str,
+        args: &[Expression],
+        scope: &Scope,
+    ) -> Result<String> {
+        let arity = |expected: usize| {
+            if args.len() == expected {
+                Ok(())
+            } else {
+                Err(Error::Generator(format!(
+                    "FHIRPath function '{}()' expects {} argument(s), got {}",
+                    name,
+                    expected,
+                    args.len()
+                )))
+            }
+        };
+
+        match name {
+            "exists" if args.is_empty() => Ok(format!("{}.exists({})", RUNTIME, target)),
+            "exists" => {
+                arity(1)?;
+                let filtered = Self::translate_filter(target, &args[0], scope)?;
+                Ok(format!("{}.exists({})", RUNTIME, filtered))
+            }
+            "empty" => {
+                arity(0)?;
+                Ok(format!("{}.empty({})", RUNTIME, target))
+            }
+            "where" => {
+                arity(1)?;
+                Self::translate_filter(target, &args[0], scope)
+            }
+            "all" => {
+                arity(1)?;
+                let (param, criteria) = Self::translate_criteria(&args[0], scope)?;
+                Ok(format!("[{}.every(({}) => {}.isTrue({}))]", target, param, RUNTIME, criteria))
+            }
+            "count" => {
+                arity(0)?;
+                Ok(format!("[{}.length]", target))
+            }
+            "not" => {
+                arity(0)?;
+                Ok(format!("{}.not({})", RUNTIME, target))
+            }
+            "hasValue" => {
+                arity(0)?;
+                Ok(format!("{}.hasValue({})", RUNTIME, target))
+            }
+            "matches" => {
+                arity(1)?;
+                let pattern = Self::translate_expression(&args[0], scope)?;
+                Ok(format!("{}.matches({}, {})", RUNTIME, target, pattern))
+            }
+            other => Err(Self::unsupported(format!("function '{}()'", other))),
+        }
+    }
+
+    /// Translate `target.where(criteria)`
+    fn translate_filter(target: &str, criteria: &Expression, scope: &Scope) -> Result<String> {
+        let (param, criteria) = Self::translate_criteria(criteria, scope)?;
+        Ok(format!("{}.filter(({}) => {}.isTrue({}))", target, param, RUNTIME, criteria))
+    }
+
+    /// Translate a criteria argument evaluated once per item; returns the item parameter name
+    fn translate_criteria(criteria: &Expression, scope: &Scope) -> Result<(String, String)> {
+        let param = format!("t{}", scope.depth);
+        let focus = format!("[{}]", param);
+        let translated =
+            Self::translate_expression(criteria, &Scope { focus: &focus, depth: scope.depth + 1 })?;
+        Ok((param, translated))
+    }
+
+    /// TypeScript string literal
+    fn string(value: &str) -> String {
+        serde_json::Value::from(value).to_string()
+    }
+
+    /// Generate the `fhirpath.ts` runtime used by translated invariants
+    pub fn generate_runtime_module() -> String {
+        RUNTIME_MODULE.to_string()
+    }
+}
+
+/// TypeScript runtime for translated FHIRPath expressions
+const RUNTIME_MODULE: &str = r#"/**
+ * Minimal FHIRPath runtime used by generated invariant checks
+ *
+ * This file is auto-generated. Do not edit manually.
+ */
+
+/** A FHIRPath collection */
+export type Collection = unknown[];
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+/** Navigate to the child elements called `name` of every item */
+export function nav(focus: Collection, name: string): Collection {
+  const result: Collection = [];
+  for (const item of focus) {
+    if (!isRecord(item)) {
+      continue;
+    }
+    // A type name at the start of a path selects the resource itself
+    if (item.resourceType === name) {
+      result.push(item);
+      continue;
+    }
+    let value = item[name];
+    if (value === undefined) {
+      // Choice elements: `value` matches `valueQuantity`, `valueString`, ...
+      const key = Object.keys(item).find(
+        (k) => k.startsWith(name) && /^[A-Z]/.test(k.slice(name.length)),
+      );
+      value = key === undefined ? undefined : item[key];
+    }
+    if (Array.isArray(value)) {
+      result.push(...value.filter((v) => v !== null && v !== undefined));
+    } else if (value !== null && value !== undefined) {
+      result.push(value);
+    }
+  }
+  return result;
+}
+
+/** Singleton evaluation of a collection as a boolean */
+export function toBoolean(collection: Collection): boolean | undefined {
+  if (collection.length === 0) {
+    return undefined;
+  }
+  const [value] = collection;
+  return typeof value === "boolean" ? value : true;
+}
+
+export function isTrue(collection: Collection): boolean {
+  return toBoolean(collection) === true;
+}
+
+export function isFalse(collection: Collection): boolean {
+  return toBoolean(collection) === false;
+}
+
+function fromBoolean(value: boolean | undefined): Collection {
+  return value === undefined ? [] : [value];
+}
+
+export function and(left: Collection, right: Collection): Collection {
+  const l = toBoolean(left);
+  const r = toBoolean(right);
+  if (l === false || r === false) {
+    return [false];
+  }
+  return l === true && r === true ? [true] : [];
+}
+
+export function or(left: Collection, right: Collection): Collection {
+  const l = toBoolean(left);
+  const r = toBoolean(right);
+  if (l === true || r === true) {
+    return [true];
+  }
+  return l === false && r === false ? [false] : [];
+}
+
+export function xor(left: Collection, right: Collection): Collection {
+  const l = toBoolean(left);
+  const r = toBoolean(right);
+  return l === undefined || r === undefined ? [] : [l !== r];
+}
+
+export function implies(left: Collection, right: Collection): Collection {
+  const l = toBoolean(left);
+  const r = toBoolean(right);
+  if (l === false) {
+    return [true];
+  }
+  if (l === true) {
+    return fromBoolean(r);
+  }
+  return r === true ? [true] : [];
+}
+
+export function not(collection: Collection): Collection {
+  const value = toBoolean(collection);
+  return value === undefined ? [] : [!value];
+}
+
+export function exists(collection: Collection): Collection {
+  return [collection.length > 0];
+}
+
+export function empty(collection: Collection): Collection {
+  return [collection.length === 0];
+}
+
+export function hasValue(collection: Collection): Collection {
+  const [value] = collection;
+  return [collection.length === 1 && ["string", "number", "boolean"].includes(typeof value)];
+}
+
+export function index(collection: Collection, position: Collection): Collection {
+  const [n] = position;
+  return typeof n === "number" && n >= 0 && n < collection.length ? [collection[n]] : [];
+}
+
+export function equals(left: Collection, right: Collection): Collection {
+  if (left.length === 0 || right.length === 0) {
+    return [];
+  }
+  return [
+    left.length === right.length &&
+      left.every((value, i) => JSON.stringify(value) === JSON.stringify(right[i])),
+  ];
+}
+
+export function compare(left: Collection, right: Collection, op: "<" | "<=" | ">" | ">="): Collection {
+  if (left.length !== 1 || right.length !== 1) {
+    return [];
+  }
+  const [l] = left as [number | string];
+  const [r] = right as [number | string];
+  switch (op) {
+    case "<":
+      return [l < r];
+    case "<=":
+      return [l <= r];
+    case ">":
+      return [l > r];
+    case ">=":
+      return [l >= r];
+  }
+}
+
+export function union(left: Collection, right: Collection): Collection {
+  const seen = new Set<string>();
+  return [...left, ...right].filter((value) => {
+    const key = JSON.stringify(value);
+    if (seen.has(key)) {
+      return false;
+    }
+    seen.add(key);
+    return true;
+  });
+}
+
+export function matches(collection: Collection, pattern: Collection): Collection {
+  if (collection.length !== 1 || pattern.length !== 1) {
+    return [];
+  }
+  return [new RegExp(String(pattern[0]), "u").test(String(collection[0]))];
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_navigation_and_exists() {
+        let ts = FhirPathTranslator::translate("name.given.exists()", "[resource]").unwrap();
+        assert_eq!(
+            ts,
+            "fhirpath.exists(fhirpath.nav(fhirpath.nav([resource], \"name\"), \"given\"))"
+        );
+    }
+
+    #[test]
+    fn test_translate_pat_1() {
+        let ts = FhirPathTranslator::translate_violation(
+            "name.exists() or telecom.exists() or address.exists() or organization.exists()",
+            "[item0]",
+        )
+        .unwrap();
+        assert!(ts.starts_with("fhirpath.isFalse(fhirpath.or(fhirpath.or(fhirpath.or("));
+        assert!(ts.contains("fhirpath.exists(fhirpath.nav([item0], \"organization\"))"));
+    }
+
+    #[test]
+    fn test_translate_criteria_scopes() {
+        let ts = FhirPathTranslator::translate(
+            "contact.all(name.exists() implies telecom.where($this.system = 'phone').exists())",
+            "[resource]",
+        )
+        .unwrap();
+        assert_eq!(
+            ts,
+            "[fhirpath.nav([resource], \"contact\").every((t0) => fhirpath.isTrue(\
+             fhirpath.implies(fhirpath.exists(fhirpath.nav([t0], \"name\")), \
+             fhirpath.exists(fhirpath.nav([t0], \"telecom\").filter((t1) => fhirpath.isTrue(\
+             fhirpath.equals(fhirpath.nav([t1], \"system\"), [\"phone\"])))))))]"
+        );
+    }
+
+    #[test]
+    fn test_translate_count_comparison_and_not() {
+        let ts = FhirPathTranslator::translate("(code.count() > 1).not()", "[value]").unwrap();
+        assert_eq!(
+            ts,
+            "fhirpath.not(fhirpath.compare([fhirpath.nav([value], \"code\").length], [1], \">\"))"
+        );
+    }
+
+    #[test]
+    fn test_translate_has_value_and_matches() {
+        let ts =
+            FhirPathTranslator::translate("hasValue() or matches('^[0-9]+$')", "[value]").unwrap();
+        assert_eq!(
+            ts,
+            "fhirpath.or(fhirpath.hasValue([value]), fhirpath.matches([value], [\"^[0-9]+$\"]))"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_expressions() {
+        let err = FhirPathTranslator::translate("reference.resolve()", "[value]").unwrap_err();
+        assert!(err.to_string().contains("function 'resolve()'"));
+
+        let err = FhirPathTranslator::translate("value is Quantity", "[value]").unwrap_err();
+        assert!(err.to_string().contains("type operator"));
+
+        let err = FhirPathTranslator::translate("%resource.id.exists()", "[value]").unwrap_err();
+        assert!(err.to_string().contains("%resource"));
+
+        assert!(FhirPathTranslator::translate("name.exists(", "[value]").is_err());
+    }
+}
@@ -60,6 +60,50 @@ impl HelpersGenerator {
         GencoTemplateEngine::format_typescript(&tokens)
     }
 
+    /// Generate the getPrimitive utility function
+    ///
+    /// Joins a primitive's inline value with its `_name` sibling element into a
+    /// FhirPrimitive<T>
+    pub fn generate_get_primitive(&self) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        // JSDoc
+        tokens.append("/**");
+        tokens.push();
+        tokens.append(" * Join a primitive's inline value with its `_name` sibling element");
+        tokens.push();
+        tokens.append(" * ");
+        tokens.push();
+        tokens.append(" * @param owner - The resource or datatype holding the primitive");
+        tokens.push();
+        tokens.append(" * @param name - The primitive element name, e.g. \"birthDate\"");
+        tokens.push();
+        tokens.append(
+            " * @returns The value with its id and extensions, or undefined if neither is present",
+        );
+        tokens.push();
+        tokens.append(" */");
+        tokens.push();
+
+        // Function declaration
+        tokens.append("export function getPrimitive<O extends object, K extends keyof O & string>(owner: O, name: K): FhirPrimitive<NonNullable<O[K]>> | undefined {");
+        tokens.push();
+        tokens.indent();
+
+        tokens.append("const value = owner[name] as NonNullable<O[K]> | undefined;");
+        tokens.push();
+        tokens.append("const element = (owner as unknown as Record<string, unknown>)[`_${name}`] as Omit<FhirPrimitive<unknown>, \"value\"> | undefined;");
+        tokens.push();
+        tokens.append("if (value === undefined && element === undefined) return undefined;");
+        tokens.push();
+        tokens.append("return { ...element, value };");
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
     /// Generate helper namespace for a resource
     ///
     /// Creates resource-specific helper methods like getOfficialName, getFullName, etc.
@@ -156,15 +200,15 @@ impl HelpersGenerator {
         tokens.append("export function isDeceased(patient: Patient): boolean {");
         tokens.indent();
         tokens.push();
-        tokens.append("if (typeof patient.deceased === \"boolean\") {");
+        tokens.append("if (patient.deceasedBoolean !== undefined) {");
         tokens.indent();
         tokens.push();
-        tokens.append("return patient.deceased;");
+        tokens.append("return patient.deceasedBoolean;");
         tokens.unindent();
         tokens.push();
         tokens.append("}");
         tokens.push();
-        tokens.append("return patient.deceased !== undefined;");
+        tokens.append("return patient.deceasedDateTime !== undefined;");
         tokens.unindent();
         tokens.push();
         tokens.append("}");
@@ -184,8 +228,7 @@ impl HelpersGenerator {
         tokens.append("if (!patient.birthDate) return undefined;");
         tokens.push();
         tokens.push();
-        tokens
-            .append("const birth = new Date(extractPrimitiveValue(patient.birthDate) as string);");
+        tokens.append("const birth = new Date(patient.birthDate);");
         tokens.push();
         tokens.append("let age = asOf.getFullYear() - birth.getFullYear();");
         tokens.push();
@@ -363,8 +406,6 @@ impl HelpersGenerator {
             }
         }
 
-        tokens.append("import { extractPrimitiveValue } from './utilities';");
-        tokens.push();
         tokens.push();
 
         // Generate helpers
@@ -397,6 +438,11 @@ impl HelpersGenerator {
 
         // extractPrimitiveValue
         tokens.append(self.generate_extract_primitive_value()?);
+        tokens.push();
+        tokens.push();
+
+        // getPrimitive
+        tokens.append(self.generate_get_primitive()?);
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
@@ -1,7 +1,18 @@
 use crate::core::Result;
 use crate::languages::typescript::backend::TypeScriptBackend;
+use heck::ToLowerCamelCase;
 use serde_json::json;
 
+/// Format a TypeScript version as a caret dependency range ("5.3" -> "^5.3.0")
+fn typescript_dependency(version: &str) -> String {
+    let version = version.trim_start_matches('^');
+    match version.split('.').count() {
+        1 => format!("^{}.0.0", version),
+        2 => format!("^{}.0", version),
+        _ => format!("^{}", version),
+    }
+}
+
 /// Configuration for package manifest generation
 #[derive(Debug, Clone)]
 pub struct PackageConfig {
@@ -32,17 +43,60 @@ impl Default for PackageConfig {
     }
 }
 
+/// TypeScript compiler settings for the generated package
+#[derive(Debug, Clone)]
+pub struct CompilerConfig {
+    /// TypeScript version used as the `typescript` dev dependency (e.g., "5.3")
+    pub typescript_version: String,
+    /// Enable strict type checking in tsconfig.json
+    pub strict: bool,
+    /// Extra compiler options merged over the generated defaults
+    ///
+    /// Keys may be given in camelCase or snake_case (`es_module_interop`).
+    pub compiler_options: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Default for CompilerConfig {
+    fn default() -> Self {
+        Self {
+            typescript_version: "5.8.0".to_string(),
+            strict: true,
+            compiler_options: serde_json::Map::new(),
+        }
+    }
+}
+
 /// Generator for TypeScript package manifests
 #[allow(dead_code)]
 pub struct ManifestGenerator {
     backend: TypeScriptBackend,
     config: PackageConfig,
+    compiler: CompilerConfig,
+    /// Runtime dependencies of the generated package (name -> version range)
+    dependencies: serde_json::Map<String, serde_json::Value>,
 }
 
 impl ManifestGenerator {
     /// Create a new manifest generator
     pub fn new(backend: TypeScriptBackend, config: PackageConfig) -> Self {
-        Self { backend, config }
+        Self {
+            backend,
+            config,
+            compiler: CompilerConfig::default(),
+            dependencies: serde_json::Map::new(),
+        }
+    }
+
+    /// Use custom TypeScript compiler settings
+    pub fn with_compiler_config(mut self, compiler: CompilerConfig) -> Self {
+        self.compiler = compiler;
+        self
+    }
+
+    /// Add a runtime dependency to package.json
+    pub fn with_dependency(mut self, name: &str, version: &str) -> Self {
+        self.dependencies.insert(name.to_string(), json!(version));
+        self
     }
 
     /// Create with default configuration
@@ -89,7 +143,7 @@ impl ManifestGenerator {
             "license": self.config.license,
             "devDependencies": {
                 "@types/node": "^20.0.0",
-                "typescript": "^5.8.0",
+                "typescript": typescript_dependency(&self.compiler.typescript_version),
                 "vitest": "^2.0.0",
                 "@biomejs/biome": "^1.9.0"
             },
@@ -98,6 +152,10 @@ impl ManifestGenerator {
             }
         });
 
+        if !self.dependencies.is_empty() {
+            package["dependencies"] = serde_json::Value::Object(self.dependencies.clone());
+        }
+
         if let Some(repo_url) = &self.config.repository_url {
             package["repository"] = json!({
                 "type": "git",
@@ -112,7 +170,7 @@ impl ManifestGenerator {
 
     /// Generate tsconfig.json for ESM
     pub fn generate_tsconfig_json(&self) -> Result<String> {
-        let tsconfig = json!({
+        let mut tsconfig = json!({
             "compilerOptions": {
                 "target": "ES2022",
                 "module": "ESNext",
@@ -123,13 +181,13 @@ impl ManifestGenerator {
                 "sourceMap": true,
                 "outDir": "./dist",
                 "rootDir": "./src",
-                "strict": true,
+                "strict": self.compiler.strict,
                 "esModuleInterop": true,
                 "skipLibCheck": true,
                 "forceConsistentCasingInFileNames": true,
                 "resolveJsonModule": true,
                 "isolatedModules": true,
-                "noUncheckedIndexedAccess": true,
+                "noUncheckedIndexedAccess": self.compiler.strict,
                 "noUnusedLocals": true,
                 "noUnusedParameters": true,
                 "noImplicitReturns": true,
@@ -139,6 +197,11 @@ impl ManifestGenerator {
             "exclude": ["node_modules", "dist", "**/*.test.ts"]
         });
 
+        let options = &mut tsconfig["compilerOptions"];
+        for (key, value) in &self.compiler.compiler_options {
+            options[key.to_lower_camel_case()] = value.clone();
+        }
+
         serde_json::to_string_pretty(&tsconfig).map_err(|e| {
             crate::core::Error::Generator(format!("Failed to serialize tsconfig.json: {}", e))
         })
@@ -279,7 +342,8 @@ Resource-specific helper namespaces:
 
 ### Utilities
 
-- `extractPrimitiveValue<T>()` - Extract value from `FhirPrimitive<T>` wrapper
+- `extractPrimitiveValue<T>()` - Extract value from a `FhirPrimitive<T>`
+- `getPrimitive()` - Join a primitive value with its `_name` sibling element
 
 ## Development
 
@@ -3,34 +3,64 @@
 mod backend;
 /// Base class generation for TypeScript (Resource, DomainResource, Element)
 pub mod base_class_generator;
+/// Bundle builder and reader utilities for TypeScript
+pub mod bundle_generator;
 pub mod choice_generator;
 /// TypeScript class generation with fluent builder API
 pub mod class_generator;
+/// Typed REST client generation for TypeScript
+pub mod client_generator;
 pub mod datatype_generator;
 /// Documentation generation for TypeScript with JSDoc and TypeDoc support
 pub mod documentation_generator;
 /// Extension helper methods generation for TypeScript
 pub mod extension_generator;
+/// Translation of FHIRPath invariants into TypeScript checks
+pub mod fhirpath_translator;
 /// Helper methods generation for TypeScript
 pub mod helpers_generator;
 /// Package manifest generation for TypeScript
 pub mod manifest_generator;
+/// Typed `$operation` parameter builders for TypeScript
+pub mod operation_generator;
+/// Profile-specific types, conformance guards and profile stamping for TypeScript
+pub mod profile_generator;
 pub mod resource_generator;
 /// Complete TypeScript SDK generation orchestrator
 pub mod sdk_generator;
+/// Typed search query builder generation for TypeScript
+pub mod search_generator;
+/// Polymorphic parsing and serialization for TypeScript classes
+pub mod serialization_generator;
 pub mod templates;
+/// Vitest suites with round-trip fixtures for TypeScript
+pub mod test_generator;
 /// Validation functions generation for TypeScript
 pub mod validation_generator;
+/// Value set code types generation for TypeScript
+pub mod valueset_generator;
+/// Zod schema generation for TypeScript
+pub mod zod_generator;
 
 pub use backend::TypeScriptBackend;
 pub use base_class_generator::BaseClassGenerator;
+pub use bundle_generator::BundleGenerator;
 pub use choice_generator::ChoiceElementGenerator;
 pub use class_generator::ClassGenerator;
+pub use client_generator::ClientGenerator;
 pub use datatype_generator::DatatypeGenerator;
 pub use documentation_generator::DocumentationGenerator;
 pub use extension_generator::{ExtensionDefinition, ExtensionGenerator, ExtensionValueType};
+pub use fhirpath_translator::FhirPathTranslator;
 pub use helpers_generator::HelpersGenerator;
-pub use manifest_generator::{ManifestGenerator, PackageConfig};
+pub use manifest_generator::{CompilerConfig, ManifestGenerator, PackageConfig};
+pub use operation_generator::OperationGenerator;
+pub use profile_generator::ProfileGenerator;
 pub use resource_generator::ResourceGenerator;
-pub use sdk_generator::TypeScriptSdkGenerator;
+pub use sdk_generator::{SdkOptions, TypeScriptSdkGenerator, ValidationStyle};
+pub use search_generator::SearchGenerator;
+pub use serialization_generator::SerializationGenerator;
+pub use test_generator::TestGenerator;
 pub use validation_generator::ValidationGenerator;
+pub use valueset_generator::ValueSetGenerator;
+pub use zod_generator::ZodGenerator;
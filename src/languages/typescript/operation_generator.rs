@@ -0,0 +1,615 @@
+//! Typed `$operation` functions for TypeScript
+//!
+//! Every OperationDefinition in the graph gets `XInput`/`XOutput` types for its
+//! in and out parameters, a `buildXParameters` function producing the request
+//! `Parameters` resource and a `parseXOutput` function reading the response:
+//!
+//! ```typescript
+//! const body = buildPatientEverythingParameters({ start: "2020-01-01" });
+//! const { return: bundle } = parsePatientEverythingOutput(response);
+//! ```
+
+use crate::core::Result;
+use crate::core::ir::{OperationDefinition, OperationParameter, PropertyType, TypeGraph};
+use crate::generator::LanguageBackend;
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::client_generator::FALLBACK_TYPES;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+use heck::ToPascalCase;
+use indexmap::IndexMap;
+use std::collections::{BTreeSet, HashSet};
+
+/// Generator for typed operation parameter builders and parsers
+pub struct OperationGenerator {
+    backend: TypeScriptBackend,
+    use_classes: bool,
+}
+
+impl OperationGenerator {
+    /// Create an operation generator using a backend's type mappings
+    pub fn new(backend: TypeScriptBackend) -> Self {
+        Self { backend, use_classes: false }
+    }
+
+    /// Build `Parameters` instances with the generated class's static `parse`
+    pub fn with_classes(mut self, use_classes: bool) -> Self {
+        self.use_classes = use_classes;
+        self
+    }
+
+    /// Assign a TypeScript base name to every operation in the graph
+    ///
+    /// Operations defined on a single resource are prefixed with it
+    /// (`Patient-everything` -> `PatientEverything`); others use their code
+    /// (`Resource-validate` -> `Validate`). Clashes get a counter. Returns
+    /// operation URL -> base name.
+    pub fn operation_names(graph: &TypeGraph) -> IndexMap<String, String> {
+        let mut taken = HashSet::new();
+        let mut names = IndexMap::new();
+
+        for (url, operation) in &graph.operations {
+            let code = operation.code.to_pascal_case();
+            let base = match operation.resources.as_slice() {
+                [resource] if resource != "Resource" => {
+                    format!("{}{}", TypeScriptBackend::sanitize_identifier(resource), code)
+                }
+                _ => code,
+            };
+
+            let mut name = base.clone();
+            let mut counter = 2;
+            while !taken.insert(name.clone()) {
+                name = format!("{}{}", base, counter);
+                counter += 1;
+            }
+            names.insert(url.clone(), name);
+        }
+
+        names
+    }
+
+    /// Generate `src/operations.ts`
+    pub fn generate_operations_module(&self, graph: &TypeGraph) -> Result<String> {
+        let names = Self::operation_names(graph);
+        let has_parameters =
+            graph.resources.get("Parameters").is_some_and(|resource| !resource.is_abstract);
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated operation parameter builders");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.line();
+
+        // Imports
+        let mut datatypes = BTreeSet::new();
+        let mut resources = BTreeSet::new();
+        for operation in graph.operations.values() {
+            Self::collect_types(graph, &operation.parameters, &mut datatypes, &mut resources);
+        }
+        if has_parameters {
+            resources.insert("Parameters".to_string());
+        }
+        for name in &datatypes {
+            tokens.append(format!("import type {{ {} }} from './types/{}';", name, name));
+            tokens.push();
+        }
+        for name in &resources {
+            let keyword =
+                if self.use_classes && name == "Parameters" { "import" } else { "import type" };
+            tokens.append(format!("{} {{ {} }} from './resources/{}';", keyword, name, name));
+            tokens.push();
+        }
+        tokens.line();
+
+        if !has_parameters
+            && let Some((_, definition)) =
+                FALLBACK_TYPES.iter().find(|(name, _)| *name == "Parameters")
+        {
+            tokens.append(helpers::jsdoc_comment(&[
+                "Minimal Parameters shape; the package does not define Parameters".to_string(),
+            ]));
+            tokens.push();
+            for line in definition.lines() {
+                tokens.append(line);
+                tokens.push();
+            }
+            tokens.line();
+        }
+
+        for line in RUNTIME.lines() {
+            if line.is_empty() {
+                tokens.line();
+            } else {
+                tokens.append(line);
+                tokens.push();
+            }
+        }
+        tokens.line();
+        tokens.append(helpers::jsdoc_comment(&[
+            "Wrap parameter entries in a `Parameters` resource".to_string(),
+        ]));
+        tokens.push();
+        tokens.append("function toParameters(parameter: ParameterEntry[]): Parameters {");
+        tokens.push();
+        if self.use_classes && has_parameters {
+            tokens
+                .append("  return Parameters.parse({ resourceType: \"Parameters\", parameter });");
+        } else {
+            tokens.append(
+                "  return { resourceType: \"Parameters\", parameter } as unknown as Parameters;",
+            );
+        }
+        tokens.push();
+        tokens.append("}");
+
+        for (url, operation) in &graph.operations {
+            tokens.line();
+            tokens.append(self.generate_operation(graph, operation, &names[url]));
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Types, specs and functions of a single operation
+    fn generate_operation(
+        &self,
+        graph: &TypeGraph,
+        operation: &OperationDefinition,
+        name: &str,
+    ) -> js::Tokens {
+        let mut tokens = js::Tokens::new();
+        let inputs: Vec<&OperationParameter> = operation.input_parameters().collect();
+        let outputs: Vec<&OperationParameter> = operation.output_parameters().collect();
+        let invocation = Self::invocation(operation);
+
+        let mut doc = Vec::new();
+        if !operation.documentation.short.is_empty() {
+            doc.push(operation.documentation.short.clone());
+            doc.push(String::new());
+        }
+        doc.push(format!("`{}` ({})", invocation, operation.url));
+
+        // Metadata
+        tokens.append(helpers::jsdoc_comment(&doc));
+        tokens.push();
+        tokens.append(format!("export const {}Operation = {{", name));
+        tokens.push();
+        tokens.append(format!("  url: {},", Self::quote(&operation.url)));
+        tokens.push();
+        tokens.append(format!("  code: {},", Self::quote(&format!("${}", operation.code))));
+        tokens.push();
+        let resources: Vec<String> = operation.resources.iter().map(|r| Self::quote(r)).collect();
+        tokens.append(format!("  resources: [{}],", resources.join(", ")));
+        tokens.push();
+        tokens.append(format!("  system: {},", operation.system));
+        tokens.push();
+        tokens.append(format!("  type: {},", operation.type_level));
+        tokens.push();
+        tokens.append(format!("  instance: {},", operation.instance));
+        tokens.push();
+        tokens.append(format!("  affectsState: {},", operation.affects_state));
+        tokens.push();
+        tokens.append("} as const;");
+        tokens.line();
+
+        // Parameter shapes
+        for (suffix, parameters, direction) in
+            [("Input", &inputs, "Input"), ("Output", &outputs, "Output")]
+        {
+            let type_name = format!("{}{}", name, suffix);
+            let doc = [format!("{} parameters of `{}`", direction, invocation)];
+            if parameters.is_empty() {
+                tokens.append(helpers::type_alias(&type_name, "Record<string, never>", Some(&doc)));
+            } else {
+                tokens.append(helpers::jsdoc_comment(&doc));
+                tokens.push();
+                tokens.append(format!("export interface {} {{", type_name));
+                for parameter in parameters {
+                    if let Some(line) = Self::first_line(&parameter.documentation) {
+                        tokens.push();
+                        tokens.append(format!("  /** {} */", line));
+                    }
+                    tokens.push();
+                    tokens.append(format!("  {};", self.field(graph, parameter)));
+                }
+                tokens.push();
+                tokens.append("}");
+            }
+            tokens.line();
+
+            tokens.append(format!(
+                "const {}Spec: ParameterSpec[] = {};",
+                type_name,
+                Self::specs(graph, parameters)
+            ));
+            tokens.line();
+        }
+
+        // Builder
+        tokens.append(helpers::jsdoc_comment(&[format!(
+            "Build the `Parameters` sent to `{}`",
+            invocation
+        )]));
+        tokens.push();
+        let default = if inputs.iter().any(|p| p.cardinality.is_required()) { "" } else { " = {}" };
+        tokens.append(format!(
+            "export function build{0}Parameters(input: {0}Input{1}): Parameters {{",
+            name, default
+        ));
+        tokens.push();
+        tokens.append(format!(
+            "  return toParameters(toParameterEntries({}InputSpec, input));",
+            name
+        ));
+        tokens.push();
+        tokens.append("}");
+        tokens.line();
+
+        // Parser
+        let direct = operation.return_resource().filter(|resource| *resource != "Parameters");
+        let mut doc = vec![format!("Read the response of `{}`", invocation)];
+        let response_type = match direct {
+            Some(resource) => {
+                doc.push(String::new());
+                doc.push(format!(
+                    "The server may return the {} itself instead of a `Parameters` resource.",
+                    resource
+                ));
+                format!("Parameters | {}", self.value_type(graph, Some(resource)))
+            }
+            None => "Parameters".to_string(),
+        };
+        tokens.append(helpers::jsdoc_comment(&doc));
+        tokens.push();
+        tokens.append(format!(
+            "export function parse{0}Output(response: {1}): {0}Output {{",
+            name, response_type
+        ));
+        tokens.push();
+        tokens.append(format!(
+            "  return fromResponse({}OutputSpec, response, {}) as {}Output;",
+            name,
+            direct.is_some(),
+            name
+        ));
+        tokens.push();
+        tokens.append("}");
+
+        tokens
+    }
+
+    /// How the operation is invoked, e.g. `Patient/[id]/$everything`
+    fn invocation(operation: &OperationDefinition) -> String {
+        let code = format!("${}", operation.code);
+        match operation.resources.as_slice() {
+            [resource] if operation.instance => format!("{}/[id]/{}", resource, code),
+            [resource] if operation.type_level => format!("{}/{}", resource, code),
+            _ => code,
+        }
+    }
+
+    /// Interface member for a parameter
+    fn field(&self, graph: &TypeGraph, parameter: &OperationParameter) -> String {
+        let optional = if parameter.cardinality.is_required() { "" } else { "?" };
+        format!(
+            "{}{}: {}",
+            Self::property_key(&parameter.name),
+            optional,
+            self.parameter_type(graph, parameter)
+        )
+    }
+
+    /// TypeScript type of a parameter's value(s)
+    fn parameter_type(&self, graph: &TypeGraph, parameter: &OperationParameter) -> String {
+        let value = if parameter.type_name.is_none() && !parameter.parts.is_empty() {
+            let fields: Vec<String> =
+                parameter.parts.iter().map(|part| self.field(graph, part)).collect();
+            format!("{{ {} }}", fields.join("; "))
+        } else {
+            self.value_type(graph, parameter.type_name.as_deref())
+        };
+
+        if parameter.cardinality.is_array() {
+            if value.contains(' ') { format!("Array<{}>", value) } else { format!("{}[]", value) }
+        } else {
+            value
+        }
+    }
+
+    /// TypeScript type of a single value of a FHIR type
+    fn value_type(&self, graph: &TypeGraph, type_name: Option<&str>) -> String {
+        match type_name {
+            Some(type_name) if Self::is_primitive(graph, type_name) => {
+                self.backend.map_type(&PropertyType::Primitive { type_name: type_name.to_string() })
+            }
+            Some(type_name)
+                if graph.datatypes.contains_key(type_name)
+                    || graph.resources.contains_key(type_name) =>
+            {
+                type_name.to_string()
+            }
+            // Polymorphic values keep their `valueX` element
+            _ if Self::element(graph, type_name) == "value[x]" => "ParameterValue".to_string(),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    /// `Parameters.parameter` element holding a value of a FHIR type
+    ///
+    /// Primitives and datatypes use `valueX`, resources use `resource`; types
+    /// the graph does not know (`Any`, `Element`, ...) keep any `valueX` element.
+    fn element(graph: &TypeGraph, type_name: Option<&str>) -> String {
+        match type_name {
+            None => "part".to_string(),
+            Some(type_name)
+                if Self::is_primitive(graph, type_name)
+                    || graph.datatypes.contains_key(type_name) =>
+            {
+                format!("value{}", Self::upper_first(type_name))
+            }
+            Some(type_name)
+                if graph.resources.contains_key(type_name)
+                    || matches!(type_name, "Resource" | "DomainResource") =>
+            {
+                "resource".to_string()
+            }
+            Some(_) => "value[x]".to_string(),
+        }
+    }
+
+    /// Whether a FHIR type name is a primitive
+    fn is_primitive(graph: &TypeGraph, type_name: &str) -> bool {
+        graph.primitives.contains_key(type_name)
+            || type_name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+    }
+
+    /// Runtime specs describing how parameters map to `Parameters.parameter`
+    fn specs(graph: &TypeGraph, parameters: &[&OperationParameter]) -> String {
+        let specs: Vec<String> = parameters
+            .iter()
+            .map(|parameter| {
+                let element = if parameter.type_name.is_none() && !parameter.parts.is_empty() {
+                    "part".to_string()
+                } else {
+                    Self::element(graph, parameter.type_name.as_deref())
+                };
+                let mut spec = format!(
+                    "{{ name: {}, element: {}, array: {}",
+                    Self::quote(&parameter.name),
+                    Self::quote(&element),
+                    parameter.cardinality.is_array()
+                );
+                if element == "part" {
+                    let parts: Vec<&OperationParameter> = parameter.parts.iter().collect();
+                    spec.push_str(&format!(", parts: {}", Self::specs(graph, &parts)));
+                }
+                spec.push_str(" }");
+                spec
+            })
+            .collect();
+        format!("[{}]", specs.join(", "))
+    }
+
+    /// Collect the datatypes and resources referenced by parameters
+    fn collect_types(
+        graph: &TypeGraph,
+        parameters: &[OperationParameter],
+        datatypes: &mut BTreeSet<String>,
+        resources: &mut BTreeSet<String>,
+    ) {
+        for parameter in parameters {
+            if let Some(type_name) = &parameter.type_name {
+                if graph.datatypes.contains_key(type_name) {
+                    datatypes.insert(type_name.clone());
+                } else if graph.resources.contains_key(type_name) {
+                    resources.insert(type_name.clone());
+                }
+            }
+            Self::collect_types(graph, &parameter.parts, datatypes, resources);
+        }
+    }
+
+    /// Object key for a parameter name, quoted when not a valid identifier
+    fn property_key(name: &str) -> String {
+        let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if valid { name.to_string() } else { Self::quote(name) }
+    }
+
+    /// First non-empty line of a description, safe inside a JSDoc comment
+    fn first_line(text: &str) -> Option<String> {
+        text.lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(|line| line.replace("*/", "*\\/"))
+    }
+
+    /// Uppercase the first character (`dateTime` -> `DateTime`)
+    fn upper_first(value: &str) -> String {
+        let mut chars = value.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// Quote a string as a TypeScript string literal
+    fn quote(value: &str) -> String {
+        serde_json::Value::from(value).to_string()
+    }
+}
+
+/// Conversion between typed parameter objects and `Parameters.parameter` entries
+const RUNTIME: &str = r#"/** Value of a parameter whose type varies, e.g. `{ valueString: "x" }` */
+export type ParameterValue = { [element: `value${string}`]: unknown };
+
+/** An entry of `Parameters.parameter` */
+type ParameterEntry = { name: string; part?: ParameterEntry[] } & Record<string, unknown>;
+
+/** How an operation parameter maps to `Parameters.parameter` */
+interface ParameterSpec {
+  name: string;
+  /** `valueX`, `resource`, `part`, or `value[x]` for values of varying type */
+  element: string;
+  array: boolean;
+  parts?: ParameterSpec[];
+}
+
+function toParameterEntries(specs: ParameterSpec[], input: object): ParameterEntry[] {
+  const values = input as Record<string, unknown>;
+  const entries: ParameterEntry[] = [];
+  for (const spec of specs) {
+    const value = values[spec.name];
+    if (value === undefined) {
+      continue;
+    }
+    for (const item of spec.array && Array.isArray(value) ? value : [value]) {
+      if (spec.element === "part") {
+        entries.push({ name: spec.name, part: toParameterEntries(spec.parts ?? [], item as object) });
+      } else if (spec.element === "value[x]") {
+        entries.push({ ...(item as object), name: spec.name });
+      } else {
+        entries.push({ name: spec.name, [spec.element]: item });
+      }
+    }
+  }
+  return entries;
+}
+
+function fromParameterEntries(specs: ParameterSpec[], entries: ParameterEntry[]): Record<string, unknown> {
+  const result: Record<string, unknown> = {};
+  for (const spec of specs) {
+    const values = entries
+      .filter((entry) => entry.name === spec.name)
+      .map((entry) => {
+        if (spec.element === "part") {
+          return fromParameterEntries(spec.parts ?? [], entry.part ?? []);
+        }
+        if (spec.element === "value[x]") {
+          return Object.fromEntries(Object.entries(entry).filter(([key]) => key.startsWith("value")));
+        }
+        return entry[spec.element];
+      });
+    if (values.length > 0) {
+      result[spec.name] = spec.array ? values : values[0];
+    }
+  }
+  return result;
+}
+
+/** Read a response, which is the returned resource itself when `direct` is set and it is not `Parameters` */
+function fromResponse(specs: ParameterSpec[], response: object, direct: boolean): Record<string, unknown> {
+  const resource = response as { resourceType?: string; parameter?: unknown };
+  if (direct && resource.resourceType !== "Parameters") {
+    return { return: response };
+  }
+  return fromParameterEntries(specs, (resource.parameter ?? []) as ParameterEntry[]);
+}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{
+        CardinalityRange, Documentation, FhirVersion, OperationKind, OperationParameterUse,
+    };
+
+    fn parameter(
+        name: &str,
+        usage: OperationParameterUse,
+        type_name: Option<&str>,
+        cardinality: CardinalityRange,
+    ) -> OperationParameter {
+        OperationParameter {
+            name: name.to_string(),
+            usage,
+            cardinality,
+            type_name: type_name.map(String::from),
+            target_profiles: vec![],
+            search_type: None,
+            binding: None,
+            documentation: String::new(),
+            parts: vec![],
+        }
+    }
+
+    fn operation(code: &str, resources: &[&str]) -> OperationDefinition {
+        OperationDefinition {
+            url: format!("http://example.org/OperationDefinition/{}", code),
+            name: code.to_string(),
+            code: code.to_string(),
+            kind: OperationKind::Operation,
+            affects_state: false,
+            resources: resources.iter().map(|r| r.to_string()).collect(),
+            system: false,
+            type_level: true,
+            instance: false,
+            parameters: vec![],
+            documentation: Documentation::default(),
+        }
+    }
+
+    #[test]
+    fn test_operation_names() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        for (url, op) in [
+            ("a", operation("everything", &["Patient"])),
+            ("b", operation("validate", &["Resource"])),
+            ("c", operation("meta-add", &["Patient", "Observation"])),
+            ("d", operation("validate", &[])),
+        ] {
+            graph.add_operation(url.to_string(), op);
+        }
+
+        let names = OperationGenerator::operation_names(&graph);
+
+        assert_eq!(names["a"], "PatientEverything");
+        assert_eq!(names["b"], "Validate");
+        assert_eq!(names["c"], "MetaAdd");
+        assert_eq!(names["d"], "Validate2");
+    }
+
+    #[test]
+    fn test_parameter_elements() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource(
+            "Bundle".to_string(),
+            crate::core::ir::ResourceType {
+                name: "Bundle".to_string(),
+                base: None,
+                properties: vec![],
+                constraints: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: String::new(),
+                is_abstract: false,
+            },
+        );
+
+        assert_eq!(OperationGenerator::element(&graph, Some("dateTime")), "valueDateTime");
+        assert_eq!(OperationGenerator::element(&graph, Some("Bundle")), "resource");
+        assert_eq!(OperationGenerator::element(&graph, Some("Resource")), "resource");
+        assert_eq!(OperationGenerator::element(&graph, Some("Any")), "value[x]");
+        assert_eq!(OperationGenerator::element(&graph, None), "part");
+
+        let generator = OperationGenerator::new(TypeScriptBackend::new());
+        let mut tuple = parameter(
+            "match",
+            OperationParameterUse::Out,
+            None,
+            CardinalityRange::optional_array(),
+        );
+        tuple.parts.push(parameter(
+            "score",
+            OperationParameterUse::Out,
+            Some("decimal"),
+            CardinalityRange::required(),
+        ));
+        assert_eq!(generator.parameter_type(&graph, &tuple), "Array<{ score: number }>");
+        assert_eq!(
+            OperationGenerator::specs(&graph, &[&tuple]),
+            "[{ name: \"match\", element: \"part\", array: true, parts: [{ name: \"score\", element: \"valueDecimal\", array: false }] }]"
+        );
+    }
+}
@@ -0,0 +1,860 @@
+//! Profile-specific TypeScript types
+//!
+//! Every profile whose base type is generated becomes an interface narrowing
+//! that base: elements the profile makes required lose their `?`, choice and
+//! reference types are restricted to the allowed types, prohibited elements
+//! become `never` and primitive fixed values become literal types. Each
+//! profile also gets an `isX` conformance guard and, for resources, a
+//! `withXProfile` helper adding its URL to `meta.profile` (plus an `XResource`
+//! class wrapper in class mode). Files are grouped by implementation guide
+//! under `src/profiles/<guide>/`:
+//!
+//! ```typescript
+//! const patient = withUSCorePatientProfile({ resourceType: "Patient", identifier, name });
+//! if (isUSCorePatient(patient)) {
+//!   patient.identifier[0].value;
+//! }
+//! ```
+//!
+//! Only constraints on top-level elements (`Patient.name`) narrow the types;
+//! constraints on nested paths (`Patient.name.family`) are left to validation.
+
+use crate::core::Result;
+use crate::core::ir::{ProfileType, Property, PropertyConstraint, PropertyType, TypeGraph};
+use crate::generator::LanguageBackend;
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+/// Generator for profile interfaces, conformance guards and profile stamping
+pub struct ProfileGenerator {
+    backend: TypeScriptBackend,
+    use_classes: bool,
+}
+
+/// Base element as narrowed by a profile
+struct NarrowedElement {
+    /// Base property with the profile's cardinality, types and binding applied
+    property: Property,
+    /// Choice variants of the base the profile no longer allows
+    excluded: Vec<String>,
+    /// Value the element must have
+    fixed_value: Option<Value>,
+}
+
+impl ProfileGenerator {
+    /// Create a profile generator using a backend's type mappings
+    pub fn new(backend: TypeScriptBackend) -> Self {
+        Self { backend, use_classes: false }
+    }
+
+    /// Narrow the generated classes and add `XResource` class wrappers
+    pub fn with_classes(mut self, use_classes: bool) -> Self {
+        self.use_classes = use_classes;
+        self
+    }
+
+    /// Assign a TypeScript name to every profile whose base type is generated
+    ///
+    /// Names avoid the generated resources and datatypes; clashes get a
+    /// counter. Returns profile URL -> name.
+    pub fn profile_names(graph: &TypeGraph) -> IndexMap<String, String> {
+        let mut taken: HashSet<String> = graph
+            .resources
+            .keys()
+            .chain(graph.datatypes.keys())
+            .map(|name| TypeScriptBackend::sanitize_identifier(name))
+            .collect();
+        let mut names = IndexMap::new();
+
+        for (url, profile) in &graph.profiles {
+            if !Self::is_generated(graph, &profile.base) {
+                continue;
+            }
+
+            let base = TypeScriptBackend::sanitize_identifier(&profile.name);
+            let mut name = base.clone();
+            let mut counter = 2;
+            while !taken.insert(name.clone()) {
+                name = format!("{}{}", base, counter);
+                counter += 1;
+            }
+            names.insert(url.clone(), name);
+        }
+
+        names
+    }
+
+    /// Directory of the implementation guide defining a profile
+    ///
+    /// Derived from the canonical base of the profile URL, without a leading
+    /// `fhir` segment: `http://hl7.org/fhir/us/core/StructureDefinition/x`
+    /// -> `us-core`. Falls back to the host when the base has no path.
+    pub fn guide_directory(url: &str) -> String {
+        let canonical = url.split("/StructureDefinition/").next().unwrap_or(url);
+        let without_scheme = canonical.split_once("://").map_or(canonical, |(_, rest)| rest);
+        let mut segments: Vec<&str> =
+            without_scheme.split('/').filter(|segment| !segment.is_empty()).collect();
+        let host = if segments.is_empty() { "" } else { segments.remove(0) };
+        if segments.first() == Some(&"fhir") {
+            segments.remove(0);
+        }
+        let parts = if segments.is_empty() { vec![host] } else { segments };
+
+        let directory: String = parts
+            .join("-")
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let directory = directory.trim_matches('-').to_string();
+        if directory.is_empty() { "profiles".to_string() } else { directory }
+    }
+
+    /// Generate `src/profiles/runtime.ts` with the helpers used by profile modules
+    pub fn generate_runtime_module(&self, graph: &TypeGraph) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated profile runtime helpers");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.line();
+
+        let stamping = Self::has_meta(graph);
+        if stamping && self.use_classes {
+            tokens.append("import { Meta } from '../types/Meta';");
+            tokens.line();
+        }
+
+        for line in RUNTIME.lines() {
+            tokens.append(line);
+            tokens.push();
+        }
+
+        if stamping {
+            tokens.line();
+            let stamp = if self.use_classes { CLASS_STAMP } else { INTERFACE_STAMP };
+            for line in stamp.lines() {
+                tokens.append(line);
+                tokens.push();
+            }
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate the module of a single profile
+    pub fn generate_profile_file(
+        &self,
+        graph: &TypeGraph,
+        profile: &ProfileType,
+        name: &str,
+    ) -> Result<String> {
+        let base_name = TypeScriptBackend::sanitize_identifier(&profile.base);
+        let is_resource = graph.resources.contains_key(&profile.base);
+        let elements = Self::narrowed_elements(graph, profile);
+        let stamping = is_resource && Self::has_meta(graph);
+        let mut tokens = js::Tokens::new();
+
+        tokens.append(format!("// Auto-generated profile {}", profile.name));
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.line();
+
+        // Imports
+        let (datatypes, value_sets) = self.collect_imports(graph, &elements, &base_name);
+        let base_module = if is_resource { "resources" } else { "types" };
+        let base_keyword = if self.use_classes && is_resource { "import" } else { "import type" };
+        tokens.append(format!(
+            "{} {{ {} }} from '../../{}/{}';",
+            base_keyword, base_name, base_module, base_name
+        ));
+        tokens.push();
+        for datatype in &datatypes {
+            tokens
+                .append(format!("import type {{ {} }} from '../../types/{}';", datatype, datatype));
+            tokens.push();
+        }
+        let mut modules = vec![(value_sets, "../../valuesets".to_string())];
+        let parameter = if is_resource { "resource" } else { "value" };
+        let conditions: Vec<String> =
+            elements.iter().flat_map(|element| Self::conditions(element, parameter)).collect();
+        let mut runtime: Vec<String> = ["hasValue", "matchesFixed"]
+            .iter()
+            .filter(|function| conditions.iter().any(|c| c.contains(&format!("{}(", function))))
+            .map(|function| function.to_string())
+            .collect();
+        if stamping {
+            runtime.push("withProfile".to_string());
+        }
+        modules.push((runtime, "../runtime".to_string()));
+        tokens.append(helpers::imports(&modules));
+        tokens.line();
+
+        // Canonical URL
+        tokens.append(helpers::jsdoc_comment(&[format!("Canonical URL of {}", profile.name)]));
+        tokens.push();
+        tokens.append(helpers::constant(&format!("{}Url", name), &Self::quote(&profile.url), None));
+        tokens.line();
+
+        // Narrowed interface
+        let mut doc = Vec::new();
+        if !profile.documentation.short.is_empty() {
+            doc.push(profile.documentation.short.clone());
+            doc.push(String::new());
+        }
+        doc.push(format!("{} constrained by {}", profile.base, profile.url));
+        let fields: Vec<(String, String, bool)> =
+            elements.iter().flat_map(|element| self.fields(element)).collect();
+        tokens.append(helpers::interface(name, Some(&base_name), &fields, Some(&doc)));
+        tokens.line();
+
+        // Conformance guard
+        tokens.append(helpers::jsdoc_comment(&[
+            format!("Check that a {} meets the constraints of {}", profile.base, profile.name),
+            String::new(),
+            "Checks required elements, allowed choice types and fixed values.".to_string(),
+        ]));
+        tokens.push();
+        tokens.append(format!(
+            "export function is{0}({1}: {2}): {1} is {0} {{",
+            name, parameter, base_name
+        ));
+        tokens.push();
+        if conditions.is_empty() {
+            tokens.append("  return true;");
+        } else {
+            tokens.append("  return (");
+            for (index, condition) in conditions.iter().enumerate() {
+                tokens.push();
+                let separator = if index + 1 < conditions.len() { " &&" } else { "" };
+                tokens.append(format!("    {}{}", condition, separator));
+            }
+            tokens.push();
+            tokens.append("  );");
+        }
+        tokens.push();
+        tokens.append("}");
+
+        // Profile stamping
+        if stamping {
+            tokens.line();
+            tokens.append(helpers::jsdoc_comment(&[format!(
+                "Add the {} URL to `meta.profile`",
+                profile.name
+            )]));
+            tokens.push();
+            tokens.append(format!(
+                "export function with{0}Profile<T extends {1}>(resource: T): T {{",
+                name, base_name
+            ));
+            tokens.push();
+            tokens.append(format!("  return withProfile(resource, {}Url);", name));
+            tokens.push();
+            tokens.append("}");
+        }
+
+        // Class wrapper
+        if self.use_classes && is_resource {
+            tokens.line();
+            tokens.append(self.generate_class_wrapper(profile, name, &base_name, stamping));
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Class of the base resource tagged with the profile
+    fn generate_class_wrapper(
+        &self,
+        profile: &ProfileType,
+        name: &str,
+        base_name: &str,
+        stamping: bool,
+    ) -> js::Tokens {
+        let class_name = format!("{}Resource", name);
+        let mut tokens = js::Tokens::new();
+
+        tokens.append(helpers::jsdoc_comment(&[format!(
+            "{} class for instances of {}",
+            profile.base, profile.name
+        )]));
+        tokens.push();
+        tokens.append(format!("export class {} extends {} {{", class_name, base_name));
+        tokens.push();
+        tokens.append(format!("  static readonly profileUrl = {}Url;", name));
+        tokens.line();
+
+        if stamping {
+            tokens.append("  constructor() {");
+            tokens.push();
+            tokens.append("    super();");
+            tokens.push();
+            tokens.append(format!("    withProfile(this, {}Url);", name));
+            tokens.push();
+            tokens.append("  }");
+            tokens.line();
+        }
+
+        tokens.append(format!(
+            "  /** Whether this instance meets the constraints of {} */",
+            profile.name
+        ));
+        tokens.push();
+        tokens.append(format!("  conforms(): this is this & {} {{", name));
+        tokens.push();
+        tokens.append(format!("    return is{}(this);", name));
+        tokens.push();
+        tokens.append("  }");
+        tokens.line();
+
+        tokens.append(format!("  /** Parse FHIR JSON into a {} */", class_name));
+        tokens.push();
+        tokens.append(format!("  static parse(json: any): {} {{", class_name));
+        tokens.push();
+        tokens.append(format!(
+            "    return Object.setPrototypeOf({}.parse(json), {}.prototype);",
+            base_name, class_name
+        ));
+        tokens.push();
+        tokens.append("  }");
+        tokens.push();
+        tokens.append("}");
+
+        tokens
+    }
+
+    /// Generate the index of an implementation guide directory
+    pub fn generate_guide_index(names: &[String]) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated profile index");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+        tokens.push();
+
+        for name in names {
+            tokens.append(format!("export * from './{}';", name));
+            tokens.push();
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate `src/profiles/index.ts` exporting the runtime and every guide
+    pub fn generate_profiles_index(guides: &[String]) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated profiles index");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+        tokens.push();
+
+        tokens.append("export * from './runtime';");
+        tokens.push();
+        for guide in guides {
+            tokens.append(format!("export * from './{}';", guide));
+            tokens.push();
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Whether a profile base is a generated resource or datatype
+    fn is_generated(graph: &TypeGraph, base: &str) -> bool {
+        graph.resources.contains_key(base) || graph.datatypes.contains_key(base)
+    }
+
+    /// Whether resources can be stamped: `meta` is declared and `Meta` is generated
+    fn has_meta(graph: &TypeGraph) -> bool {
+        graph.datatypes.contains_key("Meta")
+            && graph
+                .resources
+                .values()
+                .any(|resource| resource.properties.iter().any(|p| p.name == "meta"))
+    }
+
+    /// Top-level base elements narrowed by a profile, in constraint order
+    fn narrowed_elements(graph: &TypeGraph, profile: &ProfileType) -> Vec<NarrowedElement> {
+        let properties = graph.properties_with_base(&profile.base);
+        let mut seen = HashSet::new();
+
+        profile
+            .property_constraints
+            .iter()
+            .filter(|constraint| constraint.is_narrowing())
+            .filter_map(|constraint| {
+                let element = constraint.top_level_element(&profile.base)?;
+                let property =
+                    properties.iter().find(|p| p.name.trim_end_matches("[x]") == element)?;
+                seen.insert(element).then(|| Self::narrow(property, constraint))
+            })
+            .collect()
+    }
+
+    /// Apply a profile constraint to a base property
+    fn narrow(property: &Property, constraint: &PropertyConstraint) -> NarrowedElement {
+        let narrowed = constraint.apply(property);
+
+        // Choice variants the constraint removes must be absent
+        let kept: HashSet<String> =
+            narrowed.choice_variants().into_iter().map(|variant| variant.name).collect();
+        let excluded = property
+            .choice_variants()
+            .into_iter()
+            .map(|variant| variant.name)
+            .filter(|name| !kept.contains(name))
+            .collect();
+
+        NarrowedElement {
+            property: narrowed,
+            excluded,
+            fixed_value: constraint.fixed_value.clone(),
+        }
+    }
+
+    /// Interface fields declared for a narrowed element
+    fn fields(&self, element: &NarrowedElement) -> Vec<(String, String, bool)> {
+        let property = &element.property;
+        let mut fields = Vec::new();
+
+        if property.cardinality.max == Some(0) {
+            for variant in Property::json_properties(std::slice::from_ref(property)) {
+                fields.push((variant.name, "never".to_string(), true));
+            }
+            return fields;
+        }
+
+        if property.is_choice {
+            // A single allowed type can be required; several stay optional
+            let variants = property.choice_variants();
+            let required = property.cardinality.is_required() && variants.len() == 1;
+            for variant in &variants {
+                fields.push((
+                    variant.name.clone(),
+                    self.backend.map_property_type(variant),
+                    !required,
+                ));
+            }
+        } else {
+            let mut type_name = match &element.fixed_value {
+                Some(value @ (Value::String(_) | Value::Number(_) | Value::Bool(_))) => {
+                    value.to_string()
+                }
+                _ => self.backend.map_property_type(property),
+            };
+            if property.cardinality.is_array() && !self.use_classes {
+                type_name = helpers::array_type(&type_name);
+            }
+            fields.push((property.name.clone(), type_name, property.cardinality.is_optional()));
+        }
+
+        for name in &element.excluded {
+            fields.push((name.clone(), "never".to_string(), true));
+        }
+
+        fields
+    }
+
+    /// Guard conditions checking a narrowed element on `parameter`
+    fn conditions(element: &NarrowedElement, parameter: &str) -> Vec<String> {
+        let property = &element.property;
+        let access = |name: &str| format!("{}.{}", parameter, name);
+        let mut conditions = Vec::new();
+
+        if property.cardinality.max == Some(0) {
+            for variant in Property::json_properties(std::slice::from_ref(property)) {
+                conditions.push(format!("{} === undefined", access(&variant.name)));
+            }
+            return conditions;
+        }
+
+        if property.cardinality.is_required() {
+            let present: Vec<String> = Property::json_properties(std::slice::from_ref(property))
+                .iter()
+                .map(|variant| format!("hasValue({})", access(&variant.name)))
+                .collect();
+            if present.len() == 1 {
+                conditions.extend(present);
+            } else if !present.is_empty() {
+                conditions.push(format!("({})", present.join(" || ")));
+            }
+        }
+
+        for name in &element.excluded {
+            conditions.push(format!("{} === undefined", access(name)));
+        }
+
+        if let Some(value) = &element.fixed_value
+            && !property.is_choice
+        {
+            let value_access = access(&property.name);
+            conditions.push(format!(
+                "({} === undefined || matchesFixed({}, {}))",
+                value_access, value_access, value
+            ));
+        }
+
+        conditions
+    }
+
+    /// Datatypes and value set types referenced by narrowed fields, sorted
+    fn collect_imports(
+        &self,
+        graph: &TypeGraph,
+        elements: &[NarrowedElement],
+        base_name: &str,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut datatypes = BTreeSet::new();
+        let mut value_sets = BTreeSet::new();
+
+        for element in elements.iter().filter(|e| e.property.cardinality.max != Some(0)) {
+            for property in Property::json_properties(std::slice::from_ref(&element.property)) {
+                let type_name = match &property.property_type {
+                    PropertyType::Complex { type_name } => Some(type_name.as_str()),
+                    PropertyType::Reference { .. } => Some("Reference"),
+                    _ => None,
+                };
+                if let Some(type_name) = type_name
+                    && graph.datatypes.contains_key(type_name)
+                    && type_name != base_name
+                {
+                    datatypes.insert(type_name.to_string());
+                }
+                if let Some(value_set) = self.backend.value_set_type(&property) {
+                    value_sets.insert(value_set);
+                }
+            }
+        }
+
+        (datatypes.into_iter().collect(), value_sets.into_iter().collect())
+    }
+
+    /// Quote a string as a TypeScript string literal
+    fn quote(value: &str) -> String {
+        Value::from(value).to_string()
+    }
+}
+
+/// Presence and fixed value checks used by the conformance guards
+const RUNTIME: &str = r#"/** Whether an element is present: defined, not null and not an empty array */
+export function hasValue(value: unknown): boolean {
+  return value !== undefined && value !== null && !(Array.isArray(value) && value.length === 0);
+}
+
+/**
+ * Whether a value matches a fixed or pattern value
+ *
+ * Objects match when every field of `expected` matches, and each item of a
+ * repeating element must match. Class instances are compared by their `toJSON`.
+ */
+export function matchesFixed(actual: any, expected: any): boolean {
+  if (actual !== null && typeof actual === "object" && typeof actual.toJSON === "function") {
+    return matchesFixed(actual.toJSON(), expected);
+  }
+  if (Array.isArray(actual) && !Array.isArray(expected)) {
+    return actual.every((item) => matchesFixed(item, expected));
+  }
+  if (Array.isArray(expected)) {
+    return (
+      Array.isArray(actual) && expected.every((item, index) => matchesFixed(actual[index], item))
+    );
+  }
+  if (expected !== null && typeof expected === "object") {
+    return (
+      actual !== null &&
+      typeof actual === "object" &&
+      Object.entries(expected).every(([key, value]) => matchesFixed(actual[key], value))
+    );
+  }
+  return actual === expected;
+}"#;
+
+/// Profile stamping for interface resources
+const INTERFACE_STAMP: &str = r#"/** Copy of a resource with a profile URL added to `meta.profile` */
+export function withProfile<T extends { meta?: { profile?: string[] } }>(resource: T, url: string): T {
+  const profile = resource.meta?.profile ?? [];
+  if (profile.includes(url)) {
+    return resource;
+  }
+  return { ...resource, meta: { ...resource.meta, profile: [...profile, url] } };
+}"#;
+
+/// Profile stamping for class resources
+const CLASS_STAMP: &str = r#"/** Add a profile URL to the `meta.profile` of a resource instance */
+export function withProfile<T extends { meta?: Meta; setMeta(value: Meta): unknown }>(
+  resource: T,
+  url: string,
+): T {
+  const profile = resource.meta?.profile ?? [];
+  if (!profile.includes(url)) {
+    resource.setMeta(Meta.parse({ ...resource.meta?.toJSON(), profile: [...profile, url] }));
+  }
+  return resource;
+}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{CardinalityRange, DataType, Documentation, FhirVersion, ResourceType};
+    use serde_json::json;
+
+    fn property(
+        name: &str,
+        property_type: PropertyType,
+        cardinality: CardinalityRange,
+    ) -> Property {
+        Property {
+            name: name.to_string(),
+            path: format!("Observation.{}", name),
+            property_type,
+            cardinality,
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        }
+    }
+
+    fn constraint(path: &str) -> PropertyConstraint {
+        PropertyConstraint {
+            path: path.to_string(),
+            cardinality: None,
+            type_constraints: vec![],
+            binding: None,
+            fixed_value: None,
+            must_support: true,
+        }
+    }
+
+    fn graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        let complex = |name: &str| PropertyType::Complex { type_name: name.to_string() };
+
+        graph.add_resource(
+            "Resource".to_string(),
+            ResourceType {
+                name: "Resource".to_string(),
+                base: None,
+                properties: vec![property("meta", complex("Meta"), CardinalityRange::optional())],
+                constraints: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: String::new(),
+                is_abstract: true,
+            },
+        );
+
+        let mut value = property(
+            "value[x]",
+            PropertyType::Choice { types: vec!["Quantity".to_string(), "string".to_string()] },
+            CardinalityRange::optional(),
+        );
+        value.is_choice = true;
+        value.choice_types = vec!["Quantity".to_string(), "string".to_string()];
+        graph.add_resource(
+            "Observation".to_string(),
+            ResourceType {
+                name: "Observation".to_string(),
+                base: Some("Resource".to_string()),
+                properties: vec![
+                    property(
+                        "status",
+                        PropertyType::Primitive { type_name: "code".to_string() },
+                        CardinalityRange::required(),
+                    ),
+                    property(
+                        "category",
+                        complex("CodeableConcept"),
+                        CardinalityRange::optional_array(),
+                    ),
+                    property(
+                        "subject",
+                        PropertyType::Reference {
+                            target_types: vec!["Patient".to_string(), "Group".to_string()],
+                            target_profiles: vec![],
+                        },
+                        CardinalityRange::optional(),
+                    ),
+                    value,
+                    property("note", complex("Annotation"), CardinalityRange::optional_array()),
+                ],
+                constraints: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: String::new(),
+                is_abstract: false,
+            },
+        );
+
+        for name in ["Meta", "CodeableConcept", "Quantity", "Reference", "Annotation"] {
+            graph.add_datatype(
+                name.to_string(),
+                DataType {
+                    name: name.to_string(),
+                    base: None,
+                    properties: vec![],
+                    constraints: vec![],
+                    documentation: Documentation::default(),
+                    url: String::new(),
+                    is_abstract: false,
+                },
+            );
+        }
+
+        let url = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-vital-signs";
+        graph.add_profile(
+            url.to_string(),
+            ProfileType {
+                name: "USCoreVitalSigns".to_string(),
+                base: "Observation".to_string(),
+                property_constraints: vec![
+                    PropertyConstraint {
+                        fixed_value: Some(json!("final")),
+                        ..constraint("Observation.status")
+                    },
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange::required_array()),
+                        fixed_value: Some(json!({ "coding": [{ "code": "vital-signs" }] })),
+                        ..constraint("Observation.category")
+                    },
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange::required()),
+                        type_constraints: vec!["Patient".to_string()],
+                        ..constraint("Observation.subject")
+                    },
+                    PropertyConstraint {
+                        type_constraints: vec!["Quantity".to_string()],
+                        ..constraint("Observation.value[x]")
+                    },
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange { min: 0, max: Some(0) }),
+                        ..constraint("Observation.note")
+                    },
+                    PropertyConstraint {
+                        cardinality: Some(CardinalityRange::required()),
+                        ..constraint("Observation.subject.display")
+                    },
+                    constraint("Observation.meta"),
+                ],
+                new_properties: vec![],
+                documentation: Documentation {
+                    short: "US Core vital signs".to_string(),
+                    ..Default::default()
+                },
+                url: url.to_string(),
+            },
+        );
+        graph.add_profile(
+            "http://example.org/StructureDefinition/unknown-base".to_string(),
+            ProfileType {
+                name: "UnknownBase".to_string(),
+                base: "Device".to_string(),
+                property_constraints: vec![],
+                new_properties: vec![],
+                documentation: Documentation::default(),
+                url: "http://example.org/StructureDefinition/unknown-base".to_string(),
+            },
+        );
+
+        graph
+    }
+
+    #[test]
+    fn test_profile_names_and_guides() {
+        let names = ProfileGenerator::profile_names(&graph());
+
+        assert_eq!(names.values().collect::<Vec<_>>(), vec!["USCoreVitalSigns"]);
+        assert_eq!(
+            ProfileGenerator::guide_directory(
+                "http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient"
+            ),
+            "us-core"
+        );
+        assert_eq!(
+            ProfileGenerator::guide_directory("http://example.org/StructureDefinition/x"),
+            "example-org"
+        );
+    }
+
+    #[test]
+    fn test_generate_profile_file() {
+        let graph = graph();
+        let url = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-vital-signs";
+        let generator = ProfileGenerator::new(TypeScriptBackend::new());
+
+        let code = generator
+            .generate_profile_file(&graph, &graph.profiles[url], "USCoreVitalSigns")
+            .unwrap();
+
+        assert!(code.contains("import type { Observation } from '../../resources/Observation';"));
+        assert!(
+            code.contains("import type { CodeableConcept } from '../../types/CodeableConcept';")
+        );
+        assert!(!code.contains("import type { Annotation }"));
+        assert!(code.contains("import { hasValue, matchesFixed, withProfile } from '../runtime';"));
+        assert!(code.contains(&format!("export const USCoreVitalSignsUrl = \"{}\";", url)));
+
+        assert!(code.contains("export interface USCoreVitalSigns extends Observation {"));
+        assert!(code.contains("  status: \"final\";\n"));
+        assert!(code.contains("  category: CodeableConcept[];\n"));
+        assert!(code.contains("  subject: Reference<\"Patient\">;\n"));
+        assert!(code.contains("  valueQuantity?: Quantity;\n  valueString?: never;\n"));
+        assert!(code.contains("  note?: never;\n"));
+        assert!(!code.contains("display"));
+        assert!(!code.contains("  meta"));
+
+        assert!(code.contains(
+            "export function isUSCoreVitalSigns(resource: Observation): resource is USCoreVitalSigns {"
+        ));
+        assert!(code.contains(
+            "    (resource.status === undefined || matchesFixed(resource.status, \"final\")) &&\n"
+        ));
+        assert!(code.contains("    hasValue(resource.category) &&\n"));
+        assert!(code.contains("    hasValue(resource.subject) &&\n"));
+        assert!(code.contains("    resource.valueString === undefined &&\n"));
+        assert!(code.contains("    resource.note === undefined\n  );"));
+
+        assert!(code.contains(
+            "export function withUSCoreVitalSignsProfile<T extends Observation>(resource: T): T {"
+        ));
+        assert!(!code.contains("class"));
+    }
+
+    #[test]
+    fn test_generate_profile_class_wrapper() {
+        let graph = graph();
+        let url = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-vital-signs";
+        let generator = ProfileGenerator::new(TypeScriptBackend::new()).with_classes(true);
+
+        let code = generator
+            .generate_profile_file(&graph, &graph.profiles[url], "USCoreVitalSigns")
+            .unwrap();
+
+        assert!(code.contains("import { Observation } from '../../resources/Observation';"));
+        // Class getters are not array-typed
+        assert!(code.contains("  category: CodeableConcept;\n"));
+        assert!(code.contains("export class USCoreVitalSignsResource extends Observation {"));
+        assert!(code.contains("    withProfile(this, USCoreVitalSignsUrl);"));
+        assert!(code.contains("  conforms(): this is this & USCoreVitalSigns {"));
+        assert!(code.contains(
+            "    return Object.setPrototypeOf(Observation.parse(json), USCoreVitalSignsResource.prototype);"
+        ));
+
+        let runtime = generator.generate_runtime_module(&graph).unwrap();
+        assert!(runtime.contains("import { Meta } from '../types/Meta';"));
+        assert!(runtime.contains("resource.setMeta(Meta.parse("));
+    }
+}
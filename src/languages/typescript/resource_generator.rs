@@ -1,14 +1,16 @@
 //! TypeScript resource generation (interfaces or classes)
 
 use crate::core::Result;
-use crate::core::ir::{ResourceType, TypeGraph};
+use crate::core::ir::{Property, ResourceType, TypeGraph};
 use crate::generator::LanguageBackend;
 use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::choice_generator::ChoiceElementGenerator;
 use crate::languages::typescript::class_generator::ClassGenerator;
+use crate::languages::typescript::extension_generator::ExtensionDefinition;
 use crate::languages::typescript::templates::TypeScriptTemplates;
 use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
 use genco::prelude::*;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 /// Generator for TypeScript resource interfaces or classes
 pub struct ResourceGenerator<B: LanguageBackend> {
@@ -16,6 +18,8 @@ pub struct ResourceGenerator<B: LanguageBackend> {
     backend: B,
     /// Generate classes instead of interfaces
     use_classes: bool,
+    /// Generate typed extension helper methods on classes
+    generate_extensions: bool,
 }
 
 impl<B: LanguageBackend> ResourceGenerator<B> {
@@ -24,18 +28,29 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
         Self {
             backend,
             use_classes: false, // Default to interfaces for backward compatibility
+            generate_extensions: true,
         }
     }
 
     /// Create a new resource generator with class generation enabled
     pub fn new_with_classes(backend: B) -> Self {
-        Self { backend, use_classes: true }
+        Self { backend, use_classes: true, generate_extensions: true }
+    }
+
+    /// Enable or disable typed extension helper methods (class mode only)
+    pub fn with_extensions(mut self, generate_extensions: bool) -> Self {
+        self.generate_extensions = generate_extensions;
+        self
     }
 
     /// Generate TypeScript code for a single resource (interface or class)
     pub fn generate_resource(&self, resource: &ResourceType) -> Result<String> {
         if self.use_classes {
-            ClassGenerator::generate_resource_class(resource, &self.backend)
+            ClassGenerator::generate_resource_class_with_extensions(
+                resource,
+                &self.backend,
+                self.generate_extensions,
+            )
         } else {
             TypeScriptTemplates::resource_to_interface(resource, &self.backend)
         }
@@ -141,6 +156,19 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
         GencoTemplateEngine::format_typescript(&tokens)
     }
 
+    /// Names of the resources in the resource union
+    ///
+    /// Abstract resources (`Resource`, `DomainResource`) are left out so the
+    /// union stays discriminated by `resourceType`.
+    pub fn resource_union_members(graph: &TypeGraph) -> Vec<String> {
+        graph
+            .resources
+            .iter()
+            .filter(|(_, resource)| !resource.is_abstract)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
     /// Generate a resource type union
     ///
     /// Creates a type like:
@@ -163,7 +191,7 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
         // Type alias
         tokens.append("export type AnyResource = ");
 
-        let resource_names: Vec<String> = graph.resources.keys().cloned().collect();
+        let resource_names = Self::resource_union_members(graph);
         if !resource_names.is_empty() {
             tokens.append(helpers::union_type(&resource_names));
         } else {
@@ -181,13 +209,17 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
     pub fn collect_dependencies(&self, resource: &ResourceType) -> HashSet<String> {
         let mut deps = HashSet::new();
 
-        for property in &resource.properties {
+        for property in &Property::json_properties(&resource.properties) {
             // Collect types based on property type variant
             match &property.property_type {
                 crate::core::ir::PropertyType::Primitive { type_name } => {
                     if !Self::is_primitive(type_name) {
                         deps.insert(type_name.to_string());
                     }
+                    // `_name` siblings carry the primitive's element data
+                    if property.primitive_element_name().is_some() {
+                        deps.insert("Element".to_string());
+                    }
                 }
                 crate::core::ir::PropertyType::Complex { type_name } => {
                     deps.insert(type_name.to_string());
@@ -212,6 +244,13 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
             }
         }
 
+        // Add value types of the typed extension accessors
+        if self.use_classes && self.generate_extensions {
+            for ext in resource.extensions.iter().filter_map(ExtensionDefinition::from_extension) {
+                deps.extend(ext.complex_types());
+            }
+        }
+
         // Add base type if present
         if let Some(base) = &resource.base {
             deps.insert(base.clone());
@@ -220,6 +259,13 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
         deps
     }
 
+    /// Collect the generated value set types used by bound properties, sorted
+    pub fn collect_value_set_dependencies(&self, properties: &[Property]) -> Vec<String> {
+        let names: BTreeSet<String> =
+            properties.iter().filter_map(|p| self.backend.value_set_type(p)).collect();
+        names.into_iter().collect()
+    }
+
     /// Check if a type is a FHIR primitive
     fn is_primitive(type_name: &str) -> bool {
         matches!(
@@ -249,31 +295,57 @@ impl<B: LanguageBackend> ResourceGenerator<B> {
     }
 
     /// Generate a complete resource file with imports
+    ///
+    /// Choice elements get their variant types and accessors after the resource.
     pub fn generate_resource_file(
         &self,
         resource: &ResourceType,
         dependencies: &HashSet<String>,
-    ) -> Result<String> {
+    ) -> Result<String>
+    where
+        B: Clone,
+    {
         let mut tokens = js::Tokens::new();
+        let interface = self.generate_resource(resource)?;
 
         // Generate imports (use relative path from resources/ to types/)
+        let mut modules = Vec::new();
         if !dependencies.is_empty() {
             let mut deps_vec: Vec<String> = dependencies.iter().cloned().collect();
             deps_vec.sort();
-
-            tokens.append(helpers::imports(&[(deps_vec, "../types".to_string())]));
+            modules.push((deps_vec, "../types".to_string()));
+        }
+        let value_sets =
+            self.collect_value_set_dependencies(&Property::json_properties(&resource.properties));
+        if !value_sets.is_empty() {
+            modules.push((value_sets, "../valuesets".to_string()));
+        }
+        if self.use_classes {
+            modules.push((
+                ClassGenerator::serialization_imports(&interface),
+                "../serialization".to_string(),
+            ));
+        }
+        if modules.iter().any(|(names, _)| !names.is_empty()) {
+            tokens.append(helpers::imports(&modules));
             tokens.push();
         }
 
         // Generate the resource interface
-        let interface = self.generate_resource(resource)?;
         tokens.append(&interface);
         tokens.push();
 
         // Generate type guard for this resource
         tokens.push();
         let guard = self.generate_type_guard(&resource.name)?;
-        tokens.append(&guard);
+        tokens.append(guard.trim_end());
+
+        // Variant types and accessors for choice elements
+        let choices = ChoiceElementGenerator::new(self.backend.clone());
+        for property in resource.properties.iter().filter(|p| p.is_choice) {
+            tokens.line();
+            tokens.append(choices.generate_choice_module(property, &resource.name)?.trim_end());
+        }
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
@@ -339,6 +411,7 @@ mod tests {
         ResourceType {
             name: "Patient".to_string(),
             base: Some("DomainResource".to_string()),
+            constraints: vec![],
             properties: vec![
                 Property {
                     name: "active".to_string(),
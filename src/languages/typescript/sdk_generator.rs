@@ -1,57 +1,115 @@
-use crate::core::Result;
-use crate::core::ir::TypeGraph;
+use crate::core::ir::{DataType, InvariantRule, ResourceType, TypeGraph};
+use crate::core::{Error, Result};
+use crate::generator::{
+    CodeGenerator, FileType, GeneratedCode, GeneratedFile, GenerationManifest,
+    GenerationStatistics, GeneratorCapabilities, GeneratorConfig, GeneratorMetadata, Language,
+};
+use crate::languages::typescript::test_generator::SYNTHETIC_INSTANCES;
 use crate::languages::typescript::{
-    DatatypeGenerator, HelpersGenerator, ManifestGenerator, PackageConfig, ResourceGenerator,
-    TypeScriptBackend, ValidationGenerator,
+    BundleGenerator, ClientGenerator, CompilerConfig, DatatypeGenerator, ExtensionGenerator,
+    FhirPathTranslator, HelpersGenerator, ManifestGenerator, OperationGenerator, PackageConfig,
+    ProfileGenerator, ResourceGenerator, SearchGenerator, SerializationGenerator, TestGenerator,
+    TypeScriptBackend, ValidationGenerator, ValueSetGenerator, ZodGenerator,
 };
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Version range of the `zod` dependency added for [`ValidationStyle::Zod`]
+const ZOD_VERSION: &str = "^3.23.0";
+
+/// Runtime validation emitted when `emit_validation` is set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationStyle {
+    /// Hand-rolled `validateX` functions, including compiled FHIRPath invariants
+    #[default]
+    Functions,
+    /// Zod schemas checked against the generated interfaces (adds a `zod` dependency)
+    Zod,
+}
+
+/// Options selecting which parts of the TypeScript SDK are generated
+#[derive(Debug, Clone)]
+pub struct SdkOptions {
+    /// Generate classes with fluent builders instead of interfaces
+    pub use_classes: bool,
+    /// Generate validation types and functions
+    pub emit_validation: bool,
+    /// Kind of runtime validation to generate
+    pub validation_style: ValidationStyle,
+    /// Generate utility helper functions
+    pub emit_helpers: bool,
+    /// Generate vitest test files
+    pub emit_tests: bool,
+    /// Generate extension helpers (`extensions.ts` and typed class methods)
+    pub generate_extensions: bool,
+    /// Generate typed search query builders under `src/search/`
+    pub generate_search: bool,
+    /// Generate the typed REST client in `src/client.ts`
+    pub generate_client: bool,
+    /// Custom type mappings (FHIR type -> TypeScript type)
+    pub type_mappings: HashMap<String, String>,
+    /// TypeScript compiler settings for tsconfig.json and package.json
+    pub compiler: CompilerConfig,
+}
+
+impl Default for SdkOptions {
+    fn default() -> Self {
+        Self {
+            use_classes: false,
+            emit_validation: true,
+            validation_style: ValidationStyle::default(),
+            emit_helpers: true,
+            emit_tests: false,
+            generate_extensions: true,
+            generate_search: true,
+            generate_client: false,
+            type_mappings: HashMap::new(),
+            compiler: CompilerConfig::default(),
+        }
+    }
+}
 
 /// Complete TypeScript SDK generator that orchestrates all component generators
 pub struct TypeScriptSdkGenerator {
-    #[allow(dead_code)]
     backend: TypeScriptBackend,
-    #[allow(dead_code)]
-    resource_generator: ResourceGenerator<TypeScriptBackend>,
-    #[allow(dead_code)]
-    datatype_generator: DatatypeGenerator<TypeScriptBackend>,
     validation_generator: ValidationGenerator,
     helpers_generator: HelpersGenerator,
     manifest_generator: ManifestGenerator,
-}
-
-/// Generated file content
-#[derive(Debug, Clone)]
-pub struct GeneratedFile {
-    /// Relative path from SDK root
-    pub path: String,
-    /// File content
-    pub content: String,
+    package_config: PackageConfig,
+    options: SdkOptions,
 }
 
 impl TypeScriptSdkGenerator {
     /// Create a new SDK generator with custom package config
     pub fn new(config: PackageConfig) -> Self {
-        let backend = TypeScriptBackend::new();
-        Self {
-            resource_generator: ResourceGenerator::new(backend.clone()),
-            datatype_generator: DatatypeGenerator::new(backend.clone()),
-            validation_generator: ValidationGenerator::new(backend.clone()),
-            helpers_generator: HelpersGenerator::new(backend.clone()),
-            manifest_generator: ManifestGenerator::new(backend.clone(), config),
-            backend,
-        }
+        Self::with_options(config, SdkOptions::default())
     }
 
     /// Create a new SDK generator with class generation enabled
     pub fn new_with_classes(config: PackageConfig) -> Self {
-        let backend = TypeScriptBackend::new();
+        Self::with_options(config, SdkOptions { use_classes: true, ..Default::default() })
+    }
+
+    /// Create a new SDK generator with custom package config and generation options
+    pub fn with_options(config: PackageConfig, options: SdkOptions) -> Self {
+        let backend = TypeScriptBackend::with_version(options.compiler.typescript_version.clone())
+            .with_type_mappings(options.type_mappings.clone());
+        let mut manifest_generator = ManifestGenerator::new(backend.clone(), config.clone())
+            .with_compiler_config(options.compiler.clone());
+        if options.emit_validation && options.validation_style == ValidationStyle::Zod {
+            manifest_generator = manifest_generator.with_dependency("zod", ZOD_VERSION);
+        }
         Self {
-            resource_generator: ResourceGenerator::new_with_classes(backend.clone()),
-            datatype_generator: DatatypeGenerator::new(backend.clone()),
             validation_generator: ValidationGenerator::new(backend.clone()),
             helpers_generator: HelpersGenerator::new(backend.clone()),
-            manifest_generator: ManifestGenerator::new(backend.clone(), config),
+            manifest_generator,
             backend,
+            package_config: config,
+            options,
         }
     }
 
@@ -69,67 +127,498 @@ impl TypeScriptSdkGenerator {
     ///
     /// Returns a map of file paths to their content
     pub fn generate_sdk(&self, type_graph: &TypeGraph) -> Result<HashMap<String, String>> {
-        let mut files = HashMap::new();
+        Ok(self
+            .generate_files(type_graph)?
+            .into_iter()
+            .map(|file| (file.path.to_string_lossy().into_owned(), file.content))
+            .collect())
+    }
+
+    /// Resource and datatype generators for a backend
+    fn type_generators(
+        &self,
+        backend: &TypeScriptBackend,
+    ) -> (ResourceGenerator<TypeScriptBackend>, DatatypeGenerator<TypeScriptBackend>) {
+        let (resource_generator, datatype_generator) = if self.options.use_classes {
+            (
+                ResourceGenerator::new_with_classes(backend.clone()),
+                DatatypeGenerator::new_with_classes(backend.clone()),
+            )
+        } else {
+            (ResourceGenerator::new(backend.clone()), DatatypeGenerator::new(backend.clone()))
+        };
+        (resource_generator.with_extensions(self.options.generate_extensions), datatype_generator)
+    }
+
+    /// Generate all SDK files from a type graph, tagged with their [`FileType`]
+    pub fn generate_files(&self, type_graph: &TypeGraph) -> Result<Vec<GeneratedFile>> {
+        // Bound `code` elements use the value set types generated for this graph
+        let value_set_types = ValueSetGenerator::type_names(type_graph);
+        let backend = self.backend.clone().with_value_set_types(value_set_types.clone());
+        let (resource_generator, datatype_generator) = self.type_generators(&backend);
+        let datatype_generator =
+            datatype_generator.with_resource_types(
+                ResourceGenerator::<TypeScriptBackend>::resource_union_members(type_graph),
+            );
+
+        let mut files = Vec::new();
+        let mut add = |path: String, content: String, file_type: FileType| {
+            files.push(GeneratedFile::new(PathBuf::from(path), content, file_type));
+        };
 
         // Generate package.json
-        files.insert("package.json".to_string(), self.manifest_generator.generate_package_json()?);
+        add(
+            "package.json".to_string(),
+            self.manifest_generator.generate_package_json()?,
+            FileType::Manifest,
+        );
 
         // Generate tsconfig.json
-        files
-            .insert("tsconfig.json".to_string(), self.manifest_generator.generate_tsconfig_json()?);
+        add(
+            "tsconfig.json".to_string(),
+            self.manifest_generator.generate_tsconfig_json()?,
+            FileType::Manifest,
+        );
 
         // Generate README.md
-        files.insert("README.md".to_string(), self.manifest_generator.generate_readme()?);
+        add(
+            "README.md".to_string(),
+            self.manifest_generator.generate_readme()?,
+            FileType::Documentation,
+        );
 
         // Generate .gitignore
-        files.insert(".gitignore".to_string(), self.manifest_generator.generate_gitignore()?);
+        add(
+            ".gitignore".to_string(),
+            self.manifest_generator.generate_gitignore()?,
+            FileType::Other,
+        );
 
         // Generate .npmignore
-        files.insert(".npmignore".to_string(), self.manifest_generator.generate_npmignore()?);
+        add(
+            ".npmignore".to_string(),
+            self.manifest_generator.generate_npmignore()?,
+            FileType::Other,
+        );
 
         // Generate biome.json
-        files.insert("biome.json".to_string(), self.manifest_generator.generate_biome_json()?);
+        add(
+            "biome.json".to_string(),
+            self.manifest_generator.generate_biome_json()?,
+            FileType::Manifest,
+        );
 
         // Generate primitive types
-        files.insert(
+        add(
             "src/primitives.ts".to_string(),
-            self.datatype_generator.generate_primitive_types(type_graph)?,
+            datatype_generator.generate_primitive_types(type_graph)?,
+            FileType::Primitive,
         );
 
         // Generate datatypes
         for (name, datatype) in &type_graph.datatypes {
-            let dependencies = self.datatype_generator.collect_dependencies(datatype);
+            let dependencies = datatype_generator.collect_dependencies(datatype);
             let file_content =
-                self.datatype_generator.generate_datatype_file(datatype, &dependencies)?;
-            files.insert(format!("src/types/{}.ts", name), file_content);
+                datatype_generator.generate_datatype_file(datatype, &dependencies)?;
+            add(format!("src/types/{}.ts", name), file_content, FileType::DataType);
+        }
+
+        // Generate value set code types
+        if !value_set_types.is_empty() {
+            let mut value_sets = Vec::new();
+            for (url, value_set) in &type_graph.value_sets {
+                let Some(type_name) = value_set_types.get(url) else { continue };
+                add(
+                    format!("src/valuesets/{}.ts", type_name),
+                    ValueSetGenerator::generate_value_set_file(value_set, type_name)?,
+                    FileType::ValueSet,
+                );
+                value_sets.push(type_name.clone());
+            }
+            add(
+                "src/valuesets/index.ts".to_string(),
+                ValueSetGenerator::generate_value_sets_index(&value_sets)?,
+                FileType::Index,
+            );
         }
 
         // Generate resources
         for (name, resource) in &type_graph.resources {
-            let dependencies = self.resource_generator.collect_dependencies(resource);
+            let dependencies = resource_generator.collect_dependencies(resource);
             let file_content =
-                self.resource_generator.generate_resource_file(resource, &dependencies)?;
-            files.insert(format!("src/resources/{}.ts", name), file_content);
+                resource_generator.generate_resource_file(resource, &dependencies)?;
+            add(format!("src/resources/{}.ts", name), file_content, FileType::Resource);
         }
 
-        // Generate validation types
-        files.insert(
-            "src/validation.ts".to_string(),
-            self.validation_generator.generate_validation_types_module()?,
-        );
+        // Generate Zod schemas
+        if self.zod_validation() {
+            let zod_generator = ZodGenerator::new(backend.clone())
+                .with_schema_types(ZodGenerator::schema_types_for(type_graph));
+
+            for (name, datatype) in &type_graph.datatypes {
+                add(
+                    format!("src/schemas/{}.ts", name),
+                    zod_generator.generate_datatype_schema_file(datatype)?,
+                    FileType::Validation,
+                );
+            }
+
+            for (name, resource) in type_graph.resources.iter().filter(|(_, r)| !r.is_abstract) {
+                add(
+                    format!("src/schemas/{}.ts", name),
+                    zod_generator.generate_resource_schema_file(resource)?,
+                    FileType::Validation,
+                );
+            }
+
+            add(
+                "src/schemas/index.ts".to_string(),
+                zod_generator.generate_schemas_index(type_graph)?,
+                FileType::Index,
+            );
+        }
+
+        // Generate validation types and per-type validators
+        if self.function_validation() {
+            add(
+                "src/validation.ts".to_string(),
+                self.validation_generator.generate_validation_types_module()?,
+                FileType::Validation,
+            );
+
+            let validation_generator = ValidationGenerator::new(backend.clone())
+                .with_validated_types(type_graph.datatypes.keys().cloned().collect());
+            let mut validators = Vec::new();
+
+            // Invariants of base types are checked by the validators of their descendants
+            let inherited = |name: &str| -> Vec<InvariantRule> {
+                type_graph.constraints_with_base(name).into_iter().cloned().collect()
+            };
+
+            for (name, datatype) in &type_graph.datatypes {
+                let datatype = DataType { constraints: inherited(name), ..datatype.clone() };
+                add(
+                    format!("src/validators/{}.ts", name),
+                    validation_generator.generate_datatype_validator_file(&datatype)?,
+                    FileType::Validation,
+                );
+                validators.push(name.clone());
+            }
+
+            for (name, resource) in type_graph.resources.iter().filter(|(_, r)| !r.is_abstract) {
+                let resource = ResourceType { constraints: inherited(name), ..resource.clone() };
+                add(
+                    format!("src/validators/{}.ts", name),
+                    validation_generator.generate_resource_validator_file(&resource)?,
+                    FileType::Validation,
+                );
+                validators.push(name.clone());
+            }
+
+            add(
+                "src/fhirpath.ts".to_string(),
+                FhirPathTranslator::generate_runtime_module(),
+                FileType::Validation,
+            );
+
+            add(
+                "src/validators/index.ts".to_string(),
+                validation_generator.generate_validators_index(&validators)?,
+                FileType::Index,
+            );
+        }
 
         // Generate utility functions
-        files.insert(
-            "src/utilities.ts".to_string(),
-            self.helpers_generator.generate_utilities_module()?,
-        );
+        if self.options.emit_helpers {
+            add(
+                "src/utilities.ts".to_string(),
+                self.helpers_generator.generate_utilities_module()?,
+                FileType::Helper,
+            );
+        }
+
+        // Generate the class parse/serialize dispatcher
+        if self.options.use_classes {
+            add(
+                "src/serialization.ts".to_string(),
+                SerializationGenerator::generate_serialization_module(type_graph)?,
+                FileType::Helper,
+            );
+        }
+
+        // Generate Bundle utilities
+        if self.bundle_enabled(type_graph) {
+            add(
+                "src/bundle.ts".to_string(),
+                BundleGenerator::new(backend.clone())
+                    .with_classes(self.options.use_classes)
+                    .generate_bundle_module(type_graph)?,
+                FileType::Helper,
+            );
+        }
+
+        // Generate extension utilities
+        if self.options.generate_extensions {
+            add(
+                "src/extensions.ts".to_string(),
+                ExtensionGenerator::generate_extension_utilities_module()?,
+                FileType::Helper,
+            );
+        }
+
+        // Generate search builders
+        if self.search_enabled(type_graph) {
+            add(
+                "src/search/runtime.ts".to_string(),
+                SearchGenerator::generate_runtime_module(),
+                FileType::Helper,
+            );
+
+            let mut searchable = Vec::new();
+            for resource in SearchGenerator::searchable_resources(type_graph) {
+                add(
+                    format!("src/search/{}.ts", resource.name),
+                    SearchGenerator::generate_resource_search_file(type_graph, resource)?,
+                    FileType::Helper,
+                );
+                searchable.push(resource.name.clone());
+            }
+
+            add(
+                "src/search/index.ts".to_string(),
+                SearchGenerator::generate_search_index(&searchable)?,
+                FileType::Index,
+            );
+        }
+
+        // Generate REST client
+        if self.client_enabled(type_graph) {
+            add(
+                "src/client.ts".to_string(),
+                ClientGenerator::new()
+                    .with_classes(self.options.use_classes)
+                    .generate_client_module(type_graph)?,
+                FileType::Helper,
+            );
+        }
+
+        // Generate typed operation functions
+        if !type_graph.operations.is_empty() {
+            add(
+                "src/operations.ts".to_string(),
+                OperationGenerator::new(backend.clone())
+                    .with_classes(self.options.use_classes)
+                    .generate_operations_module(type_graph)?,
+                FileType::Helper,
+            );
+        }
+
+        // Generate profile types, grouped by implementation guide
+        let profile_names = ProfileGenerator::profile_names(type_graph);
+        if !profile_names.is_empty() {
+            let profile_generator =
+                ProfileGenerator::new(backend.clone()).with_classes(self.options.use_classes);
+            add(
+                "src/profiles/runtime.ts".to_string(),
+                profile_generator.generate_runtime_module(type_graph)?,
+                FileType::Helper,
+            );
+
+            let mut guides: IndexMap<String, Vec<String>> = IndexMap::new();
+            for (url, name) in &profile_names {
+                let guide = ProfileGenerator::guide_directory(url);
+                add(
+                    format!("src/profiles/{}/{}.ts", guide, name),
+                    profile_generator.generate_profile_file(
+                        type_graph,
+                        &type_graph.profiles[url],
+                        name,
+                    )?,
+                    FileType::Profile,
+                );
+                guides.entry(guide).or_default().push(name.clone());
+            }
+
+            for (guide, names) in &guides {
+                add(
+                    format!("src/profiles/{}/index.ts", guide),
+                    ProfileGenerator::generate_guide_index(names)?,
+                    FileType::Index,
+                );
+            }
+            add(
+                "src/profiles/index.ts".to_string(),
+                ProfileGenerator::generate_profiles_index(
+                    &guides.keys().cloned().collect::<Vec<_>>(),
+                )?,
+                FileType::Index,
+            );
+        }
+
+        // Generate tests
+        if self.options.emit_tests {
+            add(
+                "tests/sdk.test.ts".to_string(),
+                self.generate_sdk_test(type_graph),
+                FileType::Test,
+            );
+
+            let validation = self.options.emit_validation.then_some(self.options.validation_style);
+            let test_generator = TestGenerator::new()
+                .with_classes(self.options.use_classes)
+                .with_validation(validation);
+            let tested = if test_generator.has_checks() {
+                add(
+                    "tests/support.ts".to_string(),
+                    TestGenerator::generate_support_module(),
+                    FileType::Test,
+                );
+                TestGenerator::tested_resources(type_graph)
+            } else {
+                Vec::new()
+            };
+
+            for resource in tested {
+                let examples = type_graph.examples.get(&resource.name).filter(|e| !e.is_empty());
+                if let Some(examples) = examples {
+                    add(
+                        format!("tests/fixtures/{}.examples.json", resource.name),
+                        TestGenerator::generate_fixture_file(examples)?,
+                        FileType::Test,
+                    );
+                }
+                add(
+                    format!("tests/fixtures/{}.synthetic.json", resource.name),
+                    TestGenerator::generate_fixture_file(&TestGenerator::synthetic_instances(
+                        type_graph,
+                        resource,
+                        SYNTHETIC_INSTANCES,
+                    ))?,
+                    FileType::Test,
+                );
+                add(
+                    format!("tests/resources/{}.test.ts", resource.name),
+                    test_generator.generate_resource_test_file(resource, examples.is_some())?,
+                    FileType::Test,
+                );
+            }
+        }
 
         // Generate main index
-        files.insert("src/index.ts".to_string(), self.generate_main_index(type_graph)?);
+        add("src/index.ts".to_string(), self.generate_main_index(type_graph)?, FileType::Index);
 
         Ok(files)
     }
 
+    /// Warnings about parts of the graph the generated SDK does not cover
+    ///
+    /// Lists FHIRPath invariants that could not be translated into validator checks.
+    pub fn generation_warnings(&self, type_graph: &TypeGraph) -> Vec<String> {
+        if !self.function_validation() {
+            return Vec::new();
+        }
+        self.validation_generator.untranslated_invariants(type_graph)
+    }
+
+    /// Whether `validateX` functions are generated
+    fn function_validation(&self) -> bool {
+        self.options.emit_validation && self.options.validation_style == ValidationStyle::Functions
+    }
+
+    /// Whether search builders are generated
+    fn search_enabled(&self, type_graph: &TypeGraph) -> bool {
+        self.options.generate_search
+            && !SearchGenerator::searchable_resources(type_graph).is_empty()
+    }
+
+    /// Whether the Bundle utilities are generated
+    fn bundle_enabled(&self, type_graph: &TypeGraph) -> bool {
+        self.options.emit_helpers
+            && !ResourceGenerator::<TypeScriptBackend>::resource_union_members(type_graph)
+                .is_empty()
+    }
+
+    /// Whether the REST client is generated
+    fn client_enabled(&self, type_graph: &TypeGraph) -> bool {
+        self.options.generate_client && !ClientGenerator::client_resources(type_graph).is_empty()
+    }
+
+    /// Whether Zod schemas are generated
+    fn zod_validation(&self) -> bool {
+        self.options.emit_validation && self.options.validation_style == ValidationStyle::Zod
+    }
+
+    /// Derive the package configuration for a generator config
+    ///
+    /// `module_name` and `package_version` override the package name and version;
+    /// the `description`, `license` and `repository_url` custom options override
+    /// the corresponding package.json fields.
+    fn package_config_for(&self, config: &GeneratorConfig, graph: &TypeGraph) -> PackageConfig {
+        let mut package = self.package_config.clone();
+
+        if let Some(ref name) = config.module_name {
+            package.name.clone_from(name);
+        }
+        package.version.clone_from(&config.package_version);
+        package.fhir_version = graph.fhir_version.to_string();
+
+        if let Some(description) = config.get_custom::<String>("description") {
+            package.description = description;
+        }
+        if let Some(license) = config.get_custom::<String>("license") {
+            package.license = license;
+        }
+        if let Some(repository_url) = config.get_custom::<String>("repository_url") {
+            package.repository_url = Some(repository_url);
+        }
+
+        package
+    }
+
+    /// Derive the generation options for a generator config
+    ///
+    /// The `emit_*` flags come from the config itself; `generate_classes`,
+    /// `generate_extensions`, `generate_search`, `generate_client`,
+    /// `validation_style`, `strict_mode`, `target_version`, `type_mappings` and
+    /// `compiler_options` are read from custom options when present.
+    fn options_for(&self, config: &GeneratorConfig) -> SdkOptions {
+        let mut options = self.options.clone();
+
+        options.emit_validation = config.emit_validation;
+        options.emit_helpers = config.emit_helpers;
+        options.emit_tests = config.emit_tests;
+
+        if let Some(use_classes) = config.get_custom("generate_classes") {
+            options.use_classes = use_classes;
+        }
+        if let Some(generate_extensions) = config.get_custom("generate_extensions") {
+            options.generate_extensions = generate_extensions;
+        }
+        if let Some(generate_search) = config.get_custom("generate_search") {
+            options.generate_search = generate_search;
+        }
+        if let Some(generate_client) = config.get_custom("generate_client") {
+            options.generate_client = generate_client;
+        }
+        if let Some(validation_style) = config.get_custom("validation_style") {
+            options.validation_style = validation_style;
+        }
+        if let Some(type_mappings) = config.get_custom("type_mappings") {
+            options.type_mappings = type_mappings;
+        }
+        if let Some(strict) = config.get_custom("strict_mode") {
+            options.compiler.strict = strict;
+        }
+        if let Some(version) = config.get_custom("target_version") {
+            options.compiler.typescript_version = version;
+        }
+        if let Some(compiler_options) = config.get_custom("compiler_options") {
+            options.compiler.compiler_options = compiler_options;
+        }
+
+        options
+    }
+
     /// Generate the main index.ts file
     fn generate_main_index(&self, type_graph: &TypeGraph) -> Result<String> {
         let mut exports = vec![
@@ -139,14 +628,64 @@ impl TypeScriptSdkGenerator {
             "// Primitive types".to_string(),
             "export * from './primitives';".to_string(),
             "".to_string(),
-            "// Validation types and functions".to_string(),
-            "export * from './validation';".to_string(),
-            "".to_string(),
-            "// Utility functions".to_string(),
-            "export * from './utilities';".to_string(),
-            "".to_string(),
         ];
 
+        for (enabled, comment, module) in [
+            (self.function_validation(), "Validation types and functions", "validation"),
+            (self.options.emit_helpers, "Utility functions", "utilities"),
+            (self.options.use_classes, "Resource parsing and serialization", "serialization"),
+            (self.bundle_enabled(type_graph), "Bundle utilities", "bundle"),
+            (self.options.generate_extensions, "Extension utilities", "extensions"),
+        ] {
+            if enabled {
+                exports.push(format!("// {}", comment));
+                exports.push(format!("export * from './{}';", module));
+                exports.push("".to_string());
+            }
+        }
+
+        if type_graph.value_sets.values().any(|value_set| !value_set.concepts.is_empty()) {
+            exports.push("// Value sets".to_string());
+            exports.push("export * from './valuesets';".to_string());
+            exports.push("".to_string());
+        }
+
+        if self.function_validation() {
+            exports.push("// Validators".to_string());
+            exports.push("export * from './validators';".to_string());
+            exports.push("".to_string());
+        }
+
+        if self.search_enabled(type_graph) {
+            exports.push("// Search builders".to_string());
+            exports.push("export * from './search';".to_string());
+            exports.push("".to_string());
+        }
+
+        if self.client_enabled(type_graph) {
+            exports.push("// REST client".to_string());
+            exports.push("export * from './client';".to_string());
+            exports.push("".to_string());
+        }
+
+        if !type_graph.operations.is_empty() {
+            exports.push("// Operations".to_string());
+            exports.push("export * from './operations';".to_string());
+            exports.push("".to_string());
+        }
+
+        if !ProfileGenerator::profile_names(type_graph).is_empty() {
+            exports.push("// Profiles".to_string());
+            exports.push("export * from './profiles';".to_string());
+            exports.push("".to_string());
+        }
+
+        if self.zod_validation() {
+            exports.push("// Zod schemas".to_string());
+            exports.push("export * from './schemas';".to_string());
+            exports.push("".to_string());
+        }
+
         // Export datatypes
         if !type_graph.datatypes.is_empty() {
             exports.push("// Datatypes".to_string());
@@ -168,6 +707,46 @@ impl TypeScriptSdkGenerator {
         Ok(exports.join("\n"))
     }
 
+    /// Generate a vitest smoke test for the SDK entry point
+    ///
+    /// In class mode, every concrete resource is also instantiated, round-tripped
+    /// through `toJSON`/`parse` and dispatched by `parseResource`.
+    fn generate_sdk_test(&self, type_graph: &TypeGraph) -> String {
+        let mut lines = vec![
+            "// This file is auto-generated. Do not edit manually.".to_string(),
+            "import { describe, expect, it } from 'vitest';".to_string(),
+            "import * as sdk from '../src/index';".to_string(),
+            "".to_string(),
+            format!("describe('{}', () => {{", self.package_config.name),
+            "  it('loads the SDK entry point', () => {".to_string(),
+            "    expect(sdk).toBeDefined();".to_string(),
+            "  });".to_string(),
+        ];
+
+        if self.options.use_classes {
+            for resource in type_graph.resources.values().filter(|r| !r.is_abstract) {
+                let name = TypeScriptBackend::sanitize_identifier(&resource.name);
+                lines.push("".to_string());
+                lines.push(format!("  it('round-trips {}', () => {{", resource.name));
+                lines.push(format!("    const resource = new sdk.{}();", name));
+                lines.push(format!("    expect(resource.resourceType).toBe('{}');", resource.name));
+                lines.push(format!(
+                    "    expect(sdk.{}.parse(resource.toJSON()).toJSON()).toEqual(resource.toJSON());",
+                    name
+                ));
+                lines.push(format!(
+                    "    expect(sdk.parseResource(resource.toJSON())).toBeInstanceOf(sdk.{});",
+                    name
+                ));
+                lines.push("  });".to_string());
+            }
+        }
+
+        lines.push("});".to_string());
+        lines.push("".to_string());
+        lines.join("\n")
+    }
+
     /// Write generated SDK to filesystem
     #[allow(dead_code)]
     pub fn write_sdk(&self, type_graph: &TypeGraph, output_dir: &std::path::Path) -> Result<()> {
@@ -209,10 +788,99 @@ impl TypeScriptSdkGenerator {
     }
 }
 
+#[async_trait]
+impl CodeGenerator for TypeScriptSdkGenerator {
+    async fn generate(&self, graph: &TypeGraph, config: &GeneratorConfig) -> Result<GeneratedCode> {
+        let started = std::time::Instant::now();
+
+        let generator =
+            Self::with_options(self.package_config_for(config, graph), self.options_for(config));
+        let files = generator.generate_files(graph)?;
+        let warnings = generator.generation_warnings(graph);
+
+        let mut statistics = GenerationStatistics::from_files(&files);
+        // All primitives share src/primitives.ts
+        statistics.primitives = graph.primitives.len();
+        statistics.generation_time_ms = started.elapsed().as_millis() as u64;
+
+        let manifest = GenerationManifest {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            // Capabilities of the generator as configured for this run
+            generator: generator.metadata(),
+            config: config.clone(),
+            statistics,
+            warnings,
+        };
+
+        Ok(GeneratedCode::new(files, manifest))
+    }
+
+    fn language(&self) -> Language {
+        Language::TypeScript
+    }
+
+    fn metadata(&self) -> GeneratorMetadata {
+        GeneratorMetadata {
+            name: "typescript".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            language: Language::TypeScript,
+            description: "Type-safe TypeScript SDK with interfaces, validation and helpers"
+                .to_string(),
+            author: "OctoFHIR Team".to_string(),
+            capabilities: GeneratorCapabilities {
+                validation: self.options.emit_validation,
+                helpers: self.options.emit_helpers,
+                tests: self.options.emit_tests,
+                documentation: true,
+                search_parameters: self.options.generate_search,
+                profiles: true,
+                extensions: self.options.generate_extensions,
+            },
+        }
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Result<()> {
+        if let Some(ref name) = config.module_name
+            && (name.is_empty() || name.chars().any(char::is_whitespace))
+        {
+            return Err(Error::Config(format!("Invalid npm package name: '{}'", name)));
+        }
+
+        // Custom options must have the expected shape; a silently ignored option
+        // would produce an SDK that does not match the configuration
+        let check = |key: &str, expected: &str, valid: fn(&serde_json::Value) -> bool| match config
+            .custom_options
+            .get(key)
+        {
+            Some(value) if !valid(value) => Err(Error::Config(format!(
+                "Invalid TypeScript option '{}': expected {}",
+                key, expected
+            ))),
+            _ => Ok(()),
+        };
+        check("generate_classes", "a boolean", serde_json::Value::is_boolean)?;
+        check("generate_extensions", "a boolean", serde_json::Value::is_boolean)?;
+        check("generate_search", "a boolean", serde_json::Value::is_boolean)?;
+        check("generate_client", "a boolean", serde_json::Value::is_boolean)?;
+        check("strict_mode", "a boolean", serde_json::Value::is_boolean)?;
+        check("validation_style", "\"functions\" or \"zod\"", |v| {
+            serde_json::from_value::<ValidationStyle>(v.clone()).is_ok()
+        })?;
+        check("target_version", "a string", serde_json::Value::is_string)?;
+        check("compiler_options", "a table", serde_json::Value::is_object)?;
+        check("type_mappings", "a table of strings", |v| {
+            v.as_object().is_some_and(|m| m.values().all(serde_json::Value::is_string))
+        })?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::ir::{FhirVersion, GraphMetadata};
+    use std::path::Path;
 
     fn create_test_graph() -> TypeGraph {
         use indexmap::IndexMap;
@@ -223,6 +891,10 @@ mod tests {
             datatypes: IndexMap::new(),
             primitives: IndexMap::new(),
             profiles: IndexMap::new(),
+            value_sets: IndexMap::new(),
+            operations: IndexMap::new(),
+            extensions: IndexMap::new(),
+            examples: IndexMap::new(),
             fhir_version: FhirVersion::R4,
             metadata: GraphMetadata {
                 generated_at: chrono::Utc::now().to_rfc3339(),
@@ -346,4 +1018,685 @@ mod tests {
         assert_eq!(parsed["version"], "1.2.3");
         assert_eq!(parsed["license"], "MIT");
     }
+
+    #[tokio::test]
+    async fn test_code_generator_tags_files() {
+        use crate::core::ir::{Documentation, PrimitiveType};
+
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let mut graph = create_test_graph();
+        for name in ["boolean", "string"] {
+            graph.add_primitive(
+                name.to_string(),
+                PrimitiveType {
+                    name: name.to_string(),
+                    base: None,
+                    pattern: None,
+                    documentation: Documentation::default(),
+                    url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+                },
+            );
+        }
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+
+        let file_type =
+            |path: &str| code.files.iter().find(|f| f.path == Path::new(path)).map(|f| f.file_type);
+        assert_eq!(file_type("package.json"), Some(FileType::Manifest));
+        assert_eq!(file_type("src/index.ts"), Some(FileType::Index));
+        assert_eq!(file_type("src/validation.ts"), Some(FileType::Validation));
+        assert_eq!(file_type("src/primitives.ts"), Some(FileType::Primitive));
+        assert_eq!(code.manifest.statistics.total_files, code.file_count());
+        assert_eq!(code.manifest.statistics.primitives, 2);
+        assert_eq!(code.manifest.generator.language, Language::TypeScript);
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_applies_config() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let graph = create_test_graph();
+        let config = GeneratorConfig {
+            module_name: Some("@acme/fhir".to_string()),
+            package_version: "2.0.0".to_string(),
+            ..Default::default()
+        };
+
+        let code = generator.generate(&graph, &config).await.unwrap();
+        let package_json = code.files.iter().find(|f| f.path == Path::new("package.json")).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&package_json.content).unwrap();
+        assert_eq!(parsed["name"], "@acme/fhir");
+        assert_eq!(parsed["version"], "2.0.0");
+    }
+
+    #[test]
+    fn test_validate_config_rejects_bad_module_name() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let config =
+            GeneratorConfig { module_name: Some("bad name".to_string()), ..Default::default() };
+
+        assert!(generator.validate_config(&config).is_err());
+        assert!(generator.validate_config(&GeneratorConfig::default()).is_ok());
+    }
+
+    fn create_patient_graph() -> TypeGraph {
+        use crate::core::ir::{
+            CardinalityRange, Documentation, Property, PropertyType, ResourceType,
+        };
+
+        let mut graph = create_test_graph();
+        graph.add_resource(
+            "Patient".to_string(),
+            ResourceType {
+                name: "Patient".to_string(),
+                base: None,
+                constraints: vec![],
+                properties: vec![Property {
+                    name: "birthDate".to_string(),
+                    path: "Patient.birthDate".to_string(),
+                    property_type: PropertyType::Primitive { type_name: "date".to_string() },
+                    cardinality: CardinalityRange::optional(),
+                    is_choice: false,
+                    choice_types: vec![],
+                    is_modifier: false,
+                    is_summary: true,
+                    binding: None,
+                    constraints: vec![],
+                    short_description: "The date of birth for the individual".to_string(),
+                    definition: String::new(),
+                    comments: None,
+                    examples: vec![],
+                }],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: "http://hl7.org/fhir/StructureDefinition/Patient".to_string(),
+                is_abstract: false,
+            },
+        );
+        graph
+    }
+
+    fn file<'a>(code: &'a GeneratedCode, path: &str) -> Option<&'a GeneratedFile> {
+        code.files.iter().find(|f| f.path == Path::new(path))
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_emit_flags() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let graph = create_patient_graph();
+        let mut config = GeneratorConfig {
+            emit_validation: false,
+            emit_helpers: false,
+            emit_tests: true,
+            ..Default::default()
+        };
+        config.set_custom("generate_extensions".to_string(), false).unwrap();
+
+        let code = generator.generate(&graph, &config).await.unwrap();
+        assert!(file(&code, "src/validation.ts").is_none());
+        assert!(file(&code, "src/validators/index.ts").is_none());
+        assert!(file(&code, "src/utilities.ts").is_none());
+        assert!(file(&code, "src/extensions.ts").is_none());
+        assert_eq!(file(&code, "tests/sdk.test.ts").unwrap().file_type, FileType::Test);
+        // Plain interfaces without validation leave nothing for per-resource suites to check
+        assert!(file(&code, "tests/resources/Patient.test.ts").is_none());
+        assert!(file(&code, "tests/support.ts").is_none());
+
+        let index = &file(&code, "src/index.ts").unwrap().content;
+        assert!(!index.contains("'./validation'"));
+        assert!(!index.contains("'./utilities'"));
+        assert!(!index.contains("'./extensions'"));
+
+        let defaults = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        assert!(file(&defaults, "src/extensions.ts").is_some());
+        assert!(file(&defaults, "tests/sdk.test.ts").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_emits_validators() {
+        use crate::core::ir::{CardinalityRange, DataType, Documentation, Property, PropertyType};
+
+        let property = |name: &str, property_type: PropertyType| Property {
+            name: name.to_string(),
+            path: String::new(),
+            property_type,
+            cardinality: CardinalityRange::optional_array(),
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        };
+
+        let mut graph = create_patient_graph();
+        graph.resources["Patient"]
+            .properties
+            .push(property("name", PropertyType::Complex { type_name: "HumanName".to_string() }));
+        graph.add_datatype(
+            "HumanName".to_string(),
+            DataType {
+                name: "HumanName".to_string(),
+                base: None,
+                constraints: vec![],
+                properties: vec![property(
+                    "given",
+                    PropertyType::Primitive { type_name: "string".to_string() },
+                )],
+                documentation: Documentation::default(),
+                url: String::new(),
+                is_abstract: false,
+            },
+        );
+
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+
+        let patient = file(&code, "src/validators/Patient.ts").unwrap();
+        assert_eq!(patient.file_type, FileType::Validation);
+        assert!(patient.content.contains("import { validateHumanName } from './HumanName';"));
+        assert!(
+            patient.content.contains(
+                "errors.push(...validateHumanName(item0, `${path}.name[${i0}]`).errors);"
+            )
+        );
+
+        let human_name = &file(&code, "src/validators/HumanName.ts").unwrap().content;
+        assert!(human_name.contains("export function validateHumanName(value: HumanName"));
+        assert!(human_name.contains("path: `${path}.given[${i0}]`"));
+
+        let index = &file(&code, "src/validators/index.ts").unwrap().content;
+        assert!(index.contains("export * from './HumanName';"));
+        assert!(index.contains("export * from './Patient';"));
+        assert!(file(&code, "src/index.ts").unwrap().content.contains("'./validators'"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_emits_invariant_checks() {
+        use crate::core::ir::{ConstraintSeverity, InvariantRule};
+
+        let rule = |key: &str, expression: &str| InvariantRule {
+            key: key.to_string(),
+            severity: ConstraintSeverity::Error,
+            human: format!("{} violated", key),
+            expression: Some(expression.to_string()),
+            xpath: None,
+        };
+
+        let mut graph = create_patient_graph();
+        let patient = graph.resources.get_mut("Patient").unwrap();
+        patient.constraints.push(rule("pat-9", "active.exists()"));
+        patient.constraints.push(rule("pat-10", "link.other.resolve().exists()"));
+
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+
+        let runtime = file(&code, "src/fhirpath.ts").unwrap();
+        assert_eq!(runtime.file_type, FileType::Validation);
+        assert!(runtime.content.contains("export function isFalse("));
+
+        let validator = &file(&code, "src/validators/Patient.ts").unwrap().content;
+        assert!(validator.contains("import * as fhirpath from '../fhirpath';"));
+        assert!(validator.contains("constraint: \"pat-9\""));
+        assert!(!validator.contains("pat-10"));
+
+        assert_eq!(code.manifest.warnings.len(), 1);
+        assert!(code.manifest.warnings[0].contains("Invariant pat-10 on Patient"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_checks_inherited_invariants() {
+        use crate::core::ir::{ConstraintSeverity, Documentation};
+
+        let rule = |key: &str, expression: &str| InvariantRule {
+            key: key.to_string(),
+            severity: ConstraintSeverity::Error,
+            human: format!("{} violated", key),
+            expression: Some(expression.to_string()),
+            xpath: None,
+        };
+
+        let mut graph = create_patient_graph();
+        graph.add_resource(
+            "DomainResource".to_string(),
+            ResourceType {
+                name: "DomainResource".to_string(),
+                base: None,
+                constraints: vec![
+                    rule("dom-2", "contained.contained.empty()"),
+                    rule("dom-3", "contained.where(%resource.descendants().empty()).empty()"),
+                ],
+                properties: vec![],
+                search_parameters: vec![],
+                extensions: vec![],
+                documentation: Documentation::default(),
+                url: "http://hl7.org/fhir/StructureDefinition/DomainResource".to_string(),
+                is_abstract: true,
+            },
+        );
+        graph.resources.get_mut("Patient").unwrap().base = Some("DomainResource".to_string());
+
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+
+        assert!(file(&code, "src/validators/DomainResource.ts").is_none());
+        let validator = &file(&code, "src/validators/Patient.ts").unwrap().content;
+        assert!(validator.contains("constraint: \"dom-2\""));
+
+        assert_eq!(code.manifest.warnings.len(), 1);
+        assert!(code.manifest.warnings[0].contains("Invariant dom-3 on Patient"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_zod_validation() {
+        let graph = create_patient_graph();
+        let mut config = GeneratorConfig::default();
+        config.set_custom("validation_style".to_string(), "zod").unwrap();
+
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let code = generator.generate(&graph, &config).await.unwrap();
+
+        let schema = file(&code, "src/schemas/Patient.ts").unwrap();
+        assert_eq!(schema.file_type, FileType::Validation);
+        assert!(schema.content.contains("export const PatientSchema = z.object({"));
+        assert_eq!(file(&code, "src/schemas/index.ts").unwrap().file_type, FileType::Index);
+        assert!(file(&code, "src/validation.ts").is_none());
+        assert!(file(&code, "src/validators/index.ts").is_none());
+
+        let package_json: serde_json::Value =
+            serde_json::from_str(&file(&code, "package.json").unwrap().content).unwrap();
+        assert_eq!(package_json["dependencies"]["zod"], ZOD_VERSION);
+
+        let index = &file(&code, "src/index.ts").unwrap().content;
+        assert!(index.contains("export * from './schemas';"));
+        assert!(!index.contains("'./validators'"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_search_builders() {
+        let graph = create_patient_graph();
+        let generator = TypeScriptSdkGenerator::with_defaults();
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        let builder = file(&code, "src/search/Patient.ts").unwrap();
+        assert_eq!(builder.file_type, FileType::Helper);
+        assert!(builder.content.contains("export class PatientSearch extends"));
+        assert!(file(&code, "src/search/runtime.ts").is_some());
+        let search_index = &file(&code, "src/search/index.ts").unwrap().content;
+        assert!(search_index.contains("Patient: (): PatientSearch => new PatientSearch(),"));
+        assert!(file(&code, "src/index.ts").unwrap().content.contains("export * from './search';"));
+
+        let mut config = GeneratorConfig::default();
+        config.set_custom("generate_search".to_string(), false).unwrap();
+        let code = generator.generate(&graph, &config).await.unwrap();
+        assert!(file(&code, "src/search/index.ts").is_none());
+        assert!(!file(&code, "src/index.ts").unwrap().content.contains("'./search'"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_rest_client() {
+        let graph = create_patient_graph();
+        let generator = TypeScriptSdkGenerator::with_defaults();
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        assert!(file(&code, "src/client.ts").is_none());
+
+        let mut config = GeneratorConfig::default();
+        config.set_custom("generate_client".to_string(), true).unwrap();
+        let code = generator.generate(&graph, &config).await.unwrap();
+        let client = file(&code, "src/client.ts").unwrap();
+        assert_eq!(client.file_type, FileType::Helper);
+        assert!(client.content.contains("  Patient: Patient;"));
+        assert!(client.content.contains("export class FhirClient {"));
+        assert!(file(&code, "src/index.ts").unwrap().content.contains("export * from './client';"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_bundle_utilities() {
+        let graph = create_patient_graph();
+        let generator = TypeScriptSdkGenerator::with_defaults();
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        let bundle = file(&code, "src/bundle.ts").unwrap();
+        assert_eq!(bundle.file_type, FileType::Helper);
+        assert!(bundle.content.contains("export type AnyResource = Patient;"));
+        assert!(file(&code, "src/index.ts").unwrap().content.contains("export * from './bundle';"));
+
+        let config = GeneratorConfig { emit_helpers: false, ..Default::default() };
+        let code = generator.generate(&graph, &config).await.unwrap();
+        assert!(file(&code, "src/bundle.ts").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_operations() {
+        use crate::core::ir::{
+            CardinalityRange, Documentation, OperationDefinition, OperationKind,
+            OperationParameter, OperationParameterUse,
+        };
+
+        let mut graph = create_patient_graph();
+        let generator = TypeScriptSdkGenerator::with_defaults();
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        assert!(file(&code, "src/operations.ts").is_none());
+
+        graph.add_operation(
+            "http://hl7.org/fhir/OperationDefinition/Patient-match".to_string(),
+            OperationDefinition {
+                url: "http://hl7.org/fhir/OperationDefinition/Patient-match".to_string(),
+                name: "Match".to_string(),
+                code: "match".to_string(),
+                kind: OperationKind::Operation,
+                affects_state: false,
+                resources: vec!["Patient".to_string()],
+                system: false,
+                type_level: true,
+                instance: false,
+                parameters: vec![OperationParameter {
+                    name: "count".to_string(),
+                    usage: OperationParameterUse::In,
+                    cardinality: CardinalityRange::optional(),
+                    type_name: Some("integer".to_string()),
+                    target_profiles: vec![],
+                    search_type: None,
+                    binding: None,
+                    documentation: String::new(),
+                    parts: vec![],
+                }],
+                documentation: Documentation::default(),
+            },
+        );
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        let operations = file(&code, "src/operations.ts").unwrap();
+        assert_eq!(operations.file_type, FileType::Helper);
+        assert!(operations.content.contains("  count?: number;"));
+        assert!(operations.content.contains("export function buildPatientMatchParameters("));
+        assert!(
+            file(&code, "src/index.ts").unwrap().content.contains("export * from './operations';")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_profiles() {
+        use crate::core::ir::{CardinalityRange, Documentation, ProfileType, PropertyConstraint};
+
+        let mut graph = create_patient_graph();
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let url = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient";
+        graph.add_profile(
+            url.to_string(),
+            ProfileType {
+                name: "USCorePatient".to_string(),
+                base: "Patient".to_string(),
+                property_constraints: vec![PropertyConstraint {
+                    path: "Patient.birthDate".to_string(),
+                    cardinality: Some(CardinalityRange::required()),
+                    type_constraints: vec![],
+                    binding: None,
+                    fixed_value: None,
+                    must_support: true,
+                }],
+                new_properties: vec![],
+                documentation: Documentation::default(),
+                url: url.to_string(),
+            },
+        );
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        let profile = file(&code, "src/profiles/us-core/USCorePatient.ts").unwrap();
+        assert_eq!(profile.file_type, FileType::Profile);
+        assert!(profile.content.contains("export interface USCorePatient extends Patient {"));
+        assert!(profile.content.contains("  birthDate: string;"));
+        assert!(profile.content.contains("export function isUSCorePatient("));
+        assert!(
+            file(&code, "src/profiles/us-core/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from './USCorePatient';")
+        );
+        assert!(
+            file(&code, "src/profiles/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from './us-core';")
+        );
+        assert!(
+            file(&code, "src/index.ts").unwrap().content.contains("export * from './profiles';")
+        );
+        assert_eq!(code.manifest.statistics.profiles, 1);
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_emits_value_sets() {
+        use crate::core::ir::{
+            BindingStrength, CardinalityRange, CodeConcept, Property, PropertyType,
+            ValueSetBinding, ValueSetExpansion,
+        };
+
+        let code_property = |name: &str, strength: BindingStrength, value_set: &str| Property {
+            name: name.to_string(),
+            path: format!("Patient.{}", name),
+            property_type: PropertyType::Primitive { type_name: "code".to_string() },
+            cardinality: CardinalityRange::optional(),
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: Some(ValueSetBinding {
+                strength,
+                value_set: value_set.to_string(),
+                description: None,
+            }),
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        };
+        let concept = |code: &str| CodeConcept {
+            system: "http://example.org/codes".to_string(),
+            code: code.to_string(),
+            display: None,
+        };
+
+        let mut graph = create_patient_graph();
+        let patient = graph.resources.get_mut("Patient").unwrap();
+        patient.properties.push(code_property(
+            "gender",
+            BindingStrength::Required,
+            "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1",
+        ));
+        patient.properties.push(code_property(
+            "language",
+            BindingStrength::Extensible,
+            "http://example.org/ValueSet/languages",
+        ));
+        for (url, name, codes) in [
+            (
+                "http://hl7.org/fhir/ValueSet/administrative-gender",
+                "AdministrativeGender",
+                ["male", "female"],
+            ),
+            ("http://example.org/ValueSet/languages", "Languages", ["en", "de"]),
+        ] {
+            graph.add_value_set(
+                url.to_string(),
+                ValueSetExpansion {
+                    url: url.to_string(),
+                    name: name.to_string(),
+                    title: None,
+                    concepts: codes.into_iter().map(concept).collect(),
+                },
+            );
+        }
+
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+
+        let gender = file(&code, "src/valuesets/AdministrativeGender.ts").unwrap();
+        assert_eq!(gender.file_type, FileType::ValueSet);
+        assert_eq!(code.manifest.statistics.datatypes, graph.datatypes.len());
+        let gender = &gender.content;
+        assert!(gender.contains("export type AdministrativeGender = \"male\" | \"female\";"));
+        assert!(gender.contains("export const AdministrativeGenderCodes = ["));
+        assert!(
+            file(&code, "src/valuesets/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from './Languages';")
+        );
+
+        let resource = &file(&code, "src/resources/Patient.ts").unwrap().content;
+        assert!(
+            resource.contains("import { AdministrativeGender, Languages } from '../valuesets';")
+        );
+        assert!(resource.contains("gender?: AdministrativeGender;"));
+        assert!(resource.contains("language?: Languages | (string & {});"));
+
+        // Only required bindings are enforced by the validator
+        let validator = &file(&code, "src/validators/Patient.ts").unwrap().content;
+        assert!(validator.contains("import { AdministrativeGenderCodes } from '../valuesets';"));
+        assert!(
+            validator
+                .contains("!AdministrativeGenderCodes.some((c) => c.code === resource.gender)")
+        );
+        assert!(!validator.contains("LanguagesCodes"));
+
+        assert!(file(&code, "src/index.ts").unwrap().content.contains("'./valuesets'"));
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_class_mode_and_type_mappings() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let mut graph = create_patient_graph();
+        graph.add_example(
+            "Patient".to_string(),
+            serde_json::json!({ "resourceType": "Patient", "id": "example" }),
+        );
+        let mut config = GeneratorConfig { emit_tests: true, ..Default::default() };
+        config.set_custom("generate_classes".to_string(), true).unwrap();
+        config.set_custom("type_mappings".to_string(), HashMap::from([("date", "Date")])).unwrap();
+
+        let code = generator.generate(&graph, &config).await.unwrap();
+        let patient = &file(&code, "src/resources/Patient.ts").unwrap().content;
+        assert!(patient.contains("export class Patient"));
+        assert!(patient.contains("private _birthDate?: Date;"));
+
+        assert!(patient.contains(
+            "import { parseValue, toJSONValue, unknownFields } from '../serialization';"
+        ));
+
+        let serialization = file(&code, "src/serialization.ts").unwrap();
+        assert_eq!(serialization.file_type, FileType::Helper);
+        assert!(serialization.content.contains("export function parseResource(json: any)"));
+        let index = &file(&code, "src/index.ts").unwrap().content;
+        assert!(index.contains("export * from './serialization';"));
+
+        let test = &file(&code, "tests/sdk.test.ts").unwrap().content;
+        assert!(test.contains("const resource = new sdk.Patient();"));
+        assert!(
+            test.contains(
+                "expect(sdk.parseResource(resource.toJSON())).toBeInstanceOf(sdk.Patient);"
+            )
+        );
+
+        let suite = &file(&code, "tests/resources/Patient.test.ts").unwrap().content;
+        assert!(suite.contains("const examples = loadFixtures('Patient.examples.json');"));
+        assert!(suite.contains("const resource = Patient.parse(json);"));
+        assert!(suite.contains("const { errors } = validatePatient(resource);"));
+        let examples = file(&code, "tests/fixtures/Patient.examples.json").unwrap();
+        assert_eq!(examples.file_type, FileType::Test);
+        assert!(examples.content.contains("\"id\": \"example\""));
+        assert!(file(&code, "tests/fixtures/Patient.synthetic.json").is_some());
+        assert!(file(&code, "tests/support.ts").is_some());
+
+        let code = generator.generate(&graph, &GeneratorConfig::default()).await.unwrap();
+        assert!(file(&code, "src/serialization.ts").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_code_generator_compiler_options() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let graph = create_test_graph();
+        let mut config = GeneratorConfig::default();
+        config.set_custom("strict_mode".to_string(), false).unwrap();
+        config.set_custom("target_version".to_string(), "5.3").unwrap();
+        config
+            .set_custom(
+                "compiler_options".to_string(),
+                serde_json::json!({ "source_map": false, "moduleResolution": "node" }),
+            )
+            .unwrap();
+
+        let code = generator.generate(&graph, &config).await.unwrap();
+        let tsconfig: serde_json::Value =
+            serde_json::from_str(&file(&code, "tsconfig.json").unwrap().content).unwrap();
+        assert_eq!(tsconfig["compilerOptions"]["strict"], false);
+        assert_eq!(tsconfig["compilerOptions"]["sourceMap"], false);
+        assert_eq!(tsconfig["compilerOptions"]["moduleResolution"], "node");
+
+        let package_json: serde_json::Value =
+            serde_json::from_str(&file(&code, "package.json").unwrap().content).unwrap();
+        assert_eq!(package_json["devDependencies"]["typescript"], "^5.3.0");
+    }
+
+    #[tokio::test]
+    async fn test_manifest_reports_effective_capabilities() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let mut config =
+            GeneratorConfig { emit_validation: false, emit_tests: true, ..Default::default() };
+        config.set_custom("generate_search".to_string(), false).unwrap();
+
+        let code = generator.generate(&create_test_graph(), &config).await.unwrap();
+        let capabilities = &code.manifest.generator.capabilities;
+        assert!(!capabilities.search_parameters);
+        assert!(!capabilities.validation);
+        assert!(capabilities.tests);
+        assert!(capabilities.helpers);
+    }
+
+    #[test]
+    fn test_metadata_reports_search_capability() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        assert!(generator.metadata().capabilities.search_parameters);
+
+        let options = SdkOptions { generate_search: false, ..SdkOptions::default() };
+        let generator = TypeScriptSdkGenerator::with_options(PackageConfig::default(), options);
+        assert!(!generator.metadata().capabilities.search_parameters);
+    }
+
+    #[test]
+    fn test_validate_config_rejects_bad_options() {
+        let generator = TypeScriptSdkGenerator::with_defaults();
+        let mut config = GeneratorConfig::default();
+        config.set_custom("generate_classes".to_string(), "yes").unwrap();
+        assert!(generator.validate_config(&config).is_err());
+
+        let mut config = GeneratorConfig::default();
+        config.set_custom("type_mappings".to_string(), serde_json::json!({ "date": 1 })).unwrap();
+        assert!(generator.validate_config(&config).is_err());
+
+        let mut config = GeneratorConfig::default();
+        config.set_custom("generate_search".to_string(), "true").unwrap();
+        let error = generator.validate_config(&config).unwrap_err();
+        assert!(error.to_string().contains("'generate_search'"), "{}", error);
+
+        let mut config = GeneratorConfig::default();
+        config.set_custom("generate_client".to_string(), "yes").unwrap();
+        let error = generator.validate_config(&config).unwrap_err();
+        assert!(error.to_string().contains("'generate_client'"), "{}", error);
+
+        let mut config = GeneratorConfig::default();
+        config.set_custom("validation_style".to_string(), "zodd").unwrap();
+        let error = generator.validate_config(&config).unwrap_err();
+        assert!(error.to_string().contains("'validation_style'"), "{}", error);
+
+        config.set_custom("validation_style".to_string(), "zod").unwrap();
+        assert!(generator.validate_config(&config).is_ok());
+    }
 }
@@ -0,0 +1,813 @@
+//! Typed FHIR search query builders for TypeScript
+//!
+//! Every concrete resource gets a `XSearchParams<Q>` class with one getter per
+//! search parameter (including those inherited from `Resource` and
+//! `DomainResource`) and a `XSearch` entry point adding `_include`,
+//! `_revinclude` and `_sort` constrained to the resource:
+//!
+//! ```typescript
+//! search.Patient().name.contains("smi").birthdate.ge("2000").toString();
+//! // => "Patient?name:contains=smi&birthdate=ge2000"
+//! ```
+
+use crate::core::Result;
+use crate::core::ir::{ResourceType, SearchParamType, SearchParameter, TypeGraph};
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+use heck::ToLowerCamelCase;
+use std::collections::BTreeSet;
+
+/// Members of the generated builders that search parameter getters must not shadow
+const RESERVED_MEMBERS: &[&str] = &[
+    "constructor",
+    "count",
+    "include",
+    "param",
+    "prefix",
+    "query",
+    "reference",
+    "result",
+    "revinclude",
+    "sort",
+    "toQueryString",
+    "toString",
+];
+
+/// Generator for per-resource search builders and their runtime
+pub struct SearchGenerator;
+
+impl SearchGenerator {
+    /// Resources that get a search builder: every concrete resource in the graph
+    pub fn searchable_resources(graph: &TypeGraph) -> Vec<&ResourceType> {
+        graph.resources.values().filter(|resource| !resource.is_abstract).collect()
+    }
+
+    /// Name of the getter for a search parameter code
+    ///
+    /// Codes are camel-cased (`general-practitioner` -> `generalPractitioner`),
+    /// keeping a leading underscore (`_lastUpdated`). Codes clashing with builder
+    /// members get a `Param` suffix.
+    pub fn getter_name(code: &str) -> String {
+        let name = match code.strip_prefix('_') {
+            Some(rest) => format!("_{}", rest.to_lower_camel_case()),
+            None => code.to_lower_camel_case(),
+        };
+        if RESERVED_MEMBERS.contains(&name.as_str()) { format!("{}Param", name) } else { name }
+    }
+
+    /// Runtime class implementing a [`SearchParamType`]
+    fn param_class(param_type: &SearchParamType) -> &'static str {
+        match param_type {
+            SearchParamType::Number => "NumberParam",
+            SearchParamType::Date => "DateParam",
+            SearchParamType::String => "StringParam",
+            SearchParamType::Token => "TokenParam",
+            SearchParamType::Reference => "ReferenceParam",
+            SearchParamType::Composite => "CompositeParam",
+            SearchParamType::Quantity => "QuantityParam",
+            SearchParamType::Uri => "UriParam",
+            SearchParamType::Special => "SpecialParam",
+        }
+    }
+
+    /// Valid `_include` values for a resource
+    ///
+    /// Each reference parameter can be included as a whole; parameters with
+    /// several target types can also be narrowed to one of them.
+    pub fn include_targets(graph: &TypeGraph, resource: &ResourceType) -> Vec<String> {
+        let mut targets = Vec::new();
+        for parameter in Self::reference_parameters(graph, resource) {
+            targets.push(format!("{}:{}", resource.name, parameter.code));
+            if parameter.target_types.len() > 1 {
+                for target in &parameter.target_types {
+                    targets.push(format!("{}:{}:{}", resource.name, parameter.code, target));
+                }
+            }
+        }
+        targets
+    }
+
+    /// Valid `_revinclude` values for a resource
+    ///
+    /// Lists the reference parameters of every concrete resource that may point
+    /// at `resource`, narrowed to it when the parameter has several targets.
+    pub fn revinclude_sources(graph: &TypeGraph, resource: &ResourceType) -> Vec<String> {
+        let mut sources = Vec::new();
+        for source in Self::searchable_resources(graph) {
+            for parameter in Self::reference_parameters(graph, source) {
+                if !parameter.target_types.contains(&resource.name) {
+                    continue;
+                }
+                sources.push(format!("{}:{}", source.name, parameter.code));
+                if parameter.target_types.len() > 1 {
+                    sources.push(format!("{}:{}:{}", source.name, parameter.code, resource.name));
+                }
+            }
+        }
+        sources
+    }
+
+    /// Reference search parameters of a resource, including inherited ones
+    fn reference_parameters<'a>(
+        graph: &'a TypeGraph,
+        resource: &'a ResourceType,
+    ) -> impl Iterator<Item = &'a SearchParameter> {
+        graph
+            .search_parameters(resource)
+            .into_iter()
+            .filter(|parameter| parameter.param_type == SearchParamType::Reference)
+    }
+
+    /// Target types of a reference parameter that have a search builder to chain into
+    fn chain_targets<'a>(graph: &TypeGraph, parameter: &'a SearchParameter) -> Vec<&'a str> {
+        parameter
+            .target_types
+            .iter()
+            .filter(|target| graph.resources.get(*target).is_some_and(|r| !r.is_abstract))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Generate the search builder module for a concrete resource
+    pub fn generate_resource_search_file(
+        graph: &TypeGraph,
+        resource: &ResourceType,
+    ) -> Result<String> {
+        let name = &resource.name;
+        let parameters = graph.search_parameters(resource);
+        let mut tokens = js::Tokens::new();
+
+        // Imports
+        let mut runtime_imports: BTreeSet<String> =
+            ["SearchParams", "SearchQuery"].iter().map(|s| s.to_string()).collect();
+        let mut chained = BTreeSet::new();
+        for parameter in &parameters {
+            runtime_imports.insert(Self::param_class(&parameter.param_type).to_string());
+            if parameter.param_type == SearchParamType::Reference {
+                chained.extend(
+                    Self::chain_targets(graph, parameter)
+                        .into_iter()
+                        .filter(|target| *target != name.as_str()),
+                );
+            }
+        }
+        let mut imports = vec![(runtime_imports.into_iter().collect(), "./runtime".to_string())];
+        for target in chained {
+            imports.push((vec![format!("{}SearchParams", target)], format!("./{}", target)));
+        }
+        tokens.append(helpers::imports(&imports));
+        tokens.line();
+
+        // Codes, includes and revincludes
+        let codes: Vec<String> = parameters.iter().map(|p| Self::quote(&p.code)).collect();
+        let includes: Vec<String> =
+            Self::include_targets(graph, resource).iter().map(|t| Self::quote(t)).collect();
+        let revincludes: Vec<String> =
+            Self::revinclude_sources(graph, resource).iter().map(|s| Self::quote(s)).collect();
+
+        for (suffix, values, doc) in [
+            ("SearchCode", &codes, format!("Search parameter codes of {}", name)),
+            ("Include", &includes, format!("`_include` targets of {} searches", name)),
+            ("RevInclude", &revincludes, format!("`_revinclude` sources of {} searches", name)),
+        ] {
+            tokens.append(helpers::type_alias(
+                &format!("{}{}", name, suffix),
+                &Self::union_or_never(values),
+                Some(&[doc]),
+            ));
+            tokens.line();
+        }
+
+        // Parameter getters
+        tokens.append(helpers::jsdoc_comment(&[
+            format!("Search parameters of {}", name),
+            String::new(),
+            "Parameter methods return `Q`, the builder the search continues on.".to_string(),
+        ]));
+        tokens.push();
+        tokens.append(format!("export class {}SearchParams<Q> extends SearchParams<Q> {{", name));
+        tokens.indent();
+        for (index, parameter) in parameters.iter().enumerate() {
+            if index > 0 {
+                tokens.line();
+            }
+            tokens.append(Self::generate_getter(graph, parameter));
+        }
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+        tokens.line();
+
+        // Entry point
+        tokens.append(helpers::jsdoc_comment(&[format!("Search for {} resources", name)]));
+        tokens.push();
+        tokens.append(format!(
+            "export class {name}Search extends {name}SearchParams<{name}Search> {{"
+        ));
+        tokens.indent();
+        tokens.push();
+        tokens.append("constructor() {");
+        tokens.indent();
+        tokens.push();
+        tokens.append(format!("super(new SearchQuery({}));", Self::quote(name)));
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+        tokens.line();
+        tokens.append(Self::generate_list_method(
+            "Include resources referenced by the matches (`_include`)",
+            "include",
+            &format!("target: {}Include", name),
+            "\"_include\", target",
+        ));
+        tokens.line();
+        tokens.append(Self::generate_list_method(
+            "Include resources referencing the matches (`_revinclude`)",
+            "revinclude",
+            &format!("source: {}RevInclude", name),
+            "\"_revinclude\", source",
+        ));
+        tokens.line();
+        tokens.append(Self::generate_list_method(
+            "Sort the matches, descending for codes prefixed with `-` (`_sort`)",
+            "sort",
+            &format!("...codes: Array<{0}SearchCode | `-${{{0}SearchCode}}`>", name),
+            "\"_sort\", codes.join(\",\")",
+        ));
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Getter returning the typed parameter for a search parameter
+    fn generate_getter(graph: &TypeGraph, parameter: &SearchParameter) -> js::Tokens {
+        let mut tokens = js::Tokens::new();
+        let getter = Self::getter_name(&parameter.code);
+        let class = Self::param_class(&parameter.param_type);
+        let code = Self::quote(&parameter.code);
+
+        let mut doc = Vec::new();
+        if let Some(line) = parameter.description.lines().find(|line| !line.trim().is_empty()) {
+            doc.push(line.trim().replace("*/", "*\\/"));
+            doc.push(String::new());
+        }
+        doc.push(format!("`{}` ({})", parameter.code, Self::type_name(&parameter.param_type)));
+        tokens.append(helpers::jsdoc_comment(&doc));
+        tokens.push();
+
+        if parameter.param_type != SearchParamType::Reference {
+            tokens.append(format!("get {}(): {}<Q> {{", getter, class));
+            tokens.indent();
+            tokens.push();
+            tokens.append(format!("return this.param<{}<Q>>({}, {});", class, class, code));
+        } else {
+            let targets: Vec<String> =
+                parameter.target_types.iter().map(|target| Self::quote(target)).collect();
+            let chains = Self::chain_targets(graph, parameter);
+            let chain_type = if chains.is_empty() {
+                "{}".to_string()
+            } else {
+                let members: Vec<String> = chains
+                    .iter()
+                    .map(|target| format!("{}: {}SearchParams<Q>", target, target))
+                    .collect();
+                format!("{{ {} }}", members.join("; "))
+            };
+            let param_type =
+                format!("ReferenceParam<Q, {}, {}>", Self::union_or_string(&targets), chain_type);
+
+            tokens.append(format!("get {}(): {} {{", getter, param_type));
+            tokens.indent();
+            tokens.push();
+            if chains.is_empty() {
+                tokens.append(format!("return this.reference({}, {{}});", code));
+            } else {
+                tokens.append(format!("return this.reference({}, {{", code));
+                tokens.indent();
+                for target in chains {
+                    tokens.push();
+                    tokens.append(format!(
+                        "{0}: (prefix: string) => new {0}SearchParams<Q>(this.query, prefix, this.result()),",
+                        target
+                    ));
+                }
+                tokens.unindent();
+                tokens.push();
+                tokens.append("});");
+            }
+        }
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+
+        tokens
+    }
+
+    /// Method appending one query parameter and returning the builder
+    fn generate_list_method(doc: &str, name: &str, params: &str, add_args: &str) -> js::Tokens {
+        let mut tokens = js::Tokens::new();
+        tokens.append(helpers::jsdoc_comment(&[doc.to_string()]));
+        tokens.push();
+        tokens.append(format!("{}({}): this {{", name, params));
+        tokens.indent();
+        tokens.push();
+        tokens.append(format!("this.query.add({});", add_args));
+        tokens.push();
+        tokens.append("return this;");
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+        tokens
+    }
+
+    /// Generate the index module exporting the runtime, every builder and the `search` entry points
+    pub fn generate_search_index(resources: &[String]) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated search builder index");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+        tokens.push();
+
+        for name in resources {
+            tokens.append(format!("import {{ {}Search }} from './{}';", name, name));
+            tokens.push();
+        }
+        tokens.line();
+
+        tokens.append("export * from './runtime';");
+        tokens.push();
+        for name in resources {
+            tokens.append(format!("export * from './{}';", name));
+            tokens.push();
+        }
+
+        tokens.append(helpers::jsdoc_comment(&[
+            "Entry points for typed searches".to_string(),
+            String::new(),
+            "@example search.Patient().name.contains(\"smi\").birthdate.ge(\"2000\")".to_string(),
+        ]));
+        tokens.push();
+        tokens.append("export const search = {");
+        tokens.indent();
+        for name in resources {
+            tokens.push();
+            tokens.append(format!("{0}: (): {0}Search => new {0}Search(),", name));
+        }
+        tokens.unindent();
+        tokens.push();
+        tokens.append("};");
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate the runtime module shared by all search builders
+    pub fn generate_runtime_module() -> String {
+        RUNTIME_MODULE.to_string()
+    }
+
+    /// FHIR name of a search parameter type
+    fn type_name(param_type: &SearchParamType) -> &'static str {
+        match param_type {
+            SearchParamType::Number => "number",
+            SearchParamType::Date => "date",
+            SearchParamType::String => "string",
+            SearchParamType::Token => "token",
+            SearchParamType::Reference => "reference",
+            SearchParamType::Composite => "composite",
+            SearchParamType::Quantity => "quantity",
+            SearchParamType::Uri => "uri",
+            SearchParamType::Special => "special",
+        }
+    }
+
+    /// Union of literal types, or `never` when there are none
+    fn union_or_never(literals: &[String]) -> String {
+        if literals.is_empty() { "never".to_string() } else { helpers::union_type(literals) }
+    }
+
+    /// Union of literal types, or `string` when any value is accepted
+    fn union_or_string(literals: &[String]) -> String {
+        if literals.is_empty() { "string".to_string() } else { helpers::union_type(literals) }
+    }
+
+    /// Quote a string as a TypeScript string literal
+    fn quote(value: &str) -> String {
+        serde_json::Value::from(value).to_string()
+    }
+}
+
+/// TypeScript runtime for the generated search builders
+const RUNTIME_MODULE: &str = r#"/**
+ * Runtime for the generated FHIR search builders
+ *
+ * This file is auto-generated. Do not edit manually.
+ */
+
+/** Comparison prefixes of number, date and quantity parameters */
+export type SearchPrefix = "eq" | "ne" | "gt" | "lt" | "ge" | "le" | "sa" | "eb" | "ap";
+
+/** Ordered search parameters of one query */
+export class SearchQuery {
+  private readonly entries: Array<[string, string]> = [];
+
+  constructor(readonly resourceType: string) {}
+
+  /** Append a parameter; repeated parameters must all match */
+  add(name: string, value: string): void {
+    this.entries.push([name, value]);
+  }
+
+  /** Serialize the parameters as a URL query string */
+  toQueryString(): string {
+    return this.entries
+      .map(([name, value]) => `${encodeURIComponent(name).replace(/%3A/g, ":")}=${encodeURIComponent(value)}`)
+      .join("&");
+  }
+}
+
+/** Constructor of a typed search parameter */
+export type ParamConstructor<P, Q> = new (query: SearchQuery, code: string, result: Q) => P;
+
+/** Factories for the parameters of the resources a reference can be chained into */
+export type ChainTargets<C> = { [K in keyof C]: (prefix: string) => C[K] };
+
+/** Base class of the generated per-resource search parameters */
+export class SearchParams<Q> {
+  constructor(
+    protected readonly query: SearchQuery,
+    protected readonly prefix: string = "",
+    private readonly back?: Q,
+  ) {}
+
+  /** Limit the number of matches per page (`_count`) */
+  count(count: number): Q {
+    this.query.add("_count", String(count));
+    return this.result();
+  }
+
+  /** Serialize the search as a URL query string */
+  toQueryString(): string {
+    return this.query.toQueryString();
+  }
+
+  /** Search URL relative to the server base, e.g. `Patient?name=smith` */
+  toString(): string {
+    const queryString = this.toQueryString();
+    return queryString ? `${this.query.resourceType}?${queryString}` : this.query.resourceType;
+  }
+
+  /** Builder returned by parameter methods */
+  protected result(): Q {
+    return this.back ?? (this as unknown as Q);
+  }
+
+  protected param<P>(Param: ParamConstructor<P, Q>, code: string): P {
+    return new Param(this.query, this.prefix + code, this.result());
+  }
+
+  protected reference<T extends string, C>(
+    code: string,
+    chains: ChainTargets<C>,
+  ): ReferenceParam<Q, T, C> {
+    return new ReferenceParam<Q, T, C>(this.query, this.prefix + code, this.result(), chains);
+  }
+}
+
+/** Base class of typed search parameters */
+export class SearchParam<Q> {
+  constructor(
+    protected readonly query: SearchQuery,
+    protected readonly code: string,
+    private readonly result: Q,
+  ) {}
+
+  /** Match resources with (`true`) or without (`false`) a value (`:missing`) */
+  missing(missing = true): Q {
+    return this.set(`${this.code}:missing`, String(missing));
+  }
+
+  protected set(name: string, value: string): Q {
+    this.query.add(name, value);
+    return this.result;
+  }
+}
+
+/** Parameter compared with a prefix such as `ge` or `lt` */
+export abstract class PrefixedParam<Q, V> extends SearchParam<Q> {
+  protected abstract format(value: V): string;
+
+  /** Compare with an explicit prefix */
+  compare(prefix: SearchPrefix, value: V): Q {
+    const formatted = this.format(value);
+    return this.set(this.code, prefix === "eq" ? formatted : `${prefix}${formatted}`);
+  }
+
+  /** Equal to */
+  eq(value: V): Q {
+    return this.compare("eq", value);
+  }
+
+  /** Not equal to */
+  ne(value: V): Q {
+    return this.compare("ne", value);
+  }
+
+  /** Greater than */
+  gt(value: V): Q {
+    return this.compare("gt", value);
+  }
+
+  /** Less than */
+  lt(value: V): Q {
+    return this.compare("lt", value);
+  }
+
+  /** Greater than or equal to */
+  ge(value: V): Q {
+    return this.compare("ge", value);
+  }
+
+  /** Less than or equal to */
+  le(value: V): Q {
+    return this.compare("le", value);
+  }
+
+  /** Starts after */
+  sa(value: V): Q {
+    return this.compare("sa", value);
+  }
+
+  /** Ends before */
+  eb(value: V): Q {
+    return this.compare("eb", value);
+  }
+
+  /** Approximately equal to */
+  ap(value: V): Q {
+    return this.compare("ap", value);
+  }
+}
+
+/** `number` search parameter */
+export class NumberParam<Q> extends PrefixedParam<Q, number | string> {
+  protected format(value: number | string): string {
+    return String(value);
+  }
+}
+
+/** `date` search parameter */
+export class DateParam<Q> extends PrefixedParam<Q, Date | string> {
+  protected format(value: Date | string): string {
+    return value instanceof Date ? value.toISOString() : value;
+  }
+}
+
+/** Value of a `quantity` search parameter */
+export type QuantityValue = number | string | { value: number | string; system?: string; code?: string };
+
+/** `quantity` search parameter */
+export class QuantityParam<Q> extends PrefixedParam<Q, QuantityValue> {
+  protected format(value: QuantityValue): string {
+    if (typeof value !== "object") {
+      return String(value);
+    }
+    return `${value.value}|${value.system ?? ""}|${value.code ?? ""}`;
+  }
+}
+
+/** `string` search parameter */
+export class StringParam<Q> extends SearchParam<Q> {
+  /** Match values starting with `value`, ignoring case and accents */
+  eq(value: string): Q {
+    return this.set(this.code, value);
+  }
+
+  /** Match the whole value exactly (`:exact`) */
+  exact(value: string): Q {
+    return this.set(`${this.code}:exact`, value);
+  }
+
+  /** Match values containing `value` (`:contains`) */
+  contains(value: string): Q {
+    return this.set(`${this.code}:contains`, value);
+  }
+}
+
+/** `token` search parameter */
+export class TokenParam<Q> extends SearchParam<Q> {
+  /** Match a code, optionally within a code system */
+  eq(code: string, system?: string): Q {
+    return this.set(this.code, system === undefined ? code : `${system}|${code}`);
+  }
+
+  /** Match any code of a code system */
+  system(system: string): Q {
+    return this.set(this.code, `${system}|`);
+  }
+
+  /** Exclude a code (`:not`) */
+  not(code: string, system?: string): Q {
+    return this.set(`${this.code}:not`, system === undefined ? code : `${system}|${code}`);
+  }
+
+  /** Match the display text of the coding (`:text`) */
+  text(text: string): Q {
+    return this.set(`${this.code}:text`, text);
+  }
+
+  /** Match codes in a value set (`:in`) */
+  in(valueSet: string): Q {
+    return this.set(`${this.code}:in`, valueSet);
+  }
+
+  /** Match codes outside a value set (`:not-in`) */
+  notIn(valueSet: string): Q {
+    return this.set(`${this.code}:not-in`, valueSet);
+  }
+}
+
+/** `uri` search parameter */
+export class UriParam<Q> extends SearchParam<Q> {
+  /** Match the URI exactly */
+  eq(uri: string): Q {
+    return this.set(this.code, uri);
+  }
+
+  /** Match URIs below `uri` in the path hierarchy (`:below`) */
+  below(uri: string): Q {
+    return this.set(`${this.code}:below`, uri);
+  }
+
+  /** Match URIs above `uri` in the path hierarchy (`:above`) */
+  above(uri: string): Q {
+    return this.set(`${this.code}:above`, uri);
+  }
+}
+
+/** `composite` search parameter, matched by its `$`-separated components */
+export class CompositeParam<Q> extends SearchParam<Q> {
+  eq(...components: string[]): Q {
+    return this.set(this.code, components.join("$"));
+  }
+}
+
+/** `special` search parameter */
+export class SpecialParam<Q> extends SearchParam<Q> {
+  eq(value: string): Q {
+    return this.set(this.code, value);
+  }
+}
+
+/** `reference` search parameter pointing at resources of type `T` */
+export class ReferenceParam<Q, T extends string, C> extends SearchParam<Q> {
+  constructor(
+    query: SearchQuery,
+    code: string,
+    result: Q,
+    private readonly chains: ChainTargets<C>,
+  ) {
+    super(query, code, result);
+  }
+
+  /** Match a reference such as `Patient/123` */
+  eq(reference: string): Q {
+    return this.set(this.code, reference);
+  }
+
+  /** Match a reference to the resource of `type` with `id` */
+  to(type: T, id: string): Q {
+    return this.set(this.code, `${type}/${id}`);
+  }
+
+  /** Match the identifier of the referenced resource (`:identifier`) */
+  identifier(value: string, system?: string): Q {
+    return this.set(`${this.code}:identifier`, system === undefined ? value : `${system}|${value}`);
+  }
+
+  /** Search on a parameter of the referenced resource, e.g. `subject:Patient.name=smith` */
+  chain<K extends keyof C & T>(type: K): C[K] {
+    return this.chains[type](`${this.code}:${type}.`);
+  }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion};
+
+    fn search_parameter(
+        code: &str,
+        param_type: SearchParamType,
+        targets: &[&str],
+    ) -> SearchParameter {
+        SearchParameter {
+            code: code.to_string(),
+            param_type,
+            description: String::new(),
+            expression: None,
+            target_types: targets.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn resource(name: &str, search_parameters: Vec<SearchParameter>) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: Some("Resource".to_string()),
+            properties: vec![],
+            constraints: vec![],
+            search_parameters,
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: String::new(),
+            is_abstract: false,
+        }
+    }
+
+    fn graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        let mut base =
+            resource("Resource", vec![search_parameter("_id", SearchParamType::Token, &[])]);
+        base.base = None;
+        base.is_abstract = true;
+        graph.add_resource("Resource".to_string(), base);
+        graph.add_resource(
+            "Patient".to_string(),
+            resource(
+                "Patient",
+                vec![
+                    search_parameter("name", SearchParamType::String, &[]),
+                    search_parameter(
+                        "general-practitioner",
+                        SearchParamType::Reference,
+                        &["Organization", "Practitioner"],
+                    ),
+                ],
+            ),
+        );
+        graph.add_resource(
+            "Observation".to_string(),
+            resource(
+                "Observation",
+                vec![search_parameter("subject", SearchParamType::Reference, &["Patient"])],
+            ),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_getter_name() {
+        assert_eq!(SearchGenerator::getter_name("general-practitioner"), "generalPractitioner");
+        assert_eq!(SearchGenerator::getter_name("_lastUpdated"), "_lastUpdated");
+        assert_eq!(SearchGenerator::getter_name("address-use"), "addressUse");
+        assert_eq!(SearchGenerator::getter_name("count"), "countParam");
+    }
+
+    #[test]
+    fn test_include_and_revinclude_targets() {
+        let graph = graph();
+
+        assert_eq!(
+            SearchGenerator::include_targets(&graph, &graph.resources["Patient"]),
+            vec![
+                "Patient:general-practitioner",
+                "Patient:general-practitioner:Organization",
+                "Patient:general-practitioner:Practitioner",
+            ]
+        );
+        assert_eq!(
+            SearchGenerator::revinclude_sources(&graph, &graph.resources["Patient"]),
+            vec!["Observation:subject"]
+        );
+        assert!(SearchGenerator::include_targets(&graph, &graph.resources["Resource"]).is_empty());
+    }
+
+    #[test]
+    fn test_chaining_is_limited_to_generated_targets() {
+        let graph = graph();
+
+        let patient =
+            SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Patient"])
+                .unwrap();
+        // Neither target of general-practitioner has a builder in this graph
+        assert!(patient.contains(
+            "get generalPractitioner(): ReferenceParam<Q, \"Organization\" | \"Practitioner\", {}> {"
+        ));
+        assert!(patient.contains("get _id(): TokenParam<Q> {"));
+
+        let observation =
+            SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Observation"])
+                .unwrap();
+        assert!(observation.contains("import { PatientSearchParams } from './Patient';"));
+        assert!(observation.contains(
+            "get subject(): ReferenceParam<Q, \"Patient\", { Patient: PatientSearchParams<Q> }> {"
+        ));
+        assert!(observation.contains(
+            "Patient: (prefix: string) => new PatientSearchParams<Q>(this.query, prefix, this.result()),"
+        ));
+    }
+}
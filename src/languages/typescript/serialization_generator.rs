@@ -0,0 +1,187 @@
+//! Polymorphic parsing and serialization for TypeScript classes
+//!
+//! Generates `src/serialization.ts` for class mode: `parseResource` dispatches
+//! FHIR JSON on `resourceType` to the matching class's static `parse`, and the
+//! runtime helpers used by the generated classes hydrate nested values and
+//! convert them back to plain JSON.
+//!
+//! ```typescript
+//! const resource = parseResource(json);
+//! if (resource instanceof Patient) {
+//!   resource.name?.[0]?.family;
+//! }
+//! JSON.stringify(resource);
+//! ```
+
+use crate::core::Result;
+use crate::core::ir::TypeGraph;
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::resource_generator::ResourceGenerator;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+
+/// Generator for the serialization module
+pub struct SerializationGenerator;
+
+impl SerializationGenerator {
+    /// Generate `src/serialization.ts`
+    pub fn generate_serialization_module(graph: &TypeGraph) -> Result<String> {
+        let names = ResourceGenerator::<TypeScriptBackend>::resource_union_members(graph);
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated serialization utilities");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.line();
+
+        // Imports
+        if !names.is_empty() {
+            for name in &names {
+                tokens.append(format!("import {{ {} }} from './resources/{}';", name, name));
+                tokens.push();
+            }
+            tokens.line();
+        }
+
+        for line in UNKNOWN_RESOURCE.lines() {
+            tokens.append(line);
+            tokens.push();
+        }
+        tokens.line();
+
+        let mut members: Vec<String> = names.clone();
+        members.push("UnknownResource".to_string());
+        tokens.append(helpers::type_alias(
+            "ParsedResource",
+            &helpers::union_type(&members),
+            Some(&["Result of {@link parseResource}".to_string()]),
+        ));
+        tokens.line();
+
+        // Parsers by resourceType; resolved lazily since the classes import this module
+        tokens.append(helpers::jsdoc_comment(&[
+            "Static `parse` of each resource class by `resourceType`".to_string(),
+        ]));
+        tokens.push();
+        let declaration = "const resourceParsers = new Map<string, (json: any) => ParsedResource>";
+        if names.is_empty() {
+            tokens.append(format!("{}();", declaration));
+        } else {
+            tokens.append(format!("{}([", declaration));
+            for name in &names {
+                tokens.push();
+                tokens.append(format!("  [\"{}\", (json) => {}.parse(json)],", name, name));
+            }
+            tokens.push();
+            tokens.append("]);");
+        }
+
+        let header = GencoTemplateEngine::format_typescript(&tokens)?;
+        Ok(format!("{}\n{}", header.trim_end(), SERIALIZATION_MODULE))
+    }
+}
+
+/// Shape of resources whose `resourceType` has no generated class
+const UNKNOWN_RESOURCE: &str = r#"/** Resource of a type without a generated class, kept as plain JSON */
+export interface UnknownResource {
+  resourceType: string;
+  [key: string]: unknown;
+}"#;
+
+/// Dispatcher and runtime helpers of the generated classes
+const SERIALIZATION_MODULE: &str = r#"
+/**
+ * Parse FHIR JSON into the class of its `resourceType`
+ *
+ * Datatypes, backbone elements and contained resources are hydrated
+ * recursively and unknown fields are kept, so `toJSON` reproduces the input
+ * in canonical order. Resources of a type without a generated class are
+ * returned as a copy of their JSON.
+ */
+export function parseResource(json: any): ParsedResource {
+  if (json === null || typeof json !== "object" || typeof json.resourceType !== "string") {
+    throw new TypeError("Expected a FHIR resource with a string resourceType");
+  }
+  const parse = resourceParsers.get(json.resourceType);
+  return parse !== undefined ? parse(json) : { ...json };
+}
+
+/** Hydrate a JSON value, or each entry of an array, keeping `null` entries */
+export function parseValue<T>(json: any, parse: (json: any) => T): any {
+  if (Array.isArray(json)) {
+    return json.map((item) => (item == null ? item : parse(item)));
+  }
+  return json == null ? json : parse(json);
+}
+
+/**
+ * Convert a value to plain FHIR JSON
+ *
+ * Class instances are written with their `toJSON`; arrays and plain objects
+ * are converted recursively, dropping `undefined` properties.
+ */
+export function toJSONValue(value: any): any {
+  if (Array.isArray(value)) {
+    return value.map(toJSONValue);
+  }
+  if (value === null || typeof value !== "object") {
+    return value;
+  }
+  if (typeof value.toJSON === "function") {
+    return value.toJSON();
+  }
+  const result: Record<string, unknown> = {};
+  for (const [key, entry] of Object.entries(value)) {
+    if (entry !== undefined) {
+      result[key] = toJSONValue(entry);
+    }
+  }
+  return result;
+}
+
+/** Fields of a JSON object missing from `known`, kept for round-tripping */
+export function unknownFields(json: any, known: readonly string[]): Record<string, unknown> {
+  const fields: Record<string, unknown> = {};
+  for (const [key, value] of Object.entries(json ?? {})) {
+    if (!known.includes(key)) {
+      fields[key] = value;
+    }
+  }
+  return fields;
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{Documentation, FhirVersion, ResourceType};
+
+    fn resource(name: &str, is_abstract: bool) -> ResourceType {
+        ResourceType {
+            name: name.to_string(),
+            base: None,
+            properties: vec![],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: String::new(),
+            is_abstract,
+        }
+    }
+
+    #[test]
+    fn test_dispatches_concrete_resources() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource("DomainResource".to_string(), resource("DomainResource", true));
+        graph.add_resource("Patient".to_string(), resource("Patient", false));
+
+        let module = SerializationGenerator::generate_serialization_module(&graph).unwrap();
+
+        assert!(module.contains("import { Patient } from './resources/Patient';"));
+        assert!(module.contains("export type ParsedResource = Patient | UnknownResource;"));
+        assert!(module.contains("  [\"Patient\", (json) => Patient.parse(json)],"));
+        assert!(!module.contains("DomainResource"));
+        assert!(module.contains("export function parseResource(json: any): ParsedResource {"));
+    }
+}
@@ -1,7 +1,7 @@
 //! TypeScript-specific template utilities
 
 use crate::core::Result;
-use crate::core::ir::{DataType, Documentation, Property, ResourceType};
+use crate::core::ir::{DataType, Documentation, Property, PropertyType, ResourceType};
 use crate::generator::LanguageBackend;
 use crate::languages::typescript::backend::TypeScriptBackend;
 use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
@@ -54,16 +54,90 @@ impl TypeScriptTemplates {
         let doc_lines = Self::format_documentation(&datatype.documentation);
 
         // Generate properties
-        let properties = Self::format_properties(&datatype.properties, backend);
+        let properties = Self::format_properties(&Self::rendered_properties(datatype), backend);
 
         // Determine base type
         let extends = datatype.base.as_deref();
 
-        tokens.append(helpers::interface(&sanitized_name, extends, &properties, Some(&doc_lines)));
+        let name = format!("{}{}", sanitized_name, Self::type_parameters(&datatype.name));
+        tokens.append(helpers::interface(&name, extends, &properties, Some(&doc_lines)));
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
 
+    /// Type parameters declared by a generated datatype
+    ///
+    /// `Reference<T>` narrows the resource types it may target; other
+    /// datatypes are not generic.
+    pub fn type_parameters(type_name: &str) -> &'static str {
+        match type_name {
+            "Reference" => "<T extends ResourceType = ResourceType>",
+            _ => "",
+        }
+    }
+
+    /// Properties of a datatype as rendered
+    ///
+    /// `Reference.reference` and `Reference.type` are typed by the `T`
+    /// parameter as `ReferenceString<T>` and `T`.
+    pub fn rendered_properties(datatype: &DataType) -> Vec<Property> {
+        let mut properties = datatype.properties.clone();
+        if datatype.name == "Reference" {
+            for property in &mut properties {
+                let type_name = match property.name.as_str() {
+                    "reference" => "ReferenceString<T>",
+                    "type" => "T",
+                    _ => continue,
+                };
+                property.property_type =
+                    PropertyType::Primitive { type_name: type_name.to_string() };
+                property.binding = None;
+            }
+        }
+        properties
+    }
+
+    /// Generate the `ResourceType` and `ReferenceString` types used by `Reference<T>`
+    ///
+    /// `ResourceType` falls back to `string` when no resource types are known.
+    pub fn reference_target_types(resource_types: &[String]) -> js::Tokens {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append(helpers::jsdoc_comment(&[
+            "Names of the resource types a Reference can target".to_string(),
+        ]));
+        tokens.push();
+        if resource_types.is_empty() {
+            tokens.append("export type ResourceType = string;");
+        } else {
+            tokens.append("export type ResourceType =");
+            for name in resource_types {
+                tokens.push();
+                tokens.append(format!("  | {}", helpers::string_literal(name)));
+            }
+            tokens.append(";");
+        }
+        tokens.line();
+
+        tokens.append(helpers::jsdoc_comment(&[
+            "Literal reference to a resource of type `T`".to_string(),
+            String::new(),
+            "Relative (`Patient/123`), contained (`#id`), `urn:` and absolute URL references."
+                .to_string(),
+        ]));
+        tokens.push();
+        tokens.append("export type ReferenceString<T extends ResourceType = ResourceType> =");
+        for form in
+            ["`${T}/${string}`", "`#${string}`", "`urn:${string}`", "`${string}://${string}`"]
+        {
+            tokens.push();
+            tokens.append(format!("  | {}", form));
+        }
+        tokens.append(";");
+
+        tokens
+    }
+
     /// Generate a file with multiple interfaces
     pub fn generate_file<B: LanguageBackend>(
         interfaces: &[String],
@@ -126,26 +200,44 @@ impl TypeScriptTemplates {
     }
 
     /// Format properties for interface generation
+    ///
+    /// Primitives are followed by their optional `_name` sibling holding the
+    /// element's `id` and extensions, and choice elements are expanded into one
+    /// optional field per type (`valueQuantity`, `valueString`), as in FHIR JSON.
     fn format_properties<B: LanguageBackend>(
         properties: &[Property],
         backend: &B,
     ) -> Vec<(String, String, bool)> {
-        properties
-            .iter()
-            .map(|prop| {
-                let name = prop.name.clone();
-                let mut type_name = backend.map_type(&prop.property_type);
+        let mut formatted = Vec::new();
+
+        for prop in &Property::json_properties(properties) {
+            let mut type_name = backend.map_property_type(prop);
+
+            // Handle arrays
+            if prop.cardinality.is_array() {
+                type_name = helpers::array_type(&type_name);
+            }
 
-                // Handle arrays
-                if prop.cardinality.is_array() {
-                    type_name = helpers::array_type(&type_name);
-                }
+            formatted.push((prop.name.clone(), type_name, prop.cardinality.is_optional()));
 
-                let optional = prop.cardinality.is_optional();
+            if let Some(element_name) = prop.primitive_element_name() {
+                formatted.push((element_name, Self::primitive_element_type(prop), true));
+            }
+        }
 
-                (name, type_name, optional)
-            })
-            .collect()
+        formatted
+    }
+
+    /// Type of a primitive's `_name` sibling
+    ///
+    /// Arrays align with the values by index, with `null` where an item has
+    /// no element data.
+    pub fn primitive_element_type(prop: &Property) -> String {
+        if prop.cardinality.is_array() {
+            "(Element | null)[]".to_string()
+        } else {
+            "Element".to_string()
+        }
     }
 
     /// Wrap text to specified width
@@ -225,6 +317,7 @@ mod tests {
         let resource = ResourceType {
             name: "Patient".to_string(),
             base: Some("DomainResource".to_string()),
+            constraints: vec![],
             properties: vec![Property {
                 name: "id".to_string(),
                 path: "Patient.id".to_string(),
@@ -268,6 +361,7 @@ mod tests {
         let datatype = DataType {
             name: "HumanName".to_string(),
             base: Some("Element".to_string()),
+            constraints: vec![],
             properties: vec![Property {
                 name: "family".to_string(),
                 path: "HumanName.family".to_string(),
@@ -0,0 +1,442 @@
+//! Generated vitest suites for TypeScript SDKs
+//!
+//! Emitted when `emit_tests` is set: one `tests/resources/<Name>.test.ts` per
+//! concrete resource plus JSON fixtures under `tests/fixtures/`. Each suite
+//! runs two kinds of fixtures through the generated parse/serialize code
+//! (class SDKs) and validators:
+//!
+//! - package examples loaded through the canonical manager ([`TypeGraph::examples`])
+//! - synthetic instances from [`Synthesizer`], ranging from required elements
+//!   only to every element populated
+//!
+//! Synthetic instances are a fixed, seeded set written at generation time;
+//! the suites do not generate inputs of their own at test time. Interface SDKs
+//! without validation have no generated code to exercise and get no suites.
+//!
+//! ```typescript
+//! function roundTrip(json: any): void {
+//!   const resource = Patient.parse(json);
+//!   expect(resource.toJSON()).toEqual(json);
+//!   expect(parseResource(json)).toBeInstanceOf(Patient);
+//! }
+//!
+//! it.each(synthetic)('round-trips $id', (json) => {
+//!   roundTrip(json);
+//! });
+//! ```
+
+use crate::core::Result;
+use crate::core::ir::{ResourceType, TypeGraph};
+use crate::core::synth::{Coverage, Synthesizer};
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::sdk_generator::ValidationStyle;
+use crate::languages::typescript::zod_generator::ZodGenerator;
+use crate::templates::genco_engine::GencoTemplateEngine;
+use genco::prelude::*;
+use serde_json::{Value, json};
+
+/// Number of synthetic fixture instances written per resource
+pub const SYNTHETIC_INSTANCES: usize = 5;
+
+/// Generator for vitest suites and their fixtures
+#[derive(Debug, Clone, Default)]
+pub struct TestGenerator {
+    use_classes: bool,
+    validation: Option<ValidationStyle>,
+}
+
+impl TestGenerator {
+    /// Create a test generator for interface-based SDKs without validation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Round-trip through the generated classes' static `parse` and `toJSON`
+    pub fn with_classes(mut self, use_classes: bool) -> Self {
+        self.use_classes = use_classes;
+        self
+    }
+
+    /// Also check fixtures against the validation generated in this style
+    pub fn with_validation(mut self, validation: Option<ValidationStyle>) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Whether the suites exercise generated code
+    ///
+    /// Interface SDKs without validation only emit types, which a test cannot
+    /// observe at runtime.
+    pub fn has_checks(&self) -> bool {
+        self.use_classes || self.validation.is_some()
+    }
+
+    /// Resources that get a test suite: every concrete resource in the graph
+    pub fn tested_resources(graph: &TypeGraph) -> Vec<&ResourceType> {
+        graph.resources.values().filter(|resource| !resource.is_abstract).collect()
+    }
+
+    /// Generate `tests/support.ts` with the fixture loader shared by all suites
+    pub fn generate_support_module() -> String {
+        SUPPORT_MODULE.to_string()
+    }
+
+    /// Serialize fixture instances as a pretty-printed JSON array
+    pub fn generate_fixture_file(instances: &[Value]) -> Result<String> {
+        Ok(format!("{}\n", serde_json::to_string_pretty(instances)?))
+    }
+
+    /// Generate `tests/resources/<Name>.test.ts`
+    ///
+    /// The package examples block is only emitted when `has_examples` is set,
+    /// since `tests/fixtures/<Name>.examples.json` exists only then.
+    pub fn generate_resource_test_file(
+        &self,
+        resource: &ResourceType,
+        has_examples: bool,
+    ) -> Result<String> {
+        let name = TypeScriptBackend::sanitize_identifier(&resource.name);
+        let mut tokens = js::Tokens::new();
+
+        tokens.append(format!("// Auto-generated tests for {}", resource.name));
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+
+        // Imports
+        tokens.append("import { describe, expect, it } from 'vitest';");
+        tokens.push();
+        tokens.append("import { loadFixtures } from '../support';");
+        tokens.push();
+        tokens
+            .append(format!("import {{ {} }} from '../../src/resources/{}';", name, resource.name));
+        tokens.push();
+        if self.use_classes {
+            tokens.append("import { parseResource } from '../../src/serialization';");
+            tokens.push();
+        }
+        match self.validation {
+            Some(ValidationStyle::Functions) => {
+                tokens.append(format!(
+                    "import {{ validate{} }} from '../../src/validators/{}';",
+                    name, resource.name
+                ));
+                tokens.push();
+            }
+            Some(ValidationStyle::Zod) => {
+                tokens.append(format!(
+                    "import {{ {} }} from '../../src/schemas/{}';",
+                    ZodGenerator::schema_name(&resource.name),
+                    resource.name
+                ));
+                tokens.push();
+            }
+            None => {}
+        }
+        tokens.line();
+
+        if has_examples {
+            tokens.append(format!(
+                "const examples = loadFixtures('{}.examples.json');",
+                resource.name
+            ));
+            tokens.push();
+        }
+        tokens
+            .append(format!("const synthetic = loadFixtures('{}.synthetic.json');", resource.name));
+        tokens.line();
+
+        self.append_check(&mut tokens, &name);
+        tokens.line();
+
+        tokens.append(format!("describe('{}', () => {{", resource.name));
+        tokens.indent();
+        tokens.push();
+        if has_examples {
+            self.append_fixture_block(&mut tokens, "package examples", "examples");
+            tokens.line();
+        }
+        self.append_fixture_block(&mut tokens, "synthetic instances", "synthetic");
+        tokens.unindent();
+        tokens.push();
+        tokens.append("});");
+        tokens.push();
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Name of the per-fixture check and the verb used in test titles
+    fn check_name(&self) -> (&'static str, &'static str) {
+        if self.use_classes { ("roundTrip", "round-trips") } else { ("check", "validates") }
+    }
+
+    /// Append the function run for every fixture: `roundTrip(json)` through the
+    /// generated classes, or `check(json)` against the validation only
+    fn append_check(&self, tokens: &mut js::Tokens, name: &str) {
+        let (function, _) = self.check_name();
+        if self.use_classes {
+            tokens.append(
+                "/** Round-trip a fixture through parsing, serialization and validation */",
+            );
+        } else {
+            tokens.append("/** Check a fixture against the generated validation */");
+        }
+        tokens.push();
+        tokens.append(format!("function {}(json: any): void {{", function));
+        tokens.indent();
+        tokens.push();
+
+        if self.use_classes {
+            tokens.append(format!("const resource = {}.parse(json);", name));
+            tokens.push();
+            tokens.append("expect(resource.toJSON()).toEqual(json);");
+            tokens.push();
+            tokens.append(format!("expect(parseResource(json)).toBeInstanceOf({});", name));
+            tokens.push();
+        }
+        let validated = if self.use_classes { "resource" } else { "json" };
+
+        match self.validation {
+            Some(ValidationStyle::Functions) => {
+                // Invariants are not checked: synthetic instances only satisfy the structure
+                tokens.append(format!("const {{ errors }} = validate{}({});", name, validated));
+                tokens.push();
+                tokens.append(
+                    "expect(errors.filter((e) => e.severity === 'error' && !e.constraint)).toEqual([]);",
+                );
+            }
+            Some(ValidationStyle::Zod) => {
+                tokens.append(format!(
+                    "expect({}.safeParse(json).success).toBe(true);",
+                    ZodGenerator::schema_name(name)
+                ));
+            }
+            None => {}
+        }
+
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}");
+        tokens.push();
+    }
+
+    /// Append a `describe` block running the fixture check over a fixture array
+    fn append_fixture_block(&self, tokens: &mut js::Tokens, title: &str, fixtures: &str) {
+        let (function, verb) = self.check_name();
+        tokens.append(format!("describe('{}', () => {{", title));
+        tokens.indent();
+        tokens.push();
+        tokens.append(format!("it.each({})('{} $id', (json) => {{", fixtures, verb));
+        tokens.indent();
+        tokens.push();
+        tokens.append(format!("{}(json);", function));
+        tokens.unindent();
+        tokens.push();
+        tokens.append("});");
+        tokens.unindent();
+        tokens.push();
+        tokens.append("});");
+        tokens.push();
+    }
+
+    /// Synthetic instances of a resource, deterministic for a given graph
+    ///
+    /// The first instance holds required elements only, the second every
+    /// element, and the rest a seeded selection of optional elements.
+    pub fn synthetic_instances(
+        graph: &TypeGraph,
+        resource: &ResourceType,
+        count: usize,
+    ) -> Vec<Value> {
+        (0..count)
+            .filter_map(|index| {
+                let coverage = match index {
+                    0 => Coverage::Required,
+                    1 => Coverage::All,
+                    _ => Coverage::Sampled,
+                };
+                let mut instance = Synthesizer::new(graph)
+                    .with_seed(index as u64)
+                    .with_coverage(coverage)
+                    .instance(&resource.name)
+                    .ok()?;
+                instance["id"] = json!(format!("synthetic-{}", index));
+                Some(instance)
+            })
+            .collect()
+    }
+}
+
+/// `tests/support.ts`
+const SUPPORT_MODULE: &str = r#"// Auto-generated test support
+// This file is auto-generated. Do not edit manually.
+import { readFileSync } from 'node:fs';
+
+/**
+ * Load a JSON array of fixtures from `tests/fixtures`
+ */
+export function loadFixtures(file: string): any[] {
+  return JSON.parse(readFileSync(new URL(`./fixtures/${file}`, import.meta.url), 'utf8'));
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{CardinalityRange, FhirVersion, Property, PropertyType, ValueSetBinding};
+
+    fn property(
+        name: &str,
+        property_type: PropertyType,
+        cardinality: CardinalityRange,
+    ) -> Property {
+        Property {
+            name: name.to_string(),
+            path: format!("Patient.{}", name),
+            property_type,
+            cardinality,
+            is_choice: false,
+            choice_types: vec![],
+            is_modifier: false,
+            is_summary: false,
+            binding: None,
+            constraints: vec![],
+            short_description: String::new(),
+            definition: String::new(),
+            comments: None,
+            examples: vec![],
+        }
+    }
+
+    fn patient() -> ResourceType {
+        let mut deceased = property(
+            "deceased[x]",
+            PropertyType::Choice { types: vec!["boolean".to_string(), "dateTime".to_string()] },
+            CardinalityRange::optional(),
+        );
+        deceased.is_choice = true;
+        deceased.choice_types = vec!["boolean".to_string(), "dateTime".to_string()];
+
+        let mut gender = property(
+            "gender",
+            PropertyType::Primitive { type_name: "code".to_string() },
+            CardinalityRange::required(),
+        );
+        gender.binding = Some(ValueSetBinding {
+            strength: crate::core::ir::BindingStrength::Required,
+            value_set: "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1".to_string(),
+            description: None,
+        });
+
+        ResourceType {
+            name: "Patient".to_string(),
+            base: Some("DomainResource".to_string()),
+            properties: vec![
+                gender,
+                property(
+                    "active",
+                    PropertyType::Primitive { type_name: "boolean".to_string() },
+                    CardinalityRange::optional(),
+                ),
+                deceased,
+                property(
+                    "generalPractitioner",
+                    PropertyType::Reference {
+                        target_types: vec!["Practitioner".to_string()],
+                        target_profiles: vec![],
+                    },
+                    CardinalityRange::optional_array(),
+                ),
+            ],
+            constraints: vec![],
+            search_parameters: vec![],
+            is_abstract: false,
+            url: "http://hl7.org/fhir/StructureDefinition/Patient".to_string(),
+            documentation: Default::default(),
+            extensions: vec![],
+        }
+    }
+
+    fn graph() -> TypeGraph {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        graph.add_resource("Patient".to_string(), patient());
+        graph.add_value_set(
+            "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+            crate::core::ir::ValueSetExpansion {
+                url: "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+                name: "AdministrativeGender".to_string(),
+                title: None,
+                concepts: vec![crate::core::ir::CodeConcept {
+                    system: "http://hl7.org/fhir/administrative-gender".to_string(),
+                    code: "male".to_string(),
+                    display: None,
+                }],
+            },
+        );
+        graph
+    }
+
+    #[test]
+    fn test_synthetic_instances_respect_cardinality() {
+        let graph = graph();
+        let instances = TestGenerator::synthetic_instances(&graph, &patient(), 3);
+
+        assert_eq!(instances.len(), 3);
+        assert_eq!(
+            instances[0],
+            json!({ "resourceType": "Patient", "id": "synthetic-0", "gender": "male" })
+        );
+
+        let complete = &instances[1];
+        assert!(complete["active"].is_boolean());
+        let deceased = ["deceasedBoolean", "deceasedDateTime"];
+        assert_eq!(deceased.iter().filter(|key| complete.get(**key).is_some()).count(), 1);
+        // Practitioner is not in the graph, so the reference carries a display only
+        let practitioner = &complete["generalPractitioner"][0];
+        assert!(practitioner["display"].is_string() && practitioner.get("reference").is_none());
+
+        // Deterministic across runs
+        assert_eq!(instances, TestGenerator::synthetic_instances(&graph, &patient(), 3));
+    }
+
+    #[test]
+    fn test_generate_resource_test_file_with_classes() {
+        let code = TestGenerator::new()
+            .with_classes(true)
+            .with_validation(Some(ValidationStyle::Functions))
+            .generate_resource_test_file(&patient(), true)
+            .unwrap();
+
+        assert!(code.contains("import { Patient } from '../../src/resources/Patient';"));
+        assert!(code.contains("import { validatePatient } from '../../src/validators/Patient';"));
+        assert!(code.contains("const examples = loadFixtures('Patient.examples.json');"));
+        assert!(code.contains("expect(resource.toJSON()).toEqual(json);"));
+        assert!(code.contains("expect(parseResource(json)).toBeInstanceOf(Patient);"));
+        assert!(code.contains("it.each(synthetic)('round-trips $id'"));
+    }
+
+    #[test]
+    fn test_generate_resource_test_file_interfaces_with_zod() {
+        let code = TestGenerator::new()
+            .with_validation(Some(ValidationStyle::Zod))
+            .generate_resource_test_file(&patient(), false)
+            .unwrap();
+
+        assert!(code.contains("function check(json: any): void {"));
+        assert!(code.contains("expect(PatientSchema.safeParse(json).success).toBe(true);"));
+        assert!(code.contains("it.each(synthetic)('validates $id', (json) => {"));
+        assert!(code.contains("check(json);"));
+        assert!(!code.contains("JSON.stringify"));
+        assert!(!code.contains("examples"));
+        assert!(!code.contains("parseResource"));
+    }
+
+    #[test]
+    fn test_has_checks() {
+        assert!(!TestGenerator::new().has_checks());
+        assert!(TestGenerator::new().with_classes(true).has_checks());
+        assert!(
+            TestGenerator::new().with_validation(Some(ValidationStyle::Functions)).has_checks()
+        );
+    }
+}
@@ -1,20 +1,35 @@
 use crate::core::Result;
-use crate::core::ir::{Property, ResourceType};
+use crate::core::ir::{
+    BindingStrength, ConstraintSeverity, DataType, InvariantRule, Property, PropertyType,
+    ResourceType, TypeGraph,
+};
+use crate::generator::LanguageBackend;
 use crate::languages::typescript::backend::TypeScriptBackend;
-use crate::templates::genco_engine::GencoTemplateEngine;
+use crate::languages::typescript::fhirpath_translator::FhirPathTranslator;
+use crate::languages::typescript::valueset_generator::ValueSetGenerator;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
 use genco::prelude::*;
 use heck::ToLowerCamelCase;
+use std::collections::{BTreeSet, HashSet};
 
 /// Generator for TypeScript validation functions
-#[allow(dead_code)]
 pub struct ValidationGenerator {
     backend: TypeScriptBackend,
+    /// Complex types that have a generated validator; nested values of these
+    /// types are validated recursively
+    validated_types: HashSet<String>,
 }
 
 impl ValidationGenerator {
     /// Create a new validation generator
     pub fn new(backend: TypeScriptBackend) -> Self {
-        Self { backend }
+        Self { backend, validated_types: HashSet::new() }
+    }
+
+    /// Validate nested values of the given complex types with their own validators
+    pub fn with_validated_types(mut self, validated_types: HashSet<String>) -> Self {
+        self.validated_types = validated_types;
+        self
     }
 
     /// Generate the ValidationResult and ValidationError type definitions
@@ -76,192 +91,677 @@ impl ValidationGenerator {
         GencoTemplateEngine::format_typescript(&tokens)
     }
 
-    /// Generate validation function for a single property
+    /// Append an `errors.push(...)` statement with severity `error`
+    ///
+    /// `path` is the content of a template literal, `message` a complete TypeScript
+    /// expression.
+    fn push_error(tokens: &mut js::Tokens, path: &str, message: &str) {
+        Self::push_issue(tokens, path, message, "error", None);
+    }
+
+    /// Append an `errors.push(...)` statement
+    fn push_issue(
+        tokens: &mut js::Tokens,
+        path: &str,
+        message: &str,
+        severity: &str,
+        constraint: Option<&str>,
+    ) {
+        tokens.append("errors.push({");
+        tokens.indent();
+        tokens.push();
+        tokens.append(format!("path: `{}`,", path));
+        tokens.push();
+        tokens.append(format!("message: {},", message));
+        tokens.push();
+        match constraint {
+            Some(key) => {
+                tokens.append(format!("severity: \"{}\",", severity));
+                tokens.push();
+                tokens.append(format!("constraint: {}", serde_json::Value::from(key)));
+            }
+            None => tokens.append(format!("severity: \"{}\"", severity)),
+        }
+        tokens.unindent();
+        tokens.push();
+        tokens.append("});");
+    }
+
+    /// Invariants that can be translated to TypeScript, with their violation condition
+    ///
+    /// `value` is the TypeScript expression of the element the invariants apply to.
+    fn translated_invariants<'a>(
+        constraints: &'a [InvariantRule],
+        value: &str,
+    ) -> Vec<(&'a InvariantRule, String)> {
+        let focus = format!("[{}]", value);
+        constraints
+            .iter()
+            .filter_map(|rule| {
+                let expression = rule.expression.as_deref()?;
+                let violation = FhirPathTranslator::translate_violation(expression, &focus).ok()?;
+                Some((rule, violation))
+            })
+            .collect()
+    }
+
+    /// Whether any of the invariants is translated to a check
+    fn has_invariants(constraints: &[InvariantRule]) -> bool {
+        !Self::translated_invariants(constraints, "value").is_empty()
+    }
+
+    /// Generate checks for the FHIRPath invariants of an element
+    fn generate_invariant_checks(
+        tokens: &mut js::Tokens,
+        constraints: &[InvariantRule],
+        value: &str,
+        path: &str,
+    ) {
+        for (rule, violation) in Self::translated_invariants(constraints, value) {
+            let severity = match rule.severity {
+                ConstraintSeverity::Error => "error",
+                ConstraintSeverity::Warning => "warning",
+            };
+            tokens.append(format!("// Invariant {}", rule.key));
+            tokens.push();
+            Self::open_block(tokens, format!("if ({}) {{", violation));
+            Self::push_issue(
+                tokens,
+                path,
+                &serde_json::Value::from(rule.human.as_str()).to_string(),
+                severity,
+                Some(&rule.key),
+            );
+            Self::close_block(tokens, "");
+        }
+    }
+
+    /// Open an `if` block
+    fn open_block(tokens: &mut js::Tokens, header: String) {
+        tokens.append(header);
+        tokens.indent();
+        tokens.push();
+    }
+
+    /// Close a block opened with [`Self::open_block`]
+    fn close_block(tokens: &mut js::Tokens, suffix: &str) {
+        tokens.unindent();
+        tokens.push();
+        tokens.append(format!("}}{}", suffix));
+        tokens.push();
+    }
+
+    /// Primitive JavaScript type (`typeof` result) expected for a property, if checkable
+    fn primitive_check(&self, property_type: &PropertyType) -> Option<String> {
+        match property_type {
+            PropertyType::Primitive { .. } => {
+                let mapped = self.backend.map_type(property_type);
+                matches!(mapped.as_str(), "boolean" | "number" | "string").then_some(mapped)
+            }
+            _ => None,
+        }
+    }
+
+    /// Generated value set type a property's code must come from (required bindings only)
+    fn required_value_set(&self, property: &Property) -> Option<&str> {
+        match self.backend.bound_value_set(property) {
+            Some((type_name, BindingStrength::Required)) => Some(type_name),
+            _ => None,
+        }
+    }
+
+    /// Collect the code tables needed for required binding checks, recursively
+    fn collect_value_set_dependencies(
+        &self,
+        properties: &[Property],
+        codes: &mut BTreeSet<String>,
+    ) {
+        for property in &Property::json_properties(properties) {
+            if let Some(value_set) = self.required_value_set(property) {
+                codes.insert(ValueSetGenerator::codes_name(value_set));
+            }
+            if let PropertyType::BackboneElement { properties } = &property.property_type {
+                self.collect_value_set_dependencies(properties, codes);
+            }
+        }
+    }
+
+    /// Name of the generated validator for a nested complex type, if there is one
+    fn nested_validator(&self, property_type: &PropertyType) -> Option<&str> {
+        let type_name = match property_type {
+            PropertyType::Complex { type_name } => type_name.as_str(),
+            PropertyType::Reference { .. } => "Reference",
+            _ => return None,
+        };
+        self.validated_types.get(type_name).map(String::as_str)
+    }
+
+    /// Whether a single element of a property gets any validation
+    fn has_element_checks(&self, property: &Property) -> bool {
+        if Self::has_invariants(&property.constraints) {
+            return true;
+        }
+
+        match &property.property_type {
+            PropertyType::BackboneElement { properties } => !properties.is_empty(),
+            property_type => {
+                self.primitive_check(property_type).is_some()
+                    || self.nested_validator(property_type).is_some()
+            }
+        }
+    }
+
+    /// Generate validation of a single (non-null) element of a property
+    ///
+    /// `value` is the TypeScript expression holding the element, `path` the
+    /// template literal content of its FHIRPath-style location.
+    fn generate_element_validation(
+        &self,
+        tokens: &mut js::Tokens,
+        property: &Property,
+        value: &str,
+        path: &str,
+        depth: usize,
+    ) {
+        Self::generate_invariant_checks(tokens, &property.constraints, value, path);
+
+        match &property.property_type {
+            PropertyType::BackboneElement { properties } => {
+                for nested in properties {
+                    self.generate_property_validation(tokens, nested, value, path, depth + 1);
+                }
+            }
+            property_type => {
+                if let Some(expected) = self.primitive_check(property_type) {
+                    Self::open_block(
+                        tokens,
+                        format!("if (typeof {} !== \"{}\") {{", value, expected),
+                    );
+                    Self::push_error(
+                        tokens,
+                        path,
+                        &format!("\"Field '{}' must be of type {}\"", property.name, expected),
+                    );
+                    Self::close_block(tokens, "");
+
+                    if let Some(value_set) = self.required_value_set(property) {
+                        Self::open_block(
+                            tokens,
+                            format!(
+                                "if (typeof {} === \"string\" && !{}.some((c) => c.code === {})) {{",
+                                value,
+                                ValueSetGenerator::codes_name(value_set),
+                                value
+                            ),
+                        );
+                        Self::push_error(
+                            tokens,
+                            path,
+                            &format!(
+                                "`Field '{}' has code ${{{}}} which is not in {}`",
+                                property.name, value, value_set
+                            ),
+                        );
+                        Self::close_block(tokens, "");
+                    }
+                } else if let Some(type_name) = self.nested_validator(property_type) {
+                    tokens.append(format!(
+                        "errors.push(...validate{}({}, `{}`).errors);",
+                        TypeScriptBackend::sanitize_identifier(type_name),
+                        value,
+                        path
+                    ));
+                    tokens.push();
+                }
+            }
+        }
+    }
+
+    /// Generate validation for a single property
+    ///
+    /// `owner` is the TypeScript expression of the object holding the property and
+    /// `path` the template literal content of the owner's location, e.g. `${path}`.
     fn generate_property_validation(
         &self,
         tokens: &mut js::Tokens,
         property: &Property,
-        resource_name: &str,
+        owner: &str,
+        path: &str,
+        depth: usize,
     ) {
+        let variants = property.choice_variants();
+        if !variants.is_empty() {
+            self.generate_choice_validation(tokens, property, &variants, owner, path, depth);
+            return;
+        }
+
         let field_name = &property.name;
-        let path = format!("{}.{}", resource_name, field_name);
+        let value = format!("{}.{}", owner, field_name);
+        let field_path = format!("{}.{}", path, field_name);
 
         // Check required fields
         if property.cardinality.min > 0 {
             tokens.append(format!("// Check required field: {}", field_name));
             tokens.push();
-            tokens.append(format!("if (!resource.{}) {{", field_name));
-            tokens.indent();
-            tokens.push();
-            tokens.append("errors.push({");
-            tokens.indent();
-            tokens.push();
-            tokens.append(format!("path: \"{}\",", path));
-            tokens.push();
-            tokens.append(format!("message: \"Required field '{}' is missing\",", field_name));
-            tokens.push();
-            tokens.append("severity: \"error\"");
-            tokens.unindent();
-            tokens.push();
-            tokens.append("});");
-            tokens.unindent();
-            tokens.push();
-            tokens.append("}");
-            tokens.push();
-            tokens.push();
+            Self::open_block(tokens, format!("if ({} == null) {{", value));
+            Self::push_error(
+                tokens,
+                &field_path,
+                &format!("\"Required field '{}' is missing\"", field_name),
+            );
+            Self::close_block(tokens, "");
         }
 
-        // Check array cardinality
-        if property.cardinality.max.is_some() && property.cardinality.is_array() {
-            tokens.append(format!("// Check array cardinality for: {}", field_name));
-            tokens.push();
-            tokens.append(format!(
-                "if (resource.{} && Array.isArray(resource.{})) {{",
-                field_name, field_name
-            ));
-            tokens.indent();
-            tokens.push();
+        let element_checks = self.has_element_checks(property);
 
-            if let Some(max) = property.cardinality.max
-                && max != u32::MAX
-            {
-                tokens.append(format!("if (resource.{}.length > {}) {{", field_name, max));
-                tokens.indent();
-                tokens.push();
-                tokens.append("errors.push({");
-                tokens.indent();
-                tokens.push();
-                tokens.append(format!("path: \"{}\",", path));
-                tokens.push();
-                tokens.append(format!(
-                    "message: \"Array '{}' exceeds maximum length of {}\",",
-                    field_name, max
-                ));
-                tokens.push();
-                tokens.append("severity: \"error\"");
-                tokens.unindent();
-                tokens.push();
-                tokens.append("});");
-                tokens.unindent();
-                tokens.push();
-                tokens.append("}");
-                tokens.push();
+        if property.cardinality.is_array() {
+            let max = property.cardinality.max.filter(|max| *max != u32::MAX);
+            if max.is_none() && property.cardinality.min == 0 && !element_checks {
+                return;
+            }
+
+            tokens.append(format!("// Check array elements for: {}", field_name));
+            tokens.push();
+            Self::open_block(tokens, format!("if (Array.isArray({})) {{", value));
+
+            if let Some(max) = max {
+                Self::open_block(tokens, format!("if ({}.length > {}) {{", value, max));
+                Self::push_error(
+                    tokens,
+                    &field_path,
+                    &format!("\"Array '{}' exceeds maximum length of {}\"", field_name, max),
+                );
+                Self::close_block(tokens, "");
             }
 
             if property.cardinality.min > 0 {
+                Self::open_block(
+                    tokens,
+                    format!("if ({}.length < {}) {{", value, property.cardinality.min),
+                );
+                Self::push_error(
+                    tokens,
+                    &field_path,
+                    &format!(
+                        "\"Array '{}' requires at least {} element(s)\"",
+                        field_name, property.cardinality.min
+                    ),
+                );
+                Self::close_block(tokens, "");
+            }
+
+            if element_checks {
+                let item = format!("item{}", depth);
+                let index = format!("i{}", depth);
+                Self::open_block(
+                    tokens,
+                    format!(
+                        "{}.forEach(({}{}, {}) => {{",
+                        value,
+                        item,
+                        Self::binding_annotation(property),
+                        index
+                    ),
+                );
+                let item_path = format!("{}[${{{}}}]", field_path, index);
+                self.generate_element_validation(tokens, property, &item, &item_path, depth);
+                Self::close_block(tokens, ");");
+            }
+
+            Self::close_block(tokens, "");
+        } else if element_checks {
+            tokens.append(format!("// Check field: {}", field_name));
+            tokens.push();
+            Self::open_block(tokens, format!("if ({} != null) {{", value));
+            if matches!(property.property_type, PropertyType::BackboneElement { .. }) {
+                let item = format!("item{}", depth);
                 tokens.append(format!(
-                    "if (resource.{}.length < {}) {{",
-                    field_name, property.cardinality.min
-                ));
-                tokens.indent();
-                tokens.push();
-                tokens.append("errors.push({");
-                tokens.indent();
-                tokens.push();
-                tokens.append(format!("path: \"{}\",", path));
-                tokens.push();
-                tokens.append(format!(
-                    "message: \"Array '{}' requires at least {} element(s)\",",
-                    field_name, property.cardinality.min
+                    "const {}{} = {};",
+                    item,
+                    Self::binding_annotation(property),
+                    value
                 ));
                 tokens.push();
-                tokens.append("severity: \"error\"");
-                tokens.unindent();
-                tokens.push();
-                tokens.append("});");
-                tokens.unindent();
-                tokens.push();
-                tokens.append("}");
-                tokens.push();
+                self.generate_element_validation(tokens, property, &item, &field_path, depth);
+            } else {
+                self.generate_element_validation(tokens, property, &value, &field_path, depth);
             }
+            Self::close_block(tokens, "");
+        }
+    }
 
-            tokens.unindent();
-            tokens.push();
-            tokens.append("}");
-            tokens.push();
-            tokens.push();
+    /// Generate validation for a choice element and each of its variants
+    ///
+    /// A required choice needs one variant set, and no more than one may be set.
+    fn generate_choice_validation(
+        &self,
+        tokens: &mut js::Tokens,
+        property: &Property,
+        variants: &[Property],
+        owner: &str,
+        path: &str,
+        depth: usize,
+    ) {
+        let choice_name = format!("{}[x]", property.name.trim_end_matches("[x]"));
+        let values: Vec<String> =
+            variants.iter().map(|variant| format!("{}.{}", owner, variant.name)).collect();
+        let set_count = format!("[{}].filter((v) => v != null).length", values.join(", "));
+        let field_path = format!("{}.{}", path, choice_name);
+
+        tokens.append(format!("// Check choice: {}", choice_name));
+        tokens.push();
+        if property.cardinality.min > 0 {
+            Self::open_block(tokens, format!("if ({} === 0) {{", set_count));
+            Self::push_error(
+                tokens,
+                &field_path,
+                &format!("\"Required field '{}' is missing\"", choice_name),
+            );
+            Self::close_block(tokens, "");
+        }
+        Self::open_block(tokens, format!("if ({} > 1) {{", set_count));
+        Self::push_error(
+            tokens,
+            &field_path,
+            &format!("\"Only one variant of '{}' may be set\"", choice_name),
+        );
+        Self::close_block(tokens, "");
+
+        for variant in variants {
+            self.generate_property_validation(tokens, variant, owner, path, depth);
         }
     }
 
-    /// Generate validation function for a resource
-    pub fn generate_resource_validation(&self, resource: &ResourceType) -> Result<String> {
-        let mut tokens = js::Tokens::new();
+    /// Type annotation for a variable bound to an element of a property
+    ///
+    /// Backbone elements are typed as the generic `BackboneElement`, which does not
+    /// declare their nested fields.
+    fn binding_annotation(property: &Property) -> &'static str {
+        match property.property_type {
+            PropertyType::BackboneElement { .. } => ": any",
+            _ => "",
+        }
+    }
 
-        let resource_name = &resource.name;
-        let function_name = format!("validate{}", resource_name);
+    /// Append the JSDoc and opening of a validator function
+    fn open_validator(tokens: &mut js::Tokens, type_name: &str, kind: &str, param: &str) {
+        let sanitized = TypeScriptBackend::sanitize_identifier(type_name);
 
-        // JSDoc
         tokens.append("/**");
         tokens.push();
-        tokens.append(format!(" * Validate a {} resource", resource_name));
+        tokens.append(format!(" * Validate a {} {}", type_name, kind));
         tokens.push();
         tokens.append(" * ");
         tokens.push();
-        tokens.append(format!(" * @param resource - The {} to validate", resource_name));
+        tokens.append(format!(" * @param {} - The {} to validate", param, type_name));
+        tokens.push();
+        tokens.append(" * @param path - FHIRPath-style location used in error paths");
         tokens.push();
         tokens.append(" * @returns Validation result with any errors");
         tokens.push();
         tokens.append(" */");
         tokens.push();
 
-        // Function declaration
         tokens.append(format!(
-            "export function {}(resource: {}): ValidationResult {{",
-            function_name, resource_name
+            "export function validate{}({}: {}, path = \"{}\"): ValidationResult {{",
+            sanitized, param, sanitized, type_name
         ));
         tokens.indent();
         tokens.push();
 
-        // Initialize errors array
         tokens.append("const errors: ValidationError[] = [];");
         tokens.push();
         tokens.push();
+    }
 
-        // Validate resourceType
-        tokens.append("// Validate resourceType");
-        tokens.push();
-        tokens.append(format!("if (resource.resourceType !== \"{}\") {{", resource_name));
-        tokens.indent();
-        tokens.push();
-        tokens.append("errors.push({");
+    /// Append the result and closing of a validator function
+    fn close_validator(tokens: &mut js::Tokens) {
+        tokens.append("return {");
         tokens.indent();
         tokens.push();
-        tokens.append(format!("path: \"{}.resourceType\",", resource_name));
-        tokens.push();
-        tokens.append(format!(
-            "message: `Invalid resourceType: ${{resource.resourceType}}. Expected '{}'`,",
-            resource_name
-        ));
+        tokens.append("valid: errors.filter(e => e.severity === \"error\").length === 0,");
         tokens.push();
-        tokens.append("severity: \"error\"");
+        tokens.append("errors");
         tokens.unindent();
         tokens.push();
-        tokens.append("});");
+        tokens.append("};");
+
         tokens.unindent();
         tokens.push();
         tokens.append("}");
+    }
+
+    /// Generate validation function for a resource
+    pub fn generate_resource_validation(&self, resource: &ResourceType) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+        let resource_name = &resource.name;
+
+        Self::open_validator(&mut tokens, resource_name, "resource", "resource");
+
+        // Validate resourceType
+        tokens.append("// Validate resourceType");
         tokens.push();
+        Self::open_block(
+            &mut tokens,
+            format!("if (resource.resourceType !== \"{}\") {{", resource_name),
+        );
+        Self::push_error(
+            &mut tokens,
+            "${path}.resourceType",
+            &format!(
+                "`Invalid resourceType: ${{resource.resourceType}}. Expected '{}'`",
+                resource_name
+            ),
+        );
+        Self::close_block(&mut tokens, "");
         tokens.push();
 
+        Self::generate_invariant_checks(&mut tokens, &resource.constraints, "resource", "${path}");
+
         // Validate each property
         for property in &resource.properties {
-            self.generate_property_validation(&mut tokens, property, resource_name);
+            self.generate_property_validation(&mut tokens, property, "resource", "${path}", 0);
         }
 
-        // Return result
-        tokens.append("return {");
-        tokens.indent();
+        Self::close_validator(&mut tokens);
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate validation function for a complex datatype
+    pub fn generate_datatype_validation(&self, datatype: &DataType) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        Self::open_validator(&mut tokens, &datatype.name, "value", "value");
+
+        Self::generate_invariant_checks(&mut tokens, &datatype.constraints, "value", "${path}");
+
+        for property in &datatype.properties {
+            self.generate_property_validation(&mut tokens, property, "value", "${path}", 0);
+        }
+
+        Self::close_validator(&mut tokens);
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Collect the nested validators called for a set of properties
+    fn collect_validator_dependencies(
+        &self,
+        properties: &[Property],
+        dependencies: &mut BTreeSet<String>,
+    ) {
+        for property in &Property::json_properties(properties) {
+            match &property.property_type {
+                PropertyType::BackboneElement { properties } => {
+                    self.collect_validator_dependencies(properties, dependencies);
+                }
+                property_type => {
+                    if self.primitive_check(property_type).is_none()
+                        && let Some(type_name) = self.nested_validator(property_type)
+                    {
+                        dependencies.insert(type_name.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Append the imports of an SDK validator file
+    fn push_validator_imports(
+        &self,
+        tokens: &mut js::Tokens,
+        type_name: &str,
+        type_module: String,
+        constraints: &[InvariantRule],
+        properties: &[Property],
+    ) {
+        let mut dependencies = BTreeSet::new();
+        self.collect_validator_dependencies(properties, &mut dependencies);
+        dependencies.remove(type_name);
+
+        let mut modules = vec![
+            (vec![TypeScriptBackend::sanitize_identifier(type_name)], type_module),
+            (
+                vec!["ValidationError".to_string(), "ValidationResult".to_string()],
+                "../validation".to_string(),
+            ),
+        ];
+        for dependency in dependencies {
+            modules.push((
+                vec![format!("validate{}", TypeScriptBackend::sanitize_identifier(&dependency))],
+                format!("./{}", dependency),
+            ));
+        }
+
+        let mut codes = BTreeSet::new();
+        self.collect_value_set_dependencies(properties, &mut codes);
+        modules.push((codes.into_iter().collect(), "../valuesets".to_string()));
+
+        tokens.append(helpers::imports(&modules));
+        if Self::has_invariants(constraints) || Self::properties_have_invariants(properties) {
+            tokens.append("import * as fhirpath from '../fhirpath';");
+            tokens.push();
+        }
         tokens.push();
-        tokens.append("valid: errors.filter(e => e.severity === \"error\").length === 0,");
+    }
+
+    /// Whether any property, including nested backbone properties, has a translated invariant
+    fn properties_have_invariants(properties: &[Property]) -> bool {
+        properties.iter().any(|property| {
+            Self::has_invariants(&property.constraints)
+                || matches!(&property.property_type, PropertyType::BackboneElement { properties }
+                    if Self::properties_have_invariants(properties))
+        })
+    }
+
+    /// Collect the invariants that could not be translated, with the reason
+    fn collect_untranslated(
+        location: &str,
+        constraints: &[InvariantRule],
+        properties: &[Property],
+        warnings: &mut Vec<String>,
+    ) {
+        for rule in constraints {
+            let Some(expression) = rule.expression.as_deref() else { continue };
+            if let Err(e) = FhirPathTranslator::translate(expression, "[value]") {
+                warnings.push(format!(
+                    "Invariant {} on {} was not translated to TypeScript ({}): {}",
+                    rule.key, location, expression, e
+                ));
+            }
+        }
+
+        for property in properties {
+            let location = if property.path.is_empty() {
+                format!("{}.{}", location, property.name)
+            } else {
+                property.path.clone()
+            };
+            let nested = match &property.property_type {
+                PropertyType::BackboneElement { properties } => properties.as_slice(),
+                _ => &[],
+            };
+            Self::collect_untranslated(&location, &property.constraints, nested, warnings);
+        }
+    }
+
+    /// Describe every invariant in the graph that has no generated check
+    ///
+    /// Covers the types validators are generated for: concrete resources and all
+    /// datatypes, each with the invariants inherited from its base types.
+    pub fn untranslated_invariants(&self, type_graph: &TypeGraph) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let inherited = |name: &str| -> Vec<InvariantRule> {
+            type_graph.constraints_with_base(name).into_iter().cloned().collect()
+        };
+
+        for datatype in type_graph.datatypes.values() {
+            Self::collect_untranslated(
+                &datatype.name,
+                &inherited(&datatype.name),
+                &datatype.properties,
+                &mut warnings,
+            );
+        }
+        for resource in type_graph.resources.values().filter(|r| !r.is_abstract) {
+            Self::collect_untranslated(
+                &resource.name,
+                &inherited(&resource.name),
+                &resource.properties,
+                &mut warnings,
+            );
+        }
+
+        warnings
+    }
+
+    /// Generate an SDK validator file (`src/validators/<Resource>.ts`) for a resource
+    pub fn generate_resource_validator_file(&self, resource: &ResourceType) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        self.push_validator_imports(
+            &mut tokens,
+            &resource.name,
+            format!("../resources/{}", resource.name),
+            &resource.constraints,
+            &resource.properties,
+        );
+        tokens.append(self.generate_resource_validation(resource)?);
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate an SDK validator file (`src/validators/<DataType>.ts`) for a datatype
+    pub fn generate_datatype_validator_file(&self, datatype: &DataType) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        self.push_validator_imports(
+            &mut tokens,
+            &datatype.name,
+            format!("../types/{}", datatype.name),
+            &datatype.constraints,
+            &datatype.properties,
+        );
+        tokens.append(self.generate_datatype_validation(datatype)?);
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate `src/validators/index.ts` re-exporting the given validator modules
+    pub fn generate_validators_index(&self, type_names: &[String]) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated validator index");
         tokens.push();
-        tokens.append("errors");
-        tokens.unindent();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
         tokens.push();
-        tokens.append("};");
-
-        tokens.unindent();
         tokens.push();
-        tokens.append("}");
+
+        for type_name in type_names {
+            tokens.append(format!("export * from './{}';", type_name));
+            tokens.push();
+        }
 
         GencoTemplateEngine::format_typescript(&tokens)
     }
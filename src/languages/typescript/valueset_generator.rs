@@ -0,0 +1,193 @@
+//! TypeScript code types from expanded ValueSets
+
+use crate::core::Result;
+use crate::core::ir::{TypeGraph, ValueSetExpansion};
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Generator for value set string-literal unions and code tables
+pub struct ValueSetGenerator;
+
+impl ValueSetGenerator {
+    /// Assign a TypeScript type name to every non-empty value set in the graph
+    ///
+    /// Names come from the ValueSet `name`; a name already used by a resource,
+    /// datatype or another value set gets a `ValueSet` suffix (and a counter if
+    /// still ambiguous). Returns versionless URL -> type name.
+    pub fn type_names(graph: &TypeGraph) -> HashMap<String, String> {
+        let mut taken: HashSet<String> =
+            graph.resources.keys().chain(graph.datatypes.keys()).cloned().collect();
+        let mut names = HashMap::new();
+
+        for (url, value_set) in &graph.value_sets {
+            if value_set.concepts.is_empty() {
+                continue;
+            }
+
+            let base = TypeScriptBackend::sanitize_identifier(&value_set.name);
+            let mut name = base.clone();
+            let mut counter = 1;
+            while taken.contains(&name) || taken.contains(&Self::codes_name(&name)) {
+                name = if counter == 1 {
+                    format!("{}ValueSet", base)
+                } else {
+                    format!("{}ValueSet{}", base, counter)
+                };
+                counter += 1;
+            }
+
+            taken.insert(name.clone());
+            taken.insert(Self::codes_name(&name));
+            names.insert(url.clone(), name);
+        }
+
+        names
+    }
+
+    /// Name of the exported code table for a value set type
+    pub fn codes_name(type_name: &str) -> String {
+        format!("{}Codes", type_name)
+    }
+
+    /// Generate the module for a single value set
+    ///
+    /// Creates code like:
+    /// ```typescript
+    /// export type AdministrativeGender = "male" | "female" | "other" | "unknown";
+    ///
+    /// export const AdministrativeGenderCodes = [
+    ///   { code: "male", display: "Male", system: "http://hl7.org/fhir/administrative-gender" },
+    /// ] as const;
+    /// ```
+    pub fn generate_value_set_file(
+        value_set: &ValueSetExpansion,
+        type_name: &str,
+    ) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        let mut doc = vec![value_set.title.clone().unwrap_or_else(|| value_set.name.clone())];
+        doc.push(String::new());
+        doc.push(format!("@see {}", value_set.url));
+
+        // Codes can appear in several systems; the union only needs each once
+        let mut seen = HashSet::new();
+        let literals: Vec<String> = value_set
+            .concepts
+            .iter()
+            .filter(|concept| seen.insert(concept.code.as_str()))
+            .map(|concept| Self::quote(&concept.code))
+            .collect();
+
+        tokens.append(helpers::type_alias(type_name, &helpers::union_type(&literals), Some(&doc)));
+        tokens.push();
+        tokens.push();
+
+        tokens.append(helpers::jsdoc_comment(&[format!(
+            "Codes in {} with their display text",
+            type_name
+        )]));
+        tokens.push();
+        tokens.append(format!("export const {} = [", Self::codes_name(type_name)));
+        tokens.indent();
+        for concept in &value_set.concepts {
+            tokens.push();
+            let display = concept.display.as_deref().unwrap_or(&concept.code);
+            tokens.append(format!(
+                "{{ code: {}, display: {}, system: {} }},",
+                Self::quote(&concept.code),
+                Self::quote(display),
+                Self::quote(&concept.system)
+            ));
+        }
+        tokens.unindent();
+        tokens.push();
+        tokens.append("] as const;");
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate an index file that exports all value set modules
+    pub fn generate_value_sets_index(type_names: &[String]) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated value set index");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+        tokens.push();
+
+        for type_name in type_names {
+            tokens.append(format!("export * from './{}';", type_name));
+            tokens.push();
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Quote a string as a TypeScript string literal
+    fn quote(value: &str) -> String {
+        serde_json::Value::from(value).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ir::{CodeConcept, FhirVersion};
+
+    fn gender() -> ValueSetExpansion {
+        let concept = |code: &str, display: &str| CodeConcept {
+            system: "http://hl7.org/fhir/administrative-gender".to_string(),
+            code: code.to_string(),
+            display: Some(display.to_string()),
+        };
+        ValueSetExpansion {
+            url: "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+            name: "AdministrativeGender".to_string(),
+            title: None,
+            concepts: vec![concept("male", "Male"), concept("female", "Female")],
+        }
+    }
+
+    #[test]
+    fn test_generate_value_set_file() {
+        let output =
+            ValueSetGenerator::generate_value_set_file(&gender(), "AdministrativeGender").unwrap();
+
+        assert!(output.contains("export type AdministrativeGender = \"male\" | \"female\";"));
+        assert!(output.contains("export const AdministrativeGenderCodes = ["));
+        assert!(output.contains("{ code: \"female\", display: \"Female\", system: "));
+        assert!(output.contains("] as const;"));
+    }
+
+    #[test]
+    fn test_type_names_avoid_collisions() {
+        let mut graph = TypeGraph::new(FhirVersion::R4);
+        let mut value_set = gender();
+        value_set.name = "Money".to_string();
+        graph.add_value_set(value_set.url.clone(), value_set);
+        graph.add_datatype(
+            "Money".to_string(),
+            crate::core::ir::DataType {
+                name: "Money".to_string(),
+                base: None,
+                properties: vec![],
+                constraints: vec![],
+                documentation: Default::default(),
+                url: String::new(),
+                is_abstract: false,
+            },
+        );
+        let mut empty = gender();
+        empty.url = "http://example.org/ValueSet/empty".to_string();
+        empty.concepts.clear();
+        graph.add_value_set(empty.url.clone(), empty);
+
+        let names = ValueSetGenerator::type_names(&graph);
+
+        assert_eq!(names.len(), 1);
+        assert_eq!(names["http://hl7.org/fhir/ValueSet/administrative-gender"], "MoneyValueSet");
+    }
+}
@@ -0,0 +1,451 @@
+//! Zod schema generation for TypeScript
+//!
+//! Alternative to the [`ValidationGenerator`](super::ValidationGenerator) output:
+//! each datatype and concrete resource gets a Zod schema that is checked against
+//! its generated interface at compile time, so the two cannot drift apart.
+
+use crate::core::Result;
+use crate::core::ir::{
+    BindingStrength, CardinalityRange, DataType, Property, PropertyType, ResourceType, TypeGraph,
+};
+use crate::generator::LanguageBackend;
+use crate::languages::typescript::backend::TypeScriptBackend;
+use crate::languages::typescript::valueset_generator::ValueSetGenerator;
+use crate::templates::genco_engine::{GencoTemplateEngine, helpers};
+use genco::prelude::*;
+use std::collections::{BTreeSet, HashSet};
+
+/// Generator for Zod schemas mirroring the generated TypeScript types
+pub struct ZodGenerator {
+    backend: TypeScriptBackend,
+    /// Types that have a generated schema (datatypes and concrete resources)
+    schema_types: HashSet<String>,
+}
+
+/// Schema of a single element of a property
+enum ElementSchema {
+    /// Single-line Zod expression
+    Inline(String),
+    /// Inline object for backbone elements
+    Object(Vec<Property>),
+}
+
+/// Imports collected while generating a schema module
+#[derive(Default)]
+struct SchemaImports {
+    /// Other schemas, imported from sibling modules
+    schemas: BTreeSet<String>,
+    /// Value set code tables
+    codes: BTreeSet<String>,
+    /// Value set code types, imported type-only
+    value_set_types: BTreeSet<String>,
+    /// Datatypes used in type assertions, imported type-only
+    types: BTreeSet<String>,
+    /// Extra type-only imports from the module of the schema's own type
+    own_types: BTreeSet<String>,
+}
+
+impl ZodGenerator {
+    /// Create a new Zod schema generator
+    pub fn new(backend: TypeScriptBackend) -> Self {
+        Self { backend, schema_types: HashSet::new() }
+    }
+
+    /// Set the types that have a schema
+    ///
+    /// Complex and reference properties of these types are validated with a lazy
+    /// reference to their schema; other complex types are accepted as-is.
+    pub fn with_schema_types(mut self, schema_types: HashSet<String>) -> Self {
+        self.schema_types = schema_types;
+        self
+    }
+
+    /// Types that get a schema: all datatypes and concrete resources
+    pub fn schema_types_for(graph: &TypeGraph) -> HashSet<String> {
+        graph
+            .datatypes
+            .keys()
+            .chain(graph.resources.iter().filter(|(_, r)| !r.is_abstract).map(|(name, _)| name))
+            .cloned()
+            .collect()
+    }
+
+    /// Name of the schema constant for a type
+    pub fn schema_name(type_name: &str) -> String {
+        format!("{}Schema", TypeScriptBackend::sanitize_identifier(type_name))
+    }
+
+    /// Schema for a complex type name, lazily referenced to allow recursive types
+    fn type_reference(&self, type_name: &str, imports: &mut SchemaImports) -> Option<String> {
+        if !self.schema_types.contains(type_name) {
+            return None;
+        }
+        imports.schemas.insert(type_name.to_string());
+        Some(format!("z.lazy(() => {})", Self::schema_name(type_name)))
+    }
+
+    /// Schema for a FHIR primitive type
+    fn primitive_schema(&self, type_name: &str) -> String {
+        let mapped =
+            self.backend.map_type(&PropertyType::Primitive { type_name: type_name.to_string() });
+
+        match (type_name, mapped.as_str()) {
+            ("integer", "number") => "z.number().int()".to_string(),
+            ("positiveInt", "number") => "z.number().int().positive()".to_string(),
+            ("unsignedInt", "number") => "z.number().int().nonnegative()".to_string(),
+            // Template-literal and union types of the typed Reference
+            ("ReferenceString" | "ResourceType", _) => {
+                format!("z.custom<{}>((value) => typeof value === \"string\")", type_name)
+            }
+            (_, "number") => "z.number()".to_string(),
+            (_, "boolean") => "z.boolean()".to_string(),
+            (_, "string") => "z.string()".to_string(),
+            // Custom type mappings (e.g. dateTime -> Date) cannot be checked structurally
+            (_, other) => format!("z.custom<{}>()", other),
+        }
+    }
+
+    /// Schema of a single element of a property
+    fn element_schema(&self, property: &Property, imports: &mut SchemaImports) -> ElementSchema {
+        if let Some((type_name, BindingStrength::Required)) = self.backend.bound_value_set(property)
+        {
+            let codes = ValueSetGenerator::codes_name(type_name);
+            imports.codes.insert(codes.clone());
+            imports.value_set_types.insert(type_name.to_string());
+            return ElementSchema::Inline(format!(
+                "z.custom<{}>((value) => {}.some((c) => c.code === value))",
+                type_name, codes
+            ));
+        }
+
+        let schema = match &property.property_type {
+            PropertyType::Primitive { type_name } => self.primitive_schema(type_name),
+            PropertyType::Complex { type_name } => {
+                self.type_reference(type_name, imports).unwrap_or_else(|| "z.any()".to_string())
+            }
+            PropertyType::Reference { .. } => {
+                match self.type_reference("Reference", imports) {
+                    // The schema validates the untyped `Reference`; narrow it to the targets
+                    Some(schema) => {
+                        let mapped = self.backend.map_type(&property.property_type);
+                        if mapped == "Reference" {
+                            schema
+                        } else {
+                            imports.types.insert("Reference".to_string());
+                            format!("({} as z.ZodType<{}>)", schema, mapped)
+                        }
+                    }
+                    None => "z.any()".to_string(),
+                }
+            }
+            PropertyType::Choice { types } => {
+                let options: Vec<String> = types
+                    .iter()
+                    .map(|type_name| {
+                        self.type_reference(type_name, imports)
+                            .unwrap_or_else(|| self.primitive_schema(type_name))
+                    })
+                    .collect();
+                if options.len() == 1 {
+                    options.into_iter().next().unwrap_or_default()
+                } else {
+                    format!("z.union([{}])", options.join(", "))
+                }
+            }
+            PropertyType::BackboneElement { properties } => {
+                return ElementSchema::Object(properties.clone());
+            }
+        };
+
+        ElementSchema::Inline(schema)
+    }
+
+    /// Zod method calls applying a property's cardinality to its element schema
+    fn cardinality_suffix(cardinality: &CardinalityRange) -> String {
+        let mut suffix = String::new();
+        if cardinality.is_array() {
+            suffix.push(')');
+            if cardinality.min > 0 {
+                suffix.push_str(&format!(".min({})", cardinality.min));
+            }
+            if let Some(max) = cardinality.max {
+                suffix.push_str(&format!(".max({})", max));
+            }
+        }
+        if cardinality.is_optional() {
+            suffix.push_str(".optional()");
+        }
+        suffix
+    }
+
+    /// Append the `key: schema,` entries of an object shape
+    ///
+    /// Choice elements contribute one optional key per variant.
+    fn push_shape(
+        &self,
+        tokens: &mut js::Tokens,
+        properties: &[Property],
+        imports: &mut SchemaImports,
+    ) {
+        for property in &Property::json_properties(properties) {
+            let prefix = if property.cardinality.is_array() { "z.array(" } else { "" };
+            let suffix = Self::cardinality_suffix(&property.cardinality);
+
+            match self.element_schema(property, imports) {
+                ElementSchema::Inline(schema) => {
+                    tokens.append(format!("{}: {}{}{},", property.name, prefix, schema, suffix));
+                    tokens.push();
+                }
+                ElementSchema::Object(nested) => {
+                    tokens.append(format!("{}: {}z.object({{", property.name, prefix));
+                    tokens.indent();
+                    tokens.push();
+                    self.push_shape(tokens, &nested, imports);
+                    tokens.unindent();
+                    tokens.push();
+                    tokens.append(format!("}}).passthrough(){},", suffix));
+                    tokens.push();
+                }
+            }
+        }
+    }
+
+    /// Generate the object schema expression for a type
+    fn object_schema(
+        &self,
+        resource_type: Option<&str>,
+        properties: &[Property],
+        imports: &mut SchemaImports,
+    ) -> js::Tokens {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("z.object({");
+        tokens.indent();
+        tokens.push();
+        if let Some(resource_type) = resource_type {
+            tokens.append(format!(
+                "resourceType: z.literal({}),",
+                helpers::string_literal(resource_type)
+            ));
+            tokens.push();
+        }
+        self.push_shape(&mut tokens, properties, imports);
+        tokens.unindent();
+        tokens.push();
+        tokens.append("}).passthrough()");
+
+        tokens
+    }
+
+    /// Append the imports of a schema module
+    fn push_imports(
+        tokens: &mut js::Tokens,
+        type_name: &str,
+        type_module: String,
+        mut imports: SchemaImports,
+    ) {
+        imports.schemas.remove(type_name);
+
+        tokens.append("import { z } from 'zod';");
+        tokens.push();
+        let own_types: Vec<String> =
+            std::iter::once(TypeScriptBackend::sanitize_identifier(type_name))
+                .chain(imports.own_types.iter().cloned())
+                .collect();
+        tokens.append(format!(
+            "import type {{ {} }} from '{}';",
+            own_types.join(", "),
+            type_module
+        ));
+        tokens.push();
+        if !imports.value_set_types.is_empty() {
+            let types: Vec<String> = imports.value_set_types.into_iter().collect();
+            tokens.append(format!("import type {{ {} }} from '../valuesets';", types.join(", ")));
+            tokens.push();
+        }
+        imports.types.remove(type_name);
+        for datatype in imports.types {
+            tokens.append(format!(
+                "import type {{ {} }} from '../types/{}';",
+                TypeScriptBackend::sanitize_identifier(&datatype),
+                datatype
+            ));
+            tokens.push();
+        }
+
+        let mut modules: Vec<(Vec<String>, String)> = imports
+            .schemas
+            .into_iter()
+            .map(|schema| (vec![Self::schema_name(&schema)], format!("./{}", schema)))
+            .collect();
+        modules.push((imports.codes.into_iter().collect(), "../valuesets".to_string()));
+        tokens.append(helpers::imports(&modules));
+        tokens.push();
+    }
+
+    /// Append the inferred type export for a schema
+    fn push_inferred_type(tokens: &mut js::Tokens, type_name: &str) {
+        let schema = Self::schema_name(type_name);
+        tokens.push();
+        tokens.append(helpers::jsdoc_comment(&[format!(
+            "{} as inferred from {{@link {}}}",
+            type_name, schema
+        )]));
+        tokens.push();
+        tokens.append(format!(
+            "export type {}Inferred = z.infer<typeof {}>;",
+            TypeScriptBackend::sanitize_identifier(type_name),
+            schema
+        ));
+    }
+
+    /// Generate the schema module for a concrete resource (`src/schemas/{Name}.ts`)
+    ///
+    /// The schema is declared with `satisfies z.ZodType<Resource>`, so it stays a
+    /// `ZodObject` usable in discriminated unions while the compiler checks it
+    /// against the generated interface.
+    pub fn generate_resource_schema_file(&self, resource: &ResourceType) -> Result<String> {
+        let mut imports = SchemaImports::default();
+        let schema = self.object_schema(Some(&resource.name), &resource.properties, &mut imports);
+        let interface = TypeScriptBackend::sanitize_identifier(&resource.name);
+
+        let mut tokens = js::Tokens::new();
+        Self::push_imports(
+            &mut tokens,
+            &resource.name,
+            format!("../resources/{}", resource.name),
+            imports,
+        );
+
+        tokens.append(helpers::jsdoc_comment(&[format!(
+            "Zod schema for the {} resource",
+            resource.name
+        )]));
+        tokens.push();
+        tokens.append(format!("export const {} = ", Self::schema_name(&resource.name)));
+        tokens.append(schema);
+        tokens.append(format!(" satisfies z.ZodType<{}>;", interface));
+        tokens.push();
+        Self::push_inferred_type(&mut tokens, &resource.name);
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Properties of a datatype as validated by its schema
+    ///
+    /// `Reference.reference` and `Reference.type` are typed as `ReferenceString`
+    /// and `ResourceType`, which a plain `z.string()` would not satisfy.
+    fn schema_properties(datatype: &DataType, imports: &mut SchemaImports) -> Vec<Property> {
+        let mut properties = datatype.properties.clone();
+        if datatype.name == "Reference" {
+            for property in &mut properties {
+                let type_name = match property.name.as_str() {
+                    "reference" => "ReferenceString",
+                    "type" => "ResourceType",
+                    _ => continue,
+                };
+                imports.own_types.insert(type_name.to_string());
+                property.property_type =
+                    PropertyType::Primitive { type_name: type_name.to_string() };
+                property.binding = None;
+            }
+        }
+        properties
+    }
+
+    /// Generate the schema module for a datatype (`src/schemas/{Name}.ts`)
+    ///
+    /// Datatypes can be recursive (e.g. `Extension`, `Identifier` and `Reference`),
+    /// so their schemas carry an explicit `z.ZodType` annotation.
+    pub fn generate_datatype_schema_file(&self, datatype: &DataType) -> Result<String> {
+        let mut imports = SchemaImports::default();
+        let properties = Self::schema_properties(datatype, &mut imports);
+        let schema = self.object_schema(None, &properties, &mut imports);
+        let interface = TypeScriptBackend::sanitize_identifier(&datatype.name);
+
+        let mut tokens = js::Tokens::new();
+        Self::push_imports(
+            &mut tokens,
+            &datatype.name,
+            format!("../types/{}", datatype.name),
+            imports,
+        );
+
+        tokens.append(helpers::jsdoc_comment(&[format!(
+            "Zod schema for the {} datatype",
+            datatype.name
+        )]));
+        tokens.push();
+        tokens.append(format!(
+            "export const {}: z.ZodType<{}> = ",
+            Self::schema_name(&datatype.name),
+            interface
+        ));
+        tokens.append(schema);
+        tokens.append(";");
+        tokens.push();
+        Self::push_inferred_type(&mut tokens, &datatype.name);
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+
+    /// Generate `src/schemas/index.ts`
+    ///
+    /// Re-exports every schema module and defines `AnyResourceSchema`, a
+    /// discriminated union of all concrete resource schemas on `resourceType`.
+    pub fn generate_schemas_index(&self, graph: &TypeGraph) -> Result<String> {
+        let mut tokens = js::Tokens::new();
+
+        tokens.append("// Auto-generated schema index");
+        tokens.push();
+        tokens.append("// This file is auto-generated. Do not edit manually.");
+        tokens.push();
+
+        let resources: Vec<&String> =
+            graph.resources.iter().filter(|(_, r)| !r.is_abstract).map(|(name, _)| name).collect();
+
+        if !resources.is_empty() {
+            tokens.append("import { z } from 'zod';");
+            tokens.push();
+            let modules: Vec<(Vec<String>, String)> = resources
+                .iter()
+                .map(|name| (vec![Self::schema_name(name)], format!("./{}", name)))
+                .collect();
+            tokens.append(helpers::imports(&modules));
+        }
+        tokens.push();
+
+        for name in graph.datatypes.keys().chain(resources.iter().copied()) {
+            tokens.append(format!("export * from './{}';", name));
+            tokens.push();
+        }
+
+        if !resources.is_empty() {
+            tokens.push();
+            tokens.append(helpers::jsdoc_comment(&[
+                "Schema for any resource, selected by `resourceType`".to_string(),
+            ]));
+            tokens.push();
+            tokens.append(
+                "export const AnyResourceSchema = z.discriminatedUnion(\"resourceType\", [",
+            );
+            tokens.indent();
+            for name in &resources {
+                tokens.push();
+                tokens.append(format!("{},", Self::schema_name(name)));
+            }
+            tokens.unindent();
+            tokens.push();
+            tokens.append("]);");
+            tokens.push();
+            tokens.push();
+            tokens.append(helpers::jsdoc_comment(&[
+                "Any resource as inferred from {@link AnyResourceSchema}".to_string(),
+            ]));
+            tokens.push();
+            tokens.append("export type AnyResourceInferred = z.infer<typeof AnyResourceSchema>;");
+        }
+
+        GencoTemplateEngine::format_typescript(&tokens)
+    }
+}
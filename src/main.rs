@@ -2,9 +2,15 @@
 
 use anyhow::Result;
 use octofhir_codegen::cli::{
-    Cli, CommandResult, Commands, GenerateOptions, OutputFormatter, ensure_config_exists,
-    execute_generate,
+    Cli, CodegenConfig, CommandResult, Commands, ConfigCommands, GenerateOptions, OutputFormat,
+    OutputFormatter, Verbosity, discover_config, ensure_config_exists, execute_generate,
+    load_generator_registry, load_type_graph,
 };
+use octofhir_codegen::config::PluginsConfig;
+use octofhir_codegen::core::synth::{DEFAULT_SEED, Synthesizer};
+use octofhir_codegen::core::validate::Validator;
+use octofhir_codegen::generator::{GeneratorCapabilities, GeneratorRegistry};
+use std::io::Write;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -87,11 +93,23 @@ async fn execute_command(cli: &Cli, formatter: &OutputFormatter) -> Result<Comma
         Commands::Validate { detailed } => {
             execute_validate(cli.config_path().as_ref(), *detailed, formatter).await
         }
+        Commands::Config { action } => match action {
+            ConfigCommands::Migrate { output, dry_run, no_backup } => {
+                execute_config_migrate(
+                    cli.config_path().as_ref(),
+                    output.as_ref(),
+                    *dry_run,
+                    *no_backup,
+                    formatter,
+                )
+                .await
+            }
+        },
         Commands::ListGenerators { detailed } => {
-            execute_list_generators(*detailed, formatter).await
+            execute_list_generators(cli.config_path().as_ref(), *detailed, formatter).await
         }
         Commands::Describe { generator, examples } => {
-            execute_describe(generator, *examples, formatter).await
+            execute_describe(cli.config_path().as_ref(), generator, *examples, formatter).await
         }
         Commands::Analyze {
             package,
@@ -114,6 +132,23 @@ async fn execute_command(cli: &Cli, formatter: &OutputFormatter) -> Result<Comma
             )
             .await
         }
+        Commands::Synth { target, count, seed, bundle, output, package } => {
+            execute_synth(
+                cli.config_path().as_ref(),
+                target,
+                *count,
+                *seed,
+                *bundle,
+                output.as_ref(),
+                package,
+                formatter,
+            )
+            .await
+        }
+        Commands::ValidateResource { file, profile, package } => {
+            execute_validate_resource(cli.config_path().as_ref(), file, profile.as_deref(), package)
+                .await
+        }
         Commands::UpdatePackages { package, force } => {
             execute_update_packages(package.as_deref(), *force, formatter).await
         }
@@ -135,7 +170,6 @@ async fn execute_init(
     non_interactive: bool,
     formatter: &OutputFormatter,
 ) -> Result<CommandResult> {
-    use octofhir_codegen::cli::CodegenConfig;
     use std::fs;
     use std::io::{self, Write};
 
@@ -156,16 +190,20 @@ async fn execute_init(
         template.unwrap_or("typescript")
     } else {
         // Interactive mode: ask user for template
-        if template.is_some() {
-            template.unwrap()
+        if let Some(template) = template {
+            template
         } else {
             println!("\nSelect a template:");
             println!("  1. TypeScript (default)");
             println!("  2. Rust");
             println!("  3. Python");
             println!("  4. Java");
-            println!("  5. Multi-language (all generators)");
-            print!("\nEnter choice [1-5] (default: 1): ");
+            println!("  5. Kotlin Multiplatform");
+            println!("  6. JSON Schema");
+            println!("  7. OpenAPI");
+            println!("  8. Markdown documentation site");
+            println!("  9. Multi-language (all generators)");
+            print!("\nEnter choice [1-9] (default: 1): ");
             io::stdout().flush()?;
 
             let mut input = String::new();
@@ -177,7 +215,11 @@ async fn execute_init(
                 "2" => "rust",
                 "3" => "python",
                 "4" => "java",
-                "5" => "multi",
+                "5" => "kotlin",
+                "6" => "json-schema",
+                "7" => "openapi",
+                "8" => "markdown",
+                "9" => "multi",
                 _ => {
                     formatter.warning("Invalid choice, using TypeScript template");
                     "typescript"
@@ -294,30 +336,118 @@ async fn execute_validate(
     }
 }
 
+async fn execute_config_migrate(
+    explicit_config: Option<&std::path::PathBuf>,
+    output: Option<&std::path::PathBuf>,
+    dry_run: bool,
+    no_backup: bool,
+    formatter: &OutputFormatter,
+) -> Result<CommandResult> {
+    use std::fs;
+
+    formatter.header("Migrate Configuration");
+
+    let config_path = ensure_config_exists(explicit_config)?;
+    formatter.info(&format!("Config: {}", config_path.display()));
+
+    let content = fs::read_to_string(&config_path)?;
+    let Some(migration) = CodegenConfig::migrate_toml_str(&content)? else {
+        return Ok(CommandResult::success_with_message(format!(
+            "{} already uses the current configuration schema",
+            config_path.display()
+        )));
+    };
+
+    for note in &migration.notes {
+        formatter.warning(note);
+    }
+
+    let migrated = migration.config.to_commented_toml()?;
+    if dry_run {
+        println!("{}", migrated);
+        return Ok(CommandResult::success());
+    }
+
+    let target = output.cloned().unwrap_or_else(|| config_path.clone());
+    if target == config_path && !no_backup {
+        let mut backup = config_path.clone().into_os_string();
+        backup.push(".bak");
+        fs::copy(&config_path, &backup)?;
+        formatter.info(&format!("Backup: {}", std::path::Path::new(&backup).display()));
+    }
+    fs::write(&target, migrated)?;
+
+    Ok(CommandResult::success_with_message(format!(
+        "Migrated configuration written to {}",
+        target.display()
+    )))
+}
+
+/// Build the generator registry, including plugins from the discovered configuration
+fn load_registry(
+    explicit_config: Option<&std::path::PathBuf>,
+    formatter: &OutputFormatter,
+) -> GeneratorRegistry {
+    let config_path =
+        discover_config(explicit_config).ok().and_then(|r| r.path().map(|p| p.to_path_buf()));
+
+    let (plugins, config_dir) = match config_path {
+        Some(path) => {
+            let plugins = match CodegenConfig::from_file(&path) {
+                Ok(config) => config.plugins,
+                Err(e) => {
+                    formatter.warning(&format!("Ignoring plugins from {}: {}", path.display(), e));
+                    PluginsConfig::default()
+                }
+            };
+            let dir = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+            (plugins, dir)
+        }
+        None => (PluginsConfig::default(), std::path::PathBuf::from(".")),
+    };
+
+    load_generator_registry(&plugins, &config_dir, formatter)
+}
+
+/// Names of the capabilities a generator supports
+fn capability_names(capabilities: &GeneratorCapabilities) -> Vec<&'static str> {
+    [
+        (capabilities.validation, "Validation"),
+        (capabilities.helpers, "Helpers"),
+        (capabilities.tests, "Tests"),
+        (capabilities.documentation, "Documentation"),
+        (capabilities.search_parameters, "Search parameters"),
+        (capabilities.profiles, "Profiles"),
+        (capabilities.extensions, "Extensions"),
+    ]
+    .into_iter()
+    .filter_map(|(supported, name)| supported.then_some(name))
+    .collect()
+}
+
 async fn execute_list_generators(
+    explicit_config: Option<&std::path::PathBuf>,
     detailed: bool,
     formatter: &OutputFormatter,
 ) -> Result<CommandResult> {
     formatter.header("Available Generators");
-    formatter.info(&format!("Detailed: {}", detailed));
 
-    let generators = vec![
-        ("TypeScript", "Ready", "Generate type-safe TypeScript SDKs"),
-        ("Rust", "In Progress", "Generate Rust SDKs with serde"),
-        ("Python", "Planned", "Generate Python SDKs with Pydantic"),
-        ("Java", "Planned", "Generate Java SDKs with Jackson"),
-    ];
+    let registry = load_registry(explicit_config, formatter);
 
     if detailed {
-        for (name, status, description) in generators {
-            formatter.list_item(&format!("{} [{}]: {}", name, status, description));
+        for metadata in registry.list() {
+            formatter.list_item(&format!(
+                "{} v{} [{}]: {}",
+                metadata.name, metadata.version, metadata.language, metadata.description
+            ));
         }
     } else {
         formatter.table(
-            &["Generator", "Status"],
-            &generators
-                .iter()
-                .map(|(name, status, _)| vec![name.to_string(), status.to_string()])
+            &["Generator", "Language", "Version"],
+            &registry
+                .list()
+                .into_iter()
+                .map(|m| vec![m.name, m.language.to_string(), m.version])
                 .collect::<Vec<_>>(),
         );
     }
@@ -326,133 +456,102 @@ async fn execute_list_generators(
 }
 
 async fn execute_describe(
+    explicit_config: Option<&std::path::PathBuf>,
     generator: &str,
     examples: bool,
     formatter: &OutputFormatter,
 ) -> Result<CommandResult> {
     formatter.header(&format!("Generator: {}", generator));
 
-    match generator.to_lowercase().as_str() {
-        "typescript" | "ts" => {
-            formatter.key_value("Language", "TypeScript");
-            formatter.key_value("Status", "Ready");
-            formatter.key_value("Target Versions", "5.0+");
-            formatter.info("\nDescription:");
-            formatter
-                .info("  Generates type-safe TypeScript SDKs with comprehensive type definitions,");
-            formatter
-                .info("  validation functions, helper methods, and full IntelliSense support.");
-            formatter.info("\nFeatures:");
-            formatter.list_item("Union types for choice elements (e.g., value[x])");
-            formatter.list_item("Literal types for required bindings");
-            formatter.list_item("Optional validation functions");
-            formatter.list_item("Helper methods for common operations");
-            formatter.list_item("JSDoc documentation");
-            formatter.list_item("Tree-shakeable exports");
-
-            if examples {
-                formatter.info("\nConfiguration Example:");
-                println!(
-                    r#"
+    let registry = load_registry(explicit_config, formatter);
+    let Some(found) = registry.get(generator) else {
+        formatter.error(&format!("Unknown generator: {}", generator));
+        formatter.info(&format!("\nAvailable generators: {}", registry.names().join(", ")));
+        return Ok(CommandResult::error("Unknown generator"));
+    };
+
+    let metadata = found.metadata();
+    formatter.key_value("Name", &metadata.name);
+    formatter.key_value("Language", &metadata.language.to_string());
+    formatter.key_value("Version", &metadata.version);
+    formatter.key_value("Author", &metadata.author);
+    formatter.info("\nDescription:");
+    formatter.info(&format!("  {}", metadata.description));
+
+    let capabilities = capability_names(&metadata.capabilities);
+    if !capabilities.is_empty() {
+        formatter.info("\nCapabilities:");
+        for capability in capabilities {
+            formatter.list_item(capability);
+        }
+    }
+
+    if examples {
+        formatter.info("\nConfiguration Example:");
+        match metadata.name.as_str() {
+            "typescript" => println!(
+                r#"
 [generators.typescript]
 enabled = true
 module_name = "fhir-r4"
 emit_validation = true
 emit_helpers = true
 emit_tests = false
+generate_classes = false
+generate_extensions = true
+strict_mode = true
 target_version = "5.3"
+
+[generators.typescript.type_mappings]
+dateTime = "string"
+
+[generators.typescript.compiler_options]
+source_map = true
 "#
-                );
-            }
-        }
-        "rust" | "rs" => {
-            formatter.key_value("Language", "Rust");
-            formatter.key_value("Status", "In Progress");
-            formatter.key_value("Target Editions", "2021, 2024");
-            formatter.info("\nDescription:");
-            formatter.info("  Generates idiomatic Rust SDKs with serde support, builder patterns,");
-            formatter.info("  and compile-time type safety.");
-            formatter.info("\nFeatures:");
-            formatter.list_item("Serde serialization/deserialization");
-            formatter.list_item("Builder patterns for complex resources");
-            formatter.list_item("Enums for coded values");
-            formatter.list_item("Optional validation traits");
-            formatter.list_item("Comprehensive documentation");
-
-            if examples {
-                formatter.info("\nConfiguration Example:");
-                println!(
-                    r#"
-[generators.rust]
+            ),
+            "kotlin" => println!(
+                r#"
+[generators.kotlin]
 enabled = true
-crate_name = "fhir-r4"
-emit_validation = true
-emit_helpers = true
-edition = "2024"
-additional_derives = ["Hash", "Eq"]
+package_name = "com.example.fhir.r4"
+group_id = "com.example"
+kotlin_version = "2.0.21"
+serialization_version = "1.7.3"
+targets = ["jvm", "android", "ios"]
 "#
-                );
-            }
-        }
-        "python" | "py" => {
-            formatter.key_value("Language", "Python");
-            formatter.key_value("Status", "Planned");
-            formatter.key_value("Target Versions", "3.10+");
-            formatter.info("\nDescription:");
-            formatter.info("  Generates Python SDKs with Pydantic models for validation and");
-            formatter.info("  type hints for static analysis.");
-            formatter.info("\nPlanned Features:");
-            formatter.list_item("Pydantic V2 models");
-            formatter.list_item("Type stubs (.pyi files)");
-            formatter.list_item("Dataclasses support");
-            formatter.list_item("Comprehensive docstrings");
-
-            if examples {
-                formatter.info("\nConfiguration Example:");
-                println!(
-                    r#"
-[generators.python]
+            ),
+            "json-schema" => println!(
+                r#"
+[generators.json_schema]
 enabled = true
-package_name = "fhir_r4"
-use_pydantic = true
-generate_stubs = true
-target_version = "3.11"
+schema_id = "https://example.com/fhir/r4.schema.json"
 "#
-                );
-            }
-        }
-        "java" => {
-            formatter.key_value("Language", "Java");
-            formatter.key_value("Status", "Planned");
-            formatter.key_value("Target Versions", "11, 17, 21");
-            formatter.info("\nDescription:");
-            formatter.info("  Generates Java SDKs with Jackson annotations, builder patterns,");
-            formatter.info("  and Bean Validation support.");
-            formatter.info("\nPlanned Features:");
-            formatter.list_item("Jackson serialization");
-            formatter.list_item("Builder patterns");
-            formatter.list_item("Bean Validation annotations");
-            formatter.list_item("Fluent interfaces");
-            formatter.list_item("Comprehensive JavaDoc");
-
-            if examples {
-                formatter.info("\nConfiguration Example:");
-                println!(
-                    r#"
-[generators.java]
+            ),
+            "openapi" => println!(
+                r#"
+[generators.openapi]
 enabled = true
-package_name = "com.example.fhir.r4"
-target_version = "17"
-use_jackson = true
-emit_validation = true
+server_url = "https://example.com/fhir"
 "#
-                );
-            }
-        }
-        _ => {
-            formatter.error(&format!("Unknown generator: {}", generator));
-            formatter.info("\nAvailable generators: typescript, rust, python, java");
-            return Ok(CommandResult::error("Unknown generator"));
+            ),
+            "markdown" => println!(
+                r#"
+[generators.markdown]
+enabled = true
+flavor = "mdbook"
+title = "FHIR R4 Reference"
+"#
+            ),
+            name => println!(
+                r#"
+[plugins]
+paths = ["plugins/"]
+generator = "{name}"
+
+[plugins.options.{name}]
+# Plugin-specific options
+"#
+            ),
         }
     }
 
@@ -542,6 +641,89 @@ async fn execute_analyze(
     Ok(CommandResult::success())
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn execute_synth(
+    explicit_config: Option<&std::path::PathBuf>,
+    target: &str,
+    count: usize,
+    seed: Option<u64>,
+    bundle: bool,
+    output: Option<&std::path::PathBuf>,
+    packages: &[String],
+    formatter: &OutputFormatter,
+) -> Result<CommandResult> {
+    let config_path = ensure_config_exists(explicit_config)?;
+
+    // Progress messages would interleave with instances written to stdout
+    let quiet = OutputFormatter::with_verbosity(false, OutputFormat::Pretty, Verbosity::Quiet);
+    let progress = if output.is_some() { formatter } else { &quiet };
+    let graph = load_type_graph(&config_path, packages, progress).await?;
+
+    let instances =
+        Synthesizer::new(&graph).with_seed(seed.unwrap_or(DEFAULT_SEED)).generate(target, count)?;
+
+    let content = if bundle {
+        let entries: Vec<_> =
+            instances.iter().map(|resource| serde_json::json!({ "resource": resource })).collect();
+        let bundle = serde_json::json!({
+            "resourceType": "Bundle",
+            "type": "collection",
+            "entry": entries,
+        });
+        format!("{}\n", serde_json::to_string_pretty(&bundle)?)
+    } else {
+        instances.iter().map(|resource| format!("{}\n", resource)).collect()
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            Ok(CommandResult::success_with_message(format!(
+                "Wrote {} instances to {}",
+                instances.len(),
+                path.display()
+            )))
+        }
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(content.as_bytes())?;
+            stdout.flush()?;
+            Ok(CommandResult::success())
+        }
+    }
+}
+
+async fn execute_validate_resource(
+    explicit_config: Option<&std::path::PathBuf>,
+    file: &std::path::Path,
+    profile: Option<&str>,
+    packages: &[String],
+) -> Result<CommandResult> {
+    let config_path = ensure_config_exists(explicit_config)?;
+    let instance: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(file)?)?;
+
+    // The OperationOutcome on stdout must stay parseable
+    let quiet = OutputFormatter::with_verbosity(false, OutputFormat::Pretty, Verbosity::Quiet);
+    let graph = load_type_graph(&config_path, packages, &quiet).await?;
+
+    let validator = Validator::new(&graph);
+    let report = match profile {
+        Some(profile) => validator.validate_profile(&instance, profile)?,
+        None => validator.validate(&instance),
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", serde_json::to_string_pretty(&report.to_operation_outcome())?)?;
+    stdout.flush()?;
+
+    let errors = report.errors().count();
+    if errors == 0 {
+        Ok(CommandResult::success())
+    } else {
+        Ok(CommandResult::error(format!("{} has {} validation errors", file.display(), errors)))
+    }
+}
+
 async fn execute_update_packages(
     package: Option<&str>,
     force: bool,
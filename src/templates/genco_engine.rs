@@ -40,6 +40,13 @@ impl GencoTemplateEngine {
             .to_file_string()
             .map_err(|e| Error::Generator(format!("Failed to format TypeScript: {}", e)))
     }
+
+    /// Format Kotlin code using genco
+    pub fn format_kotlin(tokens: &kotlin::Tokens) -> Result<String> {
+        tokens
+            .to_file_string()
+            .map_err(|e| Error::Generator(format!("Failed to format Kotlin: {}", e)))
+    }
 }
 
 impl Default for GencoTemplateEngine {
@@ -0,0 +1,81 @@
+use octofhir_codegen::core::ir::{Documentation, FhirVersion, ResourceType, TypeGraph};
+use octofhir_codegen::languages::typescript::{BundleGenerator, TypeScriptBackend};
+
+fn create_resource(name: &str, is_abstract: bool) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: Some("DomainResource".to_string()),
+        properties: vec![],
+        constraints: vec![],
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract,
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    for (name, is_abstract) in
+        [("DomainResource", true), ("Bundle", false), ("Observation", false), ("Patient", false)]
+    {
+        graph.add_resource(name.to_string(), create_resource(name, is_abstract));
+    }
+    graph
+}
+
+#[test]
+fn test_generate_bundle_module() {
+    let result =
+        BundleGenerator::new(TypeScriptBackend::new()).generate_bundle_module(&create_graph());
+
+    insta::assert_snapshot!("typescript_bundle_utilities", result.unwrap());
+}
+
+#[test]
+fn test_bundle_module_uses_resource_union() {
+    let result = BundleGenerator::new(TypeScriptBackend::new())
+        .generate_bundle_module(&create_graph())
+        .unwrap();
+
+    assert!(result.contains("export type AnyResource = Bundle | Observation | Patient;"));
+    assert!(result.contains("export type ResourceTypeName = AnyResource[\"resourceType\"];"));
+    assert!(result.contains("Extract<AnyResource, { resourceType: T }>"));
+    assert!(!result.contains("DomainResource"));
+}
+
+#[test]
+fn test_bundle_module_helpers() {
+    let result = BundleGenerator::new(TypeScriptBackend::new())
+        .generate_bundle_module(&create_graph())
+        .unwrap();
+
+    for helper in [
+        "export class TransactionBuilder {",
+        "export function isResourceType<",
+        "export function bundleEntries<",
+        "export function bundleResources<",
+        "export function searchsetResults<",
+        "export function resolveContained(",
+        "export function resolveReference<",
+        "export function transactionLocations(",
+    ] {
+        assert!(result.contains(helper), "missing {}", helper);
+    }
+    assert!(result.contains("const fullUrl = options.fullUrl ?? `urn:uuid:${this.uuid()}`;"));
+    assert!(result.contains("Unresolved bundle references"));
+}
+
+#[test]
+fn test_resolve_reference_narrows_typed_references() {
+    let result = BundleGenerator::new(TypeScriptBackend::new())
+        .generate_bundle_module(&create_graph())
+        .unwrap();
+
+    assert!(result.contains(
+        "export type ReferenceInput<T extends string = string> = string | { reference?: string; type?: T };"
+    ));
+    assert!(result.contains("  context: Bundle | object,\n  reference: ReferenceInput<T>,\n"));
+    assert!(result.contains("const type = options.type ?? (typeof reference === \"string\" ? undefined : reference.type);"));
+}
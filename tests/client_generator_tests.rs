@@ -0,0 +1,71 @@
+use octofhir_codegen::core::ir::{Documentation, FhirVersion, ResourceType, TypeGraph};
+use octofhir_codegen::languages::typescript::ClientGenerator;
+
+fn create_resource(name: &str, is_abstract: bool) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: Some("DomainResource".to_string()),
+        properties: vec![],
+        constraints: vec![],
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract,
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    for (name, is_abstract) in
+        [("DomainResource", true), ("Bundle", false), ("Observation", false), ("Patient", false)]
+    {
+        graph.add_resource(name.to_string(), create_resource(name, is_abstract));
+    }
+    graph
+}
+
+#[test]
+fn test_generate_client_module() {
+    let result = ClientGenerator::new().generate_client_module(&create_graph());
+
+    insta::assert_snapshot!("typescript_rest_client", result.unwrap());
+}
+
+#[test]
+fn test_client_types_come_from_graph() {
+    let result = ClientGenerator::new().generate_client_module(&create_graph()).unwrap();
+
+    assert!(result.contains("import type { Observation } from './resources/Observation';"));
+    assert!(result.contains("export interface ResourceTypeMap {"));
+    assert!(result.contains("  Observation: Observation;"));
+    assert!(result.contains("export type ResourceName = keyof ResourceTypeMap;"));
+    assert!(!result.contains("DomainResource"));
+    assert!(result.contains(
+        "async read<T extends ResourceName>(type: T, id: string): Promise<ResourceTypeMap[T]> {"
+    ));
+}
+
+#[test]
+fn test_client_interactions() {
+    let result = ClientGenerator::new().generate_client_module(&create_graph()).unwrap();
+
+    for interaction in [
+        "async read<",
+        "async vread<",
+        "async search<",
+        "async *pages<",
+        "async *searchAll<",
+        "async create<",
+        "async update<",
+        "async patch<",
+        "async delete(",
+        "async history(",
+        "async operation<",
+    ] {
+        assert!(result.contains(interaction), "missing {}", interaction);
+    }
+    assert!(result.contains("fetch?: FetchLike;"));
+    assert!(result.contains("export class OperationOutcomeError extends Error {"));
+    assert!(result.contains("link.relation === \"next\""));
+}
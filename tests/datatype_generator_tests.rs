@@ -6,6 +6,7 @@ fn create_human_name() -> DataType {
     DataType {
         name: "HumanName".to_string(),
         base: Some("Element".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "use".to_string(),
@@ -135,6 +136,7 @@ fn create_address() -> DataType {
     DataType {
         name: "Address".to_string(),
         base: Some("Element".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "use".to_string(),
@@ -286,6 +288,7 @@ fn create_quantity() -> DataType {
     DataType {
         name: "Quantity".to_string(),
         base: Some("Element".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "value".to_string(),
@@ -515,3 +518,56 @@ fn test_generate_datatypes_index() {
     let output = generator.generate_datatypes_index(&graph).unwrap();
     assert_snapshot!("datatypes_index", output);
 }
+
+fn reference_property(name: &str, type_name: &str) -> Property {
+    Property {
+        name: name.to_string(),
+        path: format!("Reference.{}", name),
+        property_type: PropertyType::Primitive { type_name: type_name.to_string() },
+        cardinality: CardinalityRange::optional(),
+        is_choice: false,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: true,
+        binding: None,
+        constraints: vec![],
+        short_description: String::new(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    }
+}
+
+#[test]
+fn test_generate_typed_reference_file() {
+    let generator = DatatypeGenerator::new(TypeScriptBackend::new())
+        .with_resource_types(vec!["Organization".to_string(), "Patient".to_string()]);
+    let datatype = DataType {
+        name: "Reference".to_string(),
+        base: Some("Element".to_string()),
+        properties: vec![
+            reference_property("reference", "string"),
+            reference_property("type", "uri"),
+            reference_property("display", "string"),
+        ],
+        constraints: vec![],
+        documentation: Documentation::default(),
+        url: "http://hl7.org/fhir/StructureDefinition/Reference".to_string(),
+        is_abstract: false,
+    };
+
+    let deps = generator.collect_dependencies(&datatype);
+    let output = generator.generate_datatype_file(&datatype, &deps).unwrap();
+
+    assert!(output.contains("export type ResourceType =\n  | \"Organization\"\n  | \"Patient\";"));
+    assert!(
+        output.contains("export type ReferenceString<T extends ResourceType = ResourceType> =")
+    );
+    assert!(output.contains("  | `${T}/${string}`"));
+    assert!(output.contains(
+        "export interface Reference<T extends ResourceType = ResourceType> extends Element {"
+    ));
+    assert!(output.contains("reference?: ReferenceString<T>;"));
+    assert!(output.contains("type?: T;"));
+    assert!(output.contains("display?: string;"));
+}
@@ -45,14 +45,14 @@ impl CodeGenerator for MockGenerator {
     }
 
     fn language(&self) -> Language {
-        self.language
+        self.language.clone()
     }
 
     fn metadata(&self) -> GeneratorMetadata {
         GeneratorMetadata {
             name: "MockGenerator".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
-            language: self.language,
+            language: self.language.clone(),
             description: "Mock generator for testing".to_string(),
             author: "OctoFHIR Team".to_string(),
             capabilities: GeneratorCapabilities {
@@ -76,7 +76,7 @@ impl LanguageBackend for MockBackend {
         match property_type {
             PropertyType::Primitive { type_name } => type_name.clone(),
             PropertyType::Complex { type_name } => type_name.clone(),
-            PropertyType::Reference { target_types } => {
+            PropertyType::Reference { target_types, .. } => {
                 if target_types.is_empty() {
                     "Reference".to_string()
                 } else {
@@ -155,6 +155,7 @@ async fn test_mock_generator_generate() {
         ResourceType {
             name: "Patient".to_string(),
             base: Some("DomainResource".to_string()),
+            constraints: vec![],
             properties: vec![],
             search_parameters: vec![],
             extensions: vec![],
@@ -184,6 +185,7 @@ fn test_mock_backend_map_type() {
 
     let reference = PropertyType::Reference {
         target_types: vec!["Patient".to_string(), "Practitioner".to_string()],
+        target_profiles: vec![],
     };
     assert_eq!(backend.map_type(&reference), "Reference<Patient | Practitioner>");
 }
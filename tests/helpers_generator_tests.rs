@@ -7,6 +7,7 @@ fn create_patient_resource() -> ResourceType {
     ResourceType {
         name: "Patient".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "id".to_string(),
@@ -60,6 +61,7 @@ fn create_observation_resource() -> ResourceType {
     ResourceType {
         name: "Observation".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![],
         search_parameters: vec![],
         extensions: vec![],
@@ -80,6 +82,7 @@ fn create_generic_resource() -> ResourceType {
     ResourceType {
         name: "Condition".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![],
         search_parameters: vec![],
         extensions: vec![],
@@ -237,7 +240,9 @@ fn test_helpers_module_has_correct_imports() {
     // Verify imports
     assert!(result.contains("import { Patient } from './patient'"));
     assert!(result.contains("import { HumanName } from './humanName'"));
-    assert!(result.contains("import { extractPrimitiveValue } from './utilities'"));
+    // Primitives are inline, so nothing needs unwrapping
+    assert!(!result.contains("import { extractPrimitiveValue } from './utilities'"));
+    assert!(result.contains("const birth = new Date(patient.birthDate);"));
 }
 
 #[test]
@@ -11,6 +11,7 @@ fn test_serialize_type_graph() {
     let patient = ResourceType {
         name: "Patient".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![Property {
             name: "birthDate".to_string(),
             path: "Patient.birthDate".to_string(),
@@ -54,6 +55,7 @@ fn test_serialize_type_graph() {
 fn test_serialize_complex_property_types() {
     let reference = PropertyType::Reference {
         target_types: vec!["Patient".to_string(), "Practitioner".to_string()],
+        target_profiles: vec![],
     };
     let json = serde_json::to_string_pretty(&reference).unwrap();
     insta::assert_snapshot!("reference_type", json);
@@ -168,6 +170,7 @@ fn test_round_trip_serialization() {
     let datatype = DataType {
         name: "HumanName".to_string(),
         base: Some("Element".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "family".to_string(),
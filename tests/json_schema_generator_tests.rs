@@ -0,0 +1,218 @@
+use insta::assert_json_snapshot;
+use octofhir_codegen::core::ir::{
+    BindingStrength, CardinalityRange, CodeConcept, DataType, Documentation, FhirVersion,
+    PrimitiveType, Property, PropertyType, ResourceType, TypeGraph, ValueSetBinding,
+    ValueSetExpansion,
+};
+use octofhir_codegen::languages::json_schema::JsonSchemaGenerator;
+use serde_json::json;
+
+fn property(name: &str, property_type: PropertyType, cardinality: CardinalityRange) -> Property {
+    Property {
+        name: name.to_string(),
+        path: String::new(),
+        property_type,
+        cardinality,
+        is_choice: false,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: false,
+        binding: None,
+        constraints: vec![],
+        short_description: String::new(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    }
+}
+
+fn primitive(type_name: &str) -> PropertyType {
+    PropertyType::Primitive { type_name: type_name.to_string() }
+}
+
+fn complex(type_name: &str) -> PropertyType {
+    PropertyType::Complex { type_name: type_name.to_string() }
+}
+
+fn choice(name: &str, types: &[&str], cardinality: CardinalityRange) -> Property {
+    let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+    let mut property = property(name, PropertyType::Choice { types: types.clone() }, cardinality);
+    property.is_choice = true;
+    property.choice_types = types;
+    property
+}
+
+fn create_primitive(name: &str, pattern: Option<&str>) -> PrimitiveType {
+    PrimitiveType {
+        name: name.to_string(),
+        base: None,
+        pattern: pattern.map(String::from),
+        documentation: Documentation {
+            short: format!("FHIR primitive type {}", name),
+            ..Default::default()
+        },
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    graph.add_primitive("boolean".to_string(), create_primitive("boolean", Some("true|false")));
+    graph.add_primitive("code".to_string(), create_primitive("code", Some("[^\\s]+(\\s[^\\s]+)*")));
+    graph.add_primitive("string".to_string(), create_primitive("string", None));
+
+    graph.add_datatype(
+        "HumanName".to_string(),
+        DataType {
+            name: "HumanName".to_string(),
+            base: Some("Element".to_string()),
+            properties: vec![
+                property("family", primitive("string"), CardinalityRange::optional()),
+                property("given", primitive("string"), CardinalityRange::optional_array()),
+            ],
+            constraints: vec![],
+            documentation: Documentation {
+                short: "Name of a human".to_string(),
+                ..Default::default()
+            },
+            url: "http://hl7.org/fhir/StructureDefinition/HumanName".to_string(),
+            is_abstract: false,
+        },
+    );
+
+    let mut gender = property("gender", primitive("code"), CardinalityRange::optional());
+    gender.short_description = "male | female | other | unknown".to_string();
+    gender.binding = Some(ValueSetBinding {
+        strength: BindingStrength::Required,
+        value_set: "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1".to_string(),
+        description: None,
+    });
+    graph.add_value_set(
+        "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+        ValueSetExpansion {
+            url: "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+            name: "AdministrativeGender".to_string(),
+            title: None,
+            concepts: ["male", "female", "other", "unknown"]
+                .iter()
+                .map(|code| CodeConcept {
+                    system: "http://hl7.org/fhir/administrative-gender".to_string(),
+                    code: code.to_string(),
+                    display: None,
+                })
+                .collect(),
+        },
+    );
+
+    graph.add_resource(
+        "Patient".to_string(),
+        ResourceType {
+            name: "Patient".to_string(),
+            base: Some("DomainResource".to_string()),
+            properties: vec![
+                property("active", primitive("boolean"), CardinalityRange::optional()),
+                property("name", complex("HumanName"), CardinalityRange::required_array()),
+                gender,
+                choice("deceased", &["boolean", "dateTime"], CardinalityRange::optional()),
+                property(
+                    "contact",
+                    PropertyType::BackboneElement {
+                        properties: vec![property(
+                            "name",
+                            complex("HumanName"),
+                            CardinalityRange::required(),
+                        )],
+                    },
+                    CardinalityRange { min: 0, max: Some(2) },
+                ),
+            ],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation {
+                short: "Information about an individual receiving care".to_string(),
+                ..Default::default()
+            },
+            url: "http://hl7.org/fhir/StructureDefinition/Patient".to_string(),
+            is_abstract: false,
+        },
+    );
+    graph.add_resource(
+        "Observation".to_string(),
+        ResourceType {
+            name: "Observation".to_string(),
+            base: Some("DomainResource".to_string()),
+            properties: vec![choice(
+                "effective",
+                &["dateTime", "Period"],
+                CardinalityRange::required(),
+            )],
+            constraints: vec![],
+            search_parameters: vec![],
+            extensions: vec![],
+            documentation: Documentation::default(),
+            url: "http://hl7.org/fhir/StructureDefinition/Observation".to_string(),
+            is_abstract: false,
+        },
+    );
+
+    graph
+}
+
+#[test]
+fn test_generate_bundle() {
+    let bundle = JsonSchemaGenerator::new()
+        .with_schema_id("https://example.com/fhir/r4.schema.json")
+        .generate_bundle(&create_graph());
+
+    assert_json_snapshot!("json_schema_bundle", bundle);
+}
+
+#[test]
+fn test_required_binding_becomes_enum() {
+    let bundle = JsonSchemaGenerator::new().generate_bundle(&create_graph());
+
+    let gender = &bundle["$defs"]["Patient"]["properties"]["gender"];
+    assert_eq!(gender["$ref"], "#/$defs/code");
+    assert_eq!(gender["enum"], json!(["male", "female", "other", "unknown"]));
+}
+
+#[test]
+fn test_cardinality() {
+    let bundle = JsonSchemaGenerator::new().generate_bundle(&create_graph());
+    let patient = &bundle["$defs"]["Patient"];
+
+    assert_eq!(
+        patient["properties"]["name"],
+        json!({ "type": "array", "items": { "$ref": "#/$defs/HumanName" }, "minItems": 1 })
+    );
+    assert_eq!(patient["properties"]["contact"]["maxItems"], 2);
+    assert_eq!(patient["required"], json!(["resourceType", "name"]));
+}
+
+#[test]
+fn test_choice_elements_are_mutually_exclusive() {
+    let bundle = JsonSchemaGenerator::new().generate_bundle(&create_graph());
+
+    let patient = &bundle["$defs"]["Patient"];
+    assert_eq!(patient["properties"]["deceasedBoolean"]["$ref"], "#/$defs/boolean");
+    // Types missing from the graph fall back to their JSON representation
+    assert_eq!(patient["properties"]["deceasedDateTime"], json!({ "type": "string" }));
+    assert_eq!(
+        patient["allOf"],
+        json!([{
+            "not": { "anyOf": [{
+                "allOf": [{ "required": ["deceasedBoolean"] }, { "required": ["deceasedDateTime"] }]
+            }] }
+        }])
+    );
+
+    // A required choice needs exactly one of its variants
+    let observation = &bundle["$defs"]["Observation"];
+    assert_eq!(
+        observation["allOf"],
+        json!([{
+            "oneOf": [{ "required": ["effectiveDateTime"] }, { "required": ["effectivePeriod"] }]
+        }])
+    );
+}
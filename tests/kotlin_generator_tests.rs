@@ -0,0 +1,209 @@
+use insta::assert_snapshot;
+use octofhir_codegen::core::ir::*;
+use octofhir_codegen::languages::kotlin::{
+    GradleConfig, KotlinBackend, KotlinClassGenerator, KotlinSdkGenerator,
+};
+
+fn property(
+    name: &str,
+    property_type: PropertyType,
+    cardinality: CardinalityRange,
+    short: &str,
+) -> Property {
+    Property {
+        name: name.to_string(),
+        path: name.to_string(),
+        is_choice: matches!(property_type, PropertyType::Choice { .. }),
+        property_type,
+        cardinality,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: false,
+        binding: None,
+        constraints: vec![],
+        short_description: short.to_string(),
+        definition: short.to_string(),
+        comments: None,
+        examples: vec![],
+    }
+}
+
+fn resource(
+    name: &str,
+    base: Option<&str>,
+    is_abstract: bool,
+    props: Vec<Property>,
+) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: base.map(String::from),
+        constraints: vec![],
+        properties: props,
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation { short: format!("{} resource", name), ..Default::default() },
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract,
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+
+    graph.add_resource(
+        "Resource".to_string(),
+        resource(
+            "Resource",
+            None,
+            true,
+            vec![
+                property(
+                    "id",
+                    PropertyType::Primitive { type_name: "id".to_string() },
+                    CardinalityRange::optional(),
+                    "Logical id of this artifact",
+                ),
+                property(
+                    "meta",
+                    PropertyType::Complex { type_name: "Meta".to_string() },
+                    CardinalityRange::optional(),
+                    "Metadata about the resource",
+                ),
+            ],
+        ),
+    );
+    graph.add_resource(
+        "DomainResource".to_string(),
+        resource(
+            "DomainResource",
+            Some("Resource"),
+            true,
+            vec![property(
+                "contained",
+                PropertyType::Complex { type_name: "Resource".to_string() },
+                CardinalityRange::optional_array(),
+                "Contained, inline Resources",
+            )],
+        ),
+    );
+    graph.add_resource(
+        "Patient".to_string(),
+        resource(
+            "Patient",
+            Some("DomainResource"),
+            false,
+            vec![
+                property(
+                    "active",
+                    PropertyType::Primitive { type_name: "boolean".to_string() },
+                    CardinalityRange::optional(),
+                    "Whether this patient's record is in active use",
+                ),
+                property(
+                    "name",
+                    PropertyType::Complex { type_name: "HumanName".to_string() },
+                    CardinalityRange::optional_array(),
+                    "A name associated with the patient",
+                ),
+                property(
+                    "deceased",
+                    PropertyType::Choice {
+                        types: vec!["boolean".to_string(), "dateTime".to_string()],
+                    },
+                    CardinalityRange::optional(),
+                    "Indicates if the individual is deceased or not",
+                ),
+                property(
+                    "managingOrganization",
+                    PropertyType::Reference {
+                        target_types: vec!["Organization".to_string()],
+                        target_profiles: vec![],
+                    },
+                    CardinalityRange::optional(),
+                    "Organization that is the custodian of the patient record",
+                ),
+            ],
+        ),
+    );
+    graph.add_datatype(
+        "HumanName".to_string(),
+        DataType {
+            name: "HumanName".to_string(),
+            base: Some("Element".to_string()),
+            constraints: vec![],
+            properties: vec![
+                property(
+                    "family",
+                    PropertyType::Primitive { type_name: "string".to_string() },
+                    CardinalityRange::optional(),
+                    "Family name (often called 'Surname')",
+                ),
+                property(
+                    "given",
+                    PropertyType::Primitive { type_name: "string".to_string() },
+                    CardinalityRange::optional_array(),
+                    "Given names (not always 'first'). Includes middle names",
+                ),
+            ],
+            documentation: Documentation {
+                short: "Name of a human - parts and usage".to_string(),
+                ..Default::default()
+            },
+            url: "http://hl7.org/fhir/StructureDefinition/HumanName".to_string(),
+            is_abstract: false,
+        },
+    );
+
+    graph
+}
+
+#[test]
+fn test_kotlin_patient_data_class() {
+    let graph = create_graph();
+    let generator = KotlinClassGenerator::new(KotlinBackend::default());
+    let output = generator.generate_resource_file(&graph.resources["Patient"], &graph).unwrap();
+
+    assert_snapshot!("kotlin_patient_data_class", output);
+}
+
+#[test]
+fn test_kotlin_domain_resource_interface() {
+    let graph = create_graph();
+    let generator = KotlinClassGenerator::new(KotlinBackend::default());
+    let output =
+        generator.generate_resource_file(&graph.resources["DomainResource"], &graph).unwrap();
+
+    assert_snapshot!("kotlin_domain_resource_interface", output);
+}
+
+#[test]
+fn test_kotlin_human_name_data_class() {
+    let graph = create_graph();
+    let generator = KotlinClassGenerator::new(KotlinBackend::default());
+    let output = generator.generate_datatype_file(&graph.datatypes["HumanName"], &graph).unwrap();
+
+    assert_snapshot!("kotlin_human_name_data_class", output);
+}
+
+#[test]
+fn test_kotlin_sdk_layout() {
+    let graph = create_graph();
+    let config = GradleConfig {
+        project_name: "fhir-r4-kotlin".to_string(),
+        package_name: "com.example.fhir".to_string(),
+        ..Default::default()
+    };
+    let generator = KotlinSdkGenerator::new(config);
+    let files = generator.generate_sdk(&graph).unwrap();
+
+    let root = "src/commonMain/kotlin/com/example/fhir";
+    assert!(files.contains_key(&format!("{}/resources/Patient.kt", root)));
+    assert!(files.contains_key(&format!("{}/resources/Resource.kt", root)));
+    assert!(files.contains_key(&format!("{}/datatypes/HumanName.kt", root)));
+
+    let fhir_json = &files[&format!("{}/FhirJson.kt", root)];
+    assert!(fhir_json.contains("subclass(Patient::class)"));
+    assert!(!fhir_json.contains("subclass(DomainResource::class)"));
+
+    assert_snapshot!("kotlin_fhir_json", fhir_json);
+}
@@ -1,5 +1,5 @@
 use octofhir_codegen::languages::typescript::{
-    ManifestGenerator, PackageConfig, TypeScriptBackend,
+    CompilerConfig, ManifestGenerator, PackageConfig, TypeScriptBackend,
 };
 
 fn create_test_config() -> PackageConfig {
@@ -239,3 +239,23 @@ fn test_package_json_without_repository() {
     // Repository should not be present
     assert!(parsed["repository"].is_null());
 }
+
+#[test]
+fn test_compiler_config() {
+    let mut compiler_options = serde_json::Map::new();
+    compiler_options.insert("es_module_interop".to_string(), serde_json::json!(false));
+    let compiler =
+        CompilerConfig { typescript_version: "5.4".to_string(), strict: false, compiler_options };
+    let generator = ManifestGenerator::new(TypeScriptBackend::new(), create_test_config())
+        .with_compiler_config(compiler);
+
+    let tsconfig: serde_json::Value =
+        serde_json::from_str(&generator.generate_tsconfig_json().unwrap()).unwrap();
+    assert_eq!(tsconfig["compilerOptions"]["strict"], false);
+    assert_eq!(tsconfig["compilerOptions"]["noUncheckedIndexedAccess"], false);
+    assert_eq!(tsconfig["compilerOptions"]["esModuleInterop"], false);
+
+    let package: serde_json::Value =
+        serde_json::from_str(&generator.generate_package_json().unwrap()).unwrap();
+    assert_eq!(package["devDependencies"]["typescript"], "^5.4.0");
+}
@@ -0,0 +1,304 @@
+use insta::assert_snapshot;
+use octofhir_codegen::core::ir::{
+    BindingStrength, CardinalityRange, CodeConcept, DataType, Documentation, Extension,
+    FhirVersion, ProfileType, Property, PropertyConstraint, PropertyType, ResourceType,
+    SearchParamType, SearchParameter, TypeGraph, ValueSetBinding, ValueSetExpansion,
+};
+use octofhir_codegen::generator::{FileType, GeneratedFile};
+use octofhir_codegen::languages::markdown::{MarkdownSiteGenerator, SiteFlavor};
+use serde_json::json;
+use std::path::Path;
+
+fn property(name: &str, property_type: PropertyType, cardinality: CardinalityRange) -> Property {
+    Property {
+        name: name.to_string(),
+        path: name.to_string(),
+        property_type,
+        cardinality,
+        is_choice: false,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: false,
+        binding: None,
+        constraints: vec![],
+        short_description: String::new(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    }
+}
+
+fn primitive(name: &str) -> PropertyType {
+    PropertyType::Primitive { type_name: name.to_string() }
+}
+
+fn complex(name: &str) -> PropertyType {
+    PropertyType::Complex { type_name: name.to_string() }
+}
+
+fn resource(name: &str, base: Option<&str>, is_abstract: bool) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: base.map(String::from),
+        properties: vec![],
+        constraints: vec![],
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract,
+    }
+}
+
+fn extension(name: &str, url: &str, value_types: &[&str]) -> Extension {
+    Extension {
+        url: url.to_string(),
+        name: name.to_string(),
+        value_types: value_types.iter().map(|t| t.to_string()).collect(),
+        target_types: vec![],
+        cardinality: CardinalityRange::optional(),
+        documentation: Documentation::default(),
+        is_complex: false,
+        sub_extensions: vec![],
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+
+    let mut base = resource("Resource", None, true);
+    base.search_parameters.push(SearchParameter {
+        code: "_id".to_string(),
+        param_type: SearchParamType::Token,
+        description: "Logical id of this artifact".to_string(),
+        expression: Some("Resource.id".to_string()),
+        target_types: vec![],
+    });
+    graph.add_resource("Resource".to_string(), base);
+
+    graph.add_datatype(
+        "HumanName".to_string(),
+        DataType {
+            name: "HumanName".to_string(),
+            base: None,
+            properties: vec![property("family", primitive("string"), CardinalityRange::optional())],
+            constraints: vec![],
+            documentation: Documentation {
+                short: "Name of a human - parts and usage".to_string(),
+                ..Default::default()
+            },
+            url: "http://hl7.org/fhir/StructureDefinition/HumanName".to_string(),
+            is_abstract: false,
+        },
+    );
+    graph.add_value_set(
+        "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+        ValueSetExpansion {
+            url: "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+            name: "AdministrativeGender".to_string(),
+            title: None,
+            concepts: vec![CodeConcept {
+                system: "http://hl7.org/fhir/administrative-gender".to_string(),
+                code: "female".to_string(),
+                display: None,
+            }],
+        },
+    );
+
+    let mut active = property("active", primitive("boolean"), CardinalityRange::optional());
+    active.is_modifier = true;
+    active.is_summary = true;
+    active.short_description = "Whether this patient's record is in active use".to_string();
+    let mut gender = property("gender", primitive("code"), CardinalityRange::optional());
+    gender.binding = Some(ValueSetBinding {
+        strength: BindingStrength::Required,
+        value_set: "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1".to_string(),
+        description: None,
+    });
+    let mut deceased = property(
+        "deceased[x]",
+        PropertyType::Choice { types: vec!["boolean".to_string(), "dateTime".to_string()] },
+        CardinalityRange::optional(),
+    );
+    deceased.is_choice = true;
+    deceased.short_description = "Indicates if the individual is deceased or not".to_string();
+    let contact = property(
+        "contact",
+        PropertyType::BackboneElement {
+            properties: vec![property("name", complex("HumanName"), CardinalityRange::optional())],
+        },
+        CardinalityRange::optional_array(),
+    );
+    let organization = property(
+        "managingOrganization",
+        PropertyType::Reference {
+            target_types: vec!["Organization".to_string()],
+            target_profiles: vec![],
+        },
+        CardinalityRange::optional(),
+    );
+
+    let mut patient = resource("Patient", Some("Resource"), false);
+    patient.documentation.short = "Information about an individual receiving care".to_string();
+    patient.documentation.definition =
+        "Demographics of a [Person](person.html) or {animal} | see <details>".to_string();
+    patient.properties = vec![
+        active,
+        property("name", complex("HumanName"), CardinalityRange::optional_array()),
+        gender,
+        deceased,
+        contact,
+        organization,
+    ];
+    patient.search_parameters.push(SearchParameter {
+        code: "name".to_string(),
+        param_type: SearchParamType::String,
+        description: "A portion of either family or given name of the patient".to_string(),
+        expression: Some("Patient.name | Patient.contact.name".to_string()),
+        target_types: vec![],
+    });
+    let mut birth_place = extension(
+        "birthPlace",
+        "http://hl7.org/fhir/StructureDefinition/patient-birthPlace",
+        &["Address"],
+    );
+    birth_place.documentation.short = "Place of Birth for patient".to_string();
+    birth_place.target_types = vec!["Patient".to_string()];
+    patient.extensions.push(birth_place.clone());
+    graph.add_resource("Patient".to_string(), patient);
+    graph.add_extension(birth_place.url.clone(), birth_place);
+
+    graph.add_resource(
+        "Organization".to_string(),
+        resource("Organization", Some("Resource"), false),
+    );
+
+    let mut race = extension(
+        "usCoreRace",
+        "http://hl7.org/fhir/us/core/StructureDefinition/us-core-race",
+        &[],
+    );
+    race.is_complex = true;
+    race.target_types = vec!["Patient".to_string(), "Patient.contact".to_string()];
+    race.sub_extensions = vec![
+        extension("ombCategory", "ombCategory", &["Coding"]),
+        extension("text", "text", &["string"]),
+    ];
+    race.sub_extensions[1].cardinality = CardinalityRange::required();
+    graph.add_extension(race.url.clone(), race);
+
+    graph.add_profile(
+        "http://example.org/StructureDefinition/named-patient".to_string(),
+        ProfileType {
+            name: "NamedPatient".to_string(),
+            base: "Patient".to_string(),
+            property_constraints: vec![
+                PropertyConstraint {
+                    path: "Patient.name".to_string(),
+                    cardinality: Some(CardinalityRange::required_array()),
+                    type_constraints: vec![],
+                    binding: None,
+                    fixed_value: None,
+                    must_support: true,
+                },
+                PropertyConstraint {
+                    path: "Patient.deceased[x]".to_string(),
+                    cardinality: None,
+                    type_constraints: vec!["boolean".to_string()],
+                    binding: None,
+                    fixed_value: Some(json!(false)),
+                    must_support: false,
+                },
+            ],
+            new_properties: vec![],
+            documentation: Documentation {
+                short: "Patient with at least one name".to_string(),
+                ..Default::default()
+            },
+            url: "http://example.org/StructureDefinition/named-patient".to_string(),
+        },
+    );
+
+    graph
+}
+
+fn file<'a>(files: &'a [GeneratedFile], path: &str) -> &'a GeneratedFile {
+    files.iter().find(|f| f.path == Path::new(path)).unwrap_or_else(|| panic!("missing {}", path))
+}
+
+#[test]
+fn test_patient_page() {
+    let files = MarkdownSiteGenerator::new().generate_site(&create_graph());
+
+    let page = file(&files, "src/resources/Patient.md");
+    assert_eq!(page.file_type, FileType::Documentation);
+    assert_snapshot!("markdown_patient_page", page.content);
+}
+
+#[test]
+fn test_mdbook_layout() {
+    let files =
+        MarkdownSiteGenerator::new().with_title("FHIR \"R4\"").generate_site(&create_graph());
+
+    assert_eq!(
+        file(&files, "book.toml").content,
+        "[book]\ntitle = \"FHIR \\\"R4\\\"\"\nsrc = \"src\"\n"
+    );
+
+    let summary = &file(&files, "src/SUMMARY.md").content;
+    assert!(summary.starts_with("# Summary\n\n[Introduction](README.md)\n"));
+    assert!(summary.contains("# Resources\n\n- [Organization](resources/Organization.md)\n"));
+    assert!(summary.contains("- [NamedPatient](profiles/NamedPatient.md)"));
+    assert!(summary.contains("- [usCoreRace](extensions/usCoreRace.md)"));
+
+    let index = &file(&files, "src/README.md").content;
+    assert!(index.contains("3 resources, 1 datatypes, 1 profiles, 2 extensions"));
+    assert!(index.contains(
+        "| [Patient](resources/Patient.md) | Information about an individual receiving care |"
+    ));
+
+    let documentation = files.iter().filter(|f| f.file_type == FileType::Documentation).count();
+    assert_eq!(documentation, 7);
+}
+
+#[test]
+fn test_docusaurus_layout() {
+    let files = MarkdownSiteGenerator::new()
+        .with_flavor(SiteFlavor::Docusaurus)
+        .generate_site(&create_graph());
+
+    assert!(files.iter().all(|f| f.path.starts_with("docs")));
+    assert!(
+        file(&files, "docs/index.md")
+            .content
+            .starts_with("---\ntitle: \"FHIR R4 Reference\"\nsidebar_label: \"FHIR R4 Reference\"\n---\n\n# FHIR R4 Reference")
+    );
+    let category: serde_json::Value =
+        serde_json::from_str(&file(&files, "docs/datatypes/_category_.json").content).unwrap();
+    assert_eq!(category, json!({ "label": "Datatypes", "position": 3 }));
+    assert!(
+        file(&files, "docs/datatypes/HumanName.md")
+            .content
+            .starts_with("---\ntitle: \"HumanName\"\n")
+    );
+}
+
+#[test]
+fn test_profile_and_extension_pages() {
+    let files = MarkdownSiteGenerator::new().generate_site(&create_graph());
+
+    let profile = &file(&files, "src/profiles/NamedPatient.md").content;
+    assert!(profile.contains("- **Constrains:** [Patient](../resources/Patient.md)"));
+    assert!(profile.contains("| `name` | MS | 1..* |  |  |  |"));
+    assert!(profile.contains("| `deceased[x]` |  |  | boolean |  | `false` |"));
+
+    let race = &file(&files, "src/extensions/usCoreRace.md").content;
+    assert!(
+        race.contains("- **Context:** [Patient](../resources/Patient.md), `Patient.contact`\n")
+    );
+    assert!(race.contains("- **Value:** complex, see the sub-extensions below"));
+    assert!(race.contains("| `text` | `text` | 1..1 | string |  |"));
+
+    let human_name = &file(&files, "src/datatypes/HumanName.md").content;
+    assert!(human_name.contains("## Used by\n\n- [Patient](../resources/Patient.md)\n"));
+}
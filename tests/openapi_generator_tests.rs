@@ -0,0 +1,183 @@
+use insta::assert_json_snapshot;
+use octofhir_codegen::core::ir::{
+    CardinalityRange, Documentation, FhirVersion, Property, PropertyType, ResourceType,
+    SearchParamType, SearchParameter, TypeGraph,
+};
+use octofhir_codegen::languages::openapi::OpenApiGenerator;
+use serde_json::json;
+
+fn resource(name: &str, base: Option<&str>, is_abstract: bool) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: base.map(String::from),
+        properties: vec![],
+        constraints: vec![],
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract,
+    }
+}
+
+fn search_parameter(
+    code: &str,
+    param_type: SearchParamType,
+    description: &str,
+    target_types: &[&str],
+) -> SearchParameter {
+    SearchParameter {
+        code: code.to_string(),
+        param_type,
+        description: description.to_string(),
+        expression: None,
+        target_types: target_types.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+
+    let mut base = resource("Resource", None, true);
+    base.search_parameters = vec![
+        search_parameter("_id", SearchParamType::Token, "Logical id of this artifact", &[]),
+        search_parameter(
+            "_lastUpdated",
+            SearchParamType::Date,
+            "When the resource version last changed",
+            &[],
+        ),
+    ];
+    graph.add_resource("Resource".to_string(), base);
+
+    let mut patient = resource("Patient", Some("Resource"), false);
+    patient.documentation.short = "Information about an individual receiving care".to_string();
+    patient.properties.push(Property {
+        name: "active".to_string(),
+        path: "Patient.active".to_string(),
+        property_type: PropertyType::Primitive { type_name: "boolean".to_string() },
+        cardinality: CardinalityRange::optional(),
+        is_choice: false,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: true,
+        binding: None,
+        constraints: vec![],
+        short_description: String::new(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    });
+    patient.search_parameters = vec![
+        search_parameter("name", SearchParamType::String, "A portion of the name", &[]),
+        search_parameter("birthdate", SearchParamType::Date, "The patient's date of birth", &[]),
+        search_parameter(
+            "general-practitioner",
+            SearchParamType::Reference,
+            "Patient's nominated general practitioner",
+            &["Organization", "Practitioner"],
+        ),
+    ];
+    graph.add_resource("Patient".to_string(), patient);
+
+    graph.add_resource("Bundle".to_string(), resource("Bundle", Some("Resource"), false));
+    graph.add_resource(
+        "OperationOutcome".to_string(),
+        resource("OperationOutcome", Some("Resource"), false),
+    );
+
+    graph
+}
+
+fn generate() -> serde_json::Value {
+    OpenApiGenerator::new()
+        .with_title("FHIR R4 API")
+        .with_version("1.0.0")
+        .with_server_url("https://example.com/fhir")
+        .generate_document(&create_graph())
+}
+
+#[test]
+fn test_patient_paths() {
+    let document = generate();
+
+    assert_json_snapshot!("openapi_patient_type_path", document["paths"]["/Patient"]);
+    assert_json_snapshot!("openapi_patient_instance_path", document["paths"]["/Patient/{id}"]);
+}
+
+#[test]
+fn test_document_structure() {
+    let document = generate();
+
+    assert_eq!(document["openapi"], "3.1.0");
+    assert_eq!(document["jsonSchemaDialect"], "https://json-schema.org/draft/2020-12/schema");
+    assert_eq!(document["info"], json!({ "title": "FHIR R4 API", "version": "1.0.0" }));
+
+    let paths = document["paths"].as_object().unwrap();
+    for path in [
+        "/Patient",
+        "/Patient/{id}",
+        "/Patient/{id}/_history",
+        "/Patient/{id}/_history/{vid}",
+        "/Patient/_history",
+    ] {
+        assert!(paths.contains_key(path), "missing {}", path);
+    }
+    // Abstract resources have no endpoints
+    assert!(!paths.contains_key("/Resource"));
+
+    let operation_ids: Vec<&str> = ["get", "put", "patch", "delete"]
+        .iter()
+        .map(|method| document["paths"]["/Patient/{id}"][method]["operationId"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        operation_ids,
+        vec!["readPatient", "updatePatient", "patchPatient", "deletePatient"]
+    );
+    assert_eq!(
+        document["paths"]["/Patient/{id}/_history/{vid}"]["get"]["operationId"],
+        "vreadPatient"
+    );
+    assert_eq!(document["paths"]["/Patient/_history"]["get"]["operationId"], "historyTypePatient");
+}
+
+#[test]
+fn test_search_parameters_are_typed() {
+    let document = generate();
+    let parameters = document["paths"]["/Patient"]["get"]["parameters"].as_array().unwrap();
+    let parameter = |name: &str| parameters.iter().find(|p| p["name"] == name).unwrap();
+
+    assert_eq!(parameter("name")["schema"], json!({ "type": "string" }));
+    assert_eq!(parameter("birthdate")["x-fhir-search-type"], "date");
+    assert!(parameter("birthdate")["schema"]["pattern"].as_str().unwrap().starts_with("^(eq|ne"));
+    assert!(
+        parameter("general-practitioner")["description"]
+            .as_str()
+            .unwrap()
+            .ends_with("Targets: Organization, Practitioner")
+    );
+    // Inherited from Resource
+    assert_eq!(parameter("_id")["x-fhir-search-type"], "token");
+    assert_eq!(parameter("_lastUpdated")["in"], "query");
+}
+
+#[test]
+fn test_components_reuse_json_schema_definitions() {
+    let document = generate();
+    let schemas = &document["components"]["schemas"];
+
+    assert_eq!(schemas["Patient"]["properties"]["resourceType"]["const"], "Patient");
+    assert_eq!(
+        schemas["Resource"]["discriminator"]["mapping"]["Patient"],
+        "#/components/schemas/Patient"
+    );
+    assert_eq!(
+        document["components"]["responses"]["Error"]["content"]["application/fhir+json"]["schema"],
+        json!({ "$ref": "#/components/schemas/OperationOutcome" })
+    );
+    assert_eq!(
+        document["paths"]["/Patient"]["get"]["responses"]["200"]["content"]["application/fhir+json"]
+            ["schema"],
+        json!({ "$ref": "#/components/schemas/Bundle" })
+    );
+}
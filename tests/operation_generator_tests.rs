@@ -0,0 +1,148 @@
+use octofhir_codegen::core::ir::{
+    CardinalityRange, Documentation, FhirVersion, OperationDefinition, OperationKind,
+    OperationParameter, OperationParameterUse, ResourceType, TypeGraph,
+};
+use octofhir_codegen::languages::typescript::{OperationGenerator, TypeScriptBackend};
+
+fn create_resource(name: &str) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: Some("DomainResource".to_string()),
+        properties: vec![],
+        constraints: vec![],
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract: false,
+    }
+}
+
+fn parameter(
+    name: &str,
+    usage: OperationParameterUse,
+    type_name: Option<&str>,
+    cardinality: CardinalityRange,
+) -> OperationParameter {
+    OperationParameter {
+        name: name.to_string(),
+        usage,
+        cardinality,
+        type_name: type_name.map(String::from),
+        target_profiles: vec![],
+        search_type: None,
+        binding: None,
+        documentation: String::new(),
+        parts: vec![],
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    use OperationParameterUse::{In, Out};
+
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    for name in ["Bundle", "OperationOutcome", "Parameters", "Patient"] {
+        graph.add_resource(name.to_string(), create_resource(name));
+    }
+
+    let mut start = parameter("start", In, Some("date"), CardinalityRange::optional());
+    start.documentation = "Care date range start".to_string();
+    graph.add_operation(
+        "http://hl7.org/fhir/OperationDefinition/Patient-everything".to_string(),
+        OperationDefinition {
+            url: "http://hl7.org/fhir/OperationDefinition/Patient-everything".to_string(),
+            name: "Everything".to_string(),
+            code: "everything".to_string(),
+            kind: OperationKind::Operation,
+            affects_state: false,
+            resources: vec!["Patient".to_string()],
+            system: false,
+            type_level: false,
+            instance: true,
+            parameters: vec![
+                start,
+                parameter("_type", In, Some("code"), CardinalityRange::optional_array()),
+                parameter("return", Out, Some("Bundle"), CardinalityRange::required()),
+            ],
+            documentation: Documentation {
+                short: "Fetch Patient Record".to_string(),
+                ..Documentation::default()
+            },
+        },
+    );
+
+    let mut issue = parameter("issue", Out, None, CardinalityRange::optional_array());
+    issue.parts = vec![
+        parameter("severity", Out, Some("code"), CardinalityRange::required()),
+        parameter("detail", Out, Some("Any"), CardinalityRange::optional()),
+    ];
+    graph.add_operation(
+        "http://hl7.org/fhir/OperationDefinition/Resource-validate".to_string(),
+        OperationDefinition {
+            url: "http://hl7.org/fhir/OperationDefinition/Resource-validate".to_string(),
+            name: "Validate".to_string(),
+            code: "validate".to_string(),
+            kind: OperationKind::Operation,
+            affects_state: false,
+            resources: vec!["Resource".to_string()],
+            system: false,
+            type_level: true,
+            instance: true,
+            parameters: vec![
+                parameter("resource", In, Some("Resource"), CardinalityRange::required()),
+                parameter("mode", In, Some("code"), CardinalityRange::optional()),
+                issue,
+            ],
+            documentation: Documentation::default(),
+        },
+    );
+
+    graph
+}
+
+#[test]
+fn test_generate_operations_module() {
+    let result = OperationGenerator::new(TypeScriptBackend::new())
+        .generate_operations_module(&create_graph());
+
+    insta::assert_snapshot!("typescript_operations", result.unwrap());
+}
+
+#[test]
+fn test_operation_shapes() {
+    let result = OperationGenerator::new(TypeScriptBackend::new())
+        .generate_operations_module(&create_graph())
+        .unwrap();
+
+    assert!(result.contains("export interface PatientEverythingInput {"));
+    assert!(result.contains("  start?: string;"));
+    assert!(result.contains("  _type?: string[];"));
+    assert!(result.contains("  return: Bundle;"));
+    assert!(result.contains(
+        "export function buildPatientEverythingParameters(input: PatientEverythingInput = {}): Parameters {"
+    ));
+    assert!(result.contains(
+        "export function parsePatientEverythingOutput(response: Parameters | Bundle): PatientEverythingOutput {"
+    ));
+    assert!(
+        result.contains(
+            "export function buildValidateParameters(input: ValidateInput): Parameters {"
+        )
+    );
+    assert!(result.contains("  resource: unknown;"));
+    assert!(result.contains("  issue?: Array<{ severity: string; detail?: ParameterValue }>;"));
+    assert!(result.contains("element: \"valueDate\""));
+}
+
+#[test]
+fn test_operations_with_classes() {
+    let result = OperationGenerator::new(TypeScriptBackend::new())
+        .with_classes(true)
+        .generate_operations_module(&create_graph())
+        .unwrap();
+
+    assert!(result.contains("import { Parameters } from './resources/Parameters';"));
+    assert!(
+        result.contains("return Parameters.parse({ resourceType: \"Parameters\", parameter });")
+    );
+}
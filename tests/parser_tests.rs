@@ -96,7 +96,7 @@ fn test_patient_reference_element() {
         .find(|p| p.name == "generalPractitioner")
         .expect("generalPractitioner property not found");
 
-    if let octofhir_codegen::core::ir::PropertyType::Reference { target_types } =
+    if let octofhir_codegen::core::ir::PropertyType::Reference { target_types, .. } =
         &gp_prop.property_type
     {
         assert_eq!(target_types.len(), 3);
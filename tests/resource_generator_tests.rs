@@ -6,6 +6,7 @@ fn create_patient_resource() -> ResourceType {
     ResourceType {
         name: "Patient".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "identifier".to_string(),
@@ -173,6 +174,7 @@ fn create_observation_resource() -> ResourceType {
     ResourceType {
         name: "Observation".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "identifier".to_string(),
@@ -237,6 +239,7 @@ fn create_observation_resource() -> ResourceType {
                 path: "Observation.subject".to_string(),
                 property_type: PropertyType::Reference {
                     target_types: vec!["Patient".to_string(), "Group".to_string(), "Device".to_string(), "Location".to_string()],
+                    target_profiles: vec![],
                 },
                 cardinality: CardinalityRange::optional(),
                 is_choice: false,
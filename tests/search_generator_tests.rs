@@ -0,0 +1,210 @@
+use octofhir_codegen::core::ir::{
+    Documentation, FhirVersion, ResourceType, SearchParamType, SearchParameter, TypeGraph,
+};
+use octofhir_codegen::languages::typescript::SearchGenerator;
+
+fn search_parameter(
+    code: &str,
+    param_type: SearchParamType,
+    description: &str,
+    targets: &[&str],
+) -> SearchParameter {
+    SearchParameter {
+        code: code.to_string(),
+        param_type,
+        description: description.to_string(),
+        expression: None,
+        target_types: targets.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+fn create_resource(
+    name: &str,
+    base: Option<&str>,
+    is_abstract: bool,
+    search_parameters: Vec<SearchParameter>,
+) -> ResourceType {
+    ResourceType {
+        name: name.to_string(),
+        base: base.map(String::from),
+        properties: vec![],
+        constraints: vec![],
+        search_parameters,
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: format!("http://hl7.org/fhir/StructureDefinition/{}", name),
+        is_abstract,
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    graph.add_resource(
+        "Resource".to_string(),
+        create_resource(
+            "Resource",
+            None,
+            true,
+            vec![
+                search_parameter("_id", SearchParamType::Token, "Logical id of this artifact", &[]),
+                search_parameter(
+                    "_lastUpdated",
+                    SearchParamType::Date,
+                    "When the resource version last changed",
+                    &[],
+                ),
+            ],
+        ),
+    );
+    graph.add_resource(
+        "Patient".to_string(),
+        create_resource(
+            "Patient",
+            Some("Resource"),
+            false,
+            vec![
+                search_parameter(
+                    "name",
+                    SearchParamType::String,
+                    "A server defined search that may match any of the string fields in the HumanName",
+                    &[],
+                ),
+                search_parameter(
+                    "birthdate",
+                    SearchParamType::Date,
+                    "The patient's date of birth",
+                    &[],
+                ),
+                search_parameter("gender", SearchParamType::Token, "Gender of the patient", &[]),
+                search_parameter(
+                    "general-practitioner",
+                    SearchParamType::Reference,
+                    "Patient's nominated general practitioner",
+                    &["Organization", "Practitioner"],
+                ),
+            ],
+        ),
+    );
+    graph.add_resource(
+        "Practitioner".to_string(),
+        create_resource(
+            "Practitioner",
+            Some("Resource"),
+            false,
+            vec![search_parameter("family", SearchParamType::String, "", &[])],
+        ),
+    );
+    graph.add_resource(
+        "Observation".to_string(),
+        create_resource(
+            "Observation",
+            Some("Resource"),
+            false,
+            vec![
+                search_parameter(
+                    "subject",
+                    SearchParamType::Reference,
+                    "The subject that the observation is about",
+                    &["Group", "Patient"],
+                ),
+                search_parameter(
+                    "value-quantity",
+                    SearchParamType::Quantity,
+                    "The value of the observation",
+                    &[],
+                ),
+            ],
+        ),
+    );
+    graph
+}
+
+#[test]
+fn test_generate_patient_search() {
+    let graph = create_graph();
+
+    let result =
+        SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Patient"]);
+
+    insta::assert_snapshot!("patient_search_builder", result.unwrap());
+}
+
+#[test]
+fn test_parameters_are_typed_by_search_type() {
+    let graph = create_graph();
+
+    let result =
+        SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Patient"])
+            .unwrap();
+
+    assert!(result.contains("get name(): StringParam<Q> {"));
+    assert!(result.contains("get birthdate(): DateParam<Q> {"));
+    assert!(result.contains("get gender(): TokenParam<Q> {"));
+    // Inherited from Resource
+    assert!(result.contains("get _lastUpdated(): DateParam<Q> {"));
+    assert!(result.contains("return this.param<DateParam<Q>>(DateParam, \"_lastUpdated\");"));
+
+    let observation =
+        SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Observation"])
+            .unwrap();
+    assert!(observation.contains("get valueQuantity(): QuantityParam<Q> {"));
+}
+
+#[test]
+fn test_reference_chaining_and_includes() {
+    let graph = create_graph();
+
+    let observation =
+        SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Observation"])
+            .unwrap();
+
+    // Group has no builder in this graph, so only Patient can be chained into
+    assert!(observation.contains(
+        "get subject(): ReferenceParam<Q, \"Group\" | \"Patient\", { Patient: PatientSearchParams<Q> }> {"
+    ));
+    assert!(observation.contains(
+        "export type ObservationInclude = \"Observation:subject\" | \"Observation:subject:Group\" | \"Observation:subject:Patient\";"
+    ));
+
+    let patient =
+        SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Patient"])
+            .unwrap();
+    assert!(patient.contains(
+        "export type PatientRevInclude = \"Observation:subject\" | \"Observation:subject:Patient\";"
+    ));
+
+    let practitioner =
+        SearchGenerator::generate_resource_search_file(&graph, &graph.resources["Practitioner"])
+            .unwrap();
+    assert!(practitioner.contains("export type PractitionerInclude = never;"));
+    assert!(practitioner.contains(
+        "export type PractitionerRevInclude = \"Patient:general-practitioner\" | \"Patient:general-practitioner:Practitioner\";"
+    ));
+}
+
+#[test]
+fn test_generate_search_index() {
+    let result =
+        SearchGenerator::generate_search_index(&["Observation".to_string(), "Patient".to_string()])
+            .unwrap();
+
+    assert!(result.contains("import { PatientSearch } from './Patient';"));
+    assert!(result.contains("export * from './runtime';"));
+    assert!(result.contains("export * from './Observation';"));
+    assert!(result.contains("export const search = {"));
+    assert!(result.contains("Patient: (): PatientSearch => new PatientSearch(),"));
+}
+
+#[test]
+fn test_runtime_module_modifiers_and_prefixes() {
+    let runtime = SearchGenerator::generate_runtime_module();
+
+    for modifier in [":exact", ":contains", ":missing", ":text", ":not", ":identifier"] {
+        assert!(runtime.contains(modifier), "missing modifier {}", modifier);
+    }
+    assert!(runtime.contains(
+        "export type SearchPrefix = \"eq\" | \"ne\" | \"gt\" | \"lt\" | \"ge\" | \"le\" | \"sa\" | \"eb\" | \"ap\";"
+    ));
+    assert!(runtime.contains("chain<K extends keyof C & T>(type: K): C[K] {"));
+    assert!(runtime.contains("toQueryString(): string {"));
+}
@@ -138,6 +138,7 @@ fn test_typescript_resource_template() {
     let resource = ResourceType {
         name: "Patient".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "active".to_string(),
@@ -200,6 +201,7 @@ fn test_typescript_datatype_template() {
     let datatype = DataType {
         name: "HumanName".to_string(),
         base: Some("Element".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "use".to_string(),
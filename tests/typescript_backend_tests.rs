@@ -75,18 +75,29 @@ fn test_reference_type_mapping() {
     let backend = TypeScriptBackend::new();
 
     // Empty reference
-    let empty_ref = PropertyType::Reference { target_types: vec![] };
+    let empty_ref = PropertyType::Reference { target_types: vec![], target_profiles: vec![] };
     assert_eq!(backend.map_type(&empty_ref), "Reference");
 
     // Single target
-    let single_ref = PropertyType::Reference { target_types: vec!["Patient".to_string()] };
+    let single_ref = PropertyType::Reference {
+        target_types: vec!["Patient".to_string()],
+        target_profiles: vec![],
+    };
     assert_eq!(backend.map_type(&single_ref), "Reference<\"Patient\">");
 
     // Multiple targets
     let multi_ref = PropertyType::Reference {
         target_types: vec!["Patient".to_string(), "Organization".to_string()],
+        target_profiles: vec![],
     };
     assert_eq!(backend.map_type(&multi_ref), "Reference<\"Patient\" | \"Organization\">");
+
+    // Reference(Any) targets the abstract Resource
+    let any_ref = PropertyType::Reference {
+        target_types: vec!["Resource".to_string()],
+        target_profiles: vec!["http://hl7.org/fhir/StructureDefinition/Resource".to_string()],
+    };
+    assert_eq!(backend.map_type(&any_ref), "Reference");
 }
 
 #[test]
@@ -1,5 +1,6 @@
 use octofhir_codegen::core::ir::{
-    CardinalityRange, Documentation, Property, PropertyType, ResourceType,
+    CardinalityRange, ConstraintSeverity, DataType, Documentation, FhirVersion, InvariantRule,
+    Property, PropertyType, ResourceType, TypeGraph,
 };
 use octofhir_codegen::languages::typescript::{TypeScriptBackend, ValidationGenerator};
 
@@ -7,6 +8,7 @@ fn create_patient_resource() -> ResourceType {
     ResourceType {
         name: "Patient".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "id".to_string(),
@@ -82,6 +84,7 @@ fn create_observation_resource() -> ResourceType {
     ResourceType {
         name: "Observation".to_string(),
         base: Some("DomainResource".to_string()),
+        constraints: vec![],
         properties: vec![
             Property {
                 name: "status".to_string(),
@@ -228,7 +231,7 @@ fn test_patient_validation_checks_required_fields() {
 
     // Should check the required 'active' field
     assert!(result.contains("// Check required field: active"));
-    assert!(result.contains("if (!resource.active)"));
+    assert!(result.contains("if (resource.active == null)"));
     assert!(result.contains("Required field 'active' is missing"));
 }
 
@@ -285,3 +288,272 @@ fn test_observation_validation_checks_multiple_required() {
     assert!(result.contains("// Check required field: status"));
     assert!(result.contains("// Check required field: code"));
 }
+
+fn create_human_name_datatype() -> DataType {
+    DataType {
+        name: "HumanName".to_string(),
+        base: Some("Element".to_string()),
+        constraints: vec![],
+        properties: vec![
+            Property {
+                name: "family".to_string(),
+                path: "HumanName.family".to_string(),
+                property_type: PropertyType::Primitive { type_name: "string".to_string() },
+                cardinality: CardinalityRange::optional(),
+                is_choice: false,
+                choice_types: vec![],
+                is_modifier: false,
+                is_summary: true,
+                binding: None,
+                constraints: vec![],
+                short_description: "Family name (often called 'Surname')".to_string(),
+                definition: "The part of a name that links to the genealogy.".to_string(),
+                comments: None,
+                examples: vec![],
+            },
+            Property {
+                name: "given".to_string(),
+                path: "HumanName.given".to_string(),
+                property_type: PropertyType::Primitive { type_name: "string".to_string() },
+                cardinality: CardinalityRange::optional_array(),
+                is_choice: false,
+                choice_types: vec![],
+                is_modifier: false,
+                is_summary: true,
+                binding: None,
+                constraints: vec![],
+                short_description: "Given names (not always 'first')".to_string(),
+                definition: "Given name.".to_string(),
+                comments: None,
+                examples: vec![],
+            },
+        ],
+        documentation: Documentation::default(),
+        url: "http://hl7.org/fhir/StructureDefinition/HumanName".to_string(),
+        is_abstract: false,
+    }
+}
+
+fn create_contact_property() -> Property {
+    Property {
+        name: "contact".to_string(),
+        path: "Patient.contact".to_string(),
+        property_type: PropertyType::BackboneElement {
+            properties: vec![Property {
+                name: "name".to_string(),
+                path: "Patient.contact.name".to_string(),
+                property_type: PropertyType::Complex { type_name: "HumanName".to_string() },
+                cardinality: CardinalityRange::required(),
+                is_choice: false,
+                choice_types: vec![],
+                is_modifier: false,
+                is_summary: false,
+                binding: None,
+                constraints: vec![],
+                short_description: "A name associated with the contact person".to_string(),
+                definition: String::new(),
+                comments: None,
+                examples: vec![],
+            }],
+        },
+        cardinality: CardinalityRange::optional_array(),
+        is_choice: false,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: false,
+        binding: None,
+        constraints: vec![],
+        short_description: "A contact party for the patient".to_string(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    }
+}
+
+fn nested_validation_generator() -> ValidationGenerator {
+    ValidationGenerator::new(TypeScriptBackend::new())
+        .with_validated_types(["HumanName".to_string()].into_iter().collect())
+}
+
+#[test]
+fn test_generate_human_name_validation() {
+    let generator = nested_validation_generator();
+
+    let result = generator.generate_datatype_validation(&create_human_name_datatype()).unwrap();
+
+    insta::assert_snapshot!("human_name_validation", result);
+}
+
+#[test]
+fn test_datatype_validation_checks_element_types() {
+    let generator = nested_validation_generator();
+
+    let result = generator.generate_datatype_validation(&create_human_name_datatype()).unwrap();
+
+    assert!(
+        result
+            .contains("export function validateHumanName(value: HumanName, path = \"HumanName\")")
+    );
+    assert!(result.contains("if (typeof value.family !== \"string\")"));
+    assert!(result.contains("value.given.forEach((item0, i0) => {"));
+    assert!(result.contains("path: `${path}.given[${i0}]`"));
+}
+
+#[test]
+fn test_resource_validation_recurses_into_nested_types() {
+    let generator = nested_validation_generator();
+    let mut patient = create_patient_resource();
+    patient.properties.push(create_contact_property());
+
+    let result = generator.generate_resource_validation(&patient).unwrap();
+
+    // Nested complex types call their own validator with an indexed path
+    assert!(
+        result.contains("errors.push(...validateHumanName(item0, `${path}.name[${i0}]`).errors);")
+    );
+
+    // Backbone elements are validated inline, one level deeper
+    assert!(result.contains("resource.contact.forEach((item0: any, i0) => {"));
+    assert!(result.contains("if (item0.name == null)"));
+    assert!(result.contains("path: `${path}.contact[${i0}].name`"));
+    assert!(result.contains(
+        "errors.push(...validateHumanName(item0.name, `${path}.contact[${i0}].name`).errors);"
+    ));
+}
+
+#[test]
+fn test_resource_validation_without_validated_types_skips_nesting() {
+    let backend = TypeScriptBackend::new();
+    let generator = ValidationGenerator::new(backend);
+    let patient = create_patient_resource();
+
+    let result = generator.generate_resource_validation(&patient).unwrap();
+
+    assert!(!result.contains("validateHumanName"));
+}
+
+#[test]
+fn test_resource_validator_file_imports() {
+    let generator = nested_validation_generator();
+    let mut patient = create_patient_resource();
+    patient.properties.push(create_contact_property());
+
+    let result = generator.generate_resource_validator_file(&patient).unwrap();
+
+    insta::assert_snapshot!("patient_validator_file", result);
+}
+
+fn invariant(
+    key: &str,
+    severity: ConstraintSeverity,
+    human: &str,
+    expression: &str,
+) -> InvariantRule {
+    InvariantRule {
+        key: key.to_string(),
+        severity,
+        human: human.to_string(),
+        expression: Some(expression.to_string()),
+        xpath: None,
+    }
+}
+
+fn create_patient_with_invariants() -> ResourceType {
+    let mut patient = create_patient_resource();
+    let mut contact = create_contact_property();
+    contact.constraints.push(invariant(
+        "pat-1",
+        ConstraintSeverity::Error,
+        "SHALL at least contain a contact's details or a reference to an organization",
+        "name.exists() or telecom.exists() or address.exists() or organization.exists()",
+    ));
+    patient.properties.push(contact);
+    patient.constraints.push(invariant(
+        "pat-2",
+        ConstraintSeverity::Warning,
+        "A patient should have a name",
+        "name.exists()",
+    ));
+    patient
+}
+
+#[test]
+fn test_resource_validator_file_with_invariants() {
+    let generator = nested_validation_generator();
+
+    let result =
+        generator.generate_resource_validator_file(&create_patient_with_invariants()).unwrap();
+
+    insta::assert_snapshot!("patient_validator_file_with_invariants", result);
+}
+
+#[test]
+fn test_invariants_report_constraint_and_severity() {
+    let generator = nested_validation_generator();
+
+    let result =
+        generator.generate_resource_validator_file(&create_patient_with_invariants()).unwrap();
+
+    assert!(result.contains("import * as fhirpath from '../fhirpath';"));
+    assert!(result.contains("// Invariant pat-1"));
+    assert!(result.contains("constraint: \"pat-1\""));
+    assert!(result.contains("constraint: \"pat-2\""));
+    assert!(result.contains("severity: \"warning\""));
+    assert!(result.contains("fhirpath.isFalse("));
+}
+
+#[test]
+fn test_untranslated_invariants_are_reported() {
+    let generator = nested_validation_generator();
+    let mut patient = create_patient_resource();
+    patient.constraints.push(invariant(
+        "ref-1",
+        ConstraintSeverity::Error,
+        "References must resolve",
+        "link.other.resolve().exists()",
+    ));
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    graph.add_resource("Patient".to_string(), patient.clone());
+
+    let result = generator.generate_resource_validation(&patient).unwrap();
+    assert!(!result.contains("ref-1"));
+
+    let warnings = generator.untranslated_invariants(&graph);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("Invariant ref-1 on Patient"));
+}
+
+#[test]
+fn test_choice_validation_checks_variants() {
+    let mut observation = create_observation_resource();
+    observation.properties.push(Property {
+        name: "value".to_string(),
+        path: "Observation.value[x]".to_string(),
+        property_type: PropertyType::Choice {
+            types: vec!["Quantity".to_string(), "string".to_string()],
+        },
+        cardinality: CardinalityRange::required(),
+        is_choice: true,
+        choice_types: vec!["Quantity".to_string(), "string".to_string()],
+        is_modifier: false,
+        is_summary: true,
+        binding: None,
+        constraints: vec![],
+        short_description: "Actual result".to_string(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    });
+
+    let result = ValidationGenerator::new(TypeScriptBackend::new())
+        .generate_resource_validation(&observation)
+        .unwrap();
+
+    assert!(!result.contains("resource.value "));
+    assert!(result.contains("// Check choice: value[x]"));
+    assert!(result.contains(
+        "if ([resource.valueQuantity, resource.valueString].filter((v) => v != null).length === 0) {"
+    ));
+    assert!(result.contains("Only one variant of 'value[x]' may be set"));
+    assert!(result.contains("if (typeof resource.valueString !== \"string\") {"));
+}
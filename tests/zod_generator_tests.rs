@@ -0,0 +1,260 @@
+use octofhir_codegen::core::ir::{
+    BindingStrength, CardinalityRange, DataType, Documentation, FhirVersion, Property,
+    PropertyType, ResourceType, TypeGraph, ValueSetBinding,
+};
+use octofhir_codegen::languages::typescript::{TypeScriptBackend, ZodGenerator};
+use std::collections::HashMap;
+
+fn property(name: &str, property_type: PropertyType, cardinality: CardinalityRange) -> Property {
+    Property {
+        name: name.to_string(),
+        path: String::new(),
+        property_type,
+        cardinality,
+        is_choice: false,
+        choice_types: vec![],
+        is_modifier: false,
+        is_summary: false,
+        binding: None,
+        constraints: vec![],
+        short_description: String::new(),
+        definition: String::new(),
+        comments: None,
+        examples: vec![],
+    }
+}
+
+fn primitive(type_name: &str) -> PropertyType {
+    PropertyType::Primitive { type_name: type_name.to_string() }
+}
+
+fn complex(type_name: &str) -> PropertyType {
+    PropertyType::Complex { type_name: type_name.to_string() }
+}
+
+fn create_patient_resource() -> ResourceType {
+    let mut gender = property("gender", primitive("code"), CardinalityRange::optional());
+    gender.binding = Some(ValueSetBinding {
+        strength: BindingStrength::Required,
+        value_set: "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1".to_string(),
+        description: None,
+    });
+
+    ResourceType {
+        name: "Patient".to_string(),
+        base: Some("DomainResource".to_string()),
+        properties: vec![
+            property("id", primitive("id"), CardinalityRange::optional()),
+            property("active", primitive("boolean"), CardinalityRange::required()),
+            property("name", complex("HumanName"), CardinalityRange::optional_array()),
+            gender,
+            property(
+                "multipleBirth",
+                PropertyType::Choice { types: vec!["boolean".to_string(), "integer".to_string()] },
+                CardinalityRange::optional(),
+            ),
+            property(
+                "generalPractitioner",
+                PropertyType::Reference {
+                    target_types: vec!["Organization".to_string(), "Practitioner".to_string()],
+                    target_profiles: vec![],
+                },
+                CardinalityRange::optional_array(),
+            ),
+            property(
+                "contact",
+                PropertyType::BackboneElement {
+                    properties: vec![property(
+                        "name",
+                        complex("HumanName"),
+                        CardinalityRange::required(),
+                    )],
+                },
+                CardinalityRange { min: 1, max: Some(3) },
+            ),
+        ],
+        constraints: vec![],
+        search_parameters: vec![],
+        extensions: vec![],
+        documentation: Documentation::default(),
+        url: "http://hl7.org/fhir/StructureDefinition/Patient".to_string(),
+        is_abstract: false,
+    }
+}
+
+fn create_human_name_datatype() -> DataType {
+    DataType {
+        name: "HumanName".to_string(),
+        base: Some("Element".to_string()),
+        properties: vec![
+            property("family", primitive("string"), CardinalityRange::optional()),
+            property("given", primitive("string"), CardinalityRange::optional_array()),
+            property("extension", complex("Extension"), CardinalityRange::optional_array()),
+        ],
+        constraints: vec![],
+        documentation: Documentation::default(),
+        url: "http://hl7.org/fhir/StructureDefinition/HumanName".to_string(),
+        is_abstract: false,
+    }
+}
+
+fn create_graph() -> TypeGraph {
+    let mut graph = TypeGraph::new(FhirVersion::R4);
+    graph.add_datatype("HumanName".to_string(), create_human_name_datatype());
+    graph.add_resource("Patient".to_string(), create_patient_resource());
+    graph
+}
+
+fn zod_generator(graph: &TypeGraph) -> ZodGenerator {
+    let backend = TypeScriptBackend::new().with_value_set_types(HashMap::from([(
+        "http://hl7.org/fhir/ValueSet/administrative-gender".to_string(),
+        "AdministrativeGender".to_string(),
+    )]));
+    ZodGenerator::new(backend).with_schema_types(ZodGenerator::schema_types_for(graph))
+}
+
+#[test]
+fn test_generate_patient_schema() {
+    let graph = create_graph();
+
+    let result = zod_generator(&graph).generate_resource_schema_file(&graph.resources["Patient"]);
+
+    insta::assert_snapshot!("patient_zod_schema", result.unwrap());
+}
+
+#[test]
+fn test_generate_human_name_schema() {
+    let graph = create_graph();
+
+    let result = zod_generator(&graph).generate_datatype_schema_file(&graph.datatypes["HumanName"]);
+
+    insta::assert_snapshot!("human_name_zod_schema", result.unwrap());
+}
+
+#[test]
+fn test_schema_cardinality() {
+    let graph = create_graph();
+
+    let result =
+        zod_generator(&graph).generate_resource_schema_file(&graph.resources["Patient"]).unwrap();
+
+    assert!(result.contains("active: z.boolean(),"));
+    assert!(result.contains("id: z.string().optional(),"));
+    assert!(result.contains("name: z.array(z.lazy(() => HumanNameSchema)).optional(),"));
+    assert!(result.contains("}).passthrough()).min(1).max(3),"));
+}
+
+#[test]
+fn test_schema_checks_interface_and_exports_inferred_type() {
+    let graph = create_graph();
+    let generator = zod_generator(&graph);
+
+    let patient = generator.generate_resource_schema_file(&graph.resources["Patient"]).unwrap();
+    assert!(patient.contains("import type { Patient } from '../resources/Patient';"));
+    assert!(patient.contains("resourceType: z.literal(\"Patient\"),"));
+    assert!(patient.contains("satisfies z.ZodType<Patient>;"));
+    assert!(patient.contains("export type PatientInferred = z.infer<typeof PatientSchema>;"));
+
+    let human_name =
+        generator.generate_datatype_schema_file(&graph.datatypes["HumanName"]).unwrap();
+    assert!(human_name.contains("export const HumanNameSchema: z.ZodType<HumanName> = z.object({"));
+    // Types without a schema are accepted as-is
+    assert!(human_name.contains("extension: z.array(z.any()).optional(),"));
+}
+
+#[test]
+fn test_schema_required_binding_uses_value_set_codes() {
+    let graph = create_graph();
+
+    let result =
+        zod_generator(&graph).generate_resource_schema_file(&graph.resources["Patient"]).unwrap();
+
+    assert!(result.contains("import type { AdministrativeGender } from '../valuesets';"));
+    assert!(result.contains("import { AdministrativeGenderCodes } from '../valuesets';"));
+    assert!(result.contains(
+        "gender: z.custom<AdministrativeGender>((value) => AdministrativeGenderCodes.some((c) => c.code === value)).optional(),"
+    ));
+}
+
+#[test]
+fn test_schema_expands_choice_elements() {
+    let mut graph = create_graph();
+    let choice_types = vec!["boolean".to_string(), "dateTime".to_string()];
+    let mut deceased = property(
+        "deceased",
+        PropertyType::Choice { types: choice_types.clone() },
+        CardinalityRange::optional(),
+    );
+    deceased.is_choice = true;
+    deceased.choice_types = choice_types;
+    graph.resources.get_mut("Patient").unwrap().properties.push(deceased);
+
+    let result =
+        zod_generator(&graph).generate_resource_schema_file(&graph.resources["Patient"]).unwrap();
+
+    assert!(!result.contains("deceased:"));
+    assert!(result.contains("deceasedBoolean: z.boolean().optional(),"));
+    assert!(result.contains("deceasedDateTime: z.string().optional(),"));
+}
+
+#[test]
+fn test_schema_narrows_typed_references() {
+    let mut graph = create_graph();
+    graph.add_datatype(
+        "Reference".to_string(),
+        DataType {
+            name: "Reference".to_string(),
+            base: Some("Element".to_string()),
+            properties: vec![
+                property("reference", primitive("string"), CardinalityRange::optional()),
+                property("type", primitive("uri"), CardinalityRange::optional()),
+                property("display", primitive("string"), CardinalityRange::optional()),
+            ],
+            constraints: vec![],
+            documentation: Documentation::default(),
+            url: "http://hl7.org/fhir/StructureDefinition/Reference".to_string(),
+            is_abstract: false,
+        },
+    );
+    let generator = zod_generator(&graph);
+
+    let patient = generator.generate_resource_schema_file(&graph.resources["Patient"]).unwrap();
+    assert!(patient.contains("import type { Reference } from '../types/Reference';"));
+    assert!(patient.contains(
+        "generalPractitioner: z.array((z.lazy(() => ReferenceSchema) as z.ZodType<Reference<\"Organization\" | \"Practitioner\">>)).optional(),"
+    ));
+
+    let reference = generator.generate_datatype_schema_file(&graph.datatypes["Reference"]).unwrap();
+    assert!(reference.contains(
+        "import type { Reference, ReferenceString, ResourceType } from '../types/Reference';"
+    ));
+    assert!(reference.contains(
+        "reference: z.custom<ReferenceString>((value) => typeof value === \"string\").optional(),"
+    ));
+    assert!(reference.contains(
+        "type: z.custom<ResourceType>((value) => typeof value === \"string\").optional(),"
+    ));
+    assert!(reference.contains("display: z.string().optional(),"));
+}
+
+#[test]
+fn test_generate_schemas_index() {
+    let mut graph = create_graph();
+    let mut resource = create_patient_resource();
+    resource.name = "DomainResource".to_string();
+    resource.is_abstract = true;
+    graph.add_resource("DomainResource".to_string(), resource);
+
+    let result = zod_generator(&graph).generate_schemas_index(&graph).unwrap();
+
+    assert!(result.contains("export * from './HumanName';"));
+    assert!(result.contains("export * from './Patient';"));
+    assert!(!result.contains("DomainResource"));
+    assert!(
+        result
+            .contains("export const AnyResourceSchema = z.discriminatedUnion(\"resourceType\", [")
+    );
+    assert!(
+        result.contains("export type AnyResourceInferred = z.infer<typeof AnyResourceSchema>;")
+    );
+}